@@ -0,0 +1,395 @@
+use std::time::Duration;
+
+use futures::Stream;
+use reqwest::{header::AUTHORIZATION, Client, Method, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use shared_types::{
+    CreateOrderRequest, Order, Page, Proposal, ProviderIntent, Quote, QuoteRequest, RegisterProviderRequest,
+};
+use shared_utils::retry::Backoff;
+use uuid::Uuid;
+
+use crate::{
+    error::{ClientError, Result},
+    pagination::paginate,
+};
+
+/// Header carrying the idempotency key of an order creation
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Generate a fresh idempotency key
+///
+/// Keep the key when retrying an order creation after a crash or timeout so
+/// the API returns the original order instead of creating a duplicate.
+pub fn new_idempotency_key() -> String {
+    Uuid::new_v4().to_string()
+}
+
+/// Configures a [`PayNodeClient`]
+pub struct ClientBuilder {
+    base_url: String,
+    api_token: Option<String>,
+    timeout: Duration,
+    max_retries: u32,
+    backoff: Backoff,
+}
+
+impl ClientBuilder {
+    /// JWT sent as the bearer token on every request
+    pub fn api_token(mut self, token: impl Into<String>) -> Self {
+        self.api_token = Some(token.into());
+        self
+    }
+
+    /// Per-attempt request timeout (default 30s)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Retries after the first attempt for transient failures (default 3)
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Delay schedule between retries (default 250ms doubling up to 5s)
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    pub fn build(self) -> Result<PayNodeClient> {
+        if !(self.base_url.starts_with("https://") || self.base_url.starts_with("http://")) {
+            return Err(ClientError::InvalidConfig(format!("Invalid base URL: {}", self.base_url)));
+        }
+
+        Ok(PayNodeClient {
+            http: Client::builder().timeout(self.timeout).build()?,
+            base_url: self.base_url.trim_end_matches('/').to_string(),
+            api_token: self.api_token,
+            max_retries: self.max_retries,
+            backoff: self.backoff,
+        })
+    }
+}
+
+/// Client for the PayNode API
+///
+/// Connection failures and `429` responses, which the API did not act on,
+/// are retried with exponential backoff. Timeouts and `5xx` responses, after
+/// which a request may have taken effect, are only retried for reads and for
+/// order creation: orders carry an idempotency key, reused across retries,
+/// so a retried create never produces a duplicate.
+pub struct PayNodeClient {
+    http: Client,
+    base_url: String,
+    api_token: Option<String>,
+    max_retries: u32,
+    backoff: Backoff,
+}
+
+impl PayNodeClient {
+    pub fn builder(base_url: impl Into<String>) -> ClientBuilder {
+        ClientBuilder {
+            base_url: base_url.into(),
+            api_token: None,
+            timeout: Duration::from_secs(30),
+            max_retries: 3,
+            backoff: Backoff::new(Duration::from_millis(250), Duration::from_secs(5), 2),
+        }
+    }
+
+    // ─── Orders ─────────────────────────────────────────────────────
+
+    /// Create an order with a freshly generated idempotency key
+    pub async fn create_order(&self, request: &CreateOrderRequest) -> Result<Order> {
+        self.create_order_with_key(request, &new_idempotency_key()).await
+    }
+
+    /// Create an order, deduplicated by the caller-supplied idempotency key
+    pub async fn create_order_with_key(&self, request: &CreateOrderRequest, idempotency_key: &str) -> Result<Order> {
+        self.send(Method::POST, "/v1/orders", &[], Some(request), Some(idempotency_key))
+            .await
+    }
+
+    pub async fn get_order(&self, order_id: &str) -> Result<Order> {
+        self.get(&format!("/v1/orders/{}", order_id), &[]).await
+    }
+
+    /// Fetch a single page of the caller's orders, newest first
+    pub async fn list_orders(&self, cursor: Option<&str>, limit: Option<u32>) -> Result<Page<Order>> {
+        let mut query = Vec::new();
+        if let Some(cursor) = cursor {
+            query.push(("cursor", cursor.to_string()));
+        }
+        if let Some(limit) = limit {
+            query.push(("limit", limit.to_string()));
+        }
+        self.get("/v1/orders", &query).await
+    }
+
+    /// Stream all of the caller's orders, fetching `page_size` at a time
+    pub fn orders(&self, page_size: u32) -> impl Stream<Item = Result<Order>> + '_ {
+        paginate(move |cursor| async move { self.list_orders(cursor.as_deref(), Some(page_size)).await })
+    }
+
+    // ─── Proposals ──────────────────────────────────────────────────
+
+    /// Settlement proposals providers have made for an order
+    pub async fn list_proposals(&self, order_id: &str) -> Result<Vec<Proposal>> {
+        self.get(&format!("/v1/orders/{}/proposals", order_id), &[]).await
+    }
+
+    // ─── Quotes ─────────────────────────────────────────────────────
+
+    /// Request a signed, time-limited quote for an order
+    pub async fn create_quote(&self, request: &QuoteRequest) -> Result<Quote> {
+        self.send(Method::POST, "/v1/quotes", &[], Some(request), None).await
+    }
+
+    // ─── Provider intents ───────────────────────────────────────────
+
+    /// Register or update the caller's liquidity intent (provider tokens only)
+    pub async fn register_intent(&self, request: &RegisterProviderRequest) -> Result<ProviderIntent> {
+        self.send(Method::POST, "/v1/providers", &[], Some(request), None).await
+    }
+
+    // ─── Transport ──────────────────────────────────────────────────
+
+    async fn get<T: DeserializeOwned>(&self, path: &str, query: &[(&str, String)]) -> Result<T> {
+        self.send::<(), T>(Method::GET, path, query, None, None).await
+    }
+
+    async fn send<B: Serialize, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<&B>,
+        idempotency_key: Option<&str>,
+    ) -> Result<T> {
+        let url = format!("{}{}", self.base_url, path);
+        let body = body.map(serde_json::to_vec).transpose()?;
+        // Safe to send again even if an earlier attempt took effect
+        let replayable = method == Method::GET || idempotency_key.is_some();
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            let retries_left = attempt <= self.max_retries;
+
+            let mut request = self.http.request(method.clone(), &url).query(query);
+            if let Some(token) = &self.api_token {
+                request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+            }
+            if let Some(key) = idempotency_key {
+                request = request.header(IDEMPOTENCY_KEY_HEADER, key);
+            }
+            if let Some(body) = &body {
+                request = request
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(body.clone());
+            }
+
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(e) if retries_left && (e.is_connect() || (replayable && e.is_timeout())) => {
+                    tokio::time::sleep(self.backoff.delay_for(attempt)).await;
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            let status = response.status();
+            if status.is_success() {
+                let bytes = response.bytes().await?;
+                return Ok(serde_json::from_slice(&bytes)?);
+            }
+            if retries_left && is_retryable(status, replayable) {
+                tokio::time::sleep(self.backoff.delay_for(attempt)).await;
+                continue;
+            }
+
+            let text = response.text().await.unwrap_or_default();
+            return Err(ClientError::Api {
+                status: status.as_u16(),
+                message: error_message(&text),
+            });
+        }
+    }
+}
+
+/// Whether a failed response is worth retrying; a `5xx` only is when the
+/// request is `replayable`
+fn is_retryable(status: StatusCode, replayable: bool) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || (replayable && status.is_server_error())
+}
+
+/// Pull the `error` field out of an API error body, falling back to the raw text
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| v.get("error").and_then(|e| e.as_str()).map(str::to_string))
+        .unwrap_or_else(|| body.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    use axum::{
+        extract::Query,
+        http::{HeaderMap, StatusCode as AxumStatus},
+        routing::{get, post},
+        Json, Router,
+    };
+    use chrono::Utc;
+    use futures::TryStreamExt;
+    use shared_types::{Currency, OrderTier};
+
+    fn order() -> Order {
+        Order::new(
+            "0xorder".to_string(),
+            "0xuser".to_string(),
+            "0xtoken".to_string(),
+            "1000000".to_string(),
+            "0xrefund".to_string(),
+            "0xintegrator".to_string(),
+            50,
+            Currency::NGN,
+            OrderTier::Alpha,
+            Utc::now(),
+            1,
+            "0xtx".to_string(),
+        )
+    }
+
+    async fn serve(app: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_retry_reuses_idempotency_key() {
+        let seen = Arc::new(Mutex::new(Vec::<(String, Vec<u8>)>::new()));
+        let recorded = seen.clone();
+        let app = Router::new().route(
+            "/v1/orders",
+            post(move |headers: HeaderMap, body: axum::body::Bytes| async move {
+                let header = |name: &str| headers.get(name).unwrap().to_str().unwrap().to_string();
+                let mut seen = recorded.lock().unwrap();
+                seen.push((header(IDEMPOTENCY_KEY_HEADER), body.to_vec()));
+                if seen.len() == 1 {
+                    (AxumStatus::SERVICE_UNAVAILABLE, Json(serde_json::json!({ "error": "busy" })))
+                } else {
+                    (AxumStatus::CREATED, Json(serde_json::to_value(order()).unwrap()))
+                }
+            }),
+        );
+        let client = PayNodeClient::builder(serve(app).await)
+            .backoff(Backoff::new(Duration::from_millis(1), Duration::from_millis(1), 1))
+            .build()
+            .unwrap();
+
+        let request = CreateOrderRequest {
+            token: "0xtoken".to_string(),
+            amount: "1000000".to_string(),
+            currency: "NGN".to_string(),
+            refund_address: "0xrefund".to_string(),
+            integrator_address: "0xintegrator".to_string(),
+            quote_id: None,
+        };
+        let created = client.create_order(&request).await.unwrap();
+        assert_eq!(created.order_id, "0xorder");
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0], seen[1]);
+    }
+
+    #[tokio::test]
+    async fn test_unkeyed_posts_are_not_retried_after_server_errors() {
+        let calls = Arc::new(Mutex::new(0));
+        let counter = calls.clone();
+        let app = Router::new().route(
+            "/v1/quotes",
+            post(move |headers: HeaderMap| async move {
+                assert!(headers.get(IDEMPOTENCY_KEY_HEADER).is_none());
+                *counter.lock().unwrap() += 1;
+                (AxumStatus::BAD_GATEWAY, Json(serde_json::json!({ "error": "upstream failed" })))
+            }),
+        );
+        let client = PayNodeClient::builder(serve(app).await)
+            .backoff(Backoff::new(Duration::from_millis(1), Duration::from_millis(1), 1))
+            .build()
+            .unwrap();
+
+        let request = QuoteRequest {
+            token: "0xtoken".to_string(),
+            amount: "1".to_string(),
+            currency: "NGN".to_string(),
+            integrator_address: None,
+        };
+        assert!(matches!(client.create_quote(&request).await, Err(ClientError::Api { status: 502, .. })));
+        assert_eq!(*calls.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_orders_stream_follows_cursors_across_pages() {
+        let ids: Vec<String> = (1..=5).map(|i| format!("0x{:064x}", i)).collect();
+        let listed = ids.clone();
+        let app = Router::new().route(
+            "/v1/orders",
+            get(move |Query(query): Query<std::collections::HashMap<String, String>>| async move {
+                let limit: usize = query["limit"].parse().unwrap();
+                let start = query
+                    .get("cursor")
+                    .map_or(0, |cursor| listed.iter().position(|id| id == cursor).unwrap() + 1);
+                let data: Vec<Order> = listed[start..]
+                    .iter()
+                    .take(limit)
+                    .map(|id| Order { order_id: id.clone(), ..order() })
+                    .collect();
+                let next_cursor = (start + data.len() < listed.len()).then(|| data.last().unwrap().order_id.clone());
+                Json(Page { data, next_cursor })
+            }),
+        );
+        let client = PayNodeClient::builder(serve(app).await).build().unwrap();
+
+        let orders: Vec<Order> = client.orders(2).try_collect().await.unwrap();
+        assert_eq!(orders.into_iter().map(|order| order.order_id).collect::<Vec<_>>(), ids);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let calls = Arc::new(Mutex::new(0));
+        let counter = calls.clone();
+        let app = Router::new().route(
+            "/v1/quotes",
+            post(move || async move {
+                *counter.lock().unwrap() += 1;
+                (AxumStatus::BAD_REQUEST, Json(serde_json::json!({ "error": "Unsupported currency: XYZ" })))
+            }),
+        );
+        let client = PayNodeClient::builder(serve(app).await).build().unwrap();
+
+        let request = QuoteRequest {
+            token: "0xtoken".to_string(),
+            amount: "1".to_string(),
+            currency: "XYZ".to_string(),
+            integrator_address: None,
+        };
+        match client.create_quote(&request).await {
+            Err(ClientError::Api { status, message }) => {
+                assert_eq!(status, 400);
+                assert_eq!(message, "Unsupported currency: XYZ");
+            }
+            other => panic!("unexpected result: {:?}", other.map(|q| q.quote_id)),
+        }
+        assert_eq!(*calls.lock().unwrap(), 1);
+    }
+}
@@ -0,0 +1,33 @@
+use shared_utils::signing::SignatureError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("API error ({status}): {message}")]
+    Api { status: u16, message: String },
+
+    #[error("Failed to decode response: {0}")]
+    Decode(#[from] serde_json::Error),
+
+    #[error("Webhook signature error: {0}")]
+    Signature(#[from] SignatureError),
+
+    #[error("Invalid client configuration: {0}")]
+    InvalidConfig(String),
+}
+
+impl ClientError {
+    /// HTTP status returned by the API, if the request got that far
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ClientError::Api { status, .. } => Some(*status),
+            ClientError::Http(e) => e.status().map(|s| s.as_u16()),
+            _ => None,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
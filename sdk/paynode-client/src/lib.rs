@@ -0,0 +1,35 @@
+//! Typed Rust client for the PayNode API
+//!
+//! Wraps the api-gateway endpoints with the request and response types from
+//! `shared-types`, and takes care of authentication, idempotency keys,
+//! retries and pagination.
+//!
+//! ```no_run
+//! use futures::TryStreamExt;
+//! use paynode_client::PayNodeClient;
+//!
+//! # async fn run() -> paynode_client::Result<()> {
+//! let client = PayNodeClient::builder("https://api.paynode.example")
+//!     .api_token("eyJhbGciOi...")
+//!     .build()?;
+//!
+//! let orders: Vec<_> = client.orders(50).try_collect().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod client;
+pub mod error;
+pub mod pagination;
+pub mod webhooks;
+
+pub use client::{new_idempotency_key, ClientBuilder, PayNodeClient, IDEMPOTENCY_KEY_HEADER};
+pub use error::{ClientError, Result};
+pub use pagination::paginate;
+pub use webhooks::{verify_webhook, verify_webhook_at, DEFAULT_TOLERANCE_SECS};
+
+// Models are shared with the services so SDK users see the same shapes
+pub use shared_types::{
+    CreateOrderRequest, Order, OrderStatus, Page, Proposal, ProviderIntent, Quote, QuoteRequest,
+    RegisterProviderRequest, WebhookEventType, WebhookPayload,
+};
@@ -0,0 +1,61 @@
+use std::future::Future;
+
+use futures::{stream, Stream, TryStreamExt};
+use shared_types::Page;
+
+use crate::error::{ClientError, Result};
+
+enum Cursor {
+    Start,
+    Next(String),
+    Done,
+}
+
+/// Stream every item of a cursor-paginated listing
+///
+/// `fetch` is called with the cursor of the page to load (None for the first
+/// page); pages are requested lazily as the stream is consumed and the stream
+/// ends after the page without a `next_cursor`.
+pub fn paginate<T, F, Fut>(mut fetch: F) -> impl Stream<Item = Result<T>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<Page<T>>>,
+{
+    stream::try_unfold(Cursor::Start, move |cursor| {
+        let page = match cursor {
+            Cursor::Start => Some(fetch(None)),
+            Cursor::Next(cursor) => Some(fetch(Some(cursor))),
+            Cursor::Done => None,
+        };
+        async move {
+            let Some(page) = page else { return Ok::<_, ClientError>(None) };
+            let page = page.await?;
+            let next = match page.next_cursor {
+                Some(cursor) => Cursor::Next(cursor),
+                None => Cursor::Done,
+            };
+            Ok(Some((page.data, next)))
+        }
+    })
+    .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_paginate_follows_cursors_until_last_page() {
+        let pages = paginate(|cursor| async move {
+            Ok(match cursor.as_deref() {
+                None => Page { data: vec![1, 2], next_cursor: Some("b".to_string()) },
+                Some("b") => Page { data: vec![3], next_cursor: Some("c".to_string()) },
+                _ => Page { data: vec![], next_cursor: None },
+            })
+        });
+
+        let items: Vec<u32> = pages.try_collect().await.unwrap();
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,72 @@
+use shared_types::WebhookPayload;
+use shared_utils::signing::verify_payload;
+
+pub use shared_utils::signing::SIGNATURE_HEADER;
+
+use crate::error::Result;
+
+/// Maximum accepted age of a webhook signature
+pub const DEFAULT_TOLERANCE_SECS: i64 = 300;
+
+/// Verify a webhook delivery and decode its payload
+///
+/// # Arguments
+/// * `secret` - Signing secret returned when the endpoint was registered
+/// * `signature_header` - Value of the `X-PayNode-Signature` header
+/// * `body` - Raw request body exactly as received, before any JSON parsing
+pub fn verify_webhook(secret: &str, signature_header: &str, body: &[u8]) -> Result<WebhookPayload> {
+    verify_webhook_at(
+        secret,
+        signature_header,
+        body,
+        chrono::Utc::now().timestamp(),
+        DEFAULT_TOLERANCE_SECS,
+    )
+}
+
+/// Like [`verify_webhook`], with an explicit clock and tolerance
+pub fn verify_webhook_at(
+    secret: &str,
+    signature_header: &str,
+    body: &[u8],
+    now: i64,
+    tolerance_secs: i64,
+) -> Result<WebhookPayload> {
+    verify_payload(secret.as_bytes(), signature_header, body, now, tolerance_secs)?;
+    Ok(serde_json::from_slice(body)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ClientError;
+    use chrono::Utc;
+    use shared_types::{OrderStatus, OrderStatusChangedEvent, WebhookEventType};
+    use shared_utils::signing::{sign_payload, SignatureError};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_verify_webhook_decodes_signed_payload() {
+        let payload = WebhookPayload {
+            id: Uuid::new_v4(),
+            event_type: WebhookEventType::OrderFulfilled,
+            created_at: Utc::now(),
+            data: OrderStatusChangedEvent {
+                order_id: "0xorder".to_string(),
+                user_address: "0xuser".to_string(),
+                integrator_address: "0xintegrator".to_string(),
+                previous_status: Some(OrderStatus::Accepted),
+                status: OrderStatus::Fulfilled,
+                timestamp: Utc::now(),
+            },
+        };
+        let body = serde_json::to_vec(&payload).unwrap();
+        let header = sign_payload(b"whsec_test", 1_700_000_000, &body);
+
+        let verified = verify_webhook_at("whsec_test", &header, &body, 1_700_000_030, 300).unwrap();
+        assert_eq!(verified.id, payload.id);
+
+        let err = verify_webhook_at("whsec_other", &header, &body, 1_700_000_030, 300).unwrap_err();
+        assert!(matches!(err, ClientError::Signature(SignatureError::Mismatch)));
+    }
+}
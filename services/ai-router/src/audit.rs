@@ -0,0 +1,195 @@
+//! Routing decision audit log
+//!
+//! Every decision is stored with its candidate set, feature values and
+//! per-feature contributions, so a provider asking why it lost an order can
+//! be answered from the record. Decisions older than the retention period
+//! are pruned in the background.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use shared_database::{
+    models::{hex_to_bytes, RoutingDecisionModel},
+    RoutingDecisionRepository,
+};
+use shared_types::{CandidateEvaluation, Order, RoutingDecision};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::error::{Result, RouterError};
+use crate::scoring::{ProviderScore, Scorer};
+
+/// Audit log retention settings
+#[derive(Debug, Clone)]
+pub struct AuditConfig {
+    /// How long decisions are kept
+    pub retention: Duration,
+    /// How often expired decisions are pruned
+    pub prune_interval: Duration,
+}
+
+impl AuditConfig {
+    /// Load from `ROUTING_AUDIT_RETENTION_DAYS` (default 90) and
+    /// `ROUTING_AUDIT_PRUNE_INTERVAL_SECS` (default 3600)
+    pub fn from_env() -> Self {
+        let env_u64 = |key: &str, default: u64| {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(default)
+        };
+
+        Self {
+            retention: Duration::from_secs(env_u64("ROUTING_AUDIT_RETENTION_DAYS", 90) * 24 * 60 * 60),
+            prune_interval: Duration::from_secs(env_u64("ROUTING_AUDIT_PRUNE_INTERVAL_SECS", 3600).max(1)),
+        }
+    }
+
+    /// Decisions made before this are past retention
+    pub fn cutoff(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - chrono::Duration::from_std(self.retention).unwrap_or(chrono::Duration::MAX)
+    }
+}
+
+/// Build the decision record for a ranking produced by `scorer`
+pub fn explain(order: &Order, scorer: &dyn Scorer, ranked: &[ProviderScore], decided_at: DateTime<Utc>) -> RoutingDecision {
+    let candidates: Vec<CandidateEvaluation> = ranked
+        .iter()
+        .enumerate()
+        .map(|(i, score)| CandidateEvaluation {
+            provider: score.provider.to_lowercase(),
+            rank: i as u32 + 1,
+            score: score.score,
+            features: score.features.to_map(),
+            contributions: score.contributions.clone(),
+        })
+        .collect();
+
+    RoutingDecision {
+        id: Uuid::new_v4(),
+        order_id: order.order_id.clone(),
+        currency: order.currency.clone(),
+        tier: order.tier,
+        scorer: scorer.name().to_string(),
+        scorer_version: scorer.version(),
+        chosen_provider: candidates.first().map(|c| c.provider.clone()),
+        candidates,
+        allocations: Vec::new(),
+        decided_at,
+    }
+}
+
+/// Persists routing decisions and enforces retention
+pub struct AuditLog {
+    decisions: RoutingDecisionRepository,
+    config: AuditConfig,
+}
+
+impl AuditLog {
+    pub fn new(decisions: RoutingDecisionRepository, config: AuditConfig) -> Self {
+        Self { decisions, config }
+    }
+
+    pub async fn record(&self, decision: &RoutingDecision) -> Result<()> {
+        let candidates = serde_json::to_value(&decision.candidates)
+            .map_err(|e| RouterError::InvalidRequest(format!("Unserializable decision: {}", e)))?;
+        let allocations = serde_json::to_value(&decision.allocations)
+            .map_err(|e| RouterError::InvalidRequest(format!("Unserializable decision: {}", e)))?;
+
+        self.decisions
+            .create(&RoutingDecisionModel {
+                id: decision.id,
+                order_id: hex_to_bytes(&decision.order_id),
+                currency: decision.currency.as_str(),
+                tier: decision.tier,
+                scorer: decision.scorer.clone(),
+                scorer_version: decision.scorer_version.clone(),
+                chosen_provider: decision.chosen_provider.as_deref().map(hex_to_bytes),
+                candidates,
+                allocations,
+                decided_at: decision.decided_at,
+            })
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<RoutingDecision> {
+        Ok(self.decisions.get(id).await?.to_domain())
+    }
+
+    pub async fn list_for_order(&self, order_id: &str) -> Result<Vec<RoutingDecision>> {
+        let decisions = self.decisions.list_for_order(&hex_to_bytes(order_id)).await?;
+        Ok(decisions.iter().map(RoutingDecisionModel::to_domain).collect())
+    }
+
+    pub async fn list_for_provider(&self, provider: &str, limit: i64) -> Result<Vec<RoutingDecision>> {
+        let decisions = self.decisions.list_for_provider(provider, limit).await?;
+        Ok(decisions.iter().map(RoutingDecisionModel::to_domain).collect())
+    }
+
+    pub async fn list_recent(&self, limit: i64) -> Result<Vec<RoutingDecision>> {
+        let decisions = self.decisions.list_recent(limit).await?;
+        Ok(decisions.iter().map(RoutingDecisionModel::to_domain).collect())
+    }
+
+    /// Delete decisions past retention
+    pub async fn prune(&self) -> Result<u64> {
+        Ok(self.decisions.delete_before(self.config.cutoff(Utc::now())).await?)
+    }
+
+    /// Prune on `prune_interval` until the process exits
+    pub fn spawn_retention(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.config.prune_interval);
+            loop {
+                ticker.tick().await;
+                match self.prune().await {
+                    Ok(0) => {}
+                    Ok(removed) => info!("Pruned {} routing decisions past retention", removed),
+                    Err(e) => warn!("Routing decision pruning failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::test_support::{candidate, order};
+    use crate::scoring::{rank, ScoringWeights, WeightedLinearScorer};
+
+    #[tokio::test]
+    async fn test_decision_explains_every_candidate() {
+        let scorer = WeightedLinearScorer::new(ScoringWeights::default()).unwrap();
+        let order = order("1000");
+        let candidates = vec![
+            candidate("0xAAA", "100000", 300, 5, 5, 600),
+            candidate("0xbbb", "100000", 100, 20, 0, 60),
+        ];
+        let ranked = rank(&scorer, &order, &candidates).await.unwrap();
+
+        let decision = explain(&order, &scorer, &ranked, Utc::now());
+        assert_eq!(decision.chosen_provider.as_deref(), Some("0xbbb"));
+        assert_eq!(decision.scorer, "weighted-linear");
+        assert!(decision.scorer_version.starts_with("weighted-linear@"));
+
+        let loser = decision.candidate("0xaaa").unwrap();
+        assert_eq!(loser.rank, 2);
+        assert_eq!(loser.features.len(), 7);
+        let total: f64 = loser.contributions.values().sum();
+        assert!((total - loser.score).abs() < 1e-9);
+        assert!(loser.contributions["success_rate"] < decision.candidates[0].contributions["success_rate"]);
+    }
+
+    #[test]
+    fn test_retention_cutoff() {
+        let config = AuditConfig {
+            retention: Duration::from_secs(7 * 24 * 60 * 60),
+            prune_interval: Duration::from_secs(60),
+        };
+        let now = Utc::now();
+        assert_eq!(config.cutoff(now), now - chrono::Duration::days(7));
+    }
+}
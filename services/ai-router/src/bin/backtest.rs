@@ -0,0 +1,119 @@
+//! Replay historical orders through one or more routing strategies
+//!
+//! ```text
+//! backtest (--fixture PATH | --from RFC3339 [--to RFC3339])
+//!          [--strategy weighted,bandit,remote] [--seed N] [--json]
+//! ```
+//!
+//! Strategies are configured from the same environment variables as the
+//! router itself; `--seed` also seeds the bandit's sampler.
+
+use std::sync::Arc;
+
+use ai_router::{
+    replay, BanditConfig, RemoteModelConfig, RemoteModelScorer, Report, Scenario, Scorer, ScoringWeights,
+    ThompsonSamplingScorer, WeightedLinearScorer,
+};
+use anyhow::{anyhow, bail, Context};
+use chrono::{DateTime, Utc};
+
+const USAGE: &str = "usage: backtest (--fixture PATH | --from RFC3339 [--to RFC3339]) \
+                     [--strategy weighted,bandit,remote] [--seed N] [--json]";
+
+struct Args {
+    fixture: Option<String>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    strategies: Vec<String>,
+    seed: u64,
+    json: bool,
+}
+
+impl Args {
+    fn parse() -> anyhow::Result<Self> {
+        let mut args = Args {
+            fixture: None,
+            from: None,
+            to: None,
+            strategies: vec!["weighted".to_string()],
+            seed: 0,
+            json: false,
+        };
+
+        let mut iter = std::env::args().skip(1);
+        while let Some(flag) = iter.next() {
+            let mut value = || iter.next().ok_or_else(|| anyhow!("{} needs a value\n{}", flag, USAGE));
+            match flag.as_str() {
+                "--fixture" => args.fixture = Some(value()?),
+                "--from" => args.from = Some(parse_time(&value()?)?),
+                "--to" => args.to = Some(parse_time(&value()?)?),
+                "--strategy" => args.strategies = value()?.split(',').map(|s| s.trim().to_string()).collect(),
+                "--seed" => args.seed = value()?.parse().context("--seed must be an integer")?,
+                "--json" => args.json = true,
+                "--help" | "-h" => {
+                    println!("{}", USAGE);
+                    std::process::exit(0);
+                }
+                other => bail!("unknown argument {}\n{}", other, USAGE),
+            }
+        }
+
+        if args.fixture.is_some() == args.from.is_some() {
+            bail!("pass exactly one of --fixture or --from\n{}", USAGE);
+        }
+        Ok(args)
+    }
+}
+
+fn parse_time(value: &str) -> anyhow::Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(value)
+        .with_context(|| format!("invalid timestamp {}", value))?
+        .with_timezone(&Utc))
+}
+
+fn scorer(strategy: &str, seed: u64) -> anyhow::Result<Arc<dyn Scorer>> {
+    let weighted = WeightedLinearScorer::new(ScoringWeights::from_env()?)?;
+    let scorer: Arc<dyn Scorer> = match strategy {
+        "weighted" => Arc::new(weighted),
+        "bandit" => Arc::new(ThompsonSamplingScorer::new(BanditConfig {
+            seed: Some(seed),
+            ..BanditConfig::from_env()?
+        })),
+        "remote" => {
+            let config = RemoteModelConfig::from_env().ok_or_else(|| anyhow!("ROUTER_MODEL_URL is not set"))?;
+            Arc::new(RemoteModelScorer::new(config, weighted)?)
+        }
+        other => bail!("unknown strategy {}", other),
+    };
+    Ok(scorer)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenv::dotenv().ok();
+    let args = Args::parse()?;
+
+    let scenario = match (&args.fixture, args.from) {
+        (Some(path), _) => Scenario::from_file(path)?,
+        (None, Some(from)) => {
+            let pool = shared_database::create_pool_from_env().await?;
+            Scenario::load(&pool, from, args.to.unwrap_or_else(Utc::now)).await?
+        }
+        (None, None) => unreachable!("checked in Args::parse"),
+    };
+
+    let mut reports: Vec<Report> = Vec::with_capacity(args.strategies.len());
+    for strategy in &args.strategies {
+        let scorer = scorer(strategy, args.seed)?;
+        reports.push(replay(scorer.as_ref(), &scenario, args.seed).await?);
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    } else {
+        for report in &reports {
+            println!("{}", report);
+        }
+    }
+    Ok(())
+}
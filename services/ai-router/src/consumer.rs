@@ -0,0 +1,378 @@
+//! Order event handling
+//!
+//! Pending orders are routed and assigned as they arrive, unless their tier
+//! policy holds the decision for an admin to approve. Failures and missed
+//! deadlines go through the [`FailoverTracker`], which either hands the order
+//! to the next provider or gives it up for refund. Each leg of a split order
+//! fails over, or is refunded, on its own. Outcomes are fed back to the
+//! scorer so adaptive strategies learn from them.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use futures::StreamExt;
+use serde::Serialize;
+use shared_database::{ProviderRepository, RoutingApprovalRepository, StakeRepository};
+use shared_messaging::subjects;
+use shared_types::{
+    helpers::hex_to_bytes, LegAllocation, Order, OrderAssignedEvent, OrderFailedEvent, OrderRefundRequestedEvent, OrderStatusChangedEvent,
+    ProposalCreatedEvent, ProviderReputation, RoutingDecision,
+};
+use tracing::{error, info, warn};
+
+use crate::engine::RoutingEngine;
+use crate::error::{Result, RouterError};
+use crate::failover::{FailoverAction, FailoverTracker};
+use crate::health::HealthReader;
+use crate::policy::PendingApprovals;
+use crate::scoring::Candidate;
+
+/// Reason recorded for providers that let their deadline pass
+const TIMEOUT_REASON: &str = "no response before deadline";
+
+/// Routes pending orders and fails them over until they complete
+pub struct RouterService {
+    engine: RoutingEngine,
+    providers: ProviderRepository,
+    failover: FailoverTracker,
+    approvals: PendingApprovals,
+    health: Option<HealthReader>,
+    stakes: Option<StakeRepository>,
+    nats: async_nats::Client,
+}
+
+impl RouterService {
+    pub fn new(engine: RoutingEngine, providers: ProviderRepository, failover: FailoverTracker, nats: async_nats::Client) -> Self {
+        Self {
+            engine,
+            providers,
+            failover,
+            approvals: PendingApprovals::new(),
+            health: None,
+            stakes: None,
+            nats,
+        }
+    }
+
+    /// Save held decisions so they survive a restart
+    pub fn with_approval_store(mut self, store: RoutingApprovalRepository) -> Self {
+        self.approvals = PendingApprovals::new().with_store(store);
+        self
+    }
+
+    /// Reload the decisions held for approval before the last restart
+    pub async fn restore_approvals(&self) -> Result<usize> {
+        self.approvals.restore().await
+    }
+
+    /// Attach provider heartbeat health to candidates
+    pub fn with_health(mut self, health: HealthReader) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    /// Attach provider collateral in the order's token to candidates
+    pub fn with_stakes(mut self, stakes: StakeRepository) -> Self {
+        self.stakes = Some(stakes);
+        self
+    }
+
+    /// Live intents in the order's currency, paired with reputations and,
+    /// where known, heartbeat health and stake
+    pub async fn candidates(&self, order: &Order) -> Result<Vec<Candidate>> {
+        let intents = self.providers.list_active_intents(&order.currency.as_str()).await?;
+        let mut reputations: HashMap<String, ProviderReputation> = self
+            .providers
+            .list_reputations()
+            .await?
+            .iter()
+            .map(|model| {
+                let reputation = model.to_domain();
+                (reputation.provider.to_lowercase(), reputation)
+            })
+            .collect();
+
+        let intents: Vec<_> = intents.iter().map(|model| model.to_domain()).collect();
+        let mut health = match &self.health {
+            Some(reader) => {
+                let providers: Vec<String> = intents.iter().map(|intent| intent.provider.clone()).collect();
+                reader.get_many(&providers).await.unwrap_or_else(|e| {
+                    warn!("Provider health unavailable, scoring without it: {}", e);
+                    HashMap::new()
+                })
+            }
+            None => HashMap::new(),
+        };
+        let stakes: HashMap<String, u128> = match &self.stakes {
+            Some(stakes) => stakes
+                .list_for_token(&hex_to_bytes(&order.token))
+                .await?
+                .iter()
+                .map(|model| {
+                    let stake = model.to_domain();
+                    (stake.provider, stake.staked.parse().unwrap_or(0))
+                })
+                .collect(),
+            None => HashMap::new(),
+        };
+
+        Ok(intents
+            .into_iter()
+            .map(|intent| {
+                let provider = intent.provider.to_lowercase();
+                let reputation = reputations
+                    .remove(&provider)
+                    .unwrap_or_else(|| ProviderReputation::new(intent.provider.clone()));
+                Candidate {
+                    intent,
+                    reputation,
+                    stake: self.stakes.as_ref().map(|_| stakes.get(&provider).copied().unwrap_or(0)),
+                    health: health.remove(&provider),
+                }
+            })
+            .collect())
+    }
+
+    /// Route a new order and assign it to the winner, or to every leg of a
+    /// split, holding it instead when its tier requires approval
+    pub async fn handle_pending(&self, order: Order) -> Result<()> {
+        let candidates = self.candidates(&order).await?;
+        let decision = self.engine.route(&order, &candidates).await?;
+
+        let held = self
+            .engine
+            .policies()
+            .is_some_and(|policies| policies.needs_approval(&order, &decision));
+        if held {
+            info!("Holding order {} for approval", order.order_id);
+            return self.approvals.hold(order, decision).await;
+        }
+        self.dispatch(&order, &decision).await;
+        Ok(())
+    }
+
+    /// Decisions waiting for approval, oldest first
+    pub fn pending_approvals(&self) -> Vec<RoutingDecision> {
+        self.approvals.list()
+    }
+
+    /// Assign a held order as its decision ranked it
+    pub async fn approve(&self, order_id: &str) -> Result<RoutingDecision> {
+        let (order, decision) = self.take_held(order_id).await?;
+        info!("Routing of order {} approved", order.order_id);
+        self.dispatch(&order, &decision).await;
+        Ok(decision)
+    }
+
+    /// Refuse a held decision and request a refund of the order
+    pub async fn reject(&self, order_id: &str, reason: &str) -> Result<()> {
+        let (order, decision) = self.take_held(order_id).await?;
+        warn!("Routing of order {} rejected: {}", order.order_id, reason);
+        if let Err(e) = self.engine.fail_legs(&order, &decision.allocations).await {
+            error!("Failed to release legs of order {}: {}", order.order_id, e);
+        }
+        let event = OrderRefundRequestedEvent {
+            order_id: order.order_id.clone(),
+            attempts: 0,
+            failed_providers: Vec::new(),
+            reason: reason.to_string(),
+            leg: None,
+            amount: None,
+            timestamp: Utc::now(),
+        };
+        self.publish(subjects::ORDER_REFUND_REQUESTED, &event).await;
+        Ok(())
+    }
+
+    async fn take_held(&self, order_id: &str) -> Result<(Order, RoutingDecision)> {
+        self.approvals
+            .take(order_id)
+            .await?
+            .ok_or_else(|| RouterError::NotFound(format!("No routing decision awaiting approval for order {}", order_id)))
+    }
+
+    /// Publish the assignments of a decision and start tracking the order,
+    /// or each of its legs, giving providers their tier's proposal deadline
+    async fn dispatch(&self, order: &Order, decision: &RoutingDecision) {
+        let timeout = match self.engine.policies() {
+            Some(policies) => policies.get(order.tier).proposal_deadline,
+            None => self.failover.config().response_timeout,
+        };
+        if !decision.allocations.is_empty() {
+            for leg in self.failover.assign_legs(order, decision, timeout, Utc::now()) {
+                self.assigned(decision, &leg.provider, &leg.amount, Some(leg.leg)).await;
+            }
+            return;
+        }
+        match self.failover.assign_within(order, decision, timeout, Utc::now()) {
+            Some(provider) => self.assigned(decision, &provider, &order.amount, None).await,
+            None => warn!("No provider can fill order {}; leaving it pending", order.order_id),
+        }
+    }
+
+    /// Move an order, or the leg of it a provider held, off a provider that
+    /// failed it
+    pub async fn handle_failure(&self, order_id: &str, provider: &str, reason: &str) {
+        let Some(order) = self.failover.order(order_id) else {
+            return;
+        };
+        let liquidity = self.liquidity(&order).await;
+        let Some(action) = self.failover.fail(order_id, provider, liquidity.as_ref(), Utc::now()) else {
+            return;
+        };
+        warn!("Provider {} failed order {}: {}", provider, order_id, reason);
+        let (FailoverAction::Reassign { order, .. } | FailoverAction::Refund { order, .. }) = &action;
+        self.engine.scorer().observe_outcome(order, provider, false);
+
+        match action {
+            FailoverAction::Reassign { order, leg, amount, provider: next, attempt, decision_id } => {
+                info!("Failing order {} over to {} (attempt {})", order_id, next, attempt);
+                if let Some(leg) = leg {
+                    if let Err(e) = self.engine.reassign_leg(&order, leg, &next).await {
+                        error!("Failed to move leg {} of order {} to {}: {}", leg, order_id, next, e);
+                    }
+                }
+                let event = OrderAssignedEvent {
+                    order_id: order.order_id.clone(),
+                    provider: next,
+                    amount,
+                    leg,
+                    attempt,
+                    decision_id,
+                    timestamp: Utc::now(),
+                };
+                self.publish(subjects::ORDER_ASSIGNED, &event).await;
+            }
+            FailoverAction::Refund { order, leg, amount, attempts, failed_providers } => {
+                warn!("Order {} failed with {} providers; requesting refund", order_id, attempts);
+                if let Some(leg) = leg {
+                    let failed = LegAllocation { leg, provider: provider.to_string(), amount: amount.clone() };
+                    if let Err(e) = self.engine.fail_legs(&order, &[failed]).await {
+                        error!("Failed to close leg {} of order {}: {}", leg, order_id, e);
+                    }
+                }
+                let event = OrderRefundRequestedEvent {
+                    order_id: order.order_id.clone(),
+                    attempts,
+                    failed_providers,
+                    reason: reason.to_string(),
+                    leg,
+                    amount: leg.map(|_| amount),
+                    timestamp: Utc::now(),
+                };
+                self.publish(subjects::ORDER_REFUND_REQUESTED, &event).await;
+            }
+        }
+    }
+
+    /// Liquidity each provider has available in the order's currency, by
+    /// lowercase address; None when it could not be read, leaving failover
+    /// to the ranking alone
+    async fn liquidity(&self, order: &Order) -> Option<HashMap<String, u128>> {
+        match self.providers.list_active_intents(&order.currency.as_str()).await {
+            Ok(intents) => Some(
+                intents
+                    .iter()
+                    .map(|model| {
+                        let intent = model.to_domain();
+                        (intent.provider.to_lowercase(), intent.available_amount.parse().unwrap_or(0))
+                    })
+                    .collect(),
+            ),
+            Err(e) => {
+                warn!("Provider liquidity unavailable for failover of order {}: {}", order.order_id, e);
+                None
+            }
+        }
+    }
+
+    /// Fail over every order whose provider missed its deadline
+    pub async fn sweep_overdue(&self) {
+        for (order_id, provider) in self.failover.overdue(Utc::now()) {
+            self.handle_failure(&order_id, &provider, TIMEOUT_REASON).await;
+        }
+    }
+
+    /// Consume order and proposal events and sweep deadlines until the
+    /// subscriptions close
+    pub async fn run(self: Arc<Self>) -> anyhow::Result<()> {
+        let mut orders = self.nats.subscribe(subjects::ORDER_ALL.to_string()).await?;
+        let mut proposals = self.nats.subscribe(subjects::PROPOSAL_CREATED.to_string()).await?;
+        let mut sweep = tokio::time::interval(self.failover.config().sweep_interval);
+        info!("AI Router subscribed to {} and {}", subjects::ORDER_ALL, subjects::PROPOSAL_CREATED);
+
+        loop {
+            tokio::select! {
+                message = orders.next() => {
+                    let Some(message) = message else { break };
+                    self.handle_order_message(message.subject.as_str(), &message.payload).await;
+                }
+                message = proposals.next() => {
+                    let Some(message) = message else { break };
+                    if let Ok(event) = serde_json::from_slice::<ProposalCreatedEvent>(&message.payload) {
+                        self.failover.set_deadline(&event.order_id, &event.provider, event.deadline);
+                    }
+                }
+                _ = sweep.tick() => self.sweep_overdue().await,
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_order_message(&self, subject: &str, payload: &[u8]) {
+        match subject {
+            subjects::ORDER_PENDING => match serde_json::from_slice::<Order>(payload) {
+                Ok(order) => {
+                    let order_id = order.order_id.clone();
+                    if let Err(e) = self.handle_pending(order).await {
+                        error!("Failed to route order {}: {}", order_id, e);
+                    }
+                }
+                Err(e) => warn!("Invalid {} payload: {}", subject, e),
+            },
+            subjects::ORDER_FAILED => match serde_json::from_slice::<OrderFailedEvent>(payload) {
+                Ok(event) => self.handle_failure(&event.order_id, &event.provider, &event.reason).await,
+                Err(e) => warn!("Invalid {} payload: {}", subject, e),
+            },
+            subjects::ORDER_ACCEPTED => {
+                if let Ok(event) = serde_json::from_slice::<OrderStatusChangedEvent>(payload) {
+                    self.failover.acknowledge(&event.order_id);
+                }
+            }
+            subjects::ORDER_FULFILLED => {
+                if let Ok(event) = serde_json::from_slice::<OrderStatusChangedEvent>(payload) {
+                    for (order, provider) in self.failover.complete(&event.order_id) {
+                        self.engine.scorer().observe_outcome(&order, &provider, true);
+                    }
+                }
+            }
+            subjects::ORDER_REFUNDED | subjects::ORDER_EXPIRED => {
+                if let Ok(event) = serde_json::from_slice::<OrderStatusChangedEvent>(payload) {
+                    self.failover.complete(&event.order_id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Publish a first assignment made by `decision`
+    async fn assigned(&self, decision: &RoutingDecision, provider: &str, amount: &str, leg: Option<u32>) {
+        let event = OrderAssignedEvent {
+            order_id: decision.order_id.clone(),
+            provider: provider.to_string(),
+            amount: amount.to_string(),
+            leg,
+            attempt: 1,
+            decision_id: decision.id,
+            timestamp: Utc::now(),
+        };
+        self.publish(subjects::ORDER_ASSIGNED, &event).await;
+    }
+
+    async fn publish<T: Serialize>(&self, subject: &str, event: &T) {
+        if let Err(e) = shared_messaging::publish_event(&self.nats, subject, event).await {
+            error!("Failed to publish {}: {}", subject, e);
+        }
+    }
+}
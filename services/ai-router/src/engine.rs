@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use shared_database::{
+    models::{hex_to_bytes, OrderAllocationModel},
+    AllocationRepository,
+};
+use shared_types::{AllocationStatus, LegAllocation, Order, OrderAllocation, OrderTier, RoutingDecision};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::audit::{explain, AuditLog};
+use crate::error::Result;
+use crate::policy::TierPolicies;
+use crate::scoring::{rank, rank_partial, Candidate, ProviderScore, Scorer};
+use crate::split::{self, SplitConfig};
+
+/// Ranks candidates for orders and records each decision
+pub struct RoutingEngine {
+    scorer: Arc<dyn Scorer>,
+    audit: Option<Arc<AuditLog>>,
+    split: Option<SplitConfig>,
+    allocations: Option<AllocationRepository>,
+    policies: Option<TierPolicies>,
+}
+
+impl RoutingEngine {
+    pub fn new(scorer: Arc<dyn Scorer>) -> Self {
+        Self {
+            scorer,
+            audit: None,
+            split: None,
+            allocations: None,
+            policies: None,
+        }
+    }
+
+    pub fn with_audit(mut self, audit: Arc<AuditLog>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Split Titan-tier orders no single provider can fill, storing the legs
+    /// in `allocations`
+    pub fn with_split(mut self, config: SplitConfig, allocations: AllocationRepository) -> Self {
+        self.split = Some(config);
+        self.allocations = Some(allocations);
+        self
+    }
+
+    /// Only route to candidates the order's tier policy admits
+    pub fn with_policies(mut self, policies: TierPolicies) -> Self {
+        self.policies = Some(policies);
+        self
+    }
+
+    pub fn scorer(&self) -> &dyn Scorer {
+        self.scorer.as_ref()
+    }
+
+    pub fn policies(&self) -> Option<&TierPolicies> {
+        self.policies.as_ref()
+    }
+
+    /// Rank the candidates for an order
+    ///
+    /// With tier policies configured, candidates the order's tier rejects are
+    /// dropped, and those it accepts only with approval are ranked only when
+    /// no other candidate can take the order. A Titan-tier order that no
+    /// candidate can fill alone is split across several when splitting is
+    /// enabled; the decision then lists the legs instead of a chosen
+    /// provider. The decision is written to the audit log when one is
+    /// configured; a failed write is logged rather than holding up routing.
+    pub async fn route(&self, order: &Order, candidates: &[Candidate]) -> Result<RoutingDecision> {
+        let (admitted, needs_approval) = match &self.policies {
+            Some(policies) => policies.partition(order, candidates),
+            None => (candidates.to_vec(), Vec::new()),
+        };
+        let (mut ranked, mut legs) = self.rank_or_split(order, &admitted).await?;
+        if ranked.is_empty() && !needs_approval.is_empty() {
+            (ranked, legs) = self.rank_or_split(order, &needs_approval).await?;
+        }
+
+        let mut decision = explain(order, self.scorer.as_ref(), &ranked, Utc::now());
+        if !legs.is_empty() {
+            self.store_legs(order, &legs).await?;
+            info!("Split order {} across {} providers", order.order_id, legs.len());
+            decision.chosen_provider = None;
+            decision.allocations = legs;
+        }
+
+        if let Some(audit) = &self.audit {
+            if let Err(e) = audit.record(&decision).await {
+                warn!("Failed to record routing decision for order {}: {}", order.order_id, e);
+            }
+        }
+        Ok(decision)
+    }
+
+    /// Rank candidates able to fill the whole order, falling back to a split
+    /// for Titan-tier orders none of them can
+    async fn rank_or_split(&self, order: &Order, candidates: &[Candidate]) -> Result<(Vec<ProviderScore>, Vec<LegAllocation>)> {
+        let ranked = rank(self.scorer.as_ref(), order, candidates).await?;
+        if ranked.is_empty() && order.tier == OrderTier::Titan {
+            if let Some(config) = &self.split {
+                let partial = rank_partial(self.scorer.as_ref(), order, candidates).await?;
+                if let Some(legs) = split::plan(order, &partial, candidates, config) {
+                    return Ok((partial, legs));
+                }
+            }
+        }
+        Ok((ranked, Vec::new()))
+    }
+
+    /// Mark the stored legs of a split that will not go ahead as failed
+    pub async fn fail_legs(&self, order: &Order, legs: &[LegAllocation]) -> Result<()> {
+        let Some(repo) = &self.allocations else {
+            return Ok(());
+        };
+        let order_id = hex_to_bytes(&order.order_id);
+        for leg in legs {
+            repo.mark_failed(&order_id, leg.leg as i32).await?;
+        }
+        Ok(())
+    }
+
+    /// Move a stored leg of a split to the provider it failed over to
+    pub async fn reassign_leg(&self, order: &Order, leg: u32, provider: &str) -> Result<()> {
+        let Some(repo) = &self.allocations else {
+            return Ok(());
+        };
+        if !repo.reassign(&hex_to_bytes(&order.order_id), leg as i32, &hex_to_bytes(provider)).await? {
+            warn!("Leg {} of order {} is no longer pending; not reassigned to {}", leg, order.order_id, provider);
+        }
+        Ok(())
+    }
+
+    async fn store_legs(&self, order: &Order, legs: &[LegAllocation]) -> Result<()> {
+        let Some(repo) = &self.allocations else {
+            return Ok(());
+        };
+        let now = Utc::now();
+        let models: Vec<OrderAllocationModel> = legs
+            .iter()
+            .map(|leg| {
+                OrderAllocationModel::from_domain(&OrderAllocation {
+                    id: Uuid::new_v4(),
+                    order_id: order.order_id.clone(),
+                    leg: leg.leg,
+                    provider: leg.provider.clone(),
+                    amount: leg.amount.clone(),
+                    filled_amount: "0".to_string(),
+                    status: AllocationStatus::Pending,
+                    tx_hash: None,
+                    created_at: now,
+                    updated_at: now,
+                    settled_at: None,
+                })
+            })
+            .collect();
+        repo.create_all(&models).await?;
+        Ok(())
+    }
+}
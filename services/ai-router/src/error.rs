@@ -0,0 +1,45 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use shared_database::DatabaseError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RouterError {
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Scorer {scorer} failed: {message}")]
+    Scorer { scorer: String, message: String },
+
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+}
+
+pub type Result<T> = std::result::Result<T, RouterError>;
+
+impl IntoResponse for RouterError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            RouterError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+            RouterError::NotFound(_) | RouterError::Database(DatabaseError::NotFound(_)) => StatusCode::NOT_FOUND,
+            RouterError::InvalidConfig(_) | RouterError::Scorer { .. } | RouterError::Database(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+
+        if status.is_server_error() {
+            tracing::error!("Request failed: {}", self);
+        }
+
+        (status, Json(serde_json::json!({ "error": self.to_string() }))).into_response()
+    }
+}
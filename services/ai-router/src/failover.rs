@@ -0,0 +1,500 @@
+//! Failover to the next-best provider
+//!
+//! The ranked candidate list of every routed order is kept until the order
+//! completes. When the assigned provider fails, or does not respond before
+//! its deadline, the order moves to the best-ranked provider not yet tried.
+//! Once `max_attempts` providers have been tried, or none are left, the
+//! order is given up for refund.
+//!
+//! Each leg of a split order is tracked on its own: a failed leg moves to the
+//! best-ranked provider that has not held any leg of the order, and a leg
+//! that runs out of providers is given up for refund of its amount while the
+//! other legs carry on.
+//!
+//! Assignments live in memory: after a restart, failures for orders routed
+//! before it are ignored and the orders are left to expire.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use shared_types::{LegAllocation, Order, RoutingDecision};
+use uuid::Uuid;
+
+use crate::error::{Result, RouterError};
+
+/// Failover budget and timing
+#[derive(Debug, Clone)]
+pub struct FailoverConfig {
+    /// Providers tried per order, including the first, before refunding
+    pub max_attempts: u32,
+    /// How long an assigned provider has to respond before it is skipped
+    pub response_timeout: Duration,
+    /// How often assignments are checked for missed deadlines
+    pub sweep_interval: Duration,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            response_timeout: Duration::from_secs(300),
+            sweep_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+impl FailoverConfig {
+    /// Load from `ROUTER_FAILOVER_MAX_ATTEMPTS`,
+    /// `ROUTER_FAILOVER_RESPONSE_TIMEOUT_SECS` and
+    /// `ROUTER_FAILOVER_SWEEP_INTERVAL_SECS`, falling back to defaults
+    pub fn from_env() -> Result<Self> {
+        let defaults = Self::default();
+        let env_u64 = |key: &str| std::env::var(key).ok().and_then(|v| v.parse::<u64>().ok());
+
+        let config = Self {
+            max_attempts: env_u64("ROUTER_FAILOVER_MAX_ATTEMPTS")
+                .map(|v| v as u32)
+                .unwrap_or(defaults.max_attempts),
+            response_timeout: env_u64("ROUTER_FAILOVER_RESPONSE_TIMEOUT_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.response_timeout),
+            sweep_interval: env_u64("ROUTER_FAILOVER_SWEEP_INTERVAL_SECS")
+                .map(|secs| Duration::from_secs(secs.max(1)))
+                .unwrap_or(defaults.sweep_interval),
+        };
+        if config.max_attempts == 0 {
+            return Err(RouterError::InvalidConfig("ROUTER_FAILOVER_MAX_ATTEMPTS must be at least 1".to_string()));
+        }
+        Ok(config)
+    }
+
+}
+
+fn deadline_from(now: DateTime<Utc>, timeout: Duration) -> DateTime<Utc> {
+    now + chrono::Duration::from_std(timeout).unwrap_or(chrono::Duration::MAX)
+}
+
+/// What to do after an assigned provider failed
+///
+/// `leg` is the leg of a split order that failed, None for a whole order,
+/// and `amount` the token amount that moves or is refunded.
+#[derive(Debug, Clone)]
+pub enum FailoverAction {
+    /// Hand the order or leg to the next provider
+    Reassign { order: Order, leg: Option<u32>, amount: String, provider: String, attempt: u32, decision_id: Uuid },
+    /// Give up on the order or leg and refund it
+    Refund { order: Order, leg: Option<u32>, amount: String, attempts: u32, failed_providers: Vec<String> },
+}
+
+#[derive(Debug, Clone)]
+struct Assignment {
+    order: Order,
+    decision_id: Uuid,
+    /// Leg of a split order, None when one provider takes the whole order
+    leg: Option<u32>,
+    /// Token amount the provider pays out
+    amount: String,
+    /// Candidates best first
+    ranked: Vec<String>,
+    /// Providers assigned so far; the last one holds the order
+    tried: Vec<String>,
+    /// When the current provider must have responded by; None once it has
+    deadline: Option<DateTime<Utc>>,
+    /// How long each provider has to respond
+    timeout: Duration,
+    /// Leg given up for refund while other legs carry on
+    given_up: bool,
+}
+
+impl Assignment {
+    fn current(&self) -> &str {
+        self.tried.last().map(String::as_str).unwrap_or_default()
+    }
+}
+
+/// Ranked candidates and assignment history of in-flight orders
+pub struct FailoverTracker {
+    config: FailoverConfig,
+    /// The order's assignment, or one per leg of a split order
+    assignments: Mutex<HashMap<String, Vec<Assignment>>>,
+}
+
+impl FailoverTracker {
+    pub fn new(config: FailoverConfig) -> Self {
+        Self {
+            config,
+            assignments: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &FailoverConfig {
+        &self.config
+    }
+
+    /// Start tracking an order routed to the top candidate of `decision`
+    ///
+    /// # Returns
+    /// * `Option<String>` - The provider the order is assigned to; None when
+    ///   the decision chose no one
+    pub fn assign(&self, order: &Order, decision: &RoutingDecision, now: DateTime<Utc>) -> Option<String> {
+        self.assign_within(order, decision, self.config.response_timeout, now)
+    }
+
+    /// Like [`assign`](Self::assign), giving each provider `timeout` to
+    /// respond instead of the configured response timeout
+    pub fn assign_within(
+        &self,
+        order: &Order,
+        decision: &RoutingDecision,
+        timeout: Duration,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        let provider = decision.chosen_provider.clone()?;
+        let assignment = Assignment {
+            order: order.clone(),
+            decision_id: decision.id,
+            leg: None,
+            amount: order.amount.clone(),
+            ranked: ranked(decision),
+            tried: vec![provider.to_lowercase()],
+            deadline: Some(deadline_from(now, timeout)),
+            timeout,
+            given_up: false,
+        };
+        self.lock().insert(key(&order.order_id), vec![assignment]);
+        Some(provider)
+    }
+
+    /// Start tracking each leg of an order `decision` split, giving each
+    /// provider `timeout` to respond
+    ///
+    /// # Returns
+    /// * `Vec<LegAllocation>` - The legs assigned; empty when the decision
+    ///   did not split the order
+    pub fn assign_legs(
+        &self,
+        order: &Order,
+        decision: &RoutingDecision,
+        timeout: Duration,
+        now: DateTime<Utc>,
+    ) -> Vec<LegAllocation> {
+        if decision.allocations.is_empty() {
+            return Vec::new();
+        }
+        let legs = decision
+            .allocations
+            .iter()
+            .map(|leg| Assignment {
+                order: order.clone(),
+                decision_id: decision.id,
+                leg: Some(leg.leg),
+                amount: leg.amount.clone(),
+                ranked: ranked(decision),
+                tried: vec![leg.provider.to_lowercase()],
+                deadline: Some(deadline_from(now, timeout)),
+                timeout,
+                given_up: false,
+            })
+            .collect();
+        self.lock().insert(key(&order.order_id), legs);
+        decision.allocations.clone()
+    }
+
+    /// Move the current provider's deadline, e.g. to its proposal deadline
+    pub fn set_deadline(&self, order_id: &str, provider: &str, deadline: DateTime<Utc>) {
+        if let Some(assignments) = self.lock().get_mut(&key(order_id)) {
+            for assignment in assignments.iter_mut().filter(|assignment| !assignment.given_up) {
+                if assignment.current().eq_ignore_ascii_case(provider) {
+                    assignment.deadline = Some(deadline);
+                }
+            }
+        }
+    }
+
+    /// The order was accepted; failures still trigger failover but deadlines
+    /// no longer apply, to any of its legs
+    pub fn acknowledge(&self, order_id: &str) {
+        if let Some(assignments) = self.lock().get_mut(&key(order_id)) {
+            for assignment in assignments.iter_mut() {
+                assignment.deadline = None;
+            }
+        }
+    }
+
+    /// Stop tracking an order that reached a final state
+    ///
+    /// # Returns
+    /// * `Vec<(Order, String)>` - The order and each provider holding it, or
+    ///   one of its legs, at the end; empty when untracked
+    pub fn complete(&self, order_id: &str) -> Vec<(Order, String)> {
+        self.lock()
+            .remove(&key(order_id))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|assignment| !assignment.given_up)
+            .map(|assignment| {
+                let provider = assignment.current().to_string();
+                (assignment.order, provider)
+            })
+            .collect()
+    }
+
+    /// Record that `provider` failed the order, or its leg of the order, and
+    /// decide what happens next
+    ///
+    /// # Arguments
+    /// * `liquidity` - What each provider, by lowercase address, has
+    ///   available in the order's currency; when known, providers with less
+    ///   than the amount that moves are skipped
+    ///
+    /// # Returns
+    /// * `Option<FailoverAction>` - None when the order is unknown or no
+    ///   longer held by `provider`, so duplicate or stale failures are ignored
+    pub fn fail(
+        &self,
+        order_id: &str,
+        provider: &str,
+        liquidity: Option<&HashMap<String, u128>>,
+        now: DateTime<Utc>,
+    ) -> Option<FailoverAction> {
+        let mut assignments = self.lock();
+        let legs = assignments.get_mut(&key(order_id))?;
+        let index = legs
+            .iter()
+            .position(|assignment| !assignment.given_up && assignment.current().eq_ignore_ascii_case(provider))?;
+        // Providers that held any leg are skipped, so each provider holds at
+        // most one leg and failures stay attributable
+        let held: Vec<String> = legs.iter().flat_map(|assignment| assignment.tried.clone()).collect();
+        let assignment = &mut legs[index];
+        let amount: u128 = assignment.amount.parse().unwrap_or(0);
+        let can_fill = |candidate: &String| {
+            liquidity.is_none_or(|liquidity| liquidity.get(candidate).is_some_and(|available| *available >= amount))
+        };
+
+        let next = (assignment.tried.len() < self.config.max_attempts as usize)
+            .then(|| {
+                assignment
+                    .ranked
+                    .iter()
+                    .find(|candidate| !held.contains(candidate) && can_fill(candidate))
+                    .cloned()
+            })
+            .flatten();
+
+        match next {
+            Some(next) => {
+                assignment.tried.push(next.clone());
+                assignment.deadline = Some(deadline_from(now, assignment.timeout));
+                Some(FailoverAction::Reassign {
+                    order: assignment.order.clone(),
+                    leg: assignment.leg,
+                    amount: assignment.amount.clone(),
+                    provider: next,
+                    attempt: assignment.tried.len() as u32,
+                    decision_id: assignment.decision_id,
+                })
+            }
+            None => {
+                assignment.given_up = true;
+                assignment.deadline = None;
+                let action = FailoverAction::Refund {
+                    order: assignment.order.clone(),
+                    leg: assignment.leg,
+                    amount: assignment.amount.clone(),
+                    attempts: assignment.tried.len() as u32,
+                    failed_providers: assignment.tried.clone(),
+                };
+                if legs.iter().all(|assignment| assignment.given_up) {
+                    assignments.remove(&key(order_id));
+                }
+                Some(action)
+            }
+        }
+    }
+
+    /// Orders whose current provider, on the order or one of its legs,
+    /// missed its deadline, with that provider
+    pub fn overdue(&self, now: DateTime<Utc>) -> Vec<(String, String)> {
+        self.lock()
+            .iter()
+            .flat_map(|(order_id, assignments)| {
+                assignments
+                    .iter()
+                    .filter(|assignment| assignment.deadline.is_some_and(|deadline| deadline <= now))
+                    .map(move |assignment| (order_id.clone(), assignment.current().to_string()))
+            })
+            .collect()
+    }
+
+    /// Order a provider currently holds, if tracked
+    pub fn order(&self, order_id: &str) -> Option<Order> {
+        self.lock()
+            .get(&key(order_id))
+            .and_then(|assignments| assignments.first())
+            .map(|assignment| assignment.order.clone())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Vec<Assignment>>> {
+        self.assignments.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn key(order_id: &str) -> String {
+    order_id.to_lowercase()
+}
+
+/// Providers a decision ranked, best first
+fn ranked(decision: &RoutingDecision) -> Vec<String> {
+    decision.candidates.iter().map(|c| c.provider.to_lowercase()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::explain;
+    use crate::scoring::test_support::{candidate, order};
+    use crate::scoring::{rank, ScoringWeights, WeightedLinearScorer};
+
+    async fn decision(order: &Order) -> RoutingDecision {
+        let scorer = WeightedLinearScorer::new(ScoringWeights::default()).unwrap();
+        let candidates = vec![
+            candidate("0xfirst", "100000", 100, 50, 0, 60),
+            candidate("0xsecond", "100000", 150, 30, 2, 120),
+            candidate("0xthird", "100000", 250, 10, 5, 600),
+            candidate("0xfourth", "100000", 400, 2, 8, 3000),
+        ];
+        let ranked = rank(&scorer, order, &candidates).await.unwrap();
+        explain(order, &scorer, &ranked, Utc::now())
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_in_rank_order_then_refunds() {
+        let tracker = FailoverTracker::new(FailoverConfig::default());
+        let order = order("1000");
+        let now = Utc::now();
+        assert_eq!(tracker.assign(&order, &decision(&order).await, now).as_deref(), Some("0xfirst"));
+
+        // Failures from providers no longer holding the order are ignored
+        assert!(tracker.fail(&order.order_id, "0xsecond", None, now).is_none());
+
+        let Some(FailoverAction::Reassign { provider, attempt, .. }) = tracker.fail(&order.order_id, "0xfirst", None, now) else {
+            panic!("expected reassignment");
+        };
+        assert_eq!((provider.as_str(), attempt), ("0xsecond", 2));
+        assert!(tracker.fail(&order.order_id, "0xfirst", None, now).is_none());
+
+        let Some(FailoverAction::Reassign { provider, attempt, .. }) = tracker.fail(&order.order_id, "0xsecond", None, now) else {
+            panic!("expected reassignment");
+        };
+        assert_eq!((provider.as_str(), attempt), ("0xthird", 3));
+
+        // Budget of three attempts is spent even though a candidate remains
+        let Some(FailoverAction::Refund { attempts, failed_providers, .. }) = tracker.fail(&order.order_id, "0xthird", None, now)
+        else {
+            panic!("expected refund");
+        };
+        assert_eq!(attempts, 3);
+        assert_eq!(failed_providers, vec!["0xfirst", "0xsecond", "0xthird"]);
+        assert!(tracker.order(&order.order_id).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_overdue_assignments() {
+        let tracker = FailoverTracker::new(FailoverConfig::default());
+        let order = order("1000");
+        let now = Utc::now();
+        tracker.assign(&order, &decision(&order).await, now);
+        assert!(tracker.overdue(now).is_empty());
+
+        let later = now + chrono::Duration::seconds(301);
+        assert_eq!(tracker.overdue(later), vec![(order.order_id.clone(), "0xfirst".to_string())]);
+
+        // A proposal deadline replaces the response timeout
+        tracker.set_deadline(&order.order_id, "0xfirst", later + chrono::Duration::minutes(5));
+        assert!(tracker.overdue(later).is_empty());
+
+        tracker.acknowledge(&order.order_id);
+        assert!(tracker.overdue(later + chrono::Duration::hours(1)).is_empty());
+        let completed: Vec<String> = tracker.complete(&order.order_id).into_iter().map(|(_, provider)| provider).collect();
+        assert_eq!(completed, vec!["0xfirst"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_leg_fails_over_then_refunds_alone() {
+        let tracker = FailoverTracker::new(FailoverConfig::default());
+        let order = order("1000");
+        let now = Utc::now();
+        let mut decision = decision(&order).await;
+        decision.chosen_provider = None;
+        decision.allocations = vec![
+            LegAllocation { leg: 1, provider: "0xfirst".to_string(), amount: "600".to_string() },
+            LegAllocation { leg: 2, provider: "0xsecond".to_string(), amount: "400".to_string() },
+        ];
+        let timeout = Duration::from_secs(300);
+        assert_eq!(tracker.assign_legs(&order, &decision, timeout, now).len(), 2);
+
+        // The leg skips 0xsecond, which holds the other leg
+        let Some(FailoverAction::Reassign { provider, leg, amount, attempt, .. }) = tracker.fail(&order.order_id, "0xfirst", None, now)
+        else {
+            panic!("expected reassignment");
+        };
+        assert_eq!((provider.as_str(), leg, amount.as_str(), attempt), ("0xthird", Some(1), "600", 2));
+
+        let Some(FailoverAction::Reassign { provider, .. }) = tracker.fail(&order.order_id, "0xthird", None, now) else {
+            panic!("expected reassignment");
+        };
+        assert_eq!(provider, "0xfourth");
+
+        let Some(FailoverAction::Refund { leg, amount, failed_providers, .. }) = tracker.fail(&order.order_id, "0xfourth", None, now)
+        else {
+            panic!("expected refund");
+        };
+        assert_eq!((leg, amount.as_str()), (Some(1), "600"));
+        assert_eq!(failed_providers, vec!["0xfirst", "0xthird", "0xfourth"]);
+
+        // The other leg is still tracked and times out on its own
+        assert!(tracker.order(&order.order_id).is_some());
+        let later = now + chrono::Duration::seconds(301);
+        assert_eq!(tracker.overdue(later), vec![(order.order_id.clone(), "0xsecond".to_string())]);
+        let Some(FailoverAction::Refund { leg, amount, .. }) = tracker.fail(&order.order_id, "0xsecond", None, later) else {
+            panic!("expected refund");
+        };
+        assert_eq!((leg, amount.as_str()), (Some(2), "400"));
+        assert!(tracker.order(&order.order_id).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_leg_skips_providers_short_of_its_amount() {
+        let tracker = FailoverTracker::new(FailoverConfig::default());
+        let order = order("1000");
+        let now = Utc::now();
+        let mut decision = decision(&order).await;
+        decision.chosen_provider = None;
+        decision.allocations = vec![
+            LegAllocation { leg: 1, provider: "0xfirst".to_string(), amount: "600".to_string() },
+            LegAllocation { leg: 2, provider: "0xsecond".to_string(), amount: "400".to_string() },
+        ];
+        tracker.assign_legs(&order, &decision, Duration::from_secs(300), now);
+
+        // 0xthird is next in rank but cannot cover the 600 leg
+        let liquidity: HashMap<String, u128> =
+            [("0xthird".to_string(), 599), ("0xfourth".to_string(), 600)].into_iter().collect();
+        let Some(FailoverAction::Reassign { provider, leg, .. }) =
+            tracker.fail(&order.order_id, "0xfirst", Some(&liquidity), now)
+        else {
+            panic!("expected reassignment");
+        };
+        assert_eq!((provider.as_str(), leg), ("0xfourth", Some(1)));
+
+        // Nobody left with enough for the leg: refund it
+        let Some(FailoverAction::Refund { leg, failed_providers, .. }) =
+            tracker.fail(&order.order_id, "0xfourth", Some(&liquidity), now)
+        else {
+            panic!("expected refund");
+        };
+        assert_eq!(leg, Some(1));
+        assert_eq!(failed_providers, vec!["0xfirst", "0xfourth"]);
+    }
+}
@@ -0,0 +1,38 @@
+//! Provider health read from Redis
+//!
+//! The provider service keeps each provider's rolling heartbeat health under
+//! [`ProviderHealth::key`]; the router attaches it to candidates so uptime
+//! and latency can be scored.
+
+use std::collections::HashMap;
+
+use redis::aio::ConnectionManager;
+use shared_types::ProviderHealth;
+
+pub struct HealthReader {
+    conn: ConnectionManager,
+}
+
+impl HealthReader {
+    pub fn new(conn: ConnectionManager) -> Self {
+        Self { conn }
+    }
+
+    /// Latest health of each provider that has any, keyed by lowercase
+    /// address
+    pub async fn get_many(&self, providers: &[String]) -> redis::RedisResult<HashMap<String, ProviderHealth>> {
+        if providers.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let keys: Vec<String> = providers.iter().map(|p| ProviderHealth::key(p)).collect();
+        let mut conn = self.conn.clone();
+        let values: Vec<Option<String>> = redis::cmd("MGET").arg(&keys).query_async(&mut conn).await?;
+
+        Ok(values
+            .into_iter()
+            .flatten()
+            .filter_map(|value| serde_json::from_str::<ProviderHealth>(&value).ok())
+            .map(|health| (health.provider.to_lowercase(), health))
+            .collect())
+    }
+}
@@ -0,0 +1,37 @@
+//! Provider routing for pending orders
+//!
+//! Candidates are the active provider intents able to fill an order, paired
+//! with their reputation and heartbeat health. A [`Scorer`] assigns each one
+//! a score, the ranking pipeline orders them best first, and the
+//! [`RoutingEngine`] records
+//! the decision in the audit log. Per-tier policies decide which providers
+//! an order may go to at all. The [`RouterService`] routes pending orders
+//! from NATS and fails them over to the next-best provider when the assigned
+//! one fails. The [`simulation`] module replays historical
+//! orders through a scorer offline to compare strategies.
+
+pub mod audit;
+pub mod consumer;
+pub mod engine;
+pub mod error;
+pub mod failover;
+pub mod health;
+pub mod policy;
+pub mod routes;
+pub mod scoring;
+pub mod simulation;
+pub mod split;
+
+pub use audit::{explain, AuditConfig, AuditLog};
+pub use consumer::RouterService;
+pub use engine::RoutingEngine;
+pub use error::{Result, RouterError};
+pub use failover::{FailoverAction, FailoverConfig, FailoverTracker};
+pub use health::HealthReader;
+pub use policy::{Admission, PendingApprovals, TierPolicies, TierPolicy};
+pub use scoring::{
+    rank, rank_partial, ArmStats, BanditConfig, Candidate, CircuitBreaker, Features, ProviderScore, RemoteModelConfig,
+    RemoteModelScorer, Scorer, ScoringWeights, ThompsonSamplingScorer, WeightedLinearScorer,
+};
+pub use simulation::{replay, BehaviorModel, Report, Scenario};
+pub use split::SplitConfig;
@@ -1,3 +1,12 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use ai_router::{
+    routes, AuditConfig, AuditLog, BanditConfig, FailoverConfig, FailoverTracker, HealthReader, RemoteModelConfig,
+    RemoteModelScorer, RouterService, RoutingEngine, Scorer, ScoringWeights, SplitConfig, ThompsonSamplingScorer, TierPolicies,
+    WeightedLinearScorer,
+};
+use axum::{routing::get, Router};
+use shared_database::{AllocationRepository, ProviderRepository, RoutingApprovalRepository, RoutingDecisionRepository, StakeRepository};
 use tracing::info;
 
 #[tokio::main]
@@ -7,5 +16,86 @@ async fn main() -> anyhow::Result<()> {
 
     info!("AI Router Service starting...");
 
+    let weighted = WeightedLinearScorer::new(ScoringWeights::from_env()?)?;
+    info!("Scoring weights: {:?}", weighted.weights());
+    let strategy = std::env::var("ROUTER_STRATEGY").unwrap_or_else(|_| "weighted".to_string());
+    let scorer: Arc<dyn Scorer> = match (strategy.as_str(), RemoteModelConfig::from_env()) {
+        ("bandit", _) => Arc::new(ThompsonSamplingScorer::new(BanditConfig::from_env()?)),
+        (_, Some(config)) => {
+            info!("Remote model scoring via {} ({}ms budget)", config.endpoint, config.latency_budget.as_millis());
+            Arc::new(RemoteModelScorer::new(config, weighted)?)
+        }
+        (_, None) => Arc::new(weighted),
+    };
+    info!("Scoring providers with {}", scorer.version());
+
+    let pool = shared_database::initialize_database().await?;
+    let audit = Arc::new(AuditLog::new(RoutingDecisionRepository::new(pool.clone()), AuditConfig::from_env()));
+    audit.clone().spawn_retention();
+
+    let engine = RoutingEngine::new(scorer)
+        .with_audit(audit.clone())
+        .with_split(SplitConfig::from_env()?, AllocationRepository::new(pool.clone()))
+        .with_policies(TierPolicies::from_env()?);
+    let failover = FailoverTracker::new(FailoverConfig::from_env()?);
+    let nats_url = std::env::var("NATS_URL").unwrap_or_else(|_| "nats://127.0.0.1:4222".to_string());
+    let nats = shared_messaging::connect_nats(&nats_url).await?;
+    let mut service = RouterService::new(engine, ProviderRepository::new(pool.clone()), failover, nats)
+        .with_stakes(StakeRepository::new(pool.clone()))
+        .with_approval_store(RoutingApprovalRepository::new(pool));
+    if let Some(conn) = connect_redis().await {
+        service = service.with_health(HealthReader::new(conn));
+    }
+    let restored = service.restore_approvals().await?;
+    if restored > 0 {
+        info!("Restored {} routing decisions awaiting approval", restored);
+    }
+    let service = Arc::new(service);
+    let consumer = service.clone();
+    tokio::spawn(async move {
+        if let Err(e) = consumer.run().await {
+            tracing::error!("Order event consumer stopped: {}", e);
+        }
+    });
+
+    let app = Router::new()
+        .route("/health", get(health_check))
+        .merge(routes::router(audit))
+        .merge(routes::approvals_router(service));
+
+    let port = std::env::var("AI_ROUTER_PORT")
+        .ok()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(8002);
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    info!("AI Router listening on {}", addr);
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await?;
+
     Ok(())
 }
+
+/// Redis holds provider heartbeat health; without it providers are scored
+/// with neutral uptime and latency
+async fn connect_redis() -> Option<redis::aio::ConnectionManager> {
+    let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+    let client = match redis::Client::open(url) {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::warn!("Invalid REDIS_URL, provider health disabled: {}", e);
+            return None;
+        }
+    };
+    match redis::aio::ConnectionManager::new(client).await {
+        Ok(conn) => Some(conn),
+        Err(e) => {
+            tracing::warn!("Redis unavailable, provider health disabled: {}", e);
+            None
+        }
+    }
+}
+
+async fn health_check() -> &'static str {
+    "OK"
+}
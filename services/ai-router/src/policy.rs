@@ -0,0 +1,368 @@
+//! Tier-aware routing policies
+//!
+//! Larger orders carry more risk, so each [`OrderTier`] has its own bar for
+//! the providers it may be routed to: a minimum track record, a minimum
+//! stake, an optional whitelist and a fee ceiling, plus how long the chosen
+//! provider has to propose. Candidates below the bar are dropped before
+//! ranking.
+//!
+//! A tier requiring manual approval routes only to whitelisted providers on
+//! its own. When none of them can take an order, the decision is ranked over
+//! the remaining candidates and held in [`PendingApprovals`] until an admin
+//! approves or rejects it. Held decisions are saved so they survive a
+//! restart.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use shared_database::{models::RoutingApprovalModel, RoutingApprovalRepository};
+use shared_types::{helpers::hex_to_bytes, Order, OrderTier, RoutingDecision};
+
+use crate::error::{Result, RouterError};
+use crate::scoring::Candidate;
+
+const TIERS: [OrderTier; 5] = [OrderTier::Alpha, OrderTier::Beta, OrderTier::Delta, OrderTier::Omega, OrderTier::Titan];
+
+/// Requirements on the providers an order of one tier may be routed to
+#[derive(Debug, Clone)]
+pub struct TierPolicy {
+    /// Lowest success rate accepted, 0.0 to 1.0
+    pub min_success_rate: f64,
+    /// Fewest orders a provider must have handled
+    pub min_orders: u64,
+    /// Least collateral a provider must have staked (in wei); providers with
+    /// unknown stake only pass when this is zero
+    pub min_stake: u128,
+    /// Providers the tier is limited to; empty allows any
+    pub allowed_providers: HashSet<String>,
+    /// Highest minimum fee a provider may ask, in basis points
+    pub max_fee_bps: Option<u64>,
+    /// How long the chosen provider has to propose
+    pub proposal_deadline: Duration,
+    /// Hold decisions for admin approval unless they go to whitelisted
+    /// providers
+    pub manual_approval: bool,
+}
+
+/// How a policy treats one candidate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    Admitted,
+    /// Eligible only once an admin approves the decision
+    NeedsApproval,
+    Rejected,
+}
+
+impl TierPolicy {
+    fn open(proposal_deadline_secs: u64) -> Self {
+        Self {
+            min_success_rate: 0.0,
+            min_orders: 0,
+            min_stake: 0,
+            allowed_providers: HashSet::new(),
+            max_fee_bps: None,
+            proposal_deadline: Duration::from_secs(proposal_deadline_secs),
+            manual_approval: false,
+        }
+    }
+
+    /// Whether `candidate` meets the bar for this tier
+    pub fn admit(&self, candidate: &Candidate) -> Admission {
+        let reputation = &candidate.reputation;
+        let meets_bar = reputation.total_orders >= self.min_orders
+            && reputation.success_rate() >= self.min_success_rate
+            && candidate.stake.unwrap_or(0) >= self.min_stake
+            && self.max_fee_bps.is_none_or(|max| candidate.intent.min_fee_bps <= max);
+        if !meets_bar {
+            return Admission::Rejected;
+        }
+
+        match (self.is_whitelisted(&candidate.intent.provider), self.manual_approval) {
+            (true, _) => Admission::Admitted,
+            (false, true) => Admission::NeedsApproval,
+            (false, false) => Admission::Rejected,
+        }
+    }
+
+    /// Whether the provider may take orders without approval, as far as the
+    /// whitelist goes
+    pub fn is_whitelisted(&self, provider: &str) -> bool {
+        if self.allowed_providers.is_empty() {
+            return !self.manual_approval;
+        }
+        self.allowed_providers.contains(&provider.to_lowercase())
+    }
+
+    fn from_env(tier: OrderTier, defaults: Self) -> Result<Self> {
+        let prefix = format!("ROUTER_TIER_{}", tier.as_str());
+        let var = |name: &str| std::env::var(format!("{}_{}", prefix, name)).ok().filter(|v| !v.trim().is_empty());
+        let invalid = |name: &str| RouterError::InvalidConfig(format!("{}_{} is invalid", prefix, name));
+
+        let mut policy = defaults;
+        if let Some(v) = var("MIN_SUCCESS_RATE") {
+            policy.min_success_rate = v.parse().map_err(|_| invalid("MIN_SUCCESS_RATE"))?;
+        }
+        if let Some(v) = var("MIN_ORDERS") {
+            policy.min_orders = v.parse().map_err(|_| invalid("MIN_ORDERS"))?;
+        }
+        if let Some(v) = var("MIN_STAKE") {
+            policy.min_stake = v.parse().map_err(|_| invalid("MIN_STAKE"))?;
+        }
+        if let Some(v) = var("ALLOWED_PROVIDERS") {
+            policy.allowed_providers = v
+                .split(',')
+                .map(|p| p.trim().to_lowercase())
+                .filter(|p| !p.is_empty())
+                .collect();
+        }
+        if let Some(v) = var("MAX_FEE_BPS") {
+            policy.max_fee_bps = Some(v.parse().map_err(|_| invalid("MAX_FEE_BPS"))?);
+        }
+        if let Some(v) = var("PROPOSAL_DEADLINE_SECS") {
+            policy.proposal_deadline = Duration::from_secs(v.parse().map_err(|_| invalid("PROPOSAL_DEADLINE_SECS"))?);
+        }
+        if let Some(v) = var("MANUAL_APPROVAL") {
+            policy.manual_approval = v.parse().map_err(|_| invalid("MANUAL_APPROVAL"))?;
+        }
+
+        if !(0.0..=1.0).contains(&policy.min_success_rate) {
+            return Err(RouterError::InvalidConfig(format!("{}_MIN_SUCCESS_RATE must be between 0 and 1", prefix)));
+        }
+        if policy.proposal_deadline.is_zero() {
+            return Err(RouterError::InvalidConfig(format!("{}_PROPOSAL_DEADLINE_SECS must be positive", prefix)));
+        }
+        Ok(policy)
+    }
+}
+
+/// Routing policy of every tier
+#[derive(Debug, Clone)]
+pub struct TierPolicies {
+    policies: HashMap<OrderTier, TierPolicy>,
+}
+
+impl Default for TierPolicies {
+    /// Open policies for small orders, tightening with size; Titan orders
+    /// need approval until providers are whitelisted
+    fn default() -> Self {
+        let policies = HashMap::from([
+            (OrderTier::Alpha, TierPolicy::open(120)),
+            (OrderTier::Beta, TierPolicy::open(180)),
+            (OrderTier::Delta, TierPolicy { min_success_rate: 0.5, min_orders: 5, ..TierPolicy::open(300) }),
+            (OrderTier::Omega, TierPolicy { min_success_rate: 0.8, min_orders: 20, ..TierPolicy::open(600) }),
+            (
+                OrderTier::Titan,
+                TierPolicy {
+                    min_success_rate: 0.9,
+                    min_orders: 50,
+                    manual_approval: true,
+                    ..TierPolicy::open(900)
+                },
+            ),
+        ]);
+        Self { policies }
+    }
+}
+
+impl TierPolicies {
+    /// Load from `ROUTER_TIER_<TIER>_MIN_SUCCESS_RATE`, `_MIN_ORDERS`,
+    /// `_MIN_STAKE`, `_ALLOWED_PROVIDERS` (comma-separated), `_MAX_FEE_BPS`,
+    /// `_PROPOSAL_DEADLINE_SECS` and `_MANUAL_APPROVAL`, falling back to
+    /// defaults for unset variables
+    pub fn from_env() -> Result<Self> {
+        let mut defaults = Self::default();
+        let mut policies = HashMap::new();
+        for tier in TIERS {
+            let default = defaults.policies.remove(&tier).unwrap_or_else(|| TierPolicy::open(300));
+            policies.insert(tier, TierPolicy::from_env(tier, default)?);
+        }
+        Ok(Self { policies })
+    }
+
+    pub fn get(&self, tier: OrderTier) -> &TierPolicy {
+        &self.policies[&tier]
+    }
+
+    /// Split candidates into those the order's tier admits outright and those
+    /// it admits only with approval, dropping the rest
+    pub fn partition(&self, order: &Order, candidates: &[Candidate]) -> (Vec<Candidate>, Vec<Candidate>) {
+        let policy = self.get(order.tier);
+        let mut admitted = Vec::new();
+        let mut needs_approval = Vec::new();
+        for candidate in candidates {
+            match policy.admit(candidate) {
+                Admission::Admitted => admitted.push(candidate.clone()),
+                Admission::NeedsApproval => needs_approval.push(candidate.clone()),
+                Admission::Rejected => {}
+            }
+        }
+        (admitted, needs_approval)
+    }
+
+    /// Whether a decision assigns the order to anyone the tier only accepts
+    /// with approval
+    pub fn needs_approval(&self, order: &Order, decision: &RoutingDecision) -> bool {
+        let policy = self.get(order.tier);
+        decision
+            .chosen_provider
+            .iter()
+            .chain(decision.allocations.iter().map(|leg| &leg.provider))
+            .any(|provider| !policy.is_whitelisted(provider))
+    }
+}
+
+/// Decisions waiting for an admin, keyed by order
+///
+/// With a store attached, held decisions are also written to the database
+/// and restored on startup, so a restart does not drop them.
+#[derive(Default)]
+pub struct PendingApprovals {
+    held: Mutex<HashMap<String, (Order, RoutingDecision)>>,
+    store: Option<RoutingApprovalRepository>,
+}
+
+impl PendingApprovals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep held decisions in the database as well as in memory
+    pub fn with_store(mut self, store: RoutingApprovalRepository) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Load the decisions held before a restart, returning how many there were
+    pub async fn restore(&self) -> Result<usize> {
+        let Some(store) = &self.store else {
+            return Ok(0);
+        };
+        let mut held = Vec::new();
+        for approval in store.list().await? {
+            held.push(approval.to_domain()?);
+        }
+        let count = held.len();
+        let mut map = self.lock();
+        for (order, decision) in held {
+            map.insert(order.order_id.to_lowercase(), (order, decision));
+        }
+        Ok(count)
+    }
+
+    /// Hold a decision, saving it first when a store is attached
+    pub async fn hold(&self, order: Order, decision: RoutingDecision) -> Result<()> {
+        if let Some(store) = &self.store {
+            store.hold(&RoutingApprovalModel::from_domain(&order, &decision)?).await?;
+        }
+        self.lock().insert(order.order_id.to_lowercase(), (order, decision));
+        Ok(())
+    }
+
+    /// Remove a held decision to approve or reject it
+    pub async fn take(&self, order_id: &str) -> Result<Option<(Order, RoutingDecision)>> {
+        let held = self.lock().remove(&order_id.to_lowercase());
+        let Some(store) = &self.store else {
+            return Ok(held);
+        };
+        let stored = store.take(&hex_to_bytes(order_id)).await?;
+        match (held, stored) {
+            (Some(held), _) => Ok(Some(held)),
+            (None, Some(approval)) => Ok(Some(approval.to_domain()?)),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Held decisions, oldest first
+    pub fn list(&self) -> Vec<RoutingDecision> {
+        let mut decisions: Vec<RoutingDecision> = self.lock().values().map(|(_, decision)| decision.clone()).collect();
+        decisions.sort_by_key(|decision| decision.decided_at);
+        decisions
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Order, RoutingDecision)>> {
+        self.held.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::test_support::{candidate, order};
+
+    #[test]
+    fn test_tier_policy_admission() {
+        let policies = TierPolicies::default();
+        let veteran = candidate("0xveteran", "100000", 100, 60, 2, 60);
+        let newcomer = candidate("0xnewcomer", "100000", 100, 0, 0, 0);
+
+        let mut order = order("1000");
+        let (admitted, held) = policies.partition(&order, &[veteran.clone(), newcomer.clone()]);
+        assert_eq!((admitted.len(), held.len()), (2, 0));
+
+        // Omega requires a track record
+        order.tier = OrderTier::Omega;
+        let (admitted, _) = policies.partition(&order, &[veteran.clone(), newcomer.clone()]);
+        assert_eq!(admitted.iter().map(|c| c.intent.provider.as_str()).collect::<Vec<_>>(), vec!["0xveteran"]);
+
+        // Stake and fee ceilings
+        let strict = TierPolicy { min_stake: 1000, ..TierPolicy::open(60) };
+        assert_eq!(strict.admit(&veteran), Admission::Rejected);
+        let staked = Candidate { stake: Some(1000), ..veteran.clone() };
+        assert_eq!(strict.admit(&staked), Admission::Admitted);
+        let capped = TierPolicy { max_fee_bps: Some(50), ..TierPolicy::open(60) };
+        assert_eq!(capped.admit(&veteran), Admission::Rejected);
+    }
+
+    #[test]
+    fn test_titan_requires_whitelist_or_approval() {
+        let mut policies = TierPolicies::default();
+        let veteran = candidate("0xVeteran", "100000", 100, 60, 2, 60);
+        let mut order = order("1000");
+        order.tier = OrderTier::Titan;
+
+        let (admitted, held) = policies.partition(&order, std::slice::from_ref(&veteran));
+        assert_eq!((admitted.len(), held.len()), (0, 1));
+
+        let titan = policies.policies.get_mut(&OrderTier::Titan).unwrap();
+        titan.allowed_providers.insert("0xveteran".to_string());
+        let (admitted, held) = policies.partition(&order, std::slice::from_ref(&veteran));
+        assert_eq!((admitted.len(), held.len()), (1, 0));
+
+        // Without manual approval, providers off the whitelist are dropped
+        let whitelist_only = TierPolicy {
+            allowed_providers: HashSet::from(["0xother".to_string()]),
+            ..TierPolicy::open(60)
+        };
+        assert_eq!(whitelist_only.admit(&veteran), Admission::Rejected);
+    }
+
+    #[tokio::test]
+    async fn test_held_decisions_survive_being_saved() {
+        use crate::audit::explain;
+        use crate::scoring::{rank, ScoringWeights, WeightedLinearScorer};
+
+        let scorer = WeightedLinearScorer::new(ScoringWeights::default()).unwrap();
+        let mut order = order("1000");
+        order.order_id = "0xABCD".to_string();
+        order.tier = OrderTier::Titan;
+        let ranked = rank(&scorer, &order, &[candidate("0xveteran", "100000", 100, 60, 2, 60)]).await.unwrap();
+        let decision = explain(&order, &scorer, &ranked, chrono::Utc::now());
+
+        // What a restart restores is what was held
+        let saved = RoutingApprovalModel::from_domain(&order, &decision).unwrap();
+        assert_eq!(saved.order_id, vec![0xab, 0xcd]);
+        let (restored_order, restored_decision) = saved.to_domain().unwrap();
+        assert_eq!(restored_order.order_id, order.order_id);
+        assert_eq!(restored_order.tier, OrderTier::Titan);
+        assert_eq!(restored_decision.id, decision.id);
+        assert_eq!(restored_decision.chosen_provider, decision.chosen_provider);
+
+        let approvals = PendingApprovals::new();
+        approvals.hold(order, decision.clone()).await.unwrap();
+        assert_eq!(approvals.list().len(), 1);
+        let (_, taken) = approvals.take("0xabcd").await.unwrap().unwrap();
+        assert_eq!(taken.id, decision.id);
+        assert!(approvals.take("0xabcd").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,88 @@
+//! Admin endpoints for inspecting routing decisions and approving held ones
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use shared_types::RoutingDecision;
+use uuid::Uuid;
+
+use crate::audit::AuditLog;
+use crate::consumer::RouterService;
+use crate::error::{Result, RouterError};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 500;
+
+pub fn router(audit: Arc<AuditLog>) -> Router {
+    Router::new()
+        .route("/admin/routing/decisions", get(list_decisions))
+        .route("/admin/routing/decisions/:id", get(get_decision))
+        .with_state(audit)
+}
+
+pub fn approvals_router(service: Arc<RouterService>) -> Router {
+    Router::new()
+        .route("/admin/routing/approvals", get(list_approvals))
+        .route("/admin/routing/approvals/:order_id/approve", post(approve))
+        .route("/admin/routing/approvals/:order_id/reject", post(reject))
+        .with_state(service)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DecisionQuery {
+    /// Decisions for one order
+    pub order_id: Option<String>,
+    /// Decisions a provider was a candidate in
+    pub provider: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Recent decisions, filtered by order or by candidate provider
+async fn list_decisions(
+    State(audit): State<Arc<AuditLog>>,
+    Query(query): Query<DecisionQuery>,
+) -> Result<Json<Vec<RoutingDecision>>> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let decisions = match (query.order_id, query.provider) {
+        (Some(_), Some(_)) => {
+            return Err(RouterError::InvalidRequest("Filter by order_id or provider, not both".to_string()))
+        }
+        (Some(order_id), None) => audit.list_for_order(&order_id).await?,
+        (None, Some(provider)) => audit.list_for_provider(&provider, limit).await?,
+        (None, None) => audit.list_recent(limit).await?,
+    };
+    Ok(Json(decisions))
+}
+
+async fn get_decision(State(audit): State<Arc<AuditLog>>, Path(id): Path<Uuid>) -> Result<Json<RoutingDecision>> {
+    Ok(Json(audit.get(id).await?))
+}
+
+/// Decisions held for approval by their tier policy
+async fn list_approvals(State(service): State<Arc<RouterService>>) -> Json<Vec<RoutingDecision>> {
+    Json(service.pending_approvals())
+}
+
+async fn approve(State(service): State<Arc<RouterService>>, Path(order_id): Path<String>) -> Result<Json<RoutingDecision>> {
+    Ok(Json(service.approve(&order_id).await?))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RejectRequest {
+    pub reason: Option<String>,
+}
+
+async fn reject(
+    State(service): State<Arc<RouterService>>,
+    Path(order_id): Path<String>,
+    Json(request): Json<RejectRequest>,
+) -> Result<Json<serde_json::Value>> {
+    let reason = request.reason.unwrap_or_else(|| "routing rejected by admin".to_string());
+    service.reject(&order_id, &reason).await?;
+    Ok(Json(serde_json::json!({ "order_id": order_id, "status": "rejected" })))
+}
@@ -0,0 +1,350 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use shared_types::{Currency, Order, OrderTier, ProviderReputation};
+
+use super::{Candidate, Features, ProviderScore, Scorer};
+use crate::error::{Result, RouterError};
+
+/// Thompson sampling settings
+#[derive(Debug, Clone)]
+pub struct BanditConfig {
+    /// Outcomes in a currency and tier before a provider stops counting as
+    /// exploration there
+    pub min_observations: u64,
+    /// Largest share of decisions per currency and tier that may go to
+    /// providers still being explored
+    pub max_exploration_share: f64,
+    /// Pseudo-observations a provider's overall reputation contributes to the
+    /// prior for a currency and tier it has no history in
+    pub prior_weight: f64,
+    /// Fixed RNG seed for reproducible runs; random when None
+    pub seed: Option<u64>,
+}
+
+impl Default for BanditConfig {
+    fn default() -> Self {
+        Self {
+            min_observations: 20,
+            max_exploration_share: 0.10,
+            prior_weight: 10.0,
+            seed: None,
+        }
+    }
+}
+
+impl BanditConfig {
+    /// Load from `ROUTER_BANDIT_*`, falling back to defaults for unset variables
+    pub fn from_env() -> Result<Self> {
+        let defaults = Self::default();
+        let env = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+        let invalid = |key: &str, value: &str| RouterError::InvalidConfig(format!("{} is invalid: {}", key, value));
+
+        let mut config = defaults;
+        if let Some(value) = env("ROUTER_BANDIT_MIN_OBSERVATIONS") {
+            config.min_observations = value.trim().parse().map_err(|_| invalid("ROUTER_BANDIT_MIN_OBSERVATIONS", &value))?;
+        }
+        if let Some(value) = env("ROUTER_BANDIT_MAX_EXPLORATION") {
+            config.max_exploration_share = value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|share| (0.0..=1.0).contains(share))
+                .ok_or_else(|| invalid("ROUTER_BANDIT_MAX_EXPLORATION", &value))?;
+        }
+        if let Some(value) = env("ROUTER_BANDIT_SEED") {
+            config.seed = Some(value.trim().parse().map_err(|_| invalid("ROUTER_BANDIT_SEED", &value))?);
+        }
+        Ok(config)
+    }
+}
+
+/// Recorded outcomes for a provider in one currency and tier
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ArmStats {
+    pub successes: u64,
+    pub failures: u64,
+}
+
+impl ArmStats {
+    pub fn observations(&self) -> u64 {
+        self.successes + self.failures
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ArmKey {
+    provider: String,
+    currency: Currency,
+    tier: OrderTier,
+}
+
+impl ArmKey {
+    fn new(provider: &str, currency: &Currency, tier: OrderTier) -> Self {
+        Self {
+            provider: provider.to_lowercase(),
+            currency: currency.clone(),
+            tier,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct SegmentTraffic {
+    decisions: u64,
+    explorations: u64,
+}
+
+#[derive(Debug)]
+struct BanditState {
+    rng: StdRng,
+    arms: HashMap<ArmKey, ArmStats>,
+    traffic: HashMap<(Currency, OrderTier), SegmentTraffic>,
+}
+
+/// Thompson sampling over a Beta posterior per provider, currency and tier
+///
+/// Each candidate's score is a draw from its posterior success probability,
+/// so uncertain providers are tried in proportion to their chance of being
+/// the best. Providers with fewer than `min_observations` outcomes in a
+/// segment are exploratory there and may win at most
+/// `max_exploration_share` of its decisions while an established provider
+/// is available.
+pub struct ThompsonSamplingScorer {
+    config: BanditConfig,
+    state: Mutex<BanditState>,
+}
+
+impl ThompsonSamplingScorer {
+    pub fn new(config: BanditConfig) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self {
+            config,
+            state: Mutex::new(BanditState {
+                rng,
+                arms: HashMap::new(),
+                traffic: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Record whether an order routed to a provider settled
+    pub fn record_outcome(&self, provider: &str, currency: &Currency, tier: OrderTier, success: bool) {
+        let mut state = self.state.lock().unwrap();
+        let stats = state.arms.entry(ArmKey::new(provider, currency, tier)).or_default();
+        if success {
+            stats.successes += 1;
+        } else {
+            stats.failures += 1;
+        }
+    }
+
+    pub fn stats(&self, provider: &str, currency: &Currency, tier: OrderTier) -> ArmStats {
+        let state = self.state.lock().unwrap();
+        state
+            .arms
+            .get(&ArmKey::new(provider, currency, tier))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Beta parameters for an arm: uniform prior, plus the provider's
+    /// overall reputation scaled down to `prior_weight` observations, plus
+    /// outcomes recorded in this segment
+    fn posterior(&self, stats: ArmStats, reputation: &ProviderReputation) -> (f64, f64) {
+        let good = reputation.successful_orders as f64;
+        let bad = (reputation.failed_orders + reputation.no_shows) as f64;
+        let scale = if good + bad > 0.0 {
+            (self.config.prior_weight / (good + bad)).min(1.0)
+        } else {
+            0.0
+        };
+        (
+            1.0 + good * scale + stats.successes as f64,
+            1.0 + bad * scale + stats.failures as f64,
+        )
+    }
+}
+
+#[async_trait]
+impl Scorer for ThompsonSamplingScorer {
+    fn name(&self) -> &str {
+        "thompson-sampling"
+    }
+
+    fn version(&self) -> String {
+        format!(
+            "thompson-sampling@{} min_observations={} max_exploration={}",
+            env!("CARGO_PKG_VERSION"),
+            self.config.min_observations,
+            self.config.max_exploration_share
+        )
+    }
+
+    async fn score(&self, order: &Order, candidates: &[Candidate]) -> Result<Vec<ProviderScore>> {
+        let mut state = self.state.lock().unwrap();
+        let segment = (order.currency.clone(), order.tier);
+        let traffic = state.traffic.get(&segment).copied().unwrap_or_default();
+
+        let mut scores = Vec::with_capacity(candidates.len());
+        let mut exploratory = Vec::with_capacity(candidates.len());
+        for candidate in candidates {
+            let stats = state
+                .arms
+                .get(&ArmKey::new(&candidate.intent.provider, &order.currency, order.tier))
+                .copied()
+                .unwrap_or_default();
+            let (alpha, beta) = self.posterior(stats, &candidate.reputation);
+            let sampled = sample_beta(&mut state.rng, alpha, beta);
+            scores.push(ProviderScore {
+                provider: candidate.intent.provider.clone(),
+                score: sampled,
+                features: Features::extract(order, candidate),
+                contributions: BTreeMap::from([("sampled_success_rate".to_string(), sampled)]),
+            });
+            exploratory.push(stats.observations() < self.config.min_observations);
+        }
+
+        // Over the cap, exploratory providers rank below every established one
+        let has_established = exploratory.iter().any(|e| !e);
+        let budget_left =
+            (traffic.explorations as f64) < self.config.max_exploration_share * (traffic.decisions + 1) as f64;
+        if has_established && !budget_left {
+            for (score, exploring) in scores.iter_mut().zip(&exploratory) {
+                if *exploring {
+                    score.score -= 1.0;
+                    score.contributions.insert("exploration_cap".to_string(), -1.0);
+                }
+            }
+        }
+
+        let winner = scores
+            .iter()
+            .zip(&exploratory)
+            .max_by(|(a, _), (b, _)| {
+                a.score
+                    .partial_cmp(&b.score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| b.provider.cmp(&a.provider))
+            })
+            .map(|(_, exploring)| *exploring);
+        if let Some(exploring) = winner {
+            let traffic = state.traffic.entry(segment).or_default();
+            traffic.decisions += 1;
+            if exploring && has_established {
+                traffic.explorations += 1;
+            }
+        }
+
+        Ok(scores)
+    }
+
+    fn observe_outcome(&self, order: &Order, provider: &str, success: bool) {
+        self.record_outcome(provider, &order.currency, order.tier, success);
+    }
+}
+
+fn sample_normal(rng: &mut StdRng) -> f64 {
+    // Box-Muller; 1 - u keeps the logarithm finite
+    let u: f64 = 1.0 - rng.gen::<f64>();
+    let v: f64 = rng.gen();
+    (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+}
+
+/// Gamma(shape, 1) by Marsaglia and Tsang; shapes below one are boosted
+fn sample_gamma(rng: &mut StdRng, shape: f64) -> f64 {
+    if shape < 1.0 {
+        let u: f64 = 1.0 - rng.gen::<f64>();
+        return sample_gamma(rng, shape + 1.0) * u.powf(1.0 / shape);
+    }
+
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = sample_normal(rng);
+        let v = (1.0 + c * x).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u: f64 = 1.0 - rng.gen::<f64>();
+        if u.ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
+
+fn sample_beta(rng: &mut StdRng, alpha: f64, beta: f64) -> f64 {
+    let x = sample_gamma(rng, alpha);
+    let y = sample_gamma(rng, beta);
+    x / (x + y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::rank;
+    use crate::scoring::test_support::{candidate, order};
+
+    #[test]
+    fn test_beta_samples_match_posterior_mean() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let n = 20_000;
+        let mean = (0..n).map(|_| sample_beta(&mut rng, 9.0, 3.0)).sum::<f64>() / n as f64;
+        assert!((mean - 0.75).abs() < 0.01, "mean {}", mean);
+        assert!((0..1000).all(|_| (0.0..=1.0).contains(&sample_beta(&mut rng, 0.5, 0.5))));
+    }
+
+    #[tokio::test]
+    async fn test_seeded_runs_are_reproducible_and_favour_better_providers() {
+        let config = BanditConfig { seed: Some(42), max_exploration_share: 1.0, ..BanditConfig::default() };
+        let candidates = vec![candidate("0xgood", "100000", 100, 0, 0, 0), candidate("0xbad", "100000", 100, 0, 0, 0)];
+        let order = order("1000");
+
+        let run = || async {
+            let bandit = ThompsonSamplingScorer::new(config.clone());
+            for _ in 0..50 {
+                bandit.record_outcome("0xgood", &order.currency, order.tier, true);
+                bandit.record_outcome("0xbad", &order.currency, order.tier, false);
+            }
+            let mut wins = Vec::new();
+            for _ in 0..200 {
+                wins.push(rank(&bandit, &order, &candidates).await.unwrap()[0].provider.clone());
+            }
+            wins
+        };
+
+        let first = run().await;
+        assert_eq!(first, run().await);
+        assert!(first.iter().filter(|p| *p == "0xgood").count() > 190);
+    }
+
+    #[tokio::test]
+    async fn test_exploration_is_capped_but_not_starved() {
+        let config = BanditConfig { seed: Some(1), max_exploration_share: 0.1, ..BanditConfig::default() };
+        let bandit = ThompsonSamplingScorer::new(config);
+        let order = order("1000");
+        // Established but mediocre, so an unknown provider would often win
+        for i in 0..100 {
+            bandit.record_outcome("0xincumbent", &order.currency, order.tier, i % 2 == 0);
+        }
+        let candidates = vec![
+            candidate("0xincumbent", "100000", 100, 0, 0, 0),
+            candidate("0xnewcomer", "100000", 100, 0, 0, 0),
+        ];
+
+        let mut newcomer_wins = 0;
+        for _ in 0..500 {
+            if rank(&bandit, &order, &candidates).await.unwrap()[0].provider == "0xnewcomer" {
+                newcomer_wins += 1;
+            }
+        }
+        assert!(newcomer_wins > 0);
+        assert!(newcomer_wins <= 51, "{} exploratory wins", newcomer_wins);
+        assert_eq!(bandit.stats("0xnewcomer", &order.currency, order.tier), ArmStats::default());
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use shared_types::Order;
+
+use super::Candidate;
+
+/// Fee above which a provider is considered as expensive as it gets
+const MAX_FEE_BPS: f64 = 1000.0;
+
+/// Settlement time at which the speed feature drops to one half
+const REFERENCE_SETTLEMENT_SECS: f64 = 300.0;
+
+/// p95 heartbeat latency at which the latency feature drops to one half
+const REFERENCE_LATENCY_MS: f64 = 1000.0;
+
+/// Value of features the candidate has no data for
+fn neutral() -> f64 {
+    0.5
+}
+
+/// Normalized scoring inputs for one candidate, each in `[0, 1]` where higher
+/// is better
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Features {
+    /// Share of attempted orders settled successfully
+    pub success_rate: f64,
+    /// Share of proposals the provider responded to
+    pub reliability: f64,
+    /// Inverse of average settlement time
+    pub speed: f64,
+    /// Inverse of the provider's minimum fee
+    pub cost: f64,
+    /// Liquidity left after filling this order, as a share of what is available
+    pub capacity: f64,
+    /// Share of recent heartbeat intervals the provider was up
+    #[serde(default = "neutral")]
+    pub uptime: f64,
+    /// Inverse of the provider's p95 heartbeat latency
+    #[serde(default = "neutral")]
+    pub latency: f64,
+}
+
+impl Features {
+    /// Extract features for a candidate against an order
+    ///
+    /// Providers without history get neutral (0.5) success, reliability and
+    /// speed so they are neither favoured nor shut out; the same goes for
+    /// uptime and latency when no heartbeats are on record.
+    pub fn extract(order: &Order, candidate: &Candidate) -> Self {
+        let reputation = &candidate.reputation;
+        let intent = &candidate.intent;
+
+        let speed = if reputation.successful_orders == 0 {
+            0.5
+        } else {
+            1.0 / (1.0 + reputation.avg_settlement_time_seconds as f64 / REFERENCE_SETTLEMENT_SECS)
+        };
+
+        let available: u128 = intent.available_amount.parse().unwrap_or(0);
+        let requested: u128 = order.amount.parse().unwrap_or(u128::MAX);
+        let capacity = if available == 0 || requested > available {
+            0.0
+        } else {
+            (available - requested) as f64 / available as f64
+        };
+
+        let (uptime, latency) = match &candidate.health {
+            Some(health) => (
+                health.uptime.clamp(0.0, 1.0),
+                health
+                    .latency_p95_ms
+                    .map_or_else(neutral, |p95| 1.0 / (1.0 + p95 as f64 / REFERENCE_LATENCY_MS)),
+            ),
+            None => (neutral(), neutral()),
+        };
+
+        Self {
+            success_rate: reputation.success_rate().clamp(0.0, 1.0),
+            reliability: reputation.reliability_score().clamp(0.0, 1.0),
+            speed,
+            cost: (1.0 - intent.min_fee_bps as f64 / MAX_FEE_BPS).clamp(0.0, 1.0),
+            capacity,
+            uptime,
+            latency,
+        }
+    }
+
+    /// Named values, as recorded in routing decisions
+    pub fn to_map(&self) -> BTreeMap<String, f64> {
+        BTreeMap::from([
+            ("success_rate".to_string(), self.success_rate),
+            ("reliability".to_string(), self.reliability),
+            ("speed".to_string(), self.speed),
+            ("cost".to_string(), self.cost),
+            ("capacity".to_string(), self.capacity),
+            ("uptime".to_string(), self.uptime),
+            ("latency".to_string(), self.latency),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::test_support::{candidate, order};
+    use chrono::Utc;
+    use shared_types::{HeartbeatStatus, ProviderHealth};
+
+    #[test]
+    fn test_health_features_are_neutral_without_heartbeats() {
+        let order = order("1000");
+        let mut provider = candidate("0xa", "100000", 100, 10, 0, 60);
+        let unknown = Features::extract(&order, &provider);
+        assert_eq!((unknown.uptime, unknown.latency), (0.5, 0.5));
+
+        provider.health = Some(ProviderHealth {
+            provider: "0xa".to_string(),
+            status: HeartbeatStatus::Online,
+            uptime: 0.9,
+            latency_p50_ms: Some(200),
+            latency_p95_ms: Some(1000),
+            latency_p99_ms: Some(1500),
+            heartbeats: 120,
+            last_heartbeat_at: Some(Utc::now()),
+            window_seconds: 3600,
+            computed_at: Utc::now(),
+        });
+        let healthy = Features::extract(&order, &provider);
+        assert_eq!(healthy.uptime, 0.9);
+        assert_eq!(healthy.latency, 0.5);
+        assert_eq!(healthy.to_map()["uptime"], 0.9);
+    }
+}
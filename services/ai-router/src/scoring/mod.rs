@@ -0,0 +1,281 @@
+//! Provider scoring
+//!
+//! [`rank`] drops candidates that cannot fill the order, asks a [`Scorer`] for
+//! a score per remaining candidate, and sorts them best first. Ties break on
+//! provider address so the same inputs always produce the same ranking.
+//! [`rank_partial`] does the same for candidates able to fill only part of
+//! the order, for splitting it across providers.
+
+pub mod bandit;
+pub mod breaker;
+pub mod features;
+pub mod remote;
+pub mod weighted;
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use shared_types::{Order, ProviderHealth, ProviderIntent, ProviderReputation};
+
+use crate::error::{Result, RouterError};
+pub use bandit::{ArmStats, BanditConfig, ThompsonSamplingScorer};
+pub use breaker::CircuitBreaker;
+pub use features::Features;
+pub use remote::{RemoteModelConfig, RemoteModelScorer};
+pub use weighted::{ScoringWeights, WeightedLinearScorer};
+
+/// A provider intent considered for an order, with the provider's track record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Candidate {
+    pub intent: ProviderIntent,
+    pub reputation: ProviderReputation,
+    /// Collateral the provider has staked (in wei); None when unknown
+    #[serde(default)]
+    pub stake: Option<u128>,
+    /// Rolling heartbeat health; None when the provider sends none
+    #[serde(default)]
+    pub health: Option<ProviderHealth>,
+}
+
+impl Candidate {
+    /// Whether the intent is live, in the order's currency and large enough
+    pub fn is_eligible(&self, order: &Order) -> bool {
+        self.intent.is_valid()
+            && self.intent.currency == order.currency
+            && self.intent.can_handle_amount(&order.amount)
+    }
+
+    /// Whether the intent is live, in the order's currency and has any
+    /// liquidity to contribute to a split
+    pub fn can_fill_part(&self, order: &Order) -> bool {
+        self.intent.is_valid()
+            && self.intent.currency == order.currency
+            && self.available_amount() > 0
+    }
+
+    /// Liquidity the provider has on offer, zero when unparseable
+    pub fn available_amount(&self) -> u128 {
+        self.intent.available_amount.parse().unwrap_or(0)
+    }
+}
+
+/// Score assigned to one provider, higher is better
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderScore {
+    pub provider: String,
+    pub score: f64,
+    /// Inputs the score was computed from, kept for explainability
+    pub features: Features,
+    /// Amount each input added to the score
+    pub contributions: BTreeMap<String, f64>,
+}
+
+/// Assigns scores to the candidates for an order
+#[async_trait]
+pub trait Scorer: Send + Sync {
+    /// Name recorded alongside routing decisions
+    fn name(&self) -> &str;
+
+    /// Name plus anything that changes how scores are computed
+    fn version(&self) -> String {
+        format!("{}@{}", self.name(), env!("CARGO_PKG_VERSION"))
+    }
+
+    /// Score every candidate; order of the result does not matter
+    async fn score(&self, order: &Order, candidates: &[Candidate]) -> Result<Vec<ProviderScore>>;
+
+    /// Learn from how an order routed to `provider` turned out; scorers that
+    /// do not adapt ignore it
+    fn observe_outcome(&self, _order: &Order, _provider: &str, _success: bool) {}
+}
+
+/// Rank the eligible candidates for an order, best first
+///
+/// # Returns
+/// * `Result<Vec<ProviderScore>>` - Empty when no candidate can fill the
+///   order; an error when the scorer fails or returns scores that do not
+///   match the candidates
+pub async fn rank(scorer: &dyn Scorer, order: &Order, candidates: &[Candidate]) -> Result<Vec<ProviderScore>> {
+    rank_where(scorer, order, candidates, Candidate::is_eligible).await
+}
+
+/// Rank the candidates able to fill any part of an order, best first
+pub async fn rank_partial(scorer: &dyn Scorer, order: &Order, candidates: &[Candidate]) -> Result<Vec<ProviderScore>> {
+    rank_where(scorer, order, candidates, Candidate::can_fill_part).await
+}
+
+async fn rank_where(
+    scorer: &dyn Scorer,
+    order: &Order,
+    candidates: &[Candidate],
+    eligible: fn(&Candidate, &Order) -> bool,
+) -> Result<Vec<ProviderScore>> {
+    let eligible: Vec<Candidate> = candidates
+        .iter()
+        .filter(|candidate| eligible(candidate, order))
+        .cloned()
+        .collect();
+    if eligible.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut scores = scorer.score(order, &eligible).await?;
+    validate_scores(scorer.name(), &eligible, &scores)?;
+
+    scores.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.provider.cmp(&b.provider))
+    });
+    Ok(scores)
+}
+
+/// Every candidate must get exactly one finite score
+fn validate_scores(scorer: &str, candidates: &[Candidate], scores: &[ProviderScore]) -> Result<()> {
+    let invalid = |message: String| RouterError::Scorer { scorer: scorer.to_string(), message };
+
+    if scores.len() != candidates.len() {
+        return Err(invalid(format!("returned {} scores for {} candidates", scores.len(), candidates.len())));
+    }
+    let expected: HashSet<&str> = candidates.iter().map(|c| c.intent.provider.as_str()).collect();
+    let mut seen = HashSet::new();
+    for score in scores {
+        if !expected.contains(score.provider.as_str()) || !seen.insert(score.provider.as_str()) {
+            return Err(invalid(format!("unexpected or duplicate provider {}", score.provider)));
+        }
+        if !score.score.is_finite() {
+            return Err(invalid(format!("non-finite score for {}", score.provider)));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use shared_types::{Currency, OrderStatus, OrderTier};
+    use uuid::Uuid;
+
+    pub fn order(amount: &str) -> Order {
+        Order {
+            id: Uuid::new_v4(),
+            order_id: "0xorder".to_string(),
+            user_address: "0xuser".to_string(),
+            token: "0xtoken".to_string(),
+            amount: amount.to_string(),
+            refund_address: "0xuser".to_string(),
+            integrator_address: "0xintegrator".to_string(),
+            integrator_fee_bps: 50,
+            status: OrderStatus::Pending,
+            tier: OrderTier::Alpha,
+            currency: Currency::NGN,
+            created_at: Utc::now(),
+            expires_at: Utc::now() + Duration::hours(1),
+            updated_at: Utc::now(),
+            block_number: 1,
+            tx_hash: "0xtx".to_string(),
+        }
+    }
+
+    pub fn candidate(provider: &str, available: &str, fee_bps: u64, successes: u64, failures: u64, secs: u64) -> Candidate {
+        let mut reputation = ProviderReputation::new(provider.to_string());
+        for _ in 0..successes {
+            reputation.record_success(secs, "1000");
+        }
+        for _ in 0..failures {
+            reputation.record_failure();
+        }
+        Candidate {
+            intent: ProviderIntent {
+                provider: provider.to_string(),
+                currency: Currency::NGN,
+                available_amount: available.to_string(),
+                min_fee_bps: fee_bps,
+                max_fee_bps: fee_bps + 100,
+                commitment_window_seconds: 300,
+                is_active: true,
+                registered_at: Utc::now(),
+                expires_at: Utc::now() + Duration::hours(1),
+            },
+            reputation,
+            stake: None,
+            health: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::{candidate, order};
+    use super::*;
+    use chrono::{Duration, Utc};
+    use shared_types::Currency;
+
+    #[tokio::test]
+    async fn test_rank_orders_by_score_and_drops_ineligible() {
+        let scorer = WeightedLinearScorer::new(ScoringWeights::default()).unwrap();
+        let mut expired = candidate("0xexpired", "100000", 10, 50, 0, 30);
+        expired.intent.expires_at = Utc::now() - Duration::minutes(1);
+        let mut other_currency = candidate("0xkes", "100000", 10, 50, 0, 30);
+        other_currency.intent.currency = Currency::KES;
+
+        let candidates = vec![
+            candidate("0xslow", "100000", 400, 2, 8, 3000),
+            candidate("0xfast", "100000", 100, 20, 0, 60),
+            candidate("0xnew", "100000", 200, 0, 0, 0),
+            candidate("0xsmall", "500", 50, 50, 0, 30),
+            expired,
+            other_currency,
+        ];
+
+        let ranked = rank(&scorer, &order("1000"), &candidates).await.unwrap();
+        let providers: Vec<&str> = ranked.iter().map(|s| s.provider.as_str()).collect();
+        assert_eq!(providers, vec!["0xfast", "0xnew", "0xslow"]);
+        assert!(ranked.windows(2).all(|w| w[0].score >= w[1].score));
+
+        assert!(rank(&scorer, &order("1000000"), &candidates).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rank_is_deterministic_and_validates_scorer_output() {
+        let scorer = WeightedLinearScorer::new(ScoringWeights::default()).unwrap();
+        // Identical candidates tie and fall back to address order
+        let candidates = vec![
+            candidate("0xbbb", "100000", 100, 10, 0, 60),
+            candidate("0xaaa", "100000", 100, 10, 0, 60),
+        ];
+        let first = rank(&scorer, &order("1000"), &candidates).await.unwrap();
+        assert_eq!(first[0].provider, "0xaaa");
+        assert_eq!(first[0].score, first[1].score);
+
+        struct Broken;
+
+        #[async_trait]
+        impl Scorer for Broken {
+            fn name(&self) -> &str {
+                "broken"
+            }
+
+            async fn score(&self, _order: &Order, candidates: &[Candidate]) -> Result<Vec<ProviderScore>> {
+                Ok(candidates
+                    .iter()
+                    .map(|c| ProviderScore {
+                        provider: c.intent.provider.clone(),
+                        score: f64::NAN,
+                        features: Features::extract(&order("1000"), c),
+                        contributions: BTreeMap::new(),
+                    })
+                    .collect())
+            }
+        }
+
+        assert!(matches!(
+            rank(&Broken, &order("1000"), &candidates).await,
+            Err(RouterError::Scorer { .. })
+        ));
+    }
+}
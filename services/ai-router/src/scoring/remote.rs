@@ -0,0 +1,321 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use shared_types::{Currency, Order, OrderTier};
+use tracing::warn;
+
+use super::{breaker::CircuitBreaker, Candidate, Features, ProviderScore, Scorer, WeightedLinearScorer};
+use crate::error::{Result, RouterError};
+
+/// Remote model endpoint settings
+#[derive(Debug, Clone)]
+pub struct RemoteModelConfig {
+    pub endpoint: String,
+    pub model: String,
+    pub api_key: Option<String>,
+    /// Total time allowed for a scoring call, including connect
+    pub latency_budget: Duration,
+    /// Consecutive failures before the breaker opens
+    pub failure_threshold: u32,
+    /// How long the breaker stays open before a trial call
+    pub cooldown: Duration,
+}
+
+impl RemoteModelConfig {
+    /// Load from the environment; None when `ROUTER_MODEL_URL` is unset
+    pub fn from_env() -> Option<Self> {
+        let endpoint = std::env::var("ROUTER_MODEL_URL").ok().filter(|url| !url.trim().is_empty())?;
+        let env_u64 = |key: &str, default: u64| {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(default)
+        };
+
+        Some(Self {
+            endpoint,
+            model: std::env::var("ROUTER_MODEL_NAME").unwrap_or_else(|_| "gemini-flash".to_string()),
+            api_key: std::env::var("GEMINI_API_KEY").ok().filter(|key| !key.trim().is_empty()),
+            latency_budget: Duration::from_millis(env_u64("ROUTER_MODEL_TIMEOUT_MS", 80)),
+            failure_threshold: env_u64("ROUTER_MODEL_FAILURE_THRESHOLD", 5) as u32,
+            cooldown: Duration::from_secs(env_u64("ROUTER_MODEL_COOLDOWN_SECS", 30)),
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ScoringRequest<'a> {
+    model: &'a str,
+    order: OrderSummary<'a>,
+    candidates: Vec<CandidateFeatures<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct OrderSummary<'a> {
+    order_id: &'a str,
+    amount: &'a str,
+    currency: &'a Currency,
+    tier: OrderTier,
+}
+
+#[derive(Debug, Serialize)]
+struct CandidateFeatures<'a> {
+    provider: &'a str,
+    features: Features,
+}
+
+/// Expected response body:
+///
+/// ```json
+/// { "scores": [ { "provider": "0x…", "score": 0.87 } ] }
+/// ```
+///
+/// Unknown fields are rejected, scores must lie in `[0, 1]`, and every
+/// candidate must be scored exactly once.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScoringResponse {
+    scores: Vec<ModelScore>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ModelScore {
+    provider: String,
+    score: f64,
+}
+
+/// Validate a model response against the schema and the candidate set
+fn parse_scores(body: &[u8], candidates: &[Candidate]) -> std::result::Result<HashMap<String, f64>, String> {
+    let response: ScoringResponse = serde_json::from_slice(body).map_err(|e| format!("schema violation: {}", e))?;
+
+    let mut scores = HashMap::new();
+    for entry in response.scores {
+        if !(0.0..=1.0).contains(&entry.score) {
+            return Err(format!("score {} for {} outside [0, 1]", entry.score, entry.provider));
+        }
+        if scores.insert(entry.provider.to_lowercase(), entry.score).is_some() {
+            return Err(format!("duplicate score for {}", entry.provider));
+        }
+    }
+    if let Some(missing) = candidates
+        .iter()
+        .find(|c| !scores.contains_key(&c.intent.provider.to_lowercase()))
+    {
+        return Err(format!("no score for {}", missing.intent.provider));
+    }
+    if scores.len() != candidates.len() {
+        return Err("scores for unknown providers".to_string());
+    }
+    Ok(scores)
+}
+
+/// Scores candidates with a remote model, falling back to the local
+/// weighted-linear scorer
+///
+/// Features are extracted locally and sent to the model; any timeout,
+/// transport error or response failing validation counts against the
+/// circuit breaker and the call is answered by the fallback instead.
+pub struct RemoteModelScorer {
+    client: reqwest::Client,
+    config: RemoteModelConfig,
+    breaker: CircuitBreaker,
+    fallback: WeightedLinearScorer,
+}
+
+impl RemoteModelScorer {
+    pub fn new(config: RemoteModelConfig, fallback: WeightedLinearScorer) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(config.latency_budget)
+            .build()
+            .map_err(|e| RouterError::InvalidConfig(format!("HTTP client: {}", e)))?;
+
+        Ok(Self {
+            client,
+            breaker: CircuitBreaker::new(config.failure_threshold, config.cooldown),
+            config,
+            fallback,
+        })
+    }
+
+    pub fn breaker(&self) -> &CircuitBreaker {
+        &self.breaker
+    }
+
+    async fn score_remote(&self, order: &Order, candidates: &[Candidate]) -> std::result::Result<Vec<ProviderScore>, String> {
+        let features: Vec<Features> = candidates.iter().map(|c| Features::extract(order, c)).collect();
+        let request = ScoringRequest {
+            model: &self.config.model,
+            order: OrderSummary {
+                order_id: &order.order_id,
+                amount: &order.amount,
+                currency: &order.currency,
+                tier: order.tier,
+            },
+            candidates: candidates
+                .iter()
+                .zip(&features)
+                .map(|(c, f)| CandidateFeatures { provider: &c.intent.provider, features: *f })
+                .collect(),
+        };
+
+        let mut builder = self.client.post(&self.config.endpoint).json(&request);
+        if let Some(key) = &self.config.api_key {
+            builder = builder.bearer_auth(key);
+        }
+
+        let call = async {
+            let response = builder.send().await.map_err(|e| e.to_string())?;
+            if !response.status().is_success() {
+                return Err(format!("status {}", response.status().as_u16()));
+            }
+            response.bytes().await.map_err(|e| e.to_string())
+        };
+        let body = tokio::time::timeout(self.config.latency_budget, call)
+            .await
+            .map_err(|_| format!("exceeded {}ms budget", self.config.latency_budget.as_millis()))??;
+
+        let scores = parse_scores(&body, candidates)?;
+        Ok(candidates
+            .iter()
+            .zip(features)
+            .map(|(c, features)| {
+                let score = scores[&c.intent.provider.to_lowercase()];
+                ProviderScore {
+                    provider: c.intent.provider.clone(),
+                    score,
+                    features,
+                    // The model is opaque; its score is the only contribution
+                    contributions: BTreeMap::from([("model".to_string(), score)]),
+                }
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Scorer for RemoteModelScorer {
+    fn name(&self) -> &str {
+        "remote-model"
+    }
+
+    fn version(&self) -> String {
+        format!("remote-model@{} model={}", env!("CARGO_PKG_VERSION"), self.config.model)
+    }
+
+    async fn score(&self, order: &Order, candidates: &[Candidate]) -> Result<Vec<ProviderScore>> {
+        if !self.breaker.allow() {
+            return self.fallback.score(order, candidates).await;
+        }
+
+        match self.score_remote(order, candidates).await {
+            Ok(scores) => {
+                self.breaker.record_success();
+                Ok(scores)
+            }
+            Err(e) => {
+                self.breaker.record_failure();
+                warn!("Remote scoring failed for order {}, using {}: {}", order.order_id, self.fallback.name(), e);
+                self.fallback.score(order, candidates).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::test_support::{candidate, order};
+    use crate::scoring::ScoringWeights;
+    use axum::{routing::post, Json, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    async fn mock_model(response: serde_json::Value, delay: Duration, hits: Arc<AtomicUsize>) -> String {
+        let app = Router::new().route(
+            "/score",
+            post(move |Json(request): Json<serde_json::Value>| {
+                let response = response.clone();
+                let hits = hits.clone();
+                async move {
+                    hits.fetch_add(1, Ordering::SeqCst);
+                    assert_eq!(request["model"], "test-model");
+                    assert!(request["candidates"][0]["features"]["success_rate"].is_number());
+                    tokio::time::sleep(delay).await;
+                    Json(response)
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}/score", addr)
+    }
+
+    fn scorer(endpoint: String) -> RemoteModelScorer {
+        let config = RemoteModelConfig {
+            endpoint,
+            model: "test-model".to_string(),
+            api_key: Some("key".to_string()),
+            latency_budget: Duration::from_millis(200),
+            failure_threshold: 2,
+            cooldown: Duration::from_secs(60),
+        };
+        RemoteModelScorer::new(config, WeightedLinearScorer::new(ScoringWeights::default()).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_uses_model_scores_when_valid() {
+        let candidates = vec![candidate("0xaaa", "100000", 100, 10, 0, 60), candidate("0xbbb", "100000", 100, 10, 0, 60)];
+        let body = serde_json::json!({ "scores": [
+            { "provider": "0xaaa", "score": 0.1 },
+            { "provider": "0xBBB", "score": 0.9 }
+        ]});
+        let scorer = scorer(mock_model(body, Duration::ZERO, Arc::default()).await);
+
+        let ranked = crate::scoring::rank(&scorer, &order("1000"), &candidates).await.unwrap();
+        assert_eq!(ranked[0].provider, "0xbbb");
+        assert_eq!(ranked[0].score, 0.9);
+        assert!(!scorer.breaker().is_open());
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_on_invalid_or_slow_responses_and_opens_breaker() {
+        let candidates = vec![candidate("0xaaa", "100000", 100, 10, 0, 60)];
+        let local = WeightedLinearScorer::new(ScoringWeights::default())
+            .unwrap()
+            .score(&order("1000"), &candidates)
+            .await
+            .unwrap();
+
+        // Out of range score fails validation
+        let hits = Arc::new(AtomicUsize::new(0));
+        let body = serde_json::json!({ "scores": [{ "provider": "0xaaa", "score": 7 }] });
+        let invalid = scorer(mock_model(body, Duration::ZERO, hits.clone()).await);
+        let scores = invalid.score(&order("1000"), &candidates).await.unwrap();
+        assert_eq!(scores[0].score, local[0].score);
+
+        // Extra fields violate the schema; second failure opens the breaker
+        let scores = {
+            let body = serde_json::json!({ "scores": [{ "provider": "0xaaa", "score": 0.5, "why": "x" }] });
+            let strict = scorer(mock_model(body, Duration::ZERO, Arc::default()).await);
+            strict.score(&order("1000"), &candidates).await.unwrap()
+        };
+        assert_eq!(scores[0].score, local[0].score);
+
+        invalid.score(&order("1000"), &candidates).await.unwrap();
+        assert!(invalid.breaker().is_open());
+        invalid.score(&order("1000"), &candidates).await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        // Responses slower than the budget are abandoned
+        let body = serde_json::json!({ "scores": [{ "provider": "0xaaa", "score": 0.99 }] });
+        let slow = scorer(mock_model(body, Duration::from_millis(500), Arc::default()).await);
+        let started = std::time::Instant::now();
+        let scores = slow.score(&order("1000"), &candidates).await.unwrap();
+        assert!(started.elapsed() < Duration::from_millis(450));
+        assert_eq!(scores[0].score, local[0].score);
+    }
+}
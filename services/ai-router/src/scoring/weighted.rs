@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use shared_types::Order;
+
+use super::{Candidate, Features, ProviderScore, Scorer};
+use crate::error::{Result, RouterError};
+
+/// Relative importance of each feature in the weighted-linear score
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoringWeights {
+    pub success_rate: f64,
+    pub reliability: f64,
+    pub speed: f64,
+    pub cost: f64,
+    pub capacity: f64,
+    pub uptime: f64,
+    pub latency: f64,
+}
+
+impl Default for ScoringWeights {
+    fn default() -> Self {
+        Self {
+            success_rate: 0.30,
+            reliability: 0.15,
+            speed: 0.15,
+            cost: 0.15,
+            capacity: 0.10,
+            uptime: 0.10,
+            latency: 0.05,
+        }
+    }
+}
+
+impl ScoringWeights {
+    /// Load weights from `ROUTER_WEIGHT_*`, falling back to defaults for
+    /// unset variables
+    ///
+    /// Weights are relative: they are normalized to sum to one, so they must
+    /// be non-negative with at least one above zero.
+    pub fn from_env() -> Result<Self> {
+        let defaults = Self::default();
+        let env_f64 = |key: &str, default: f64| -> Result<f64> {
+            match std::env::var(key) {
+                Ok(value) if !value.trim().is_empty() => value
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| RouterError::InvalidConfig(format!("{} must be a number, got {}", key, value))),
+                _ => Ok(default),
+            }
+        };
+
+        Self {
+            success_rate: env_f64("ROUTER_WEIGHT_SUCCESS_RATE", defaults.success_rate)?,
+            reliability: env_f64("ROUTER_WEIGHT_RELIABILITY", defaults.reliability)?,
+            speed: env_f64("ROUTER_WEIGHT_SPEED", defaults.speed)?,
+            cost: env_f64("ROUTER_WEIGHT_COST", defaults.cost)?,
+            capacity: env_f64("ROUTER_WEIGHT_CAPACITY", defaults.capacity)?,
+            uptime: env_f64("ROUTER_WEIGHT_UPTIME", defaults.uptime)?,
+            latency: env_f64("ROUTER_WEIGHT_LATENCY", defaults.latency)?,
+        }
+        .normalized()
+    }
+
+    /// Scale weights to sum to one
+    pub fn normalized(self) -> Result<Self> {
+        let weights = [
+            self.success_rate,
+            self.reliability,
+            self.speed,
+            self.cost,
+            self.capacity,
+            self.uptime,
+            self.latency,
+        ];
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(RouterError::InvalidConfig("Scoring weights must be non-negative".to_string()));
+        }
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return Err(RouterError::InvalidConfig("At least one scoring weight must be positive".to_string()));
+        }
+
+        Ok(Self {
+            success_rate: self.success_rate / total,
+            reliability: self.reliability / total,
+            speed: self.speed / total,
+            cost: self.cost / total,
+            capacity: self.capacity / total,
+            uptime: self.uptime / total,
+            latency: self.latency / total,
+        })
+    }
+
+    /// Weighted value of each feature; the score is their sum
+    fn contributions(&self, features: &Features) -> BTreeMap<String, f64> {
+        BTreeMap::from([
+            ("success_rate".to_string(), self.success_rate * features.success_rate),
+            ("reliability".to_string(), self.reliability * features.reliability),
+            ("speed".to_string(), self.speed * features.speed),
+            ("cost".to_string(), self.cost * features.cost),
+            ("capacity".to_string(), self.capacity * features.capacity),
+            ("uptime".to_string(), self.uptime * features.uptime),
+            ("latency".to_string(), self.latency * features.latency),
+        ])
+    }
+}
+
+/// Default scorer: a weighted sum of the extracted features
+///
+/// Deterministic and dependency-free, so it doubles as the fallback when a
+/// model-backed scorer is unavailable.
+#[derive(Debug, Clone)]
+pub struct WeightedLinearScorer {
+    weights: ScoringWeights,
+}
+
+impl WeightedLinearScorer {
+    pub fn new(weights: ScoringWeights) -> Result<Self> {
+        Ok(Self { weights: weights.normalized()? })
+    }
+
+    pub fn weights(&self) -> &ScoringWeights {
+        &self.weights
+    }
+}
+
+#[async_trait]
+impl Scorer for WeightedLinearScorer {
+    fn name(&self) -> &str {
+        "weighted-linear"
+    }
+
+    fn version(&self) -> String {
+        let w = &self.weights;
+        format!(
+            "weighted-linear@{} success_rate={:.3} reliability={:.3} speed={:.3} cost={:.3} capacity={:.3} \
+             uptime={:.3} latency={:.3}",
+            env!("CARGO_PKG_VERSION"),
+            w.success_rate,
+            w.reliability,
+            w.speed,
+            w.cost,
+            w.capacity,
+            w.uptime,
+            w.latency
+        )
+    }
+
+    async fn score(&self, order: &Order, candidates: &[Candidate]) -> Result<Vec<ProviderScore>> {
+        Ok(candidates
+            .iter()
+            .map(|candidate| {
+                let features = Features::extract(order, candidate);
+                let contributions = self.weights.contributions(&features);
+                ProviderScore {
+                    provider: candidate.intent.provider.clone(),
+                    score: contributions.values().sum(),
+                    features,
+                    contributions,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weights_are_normalized_and_validated() {
+        let weights = ScoringWeights {
+            success_rate: 2.0,
+            reliability: 1.0,
+            speed: 1.0,
+            cost: 0.0,
+            capacity: 0.0,
+            uptime: 0.0,
+            latency: 0.0,
+        }
+        .normalized()
+        .unwrap();
+        assert_eq!(weights.success_rate, 0.5);
+        assert_eq!(weights.speed, 0.25);
+
+        let negative = ScoringWeights { cost: -1.0, ..ScoringWeights::default() };
+        assert!(matches!(negative.normalized(), Err(RouterError::InvalidConfig(_))));
+
+        let zero = ScoringWeights {
+            success_rate: 0.0,
+            reliability: 0.0,
+            speed: 0.0,
+            cost: 0.0,
+            capacity: 0.0,
+            uptime: 0.0,
+            latency: 0.0,
+        };
+        assert!(zero.normalized().is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, Rng};
+use shared_types::{Order, ProposalStatus, ProviderIntent};
+
+use super::Scenario;
+
+/// Settlement time assumed for providers with no recorded settlements
+const DEFAULT_SETTLEMENT_SECS: f64 = 300.0;
+
+/// How a provider is assumed to behave when given an order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProviderBehavior {
+    pub success_probability: f64,
+    pub mean_settlement_secs: f64,
+}
+
+impl Default for ProviderBehavior {
+    fn default() -> Self {
+        Self {
+            success_probability: 0.5,
+            mean_settlement_secs: DEFAULT_SETTLEMENT_SECS,
+        }
+    }
+}
+
+/// What happened when an order was historically offered to a provider
+#[derive(Debug, Clone, Copy)]
+struct HistoricalOutcome {
+    fee_bps: u64,
+    /// Some(secs) when settled, None when the provider timed out; absent
+    /// entirely when the proposal never reached a conclusion
+    result: Option<Option<f64>>,
+}
+
+/// Result of handing an order to a provider in the simulation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimulatedOutcome {
+    pub success: bool,
+    pub fee_bps: u64,
+    /// Seconds to settle, when successful
+    pub settlement_secs: Option<f64>,
+}
+
+/// Provider behavior model for replays
+///
+/// Where history records how an order went with the chosen provider, that
+/// outcome is replayed as-is. Otherwise success is drawn from the provider's
+/// reputation (Laplace-smoothed, no-shows counting as failures) and
+/// settlement time from an exponential distribution around its average.
+/// Fees come from the historical proposal when there is one, else the
+/// provider's minimum fee.
+#[derive(Debug, Clone, Default)]
+pub struct BehaviorModel {
+    providers: HashMap<String, ProviderBehavior>,
+    history: HashMap<(String, String), HistoricalOutcome>,
+}
+
+impl BehaviorModel {
+    pub fn from_scenario(scenario: &Scenario) -> Self {
+        let providers = scenario
+            .reputations
+            .iter()
+            .map(|reputation| {
+                let successes = reputation.successful_orders as f64;
+                let attempts = (reputation.successful_orders + reputation.failed_orders + reputation.no_shows) as f64;
+                let mean_settlement_secs = if reputation.successful_orders == 0 {
+                    DEFAULT_SETTLEMENT_SECS
+                } else {
+                    reputation.avg_settlement_time_seconds as f64
+                };
+                let behavior = ProviderBehavior {
+                    success_probability: (successes + 1.0) / (attempts + 2.0),
+                    mean_settlement_secs,
+                };
+                (reputation.provider.to_lowercase(), behavior)
+            })
+            .collect();
+
+        let history = scenario
+            .proposals
+            .iter()
+            .map(|proposal| {
+                let result = match proposal.status {
+                    ProposalStatus::Executed => {
+                        let started = proposal.accepted_at.unwrap_or(proposal.created_at);
+                        let secs = proposal
+                            .executed_at
+                            .map(|executed| (executed - started).num_seconds().max(0) as f64);
+                        Some(secs.or(Some(DEFAULT_SETTLEMENT_SECS)))
+                    }
+                    ProposalStatus::TimedOut => Some(None),
+                    _ => None,
+                };
+                let key = (proposal.order_id.to_lowercase(), proposal.provider.to_lowercase());
+                (key, HistoricalOutcome { fee_bps: proposal.proposed_fee_bps, result })
+            })
+            .collect();
+
+        Self { providers, history }
+    }
+
+    pub fn behavior(&self, provider: &str) -> ProviderBehavior {
+        self.providers.get(&provider.to_lowercase()).copied().unwrap_or_default()
+    }
+
+    /// Simulate handing `order` to the provider behind `intent`
+    pub fn outcome(&self, order: &Order, intent: &ProviderIntent, rng: &mut StdRng) -> SimulatedOutcome {
+        let key = (order.order_id.to_lowercase(), intent.provider.to_lowercase());
+        let historical = self.history.get(&key);
+        let fee_bps = historical.map(|h| h.fee_bps).unwrap_or(intent.min_fee_bps);
+
+        if let Some(result) = historical.and_then(|h| h.result) {
+            return SimulatedOutcome { success: result.is_some(), fee_bps, settlement_secs: result };
+        }
+
+        let behavior = self.behavior(&intent.provider);
+        let success = rng.gen::<f64>() < behavior.success_probability;
+        let settlement_secs = success.then(|| {
+            let u: f64 = rng.gen();
+            -behavior.mean_settlement_secs * (1.0 - u).ln()
+        });
+        SimulatedOutcome { success, fee_bps, settlement_secs }
+    }
+}
@@ -0,0 +1,324 @@
+//! Offline routing simulation
+//!
+//! Replays a [`Scenario`] of historical orders through any [`Scorer`] to
+//! estimate how a strategy would have performed before it goes live. Each
+//! order is offered to the intents that were live when it was created, the
+//! scorer's top choice is handed to the [`BehaviorModel`], and the outcome
+//! feeds back into the provider's reputation and the scorer itself.
+//!
+//! Liquidity is not depleted as orders are routed, so results for providers
+//! with thin intents are optimistic.
+
+pub mod behavior;
+pub mod report;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use shared_database::{DatabaseError, OrderRepository, ProposalRepository, ProviderRepository};
+use shared_types::{helpers::bytes_to_hex, Order, Proposal, ProposalStatus, ProviderIntent, ProviderReputation};
+use sqlx::PgPool;
+
+use crate::error::{Result, RouterError};
+use crate::scoring::{rank, Candidate, Scorer};
+pub use behavior::{BehaviorModel, ProviderBehavior, SimulatedOutcome};
+pub use report::Report;
+use report::Tally;
+
+/// Historical data a strategy is replayed against
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Scenario {
+    pub orders: Vec<Order>,
+    /// Proposals made for the orders, used to replay known outcomes
+    #[serde(default)]
+    pub proposals: Vec<Proposal>,
+    /// Every intent registered over the period, live or not
+    pub intents: Vec<ProviderIntent>,
+    /// Reputation of each provider at the start of the period
+    #[serde(default)]
+    pub reputations: Vec<ProviderReputation>,
+}
+
+impl Scenario {
+    /// Read a scenario from a JSON fixture
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| RouterError::InvalidRequest(format!("cannot read {}: {}", path.display(), e)))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| RouterError::InvalidRequest(format!("invalid scenario {}: {}", path.display(), e)))
+    }
+
+    /// Load the orders and proposals created in `[from, to)` along with all
+    /// intents, and each provider's reputation as it stood at `from`
+    ///
+    /// Stored reputations already count the period being replayed, so they
+    /// are rebuilt from the proposals that concluded before `from` instead.
+    pub async fn load(pool: &PgPool, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Self> {
+        let order_repo = OrderRepository::new(pool.clone());
+        let order_models = order_repo.list_created_between(from, to).await?;
+        let mut orders = Vec::with_capacity(order_models.len());
+        for model in &order_models {
+            orders.push(model.to_domain(pool).await.map_err(DatabaseError::from)?);
+        }
+
+        let proposal_repo = ProposalRepository::new(pool.clone());
+        let proposals = proposal_repo.list_created_between(from, to).await?;
+        let intents = ProviderRepository::new(pool.clone()).list_intents().await?;
+
+        let earlier_proposals = proposal_repo.list_created_between(DateTime::UNIX_EPOCH, from).await?;
+        let amounts: HashMap<String, String> = order_repo
+            .list_created_between(DateTime::UNIX_EPOCH, from)
+            .await?
+            .into_iter()
+            .map(|order| (bytes_to_hex(&order.order_id), order.amount))
+            .collect();
+        let earlier_proposals: Vec<Proposal> = earlier_proposals.iter().map(|p| p.to_domain()).collect();
+
+        Ok(Self {
+            orders,
+            proposals: proposals.iter().map(|p| p.to_domain()).collect(),
+            intents: intents.iter().map(|i| i.to_domain()).collect(),
+            reputations: reputations_as_of(&earlier_proposals, &amounts, from),
+        })
+    }
+
+    /// Intents live when `order` was created, made valid so they can be
+    /// ranked regardless of the current time
+    fn candidates_at(&self, order: &Order, reputations: &HashMap<String, ProviderReputation>) -> Vec<Candidate> {
+        self.intents
+            .iter()
+            .filter(|intent| intent.registered_at <= order.created_at && order.created_at < intent.expires_at)
+            .map(|intent| {
+                let provider = intent.provider.to_lowercase();
+                Candidate {
+                    intent: ProviderIntent { is_active: true, expires_at: DateTime::<Utc>::MAX_UTC, ..intent.clone() },
+                    reputation: reputations
+                        .get(&provider)
+                        .cloned()
+                        .unwrap_or_else(|| ProviderReputation::new(intent.provider.clone())),
+                    stake: None,
+                    health: None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Reputations rebuilt from proposals that concluded before `as_of`: an
+/// executed proposal is a success, one that timed out a no-show
+///
+/// `amounts` maps order IDs to their amounts, for settled volume.
+fn reputations_as_of(
+    proposals: &[Proposal],
+    amounts: &HashMap<String, String>,
+    as_of: DateTime<Utc>,
+) -> Vec<ProviderReputation> {
+    let mut concluded: Vec<(DateTime<Utc>, &Proposal)> = proposals
+        .iter()
+        .filter_map(|proposal| {
+            let at = match proposal.status {
+                ProposalStatus::Executed => proposal.executed_at?,
+                ProposalStatus::TimedOut => proposal.deadline,
+                _ => return None,
+            };
+            (at < as_of).then_some((at, proposal))
+        })
+        .collect();
+    concluded.sort_by_key(|(at, _)| *at);
+
+    let mut reputations: HashMap<String, ProviderReputation> = HashMap::new();
+    for (at, proposal) in concluded {
+        let reputation = reputations
+            .entry(proposal.provider.to_lowercase())
+            .or_insert_with(|| ProviderReputation::new(proposal.provider.clone()));
+        if proposal.status == ProposalStatus::Executed {
+            let started = proposal.accepted_at.unwrap_or(proposal.created_at);
+            let amount = amounts.get(&proposal.order_id.to_lowercase()).map_or("0", String::as_str);
+            reputation.record_success((at - started).num_seconds().max(0) as u64, amount);
+        } else {
+            reputation.record_no_show();
+        }
+        reputation.last_updated = at;
+    }
+
+    let mut reputations: Vec<ProviderReputation> = reputations.into_values().collect();
+    reputations.sort_by(|a, b| a.provider.cmp(&b.provider));
+    reputations
+}
+
+/// Replay every order in `scenario` through `scorer`, oldest first
+///
+/// The same scorer, scenario and seed always produce the same report, as
+/// long as the scorer is itself deterministic.
+pub async fn replay(scorer: &dyn Scorer, scenario: &Scenario, seed: u64) -> Result<Report> {
+    let behavior = BehaviorModel::from_scenario(scenario);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut reputations: HashMap<String, ProviderReputation> = scenario
+        .reputations
+        .iter()
+        .map(|reputation| (reputation.provider.to_lowercase(), reputation.clone()))
+        .collect();
+
+    let mut orders: Vec<&Order> = scenario.orders.iter().collect();
+    orders.sort_by_key(|order| order.created_at);
+
+    let mut tally = Tally::default();
+    for order in orders {
+        tally.orders += 1;
+        let candidates = scenario.candidates_at(order, &reputations);
+        let ranked = rank(scorer, order, &candidates).await?;
+        let Some(best) = ranked.first() else {
+            tally.unroutable += 1;
+            continue;
+        };
+        let Some(chosen) = candidates.iter().find(|c| c.intent.provider == best.provider) else {
+            continue;
+        };
+
+        let outcome = behavior.outcome(order, &chosen.intent, &mut rng);
+        let provider = chosen.intent.provider.to_lowercase();
+        *tally.provider_orders.entry(provider.clone()).or_default() += 1;
+        tally.fee_bps_total += outcome.fee_bps;
+
+        let reputation = reputations
+            .entry(provider)
+            .or_insert_with(|| ProviderReputation::new(chosen.intent.provider.clone()));
+        match outcome.settlement_secs {
+            Some(secs) if outcome.success => {
+                tally.successes += 1;
+                tally.settlement_secs_total += secs;
+                reputation.record_success(secs.round() as u64, &order.amount);
+            }
+            _ => reputation.record_failure(),
+        }
+        scorer.observe_outcome(order, &chosen.intent.provider, outcome.success);
+    }
+
+    Ok(tally.into_report(scorer.version()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::test_support::order;
+    use crate::scoring::{ScoringWeights, WeightedLinearScorer};
+    use chrono::Duration;
+    use shared_types::Currency;
+
+    fn scenario() -> Scenario {
+        let start = Utc::now() - Duration::days(7);
+        let intent = |provider: &str, fee_bps: u64| ProviderIntent {
+            provider: provider.to_string(),
+            currency: Currency::NGN,
+            available_amount: "1000000".to_string(),
+            min_fee_bps: fee_bps,
+            max_fee_bps: fee_bps * 2,
+            commitment_window_seconds: 300,
+            is_active: false,
+            registered_at: start,
+            expires_at: start + Duration::hours(30),
+        };
+        let reputation = |provider: &str, successes: u64, failures: u64| {
+            let mut reputation = ProviderReputation::new(provider.to_string());
+            (0..successes).for_each(|_| reputation.record_success(120, "1000"));
+            (0..failures).for_each(|_| reputation.record_failure());
+            reputation
+        };
+
+        let orders = (0..40)
+            .map(|i| Order {
+                order_id: format!("0x{:02x}", i),
+                created_at: start + Duration::hours(i),
+                ..order("1000")
+            })
+            .collect();
+        Scenario {
+            orders,
+            proposals: Vec::new(),
+            intents: vec![intent("0xreliable", 100), intent("0xflaky", 90)],
+            reputations: vec![reputation("0xreliable", 95, 5), reputation("0xflaky", 40, 60)],
+        }
+    }
+
+    fn scorer() -> WeightedLinearScorer {
+        WeightedLinearScorer::new(ScoringWeights::default()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_replay_is_reproducible_and_favours_reliable_provider() {
+        let scenario = scenario();
+        let first = replay(&scorer(), &scenario, 7).await.unwrap();
+        let second = replay(&scorer(), &scenario, 7).await.unwrap();
+        assert_eq!(serde_json::to_value(&first).unwrap(), serde_json::to_value(&second).unwrap());
+
+        // Orders after the intents expired cannot be routed
+        assert_eq!(first.orders, 40);
+        assert_eq!(first.unroutable, 10);
+        assert!(first.provider_orders["0xreliable"] > first.routed / 2);
+        assert!(first.success_rate > 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_replay_uses_historical_outcomes() {
+        let mut scenario = scenario();
+        scenario.orders.truncate(1);
+        let order_id = scenario.orders[0].order_id.clone();
+        let created_at = scenario.orders[0].created_at;
+        scenario.proposals.push(Proposal {
+            proposal_id: "0xproposal".to_string(),
+            order_id,
+            provider: "0xreliable".to_string(),
+            proposed_fee_bps: 150,
+            status: ProposalStatus::Executed,
+            created_at,
+            deadline: created_at + Duration::minutes(5),
+            accepted_at: Some(created_at),
+            executed_at: Some(created_at + Duration::seconds(45)),
+            tx_hash: None,
+        });
+
+        let report = replay(&scorer(), &scenario, 1).await.unwrap();
+        assert_eq!(report.routed, 1);
+        assert_eq!(report.success_rate, 1.0);
+        assert_eq!(report.avg_fee_bps, 150.0);
+        assert_eq!(report.avg_settlement_secs, 45.0);
+    }
+
+    #[test]
+    fn test_reputations_only_count_proposals_concluded_before_the_period() {
+        let from = Utc::now() - Duration::days(7);
+        let proposal = |order_id: &str, status: ProposalStatus, concluded_at: DateTime<Utc>| Proposal {
+            proposal_id: format!("0xproposal{}", order_id),
+            order_id: order_id.to_string(),
+            provider: "0xProvider".to_string(),
+            proposed_fee_bps: 100,
+            status,
+            created_at: concluded_at - Duration::minutes(2),
+            deadline: concluded_at,
+            accepted_at: Some(concluded_at - Duration::minutes(2)),
+            executed_at: (status == ProposalStatus::Executed).then_some(concluded_at),
+            tx_hash: None,
+        };
+        let proposals = vec![
+            proposal("0x01", ProposalStatus::Executed, from - Duration::hours(3)),
+            proposal("0x02", ProposalStatus::TimedOut, from - Duration::hours(2)),
+            proposal("0x03", ProposalStatus::Accepted, from - Duration::hours(1)),
+            // Created before the period but settled during it
+            proposal("0x04", ProposalStatus::Executed, from + Duration::hours(1)),
+        ];
+        let amounts = HashMap::from([("0x01".to_string(), "1000".to_string()), ("0x04".to_string(), "5000".to_string())]);
+
+        let reputations = reputations_as_of(&proposals, &amounts, from);
+        assert_eq!(reputations.len(), 1);
+        let reputation = &reputations[0];
+        assert_eq!(reputation.provider, "0xProvider");
+        assert_eq!((reputation.total_orders, reputation.successful_orders, reputation.no_shows), (2, 1, 1));
+        assert_eq!(reputation.avg_settlement_time_seconds, 120);
+        assert_eq!(reputation.total_volume, "1000");
+        assert_eq!(reputation.last_updated, from - Duration::hours(2));
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Aggregate results of replaying a scenario through one strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    /// Scorer version the scenario was replayed with
+    pub strategy: String,
+    pub orders: u64,
+    pub routed: u64,
+    /// Orders no candidate could fill
+    pub unroutable: u64,
+    /// Share of routed orders that settled
+    pub success_rate: f64,
+    /// Mean fee across routed orders
+    pub avg_fee_bps: f64,
+    /// Mean settlement time across settled orders
+    pub avg_settlement_secs: f64,
+    /// Orders routed to each provider
+    pub provider_orders: BTreeMap<String, u64>,
+    /// Share of routed orders taken by the busiest provider
+    pub top_provider_share: f64,
+    /// Herfindahl-Hirschman index of provider shares, from 1/n (even) to 1
+    /// (a single provider)
+    pub concentration_hhi: f64,
+}
+
+/// Running totals collected during a replay
+#[derive(Debug, Default)]
+pub(crate) struct Tally {
+    pub orders: u64,
+    pub unroutable: u64,
+    pub successes: u64,
+    pub fee_bps_total: u64,
+    pub settlement_secs_total: f64,
+    pub provider_orders: BTreeMap<String, u64>,
+}
+
+impl Tally {
+    pub fn into_report(self, strategy: String) -> Report {
+        let routed: u64 = self.provider_orders.values().sum();
+        let ratio = |num: f64, den: u64| if den == 0 { 0.0 } else { num / den as f64 };
+        let shares: Vec<f64> = self
+            .provider_orders
+            .values()
+            .map(|count| ratio(*count as f64, routed))
+            .collect();
+
+        Report {
+            strategy,
+            orders: self.orders,
+            routed,
+            unroutable: self.unroutable,
+            success_rate: ratio(self.successes as f64, routed),
+            avg_fee_bps: ratio(self.fee_bps_total as f64, routed),
+            avg_settlement_secs: ratio(self.settlement_secs_total, self.successes),
+            top_provider_share: shares.iter().cloned().fold(0.0, f64::max),
+            concentration_hhi: shares.iter().map(|s| s * s).sum(),
+            provider_orders: self.provider_orders,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Strategy:            {}", self.strategy)?;
+        writeln!(f, "Orders:              {} ({} routed, {} unroutable)", self.orders, self.routed, self.unroutable)?;
+        writeln!(f, "Success rate:        {:.2}%", self.success_rate * 100.0)?;
+        writeln!(f, "Average fee:         {:.1} bps", self.avg_fee_bps)?;
+        writeln!(f, "Average settlement:  {:.0}s", self.avg_settlement_secs)?;
+        writeln!(f, "Top provider share:  {:.2}%", self.top_provider_share * 100.0)?;
+        writeln!(f, "Concentration (HHI): {:.3}", self.concentration_hhi)?;
+        for (provider, count) in &self.provider_orders {
+            writeln!(f, "  {:<44} {}", provider, count)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_metrics() {
+        let tally = Tally {
+            orders: 5,
+            unroutable: 1,
+            successes: 3,
+            fee_bps_total: 400,
+            settlement_secs_total: 360.0,
+            provider_orders: BTreeMap::from([("0xa".to_string(), 3), ("0xb".to_string(), 1)]),
+        };
+        let report = tally.into_report("test".to_string());
+        assert_eq!(report.routed, 4);
+        assert_eq!(report.success_rate, 0.75);
+        assert_eq!(report.avg_fee_bps, 100.0);
+        assert_eq!(report.avg_settlement_secs, 120.0);
+        assert_eq!(report.top_provider_share, 0.75);
+        assert_eq!(report.concentration_hhi, 0.625);
+
+        let empty = Tally::default().into_report("test".to_string());
+        assert_eq!(empty.success_rate, 0.0);
+        assert_eq!(empty.concentration_hhi, 0.0);
+    }
+}
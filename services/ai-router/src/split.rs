@@ -0,0 +1,143 @@
+//! Split-order routing
+//!
+//! Titan-tier orders can exceed every provider's available liquidity, leaving
+//! no single candidate eligible. Such orders are divided into legs across the
+//! best-ranked providers able to take part of them: each provider in rank
+//! order takes as much of the remainder as it has available until the order
+//! is covered.
+
+use shared_types::{LegAllocation, Order};
+
+use crate::error::{Result, RouterError};
+use crate::scoring::{Candidate, ProviderScore};
+
+/// Limits on how orders are split
+#[derive(Debug, Clone)]
+pub struct SplitConfig {
+    /// Most providers one order may be split across
+    pub max_legs: usize,
+    /// Smallest leg, other than the last, as basis points of the order;
+    /// providers that could only take less are skipped
+    pub min_leg_bps: u64,
+}
+
+impl Default for SplitConfig {
+    fn default() -> Self {
+        Self {
+            max_legs: 5,
+            min_leg_bps: 500,
+        }
+    }
+}
+
+impl SplitConfig {
+    /// Load from `ROUTER_SPLIT_MAX_LEGS` and `ROUTER_SPLIT_MIN_LEG_BPS`,
+    /// falling back to defaults for unset variables
+    pub fn from_env() -> Result<Self> {
+        let defaults = Self::default();
+        let env_u64 = |key: &str| std::env::var(key).ok().and_then(|v| v.parse::<u64>().ok());
+
+        let config = Self {
+            max_legs: env_u64("ROUTER_SPLIT_MAX_LEGS")
+                .map(|v| v as usize)
+                .unwrap_or(defaults.max_legs),
+            min_leg_bps: env_u64("ROUTER_SPLIT_MIN_LEG_BPS").unwrap_or(defaults.min_leg_bps),
+        };
+        if config.max_legs < 2 {
+            return Err(RouterError::InvalidConfig("ROUTER_SPLIT_MAX_LEGS must be at least 2".to_string()));
+        }
+        if config.min_leg_bps > 10_000 {
+            return Err(RouterError::InvalidConfig("ROUTER_SPLIT_MIN_LEG_BPS must be at most 10000".to_string()));
+        }
+        Ok(config)
+    }
+}
+
+/// Divide an order across ranked providers, best first
+///
+/// # Arguments
+/// * `ranked` - Scores of the candidates able to fill part of the order,
+///   best first
+///
+/// # Returns
+/// * `Option<Vec<LegAllocation>>` - Legs summing to the order amount; None
+///   when the providers within `max_legs` cannot cover it
+pub fn plan(order: &Order, ranked: &[ProviderScore], candidates: &[Candidate], config: &SplitConfig) -> Option<Vec<LegAllocation>> {
+    let total: u128 = order.amount.parse().ok().filter(|amount| *amount > 0)?;
+    let min_leg = total.checked_mul(config.min_leg_bps as u128)? / 10_000;
+
+    let mut remaining = total;
+    let mut legs = Vec::new();
+    for score in ranked {
+        if legs.len() == config.max_legs {
+            break;
+        }
+        let Some(candidate) = candidates.iter().find(|c| c.intent.provider == score.provider) else {
+            continue;
+        };
+        let take = candidate.available_amount().min(remaining);
+        if take == 0 || (take < min_leg && take < remaining) {
+            continue;
+        }
+
+        legs.push(LegAllocation {
+            leg: legs.len() as u32 + 1,
+            provider: candidate.intent.provider.clone(),
+            amount: take.to_string(),
+        });
+        remaining -= take;
+        if remaining == 0 {
+            return Some(legs);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::test_support::{candidate, order};
+    use crate::scoring::{rank_partial, ScoringWeights, WeightedLinearScorer};
+
+    #[tokio::test]
+    async fn test_plan_fills_best_providers_first() {
+        let scorer = WeightedLinearScorer::new(ScoringWeights::default()).unwrap();
+        let order = order("100000");
+        let candidates = vec![
+            candidate("0xbest", "60000", 100, 50, 0, 60),
+            candidate("0xdust", "1000", 100, 40, 0, 60),
+            candidate("0xgood", "30000", 150, 30, 2, 120),
+            candidate("0xok", "50000", 250, 10, 5, 600),
+        ];
+        let ranked = rank_partial(&scorer, &order, &candidates).await.unwrap();
+        assert_eq!(ranked.len(), 4);
+
+        let legs = plan(&order, &ranked, &candidates, &SplitConfig::default()).unwrap();
+        let split: Vec<(u32, &str, &str)> = legs
+            .iter()
+            .map(|l| (l.leg, l.provider.as_str(), l.amount.as_str()))
+            .collect();
+        // The dust provider is below the 5% minimum and skipped
+        assert_eq!(split, vec![(1, "0xbest", "60000"), (2, "0xgood", "30000"), (3, "0xok", "10000")]);
+
+        let two_legs = SplitConfig { max_legs: 2, ..SplitConfig::default() };
+        assert!(plan(&order, &ranked, &candidates, &two_legs).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_min_leg_keeps_precision_on_small_orders() {
+        let scorer = WeightedLinearScorer::new(ScoringWeights::default()).unwrap();
+        // 5% of 9999 is 499; dividing first would round the minimum to zero
+        let order = order("9999");
+        let candidates = vec![
+            candidate("0xbest", "9000", 100, 50, 0, 60),
+            candidate("0xdust", "400", 100, 40, 0, 60),
+            candidate("0xgood", "5000", 150, 30, 2, 120),
+        ];
+        let ranked = rank_partial(&scorer, &order, &candidates).await.unwrap();
+
+        let legs = plan(&order, &ranked, &candidates, &SplitConfig::default()).unwrap();
+        let split: Vec<(&str, &str)> = legs.iter().map(|l| (l.provider.as_str(), l.amount.as_str())).collect();
+        assert_eq!(split, vec![("0xbest", "9000"), ("0xgood", "999")]);
+    }
+}
@@ -0,0 +1,221 @@
+//! Operator endpoints, proxied to the AI router and the order service
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use shared_types::{Dispute, DisputeStatus, ResolveDisputeRequest, RoutingDecision};
+use uuid::Uuid;
+
+use crate::{
+    auth::{Principal, Role},
+    error::{ApiError, ErrorBody, Result},
+    state::AppState,
+    upstream::Upstream,
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/v1/admin/routing/decisions", get(list_routing_decisions))
+        .route("/v1/admin/routing/decisions/:id", get(get_routing_decision))
+        .route("/v1/admin/disputes", get(list_disputes))
+        .route("/v1/admin/disputes/:id", get(get_dispute))
+        .route("/v1/admin/disputes/:id/resolve", post(resolve_dispute))
+}
+
+fn require_admin(principal: &Principal) -> Result<()> {
+    if principal.role != Role::Admin {
+        return Err(ApiError::Forbidden);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RoutingDecisionQuery {
+    /// Only decisions for this order (bytes32 hex)
+    pub order_id: Option<String>,
+    /// Only decisions this provider was a candidate in
+    pub provider: Option<String>,
+    /// Maximum decisions returned, at most 500 (default 50)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DisputeQuery {
+    /// Only disputes in this status
+    pub status: Option<DisputeStatus>,
+    /// Only disputes against this provider
+    pub provider: Option<String>,
+    /// Maximum disputes returned, at most 200 (default 50)
+    pub limit: Option<i64>,
+}
+
+/// Resolution as forwarded to the order service
+#[derive(Serialize)]
+struct ForwardedResolution<'a> {
+    resolved_by: &'a str,
+    #[serde(flatten)]
+    request: &'a ResolveDisputeRequest,
+}
+
+/// List routing decisions, newest first
+///
+/// Each decision lists every eligible candidate with its feature values and
+/// the contribution of each feature to its score.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/routing/decisions",
+    tag = "admin",
+    params(RoutingDecisionQuery),
+    responses(
+        (status = 200, description = "Routing decisions", body = [RoutingDecision]),
+        (status = 400, description = "Invalid filter", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Admin role required", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_routing_decisions(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Query(query): Query<RoutingDecisionQuery>,
+) -> Result<Response> {
+    require_admin(&principal)?;
+    Ok(upstream
+        .ai_router_query(&["admin", "routing", "decisions"], &query)
+        .await?
+        .into_response())
+}
+
+/// Get a single routing decision
+#[utoipa::path(
+    get,
+    path = "/v1/admin/routing/decisions/{id}",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "Routing decision ID")),
+    responses(
+        (status = 200, description = "Routing decision", body = RoutingDecision),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Admin role required", body = ErrorBody),
+        (status = 404, description = "Decision not found", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_routing_decision(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+) -> Result<Response> {
+    require_admin(&principal)?;
+    Ok(upstream
+        .ai_router::<()>(Method::GET, &["admin", "routing", "decisions", &id.to_string()], None)
+        .await?
+        .into_response())
+}
+
+/// List disputes, oldest first
+#[utoipa::path(
+    get,
+    path = "/v1/admin/disputes",
+    tag = "admin",
+    params(DisputeQuery),
+    responses(
+        (status = 200, description = "Disputes", body = [Dispute]),
+        (status = 400, description = "Invalid filter", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Admin role required", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_disputes(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Query(query): Query<DisputeQuery>,
+) -> Result<Response> {
+    require_admin(&principal)?;
+    Ok(upstream.order_service_query(&["disputes"], &query).await?.into_response())
+}
+
+/// Get a single dispute, with the provider's proof and evidence
+#[utoipa::path(
+    get,
+    path = "/v1/admin/disputes/{id}",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "Dispute ID")),
+    responses(
+        (status = 200, description = "Dispute", body = Dispute),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Admin role required", body = ErrorBody),
+        (status = 404, description = "Dispute not found", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_dispute(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+) -> Result<Response> {
+    require_admin(&principal)?;
+    Ok(upstream
+        .order_service::<()>(Method::GET, &["disputes", &id.to_string()], None)
+        .await?
+        .into_response())
+}
+
+/// Release the escrow to the provider, or refund it to the user
+///
+/// A refund counts against the provider's reputation.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/disputes/{id}/resolve",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "Dispute ID")),
+    request_body = ResolveDisputeRequest,
+    responses(
+        (status = 200, description = "Dispute resolved", body = Dispute),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Admin role required", body = ErrorBody),
+        (status = 404, description = "Dispute not found", body = ErrorBody),
+        (status = 409, description = "Dispute already resolved", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn resolve_dispute(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    Json(request): Json<ResolveDisputeRequest>,
+) -> Result<Response> {
+    require_admin(&principal)?;
+    let body = ForwardedResolution {
+        resolved_by: &principal.address,
+        request: &request,
+    };
+    Ok(upstream
+        .order_service(Method::POST, &["disputes", &id.to_string(), "resolve"], Some(&body))
+        .await?
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_admins_can_inspect_routing() {
+        let principal = |role| Principal { address: "0xops".to_string(), role };
+        assert!(require_admin(&principal(Role::Admin)).is_ok());
+        for role in [Role::User, Role::Integrator, Role::Provider] {
+            assert!(matches!(require_admin(&principal(role)), Err(ApiError::Forbidden)));
+        }
+        assert!(!principal(Role::Admin).owns_order("0xops", "0xops"));
+    }
+}
@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+use shared_types::OrderStatusChangedEvent;
+use thiserror::Error;
+
+/// Query parameter accepted in place of the Authorization header, since
+/// browser WebSocket and EventSource APIs cannot set custom headers
+const TOKEN_QUERY_PARAM: &str = "access_token";
+
+/// Kind of caller a token was issued to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// End user; sees orders they created
+    User,
+    /// dApp integrator; sees orders placed through their integration
+    Integrator,
+    /// Liquidity provider; manages its own intents
+    Provider,
+    /// Operator; inspects routing decisions
+    Admin,
+}
+
+/// JWT claims issued to gateway callers
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    /// Wallet address of the caller
+    pub sub: String,
+    pub role: Role,
+    /// Expiry as unix seconds
+    pub exp: usize,
+}
+
+/// Authenticated caller
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    pub address: String,
+    pub role: Role,
+}
+
+impl Principal {
+    /// Check if the caller is the user or integrator of an order
+    pub fn owns_order(&self, user_address: &str, integrator_address: &str) -> bool {
+        let owner = match self.role {
+            Role::User => user_address,
+            Role::Integrator => integrator_address,
+            Role::Provider | Role::Admin => return false,
+        };
+        owner.eq_ignore_ascii_case(&self.address)
+    }
+
+    /// Check if the caller is a party to the order the event belongs to
+    pub fn can_view(&self, event: &OrderStatusChangedEvent) -> bool {
+        self.owns_order(&event.user_address, &event.integrator_address)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("Missing bearer token")]
+    MissingToken,
+
+    #[error("Invalid token: {0}")]
+    InvalidToken(#[from] jsonwebtoken::errors::Error),
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({ "error": self.to_string() })),
+        )
+            .into_response()
+    }
+}
+
+/// HS256 JWT verifier
+pub struct JwtAuth {
+    decoding_key: DecodingKey,
+    validation: Validation,
+    #[cfg(test)]
+    encoding_key: jsonwebtoken::EncodingKey,
+}
+
+impl JwtAuth {
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            decoding_key: DecodingKey::from_secret(secret),
+            validation: Validation::new(Algorithm::HS256),
+            #[cfg(test)]
+            encoding_key: jsonwebtoken::EncodingKey::from_secret(secret),
+        }
+    }
+
+    /// Load the signing secret from `GATEWAY_JWT_SECRET`
+    pub fn from_env() -> anyhow::Result<Self> {
+        let secret = shared_utils::signing::required_secret("GATEWAY_JWT_SECRET")?;
+        Ok(Self::new(secret.as_bytes()))
+    }
+
+    /// Validate a token and return the caller it was issued to
+    pub fn verify(&self, token: &str) -> Result<Principal, AuthError> {
+        let data = jsonwebtoken::decode::<Claims>(token, &self.decoding_key, &self.validation)?;
+        Ok(Principal {
+            address: data.claims.sub,
+            role: data.claims.role,
+        })
+    }
+
+    #[cfg(test)]
+    pub fn issue(&self, address: &str, role: Role) -> String {
+        let claims = Claims {
+            sub: address.to_string(),
+            role,
+            exp: (chrono::Utc::now() + chrono::Duration::hours(1)).timestamp() as usize,
+        };
+        jsonwebtoken::encode(&jsonwebtoken::Header::default(), &claims, &self.encoding_key).unwrap()
+    }
+}
+
+fn token_from_parts(parts: &Parts) -> Option<&str> {
+    if let Some(header) = parts.headers.get(AUTHORIZATION).and_then(|v| v.to_str().ok()) {
+        return header.strip_prefix("Bearer ").map(str::trim);
+    }
+
+    parts.uri.query()?.split('&').find_map(|pair| {
+        pair.split_once('=')
+            .filter(|(key, _)| *key == TOKEN_QUERY_PARAM)
+            .map(|(_, value)| value)
+    })
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Principal
+where
+    Arc<JwtAuth>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let auth = Arc::<JwtAuth>::from_ref(state);
+        let token = token_from_parts(parts).ok_or(AuthError::MissingToken)?;
+        auth.verify(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_roundtrip_and_rejection() {
+        let auth = JwtAuth::new(b"test-secret");
+        let token = auth.issue("0xalice", Role::User);
+
+        let principal = auth.verify(&token).unwrap();
+        assert_eq!(principal, Principal { address: "0xalice".to_string(), role: Role::User });
+
+        assert!(JwtAuth::new(b"other-secret").verify(&token).is_err());
+        assert!(auth.verify("not-a-token").is_err());
+    }
+}
@@ -0,0 +1,38 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ApiError {
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Forbidden")]
+    Forbidden,
+
+    #[error("Upstream service unavailable: {0}")]
+    Upstream(#[from] reqwest::Error),
+}
+
+pub type Result<T> = std::result::Result<T, ApiError>;
+
+/// Error body returned by every gateway endpoint
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ErrorBody {
+    pub error: String,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            ApiError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Forbidden => StatusCode::FORBIDDEN,
+            ApiError::Upstream(_) => StatusCode::BAD_GATEWAY,
+        };
+        (status, Json(ErrorBody { error: self.to_string() })).into_response()
+    }
+}
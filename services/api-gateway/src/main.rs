@@ -1,21 +1,68 @@
 use axum::{routing::get, Router};
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 use tracing::info;
 
+mod admin;
+mod auth;
+mod error;
+mod openapi;
+mod orders;
+mod providers;
+mod quotes;
+mod state;
+mod streaming;
+mod upstream;
+mod webhooks;
+
+use auth::JwtAuth;
+use state::AppState;
+use streaming::OrderEventHub;
+use upstream::{Upstream, UpstreamConfig};
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt::init();
     dotenv::dotenv().ok();
 
+    let buffer_size = std::env::var("STREAM_BUFFER_SIZE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1024);
+    let state = AppState {
+        hub: Arc::new(OrderEventHub::new(buffer_size)),
+        auth: Arc::new(JwtAuth::from_env()?),
+        upstream: Arc::new(Upstream::new(UpstreamConfig::from_env())?),
+    };
+
+    let nats_url = std::env::var("NATS_URL").unwrap_or_else(|_| "nats://127.0.0.1:4222".to_string());
+    let nats = shared_messaging::connect_nats(&nats_url).await?;
+    let hub = state.hub.clone();
+    tokio::spawn(async move {
+        if let Err(e) = streaming::bridge_nats(nats, hub).await {
+            tracing::error!("Order event bridge stopped: {}", e);
+        }
+    });
+
     let app = Router::new()
-        .route("/health", get(health_check));
+        .route("/health", get(health_check))
+        .merge(admin::router())
+        .merge(orders::router())
+        .merge(providers::router())
+        .merge(quotes::router())
+        .merge(streaming::router())
+        .merge(webhooks::router())
+        .merge(openapi::router())
+        .with_state(state);
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], 8000));
+    let port = std::env::var("API_GATEWAY_PORT")
+        .ok()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(8000);
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
     info!("API Gateway listening on {}", addr);
 
-    axum::Server::bind(&addr)
-        .serve(app.into_make_service())
-        .await?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await?;
 
     Ok(())
 }
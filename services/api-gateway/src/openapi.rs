@@ -0,0 +1,111 @@
+//! OpenAPI 3.1 document generated from the gateway handlers
+//!
+//! The generated document is committed as `openapi.json` at the crate root so
+//! SDK generators can consume it; a test fails when the two drift apart.
+//! Regenerate with `UPDATE_OPENAPI=1 cargo test -p api-gateway openapi`.
+
+use axum::{routing::get, Json, Router};
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
+
+use crate::{admin, error::ErrorBody, orders, providers, quotes, state::AppState, streaming, webhooks};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "PayNode API",
+        description = "Public API of the PayNode off-ramp aggregator",
+        license(name = "MIT")
+    ),
+    paths(
+        admin::list_routing_decisions,
+        admin::get_routing_decision,
+        admin::list_disputes,
+        admin::get_dispute,
+        admin::resolve_dispute,
+        orders::create_order,
+        orders::list_orders,
+        orders::get_order,
+        orders::list_proposals,
+        orders::list_rates,
+        orders::open_dispute,
+        orders::list_disputes,
+        providers::register_provider,
+        providers::list_intents,
+        providers::update_intent,
+        providers::pause_intent,
+        providers::resume_intent,
+        providers::submit_rate,
+        providers::get_reputation,
+        providers::get_health,
+        providers::get_stake,
+        providers::get_heartbeat_key,
+        providers::list_disputes,
+        providers::submit_evidence,
+        quotes::create_quote,
+        streaming::sse_handler,
+        streaming::ws_handler,
+        webhooks::register_webhook,
+        webhooks::list_webhooks,
+        webhooks::enable_webhook,
+        webhooks::list_attempts,
+        webhooks::redeliver,
+    ),
+    components(schemas(ErrorBody)),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "admin", description = "Operator tooling"),
+        (name = "orders", description = "Order creation, lookup and disputes"),
+        (name = "providers", description = "Liquidity provider intents"),
+        (name = "quotes", description = "Expected payouts before order creation"),
+        (name = "streaming", description = "Real-time order status updates"),
+        (name = "webhooks", description = "Order event delivery to integrators"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the JWT bearer scheme referenced by `security(("bearer" = []))`
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+    }
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/openapi.json", get(openapi_json))
+}
+
+async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+    #[test]
+    fn test_committed_openapi_matches_handlers() {
+        let generated = ApiDoc::openapi().to_pretty_json().unwrap() + "\n";
+
+        if std::env::var("UPDATE_OPENAPI").is_ok() {
+            std::fs::write(SPEC_PATH, &generated).unwrap();
+            return;
+        }
+
+        let committed = std::fs::read_to_string(SPEC_PATH).unwrap_or_default();
+        assert!(
+            committed == generated,
+            "openapi.json is out of date; run `UPDATE_OPENAPI=1 cargo test -p api-gateway openapi` and commit the result"
+        );
+    }
+}
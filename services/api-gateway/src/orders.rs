@@ -0,0 +1,672 @@
+//! Order endpoints, proxied to the order service
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use shared_types::{
+    helpers::{is_valid_address, is_valid_bytes32},
+    CreateOrderRequest, Dispute, FxRateSnapshot, OpenDisputeRequest, Order, Page, Proposal,
+};
+
+use crate::{
+    auth::{Principal, Role},
+    error::{ApiError, ErrorBody, Result},
+    quotes::fetch_valid_quote,
+    state::AppState,
+    upstream::Upstream,
+};
+
+/// Header carrying the key that makes an order creation idempotent
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Order creation as forwarded to the order service
+#[derive(Serialize)]
+struct ForwardedOrder<'a> {
+    user_address: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    idempotency_key: Option<&'a str>,
+    #[serde(flatten)]
+    request: &'a CreateOrderRequest,
+}
+
+/// Dispute as forwarded to the order service
+#[derive(Serialize)]
+struct ForwardedDispute<'a> {
+    user_address: &'a str,
+    #[serde(flatten)]
+    request: &'a OpenDisputeRequest,
+}
+
+/// Largest page the gateway will request from the order service
+const MAX_PAGE_SIZE: u32 = 100;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListOrdersQuery {
+    /// Cursor returned as `next_cursor` by the previous page
+    pub cursor: Option<String>,
+    /// Page size, at most 100 (default 20)
+    pub limit: Option<u32>,
+}
+
+/// Order listing as forwarded to the order service
+#[derive(Serialize)]
+struct ForwardedListQuery<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_address: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    integrator_address: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cursor: Option<&'a str>,
+    limit: u32,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/v1/orders", post(create_order).get(list_orders))
+        .route("/v1/orders/:order_id", get(get_order))
+        .route("/v1/orders/:order_id/proposals", get(list_proposals))
+        .route("/v1/orders/:order_id/rates", get(list_rates))
+        .route("/v1/orders/:order_id/disputes", post(open_dispute).get(list_disputes))
+}
+
+fn validate(request: &CreateOrderRequest) -> Result<()> {
+    for (field, address) in [
+        ("token", &request.token),
+        ("refund_address", &request.refund_address),
+        ("integrator_address", &request.integrator_address),
+    ] {
+        if !is_valid_address(address) {
+            return Err(ApiError::InvalidRequest(format!("{} is not a valid address", field)));
+        }
+    }
+    match request.amount.parse::<u128>() {
+        Ok(amount) if amount > 0 => {}
+        _ => return Err(ApiError::InvalidRequest("amount must be a positive integer".to_string())),
+    }
+    if !shared_utils::validate_currency(&request.currency) {
+        return Err(ApiError::InvalidRequest(format!("Unsupported currency: {}", request.currency)));
+    }
+    Ok(())
+}
+
+fn validate_order_id(order_id: &str) -> Result<()> {
+    if !is_valid_bytes32(order_id) {
+        return Err(ApiError::InvalidRequest(format!("{} is not a valid order ID", order_id)));
+    }
+    Ok(())
+}
+
+/// Fetch an order and check the caller is party to it
+async fn fetch_owned_order(upstream: &Upstream, principal: &Principal, order_id: &str) -> Result<std::result::Result<Order, Response>> {
+    validate_order_id(order_id)?;
+    let response = upstream
+        .order_service::<()>(Method::GET, &["orders", order_id], None)
+        .await?;
+    let Some(order) = response.json::<Order>() else {
+        return Ok(Err(response.into_response()));
+    };
+    if !principal.owns_order(&order.user_address, &order.integrator_address) {
+        return Err(ApiError::Forbidden);
+    }
+    Ok(Ok(order))
+}
+
+/// Create an off-ramp order for the authenticated user
+#[utoipa::path(
+    post,
+    path = "/v1/orders",
+    tag = "orders",
+    request_body = CreateOrderRequest,
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Repeating a create with the same key returns the order it first created"),
+    ),
+    responses(
+        (status = 201, description = "Order created, or the order an earlier request with the same idempotency key created", body = Order),
+        (status = 400, description = "Invalid request, or a quote that is unknown, expired or does not match", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only users can create orders", body = ErrorBody),
+        (status = 409, description = "Idempotency key already used for a different order, or quote already used", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_order(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    headers: HeaderMap,
+    Json(request): Json<CreateOrderRequest>,
+) -> Result<Response> {
+    if principal.role != Role::User {
+        return Err(ApiError::Forbidden);
+    }
+    validate(&request)?;
+    let idempotency_key = headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .map(|value| {
+            value
+                .to_str()
+                .map_err(|_| ApiError::InvalidRequest(format!("{} must be printable ASCII", IDEMPOTENCY_KEY_HEADER)))
+        })
+        .transpose()?;
+    if let Some(quote_id) = request.quote_id {
+        let quote = fetch_valid_quote(&upstream, quote_id).await?;
+        if !quote.matches_order(&request) {
+            return Err(ApiError::InvalidRequest("Order does not match the quoted terms".to_string()));
+        }
+    }
+
+    let body = ForwardedOrder {
+        user_address: &principal.address,
+        idempotency_key,
+        request: &request,
+    };
+    Ok(upstream
+        .order_service(Method::POST, &["orders"], Some(&body))
+        .await?
+        .into_response())
+}
+
+/// List orders the caller is the user or integrator of, newest first
+#[utoipa::path(
+    get,
+    path = "/v1/orders",
+    tag = "orders",
+    params(ListOrdersQuery),
+    responses(
+        (status = 200, description = "One page of orders", body = Page<Order>),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Providers cannot list orders", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_orders(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Query(query): Query<ListOrdersQuery>,
+) -> Result<Response> {
+    let address = Some(principal.address.as_str());
+    let (user_address, integrator_address) = match principal.role {
+        Role::User => (address, None),
+        Role::Integrator => (None, address),
+        Role::Provider | Role::Admin => return Err(ApiError::Forbidden),
+    };
+
+    let forwarded = ForwardedListQuery {
+        user_address,
+        integrator_address,
+        cursor: query.cursor.as_deref(),
+        limit: query.limit.unwrap_or(20).clamp(1, MAX_PAGE_SIZE),
+    };
+    Ok(upstream.order_service_query(&["orders"], &forwarded).await?.into_response())
+}
+
+/// Get an order the caller is the user or integrator of
+#[utoipa::path(
+    get,
+    path = "/v1/orders/{order_id}",
+    tag = "orders",
+    params(("order_id" = String, Path, description = "Blockchain order ID (bytes32 hex)")),
+    responses(
+        (status = 200, description = "Order", body = Order),
+        (status = 400, description = "Invalid order ID", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Caller is not party to the order", body = ErrorBody),
+        (status = 404, description = "Order not found", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_order(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(order_id): Path<String>,
+) -> Result<Response> {
+    Ok(match fetch_owned_order(&upstream, &principal, &order_id).await? {
+        Ok(order) => Json(order).into_response(),
+        Err(response) => response,
+    })
+}
+
+/// List settlement proposals made for an order
+#[utoipa::path(
+    get,
+    path = "/v1/orders/{order_id}/proposals",
+    tag = "orders",
+    params(("order_id" = String, Path, description = "Blockchain order ID (bytes32 hex)")),
+    responses(
+        (status = 200, description = "Proposals for the order", body = [Proposal]),
+        (status = 400, description = "Invalid order ID", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Caller is not party to the order", body = ErrorBody),
+        (status = 404, description = "Order not found", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_proposals(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(order_id): Path<String>,
+) -> Result<Response> {
+    if let Err(response) = fetch_owned_order(&upstream, &principal, &order_id).await? {
+        return Ok(response);
+    }
+    Ok(upstream
+        .order_service::<()>(Method::GET, &["orders", &order_id, "proposals"], None)
+        .await?
+        .into_response())
+}
+
+/// FX rates the order was priced with, from the quote it was created from
+#[utoipa::path(
+    get,
+    path = "/v1/orders/{order_id}/rates",
+    tag = "orders",
+    params(("order_id" = String, Path, description = "Blockchain order ID (bytes32 hex)")),
+    responses(
+        (status = 200, description = "Rates recorded for the order, oldest first", body = [FxRateSnapshot]),
+        (status = 400, description = "Invalid order ID", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Caller is not party to the order", body = ErrorBody),
+        (status = 404, description = "Order not found", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_rates(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(order_id): Path<String>,
+) -> Result<Response> {
+    if let Err(response) = fetch_owned_order(&upstream, &principal, &order_id).await? {
+        return Ok(response);
+    }
+    Ok(upstream
+        .order_service::<()>(Method::GET, &["orders", &order_id, "rates"], None)
+        .await?
+        .into_response())
+}
+
+/// Dispute a payout the authenticated user did not receive
+///
+/// Settlement of the order is held until an admin resolves the dispute.
+#[utoipa::path(
+    post,
+    path = "/v1/orders/{order_id}/disputes",
+    tag = "orders",
+    params(("order_id" = String, Path, description = "Blockchain order ID (bytes32 hex)")),
+    request_body = OpenDisputeRequest,
+    responses(
+        (status = 201, description = "Dispute opened", body = Dispute),
+        (status = 400, description = "Invalid request", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only the order's user can dispute it", body = ErrorBody),
+        (status = 404, description = "Order not found", body = ErrorBody),
+        (status = 409, description = "Order is not disputable, or already disputed", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn open_dispute(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(order_id): Path<String>,
+    Json(request): Json<OpenDisputeRequest>,
+) -> Result<Response> {
+    if principal.role != Role::User {
+        return Err(ApiError::Forbidden);
+    }
+    if let Err(response) = fetch_owned_order(&upstream, &principal, &order_id).await? {
+        return Ok(response);
+    }
+    let body = ForwardedDispute {
+        user_address: &principal.address,
+        request: &request,
+    };
+    Ok(upstream
+        .order_service(Method::POST, &["orders", &order_id, "disputes"], Some(&body))
+        .await?
+        .into_response())
+}
+
+/// List disputes opened on an order, newest first
+#[utoipa::path(
+    get,
+    path = "/v1/orders/{order_id}/disputes",
+    tag = "orders",
+    params(("order_id" = String, Path, description = "Blockchain order ID (bytes32 hex)")),
+    responses(
+        (status = 200, description = "Disputes on the order", body = [Dispute]),
+        (status = 400, description = "Invalid order ID", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Caller is not party to the order", body = ErrorBody),
+        (status = 404, description = "Order not found", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_disputes(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(order_id): Path<String>,
+) -> Result<Response> {
+    if let Err(response) = fetch_owned_order(&upstream, &principal, &order_id).await? {
+        return Ok(response);
+    }
+    Ok(upstream
+        .order_service::<()>(Method::GET, &["orders", &order_id, "disputes"], None)
+        .await?
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::serve_stub;
+    use axum::{body::Body, http::Request, http::StatusCode};
+    use shared_types::{Currency, OrderTier};
+    use std::sync::Mutex;
+    use tower::ServiceExt;
+
+    const ALICE: &str = "0x00000000000000000000000000000000000a11ce";
+    const BOB: &str = "0x0000000000000000000000000000000000000b0b";
+    const INTEGRATOR: &str = "0xcccccccccccccccccccccccccccccccccccccccc";
+
+    type Orders = Arc<Mutex<Vec<Order>>>;
+
+    fn order_request() -> CreateOrderRequest {
+        CreateOrderRequest {
+            token: format!("0x{}", "a".repeat(40)),
+            amount: "1000000".to_string(),
+            currency: "NGN".to_string(),
+            refund_address: format!("0x{}", "b".repeat(40)),
+            integrator_address: INTEGRATOR.to_string(),
+            quote_id: None,
+        }
+    }
+
+    /// Stand-in for the order service's order routes, backed by memory
+    fn order_service_stub(orders: Orders) -> Router {
+        async fn create(State(orders): State<Orders>, Json(body): Json<serde_json::Value>) -> (StatusCode, Json<Order>) {
+            let request: CreateOrderRequest = serde_json::from_value(body.clone()).unwrap();
+            let mut orders = orders.lock().unwrap();
+            let order = Order::new(
+                format!("0x{:064x}", orders.len() + 1),
+                body["user_address"].as_str().unwrap().to_string(),
+                request.token,
+                request.amount,
+                request.refund_address,
+                request.integrator_address,
+                50,
+                Currency::from(request.currency.as_str()),
+                OrderTier::Alpha,
+                chrono::Utc::now(),
+                0,
+                String::new(),
+            );
+            orders.push(order.clone());
+            (StatusCode::CREATED, Json(order))
+        }
+
+        async fn get(
+            State(orders): State<Orders>,
+            Path(order_id): Path<String>,
+        ) -> std::result::Result<Json<Order>, StatusCode> {
+            let orders = orders.lock().unwrap();
+            let order = orders.iter().find(|order| order.order_id == order_id);
+            order.cloned().map(Json).ok_or(StatusCode::NOT_FOUND)
+        }
+
+        #[derive(Deserialize)]
+        struct ListQuery {
+            user_address: Option<String>,
+            integrator_address: Option<String>,
+            cursor: Option<String>,
+            limit: usize,
+        }
+
+        async fn list(State(orders): State<Orders>, Query(query): Query<ListQuery>) -> Json<Page<Order>> {
+            let orders = orders.lock().unwrap();
+            let matches = |filter: &Option<String>, address: &str| filter.as_deref().is_none_or(|f| f == address);
+            let listed: Vec<&Order> = orders
+                .iter()
+                .rev()
+                .filter(|order| {
+                    matches(&query.user_address, &order.user_address)
+                        && matches(&query.integrator_address, &order.integrator_address)
+                })
+                .collect();
+            let start = query.cursor.map_or(0, |cursor| {
+                listed.iter().position(|order| order.order_id == cursor).unwrap() + 1
+            });
+            let data: Vec<Order> = listed[start..].iter().take(query.limit).map(|order| (*order).clone()).collect();
+            let next_cursor = (start + data.len() < listed.len()).then(|| data.last().unwrap().order_id.clone());
+            Json(Page { data, next_cursor })
+        }
+
+        Router::new()
+            .route("/orders", post(create).get(list))
+            .route("/orders/:order_id", axum::routing::get(get))
+            .route(
+                "/orders/:order_id/proposals",
+                axum::routing::get(|| async { Json(Vec::<Proposal>::new()) }),
+            )
+            .route(
+                "/orders/:order_id/disputes",
+                post(|| async { (StatusCode::CREATED, Json(serde_json::json!({}))) })
+                    .get(|| async { Json(Vec::<Dispute>::new()) }),
+            )
+            .with_state(orders)
+    }
+
+    fn call(method: &str, uri: &str, token: &str, body: Option<&CreateOrderRequest>) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", format!("Bearer {}", token))
+            .header("content-type", "application/json")
+            .body(body.map_or_else(Body::empty, |body| Body::from(serde_json::to_vec(body).unwrap())))
+            .unwrap()
+    }
+
+    async fn read_json<T: serde::de::DeserializeOwned>(response: Response) -> T {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_orders_are_created_and_read_through_the_gateway() {
+        let state = AppState::with_test_upstream(&serve_stub(order_service_stub(Orders::default())).await);
+        let app = router().with_state(state.clone());
+        let alice = state.auth.issue(ALICE, Role::User);
+        let bob = state.auth.issue(BOB, Role::User);
+        let integrator = state.auth.issue(INTEGRATOR, Role::Integrator);
+
+        let response = app
+            .clone()
+            .oneshot(call("POST", "/v1/orders", &integrator, Some(&order_request())))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app
+            .clone()
+            .oneshot(call("POST", "/v1/orders", &alice, Some(&order_request())))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let order: Order = read_json(response).await;
+        assert_eq!(order.user_address, ALICE);
+
+        let uri = format!("/v1/orders/{}", order.order_id);
+        for (token, status) in [(&alice, StatusCode::OK), (&integrator, StatusCode::OK), (&bob, StatusCode::FORBIDDEN)] {
+            let response = app.clone().oneshot(call("GET", &uri, token, None)).await.unwrap();
+            assert_eq!(response.status(), status);
+        }
+        let response = app.clone().oneshot(call("GET", &uri, &alice, None)).await.unwrap();
+        assert_eq!(read_json::<Order>(response).await.order_id, order.order_id);
+
+        let missing = format!("/v1/orders/0x{}", "f".repeat(64));
+        let response = app.clone().oneshot(call("GET", &missing, &alice, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let proposals = format!("{}/proposals", uri);
+        let response = app.clone().oneshot(call("GET", &proposals, &bob, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = app.oneshot(call("GET", &proposals, &alice, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(read_json::<Vec<Proposal>>(response).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_is_forwarded_with_the_order() {
+        let echo = Router::new().route(
+            "/orders",
+            post(|Json(body): Json<serde_json::Value>| async move { (StatusCode::CREATED, Json(body)) }),
+        );
+        let state = AppState::with_test_upstream(&serve_stub(echo).await);
+        let app = router().with_state(state.clone());
+        let alice = state.auth.issue(ALICE, Role::User);
+
+        let mut request = call("POST", "/v1/orders", &alice, Some(&order_request()));
+        request.headers_mut().insert(IDEMPOTENCY_KEY_HEADER, "order-key-1".parse().unwrap());
+        let forwarded: serde_json::Value = read_json(app.clone().oneshot(request).await.unwrap()).await;
+        assert_eq!(forwarded["idempotency_key"], "order-key-1");
+        assert_eq!(forwarded["user_address"], ALICE);
+
+        let response = app.oneshot(call("POST", "/v1/orders", &alice, Some(&order_request()))).await.unwrap();
+        let forwarded: serde_json::Value = read_json(response).await;
+        assert!(forwarded.get("idempotency_key").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_order_listing_pages_through_the_callers_orders() {
+        let state = AppState::with_test_upstream(&serve_stub(order_service_stub(Orders::default())).await);
+        let app = router().with_state(state.clone());
+        let alice = state.auth.issue(ALICE, Role::User);
+        let bob = state.auth.issue(BOB, Role::User);
+        let integrator = state.auth.issue(INTEGRATOR, Role::Integrator);
+
+        let mut created = Vec::new();
+        for token in [&alice, &bob, &alice, &alice, &bob, &alice, &alice] {
+            let response = app
+                .clone()
+                .oneshot(call("POST", "/v1/orders", token, Some(&order_request())))
+                .await
+                .unwrap();
+            created.push(read_json::<Order>(response).await);
+        }
+
+        let list_all = |token: String| {
+            let app = app.clone();
+            async move {
+                let mut seen = Vec::new();
+                let mut cursor: Option<String> = None;
+                loop {
+                    let uri = match &cursor {
+                        Some(cursor) => format!("/v1/orders?limit=2&cursor={}", cursor),
+                        None => "/v1/orders?limit=2".to_string(),
+                    };
+                    let response = app.clone().oneshot(call("GET", &uri, &token, None)).await.unwrap();
+                    assert_eq!(response.status(), StatusCode::OK);
+                    let page: Page<Order> = read_json(response).await;
+                    assert!(page.data.len() <= 2);
+                    seen.extend(page.data.into_iter().map(|order| order.order_id));
+                    match page.next_cursor {
+                        Some(next) => cursor = Some(next),
+                        None => return seen,
+                    }
+                }
+            }
+        };
+
+        let newest_first = |owner: Option<&str>| -> Vec<String> {
+            created
+                .iter()
+                .rev()
+                .filter(|order| owner.is_none_or(|owner| order.user_address == owner))
+                .map(|order| order.order_id.clone())
+                .collect()
+        };
+        assert_eq!(list_all(alice).await, newest_first(Some(ALICE)));
+        assert_eq!(list_all(bob).await, newest_first(Some(BOB)));
+        assert_eq!(list_all(integrator).await, newest_first(None));
+
+        let provider = state.auth.issue(INTEGRATOR, Role::Provider);
+        let response = app.oneshot(call("GET", "/v1/orders", &provider, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_order_disputes_are_listed_for_parties_to_the_order() {
+        let state = AppState::with_test_upstream(&serve_stub(order_service_stub(Orders::default())).await);
+        let app = router().with_state(state.clone());
+        let alice = state.auth.issue(ALICE, Role::User);
+        let bob = state.auth.issue(BOB, Role::User);
+        let integrator = state.auth.issue(INTEGRATOR, Role::Integrator);
+
+        let response = app
+            .clone()
+            .oneshot(call("POST", "/v1/orders", &alice, Some(&order_request())))
+            .await
+            .unwrap();
+        let order: Order = read_json(response).await;
+
+        let uri = format!("/v1/orders/{}/disputes", order.order_id);
+        for (token, status) in [(&alice, StatusCode::OK), (&integrator, StatusCode::OK), (&bob, StatusCode::FORBIDDEN)] {
+            let response = app.clone().oneshot(call("GET", &uri, token, None)).await.unwrap();
+            assert_eq!(response.status(), status);
+        }
+        let missing = format!("/v1/orders/0x{}/disputes", "f".repeat(64));
+        let response = app.oneshot(call("GET", &missing, &alice, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_disputes_are_only_opened_by_the_orders_user() {
+        let state = AppState::with_test_upstream(&serve_stub(order_service_stub(Orders::default())).await);
+        let app = router().with_state(state.clone());
+        let alice = state.auth.issue(ALICE, Role::User);
+        let bob = state.auth.issue(BOB, Role::User);
+
+        let response = app
+            .clone()
+            .oneshot(call("POST", "/v1/orders", &alice, Some(&order_request())))
+            .await
+            .unwrap();
+        let order: Order = read_json(response).await;
+        let dispute = |order_id: &str, token: &str| {
+            Request::post(format!("/v1/orders/{}/disputes", order_id))
+                .header("authorization", format!("Bearer {}", token))
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"reason":"Payout never arrived"}"#))
+                .unwrap()
+        };
+
+        let escape = format!("..%2F..%2Fdisputes%2F{}%2Fresolve%3F", uuid::Uuid::new_v4());
+        let response = app.clone().oneshot(dispute(&escape, &alice)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app.clone().oneshot(dispute(&order.order_id, &bob)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app.oneshot(dispute(&order.order_id, &alice)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[test]
+    fn test_create_order_validation() {
+        let mut request = order_request();
+        assert!(validate(&request).is_ok());
+
+        request.amount = "0".to_string();
+        assert!(validate(&request).is_err());
+
+        request.amount = "10".to_string();
+        request.currency = "XYZ".to_string();
+        assert!(validate(&request).is_err());
+    }
+}
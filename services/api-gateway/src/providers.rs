@@ -0,0 +1,427 @@
+//! Provider endpoints, proxied to the provider service
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
+    Json, Router,
+};
+use reqwest::Method;
+use serde::Serialize;
+use shared_types::{
+    Dispute, ProviderHealth, ProviderIntent, ProviderReputation, ProviderStake, RegisterProviderRequest, SubmitEvidenceRequest,
+    SubmitRateRequest, UpdateIntentRequest,
+};
+use uuid::Uuid;
+
+use crate::{
+    auth::{Principal, Role},
+    error::{ApiError, ErrorBody, Result},
+    state::AppState,
+    upstream::Upstream,
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/v1/providers", post(register_provider))
+        .route("/v1/providers/intents", get(list_intents))
+        .route("/v1/providers/intents/:currency", put(update_intent))
+        .route("/v1/providers/intents/:currency/pause", post(pause_intent))
+        .route("/v1/providers/intents/:currency/resume", post(resume_intent))
+        .route("/v1/providers/rates", post(submit_rate))
+        .route("/v1/providers/reputation", get(get_reputation))
+        .route("/v1/providers/health", get(get_health))
+        .route("/v1/providers/stake", get(get_stake))
+        .route("/v1/providers/heartbeat-key", get(get_heartbeat_key))
+        .route("/v1/providers/disputes", get(list_disputes))
+        .route("/v1/providers/disputes/:id/evidence", post(submit_evidence))
+}
+
+/// Evidence as forwarded to the order service
+#[derive(Serialize)]
+struct ForwardedEvidence<'a> {
+    provider: &'a str,
+    #[serde(flatten)]
+    request: &'a SubmitEvidenceRequest,
+}
+
+/// Dispute listing as forwarded to the order service
+#[derive(Serialize)]
+struct ForwardedDisputeQuery<'a> {
+    provider: &'a str,
+}
+
+fn require_provider(principal: &Principal) -> Result<()> {
+    if principal.role != Role::Provider {
+        return Err(ApiError::Forbidden);
+    }
+    Ok(())
+}
+
+/// Reject anything but a supported currency code before it reaches a path
+fn validate_intent_currency(currency: &str) -> Result<()> {
+    if !shared_utils::validate_currency(currency) {
+        return Err(ApiError::InvalidRequest(format!("Unsupported currency: {}", currency)));
+    }
+    Ok(())
+}
+
+fn validate(request: &RegisterProviderRequest) -> Result<()> {
+    if !shared_utils::validate_currency(&request.currency) {
+        return Err(ApiError::InvalidRequest(format!("Unsupported currency: {}", request.currency)));
+    }
+    if request.available_amount.parse::<u128>().is_err() {
+        return Err(ApiError::InvalidRequest("available_amount must be an integer".to_string()));
+    }
+    if request.min_fee_bps > request.max_fee_bps || request.max_fee_bps > 10_000 {
+        return Err(ApiError::InvalidRequest("Fee range must satisfy min_fee_bps <= max_fee_bps <= 10000".to_string()));
+    }
+    Ok(())
+}
+
+/// Register or update the authenticated provider's liquidity intent
+#[utoipa::path(
+    post,
+    path = "/v1/providers",
+    tag = "providers",
+    request_body = RegisterProviderRequest,
+    responses(
+        (status = 201, description = "Intent registered", body = ProviderIntent),
+        (status = 400, description = "Invalid request", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only providers can register intents", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn register_provider(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Json(request): Json<RegisterProviderRequest>,
+) -> Result<Response> {
+    require_provider(&principal)?;
+    validate(&request)?;
+
+    Ok(upstream
+        .provider_service(Method::POST, &["providers", &principal.address, "intents"], Some(&request))
+        .await?
+        .into_response())
+}
+
+/// The authenticated provider's live intents
+#[utoipa::path(
+    get,
+    path = "/v1/providers/intents",
+    tag = "providers",
+    responses(
+        (status = 200, description = "Active intents", body = [ProviderIntent]),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only providers have intents", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_intents(State(upstream): State<Arc<Upstream>>, principal: Principal) -> Result<Response> {
+    require_provider(&principal)?;
+    Ok(upstream
+        .provider_service::<()>(Method::GET, &["providers", &principal.address, "intents"], None)
+        .await?
+        .into_response())
+}
+
+/// Change some terms of the authenticated provider's intent in a currency
+#[utoipa::path(
+    put,
+    path = "/v1/providers/intents/{currency}",
+    tag = "providers",
+    params(("currency" = String, Path, description = "Currency of the intent")),
+    request_body = UpdateIntentRequest,
+    responses(
+        (status = 200, description = "Intent updated", body = ProviderIntent),
+        (status = 400, description = "Invalid request", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only providers have intents", body = ErrorBody),
+        (status = 404, description = "No intent in the currency", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn update_intent(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(currency): Path<String>,
+    Json(request): Json<UpdateIntentRequest>,
+) -> Result<Response> {
+    require_provider(&principal)?;
+    validate_intent_currency(&currency)?;
+    Ok(upstream
+        .provider_service(
+            Method::PUT,
+            &["providers", &principal.address, "intents", &currency],
+            Some(&request),
+        )
+        .await?
+        .into_response())
+}
+
+/// Stop receiving orders in a currency until the intent is resumed
+#[utoipa::path(
+    post,
+    path = "/v1/providers/intents/{currency}/pause",
+    tag = "providers",
+    params(("currency" = String, Path, description = "Currency of the intent")),
+    responses(
+        (status = 200, description = "Intent paused", body = ProviderIntent),
+        (status = 400, description = "Unsupported currency", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only providers have intents", body = ErrorBody),
+        (status = 404, description = "No intent in the currency", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn pause_intent(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(currency): Path<String>,
+) -> Result<Response> {
+    set_active(&upstream, &principal, &currency, "pause").await
+}
+
+/// Resume a paused intent
+#[utoipa::path(
+    post,
+    path = "/v1/providers/intents/{currency}/resume",
+    tag = "providers",
+    params(("currency" = String, Path, description = "Currency of the intent")),
+    responses(
+        (status = 200, description = "Intent resumed", body = ProviderIntent),
+        (status = 400, description = "Unsupported currency", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only providers have intents", body = ErrorBody),
+        (status = 404, description = "No intent in the currency", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn resume_intent(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(currency): Path<String>,
+) -> Result<Response> {
+    set_active(&upstream, &principal, &currency, "resume").await
+}
+
+async fn set_active(upstream: &Upstream, principal: &Principal, currency: &str, action: &str) -> Result<Response> {
+    require_provider(principal)?;
+    validate_intent_currency(currency)?;
+    Ok(upstream
+        .provider_service::<()>(
+            Method::POST,
+            &["providers", &principal.address, "intents", currency, action],
+            None,
+        )
+        .await?
+        .into_response())
+}
+
+/// Submit the USD rate the authenticated provider pays out at in a currency
+/// it has a live intent for; the FX oracle prices quotes with it until it
+/// goes stale
+#[utoipa::path(
+    post,
+    path = "/v1/providers/rates",
+    tag = "providers",
+    request_body = SubmitRateRequest,
+    responses(
+        (status = 204, description = "Rate recorded"),
+        (status = 400, description = "Invalid rate, or no live intent in the currency", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only providers submit rates", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn submit_rate(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Json(request): Json<SubmitRateRequest>,
+) -> Result<Response> {
+    require_provider(&principal)?;
+    validate_intent_currency(&request.currency)?;
+    Ok(upstream
+        .provider_service(Method::POST, &["providers", &principal.address, "rates"], Some(&request))
+        .await?
+        .into_response())
+}
+
+/// The authenticated provider's track record
+#[utoipa::path(
+    get,
+    path = "/v1/providers/reputation",
+    tag = "providers",
+    responses(
+        (status = 200, description = "Provider reputation", body = ProviderReputation),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only providers have a reputation", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_reputation(State(upstream): State<Arc<Upstream>>, principal: Principal) -> Result<Response> {
+    require_provider(&principal)?;
+    Ok(upstream
+        .provider_service::<()>(Method::GET, &["providers", &principal.address, "reputation"], None)
+        .await?
+        .into_response())
+}
+
+/// The authenticated provider's rolling heartbeat health
+#[utoipa::path(
+    get,
+    path = "/v1/providers/health",
+    tag = "providers",
+    responses(
+        (status = 200, description = "Uptime and latency percentiles", body = ProviderHealth),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only providers send heartbeats", body = ErrorBody),
+        (status = 404, description = "No heartbeats received", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_health(State(upstream): State<Arc<Upstream>>, principal: Principal) -> Result<Response> {
+    require_provider(&principal)?;
+    Ok(upstream
+        .provider_service::<()>(Method::GET, &["providers", &principal.address, "health"], None)
+        .await?
+        .into_response())
+}
+
+/// The authenticated provider's staked collateral, per token
+#[utoipa::path(
+    get,
+    path = "/v1/providers/stake",
+    tag = "providers",
+    responses(
+        (status = 200, description = "Stake per token", body = [ProviderStake]),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only providers stake collateral", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_stake(State(upstream): State<Arc<Upstream>>, principal: Principal) -> Result<Response> {
+    require_provider(&principal)?;
+    Ok(upstream
+        .balance_service::<()>(Method::GET, &["providers", &principal.address, "stake"], None)
+        .await?
+        .into_response())
+}
+
+/// Key the authenticated provider signs its heartbeats with
+#[utoipa::path(
+    get,
+    path = "/v1/providers/heartbeat-key",
+    tag = "providers",
+    responses(
+        (status = 200, description = "Heartbeat signing key"),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only providers send heartbeats", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_heartbeat_key(State(upstream): State<Arc<Upstream>>, principal: Principal) -> Result<Response> {
+    require_provider(&principal)?;
+    Ok(upstream
+        .provider_service::<()>(Method::GET, &["providers", &principal.address, "heartbeat-key"], None)
+        .await?
+        .into_response())
+}
+
+/// Disputes opened against the authenticated provider, oldest first
+#[utoipa::path(
+    get,
+    path = "/v1/providers/disputes",
+    tag = "providers",
+    responses(
+        (status = 200, description = "Disputes against the provider", body = [Dispute]),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only providers are disputed", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_disputes(State(upstream): State<Arc<Upstream>>, principal: Principal) -> Result<Response> {
+    require_provider(&principal)?;
+    let query = ForwardedDisputeQuery { provider: &principal.address };
+    Ok(upstream.order_service_query(&["disputes"], &query).await?.into_response())
+}
+
+/// Answer a dispute with the payout's payment proof and supporting evidence
+///
+/// The proof must be signed by the provider of the order's accepted proposal
+/// and not already answer another dispute. Evidence is stored in the proof's
+/// `metadata.evidence`. It may be replaced until the dispute is resolved.
+#[utoipa::path(
+    post,
+    path = "/v1/providers/disputes/{id}/evidence",
+    tag = "providers",
+    params(("id" = Uuid, Path, description = "Dispute ID")),
+    request_body = SubmitEvidenceRequest,
+    responses(
+        (status = 200, description = "Evidence recorded", body = Dispute),
+        (status = 400, description = "Proof is not for the disputed order or not signed by its provider", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Dispute is against another provider", body = ErrorBody),
+        (status = 404, description = "Dispute not found", body = ErrorBody),
+        (status = 409, description = "Dispute already resolved, or proof already used", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn submit_evidence(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    Json(request): Json<SubmitEvidenceRequest>,
+) -> Result<Response> {
+    require_provider(&principal)?;
+    let body = ForwardedEvidence {
+        provider: &principal.address,
+        request: &request,
+    };
+    Ok(upstream
+        .order_service(Method::POST, &["disputes", &id.to_string(), "evidence"], Some(&body))
+        .await?
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::serve_stub;
+    use axum::{body::Body, http::Request, http::StatusCode};
+    use std::sync::Mutex;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_intent_routes_stay_on_the_callers_intents() {
+        let hits = Arc::new(Mutex::new(Vec::<String>::new()));
+        let recorded = hits.clone();
+        let stub = Router::new().route(
+            "/providers/:provider/intents/:currency/:action",
+            post(move |Path((provider, currency, action)): Path<(String, String, String)>| async move {
+                recorded.lock().unwrap().push(format!("{}/{}/{}", provider, currency, action));
+                Json(serde_json::json!({}))
+            }),
+        );
+        let state = AppState::with_test_upstream(&serve_stub(stub).await);
+        let provider = state.auth.issue("0xprovider", Role::Provider);
+        let pause = |currency: &str| {
+            Request::post(format!("/v1/providers/intents/{}/pause", currency))
+                .header("authorization", format!("Bearer {}", provider))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let escape = "..%2F..%2F0xvictim%2Fintents%2FNGN";
+        let response = router().with_state(state.clone()).oneshot(pause(escape)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = router().with_state(state).oneshot(pause("NGN")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(*hits.lock().unwrap(), vec!["0xprovider/NGN/pause".to_string()]);
+    }
+}
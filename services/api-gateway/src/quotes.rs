@@ -0,0 +1,66 @@
+//! Quote endpoints, proxied to the order service
+
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use reqwest::Method;
+use shared_types::{Quote, QuoteRequest};
+use uuid::Uuid;
+
+use crate::{
+    auth::Principal,
+    error::{ApiError, ErrorBody, Result},
+    state::AppState,
+    upstream::Upstream,
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/v1/quotes", post(create_quote))
+}
+
+/// Get the expected fiat payout for an order before locking funds
+///
+/// The returned quote is valid until `expires_at`; pass its `quote_id` when
+/// creating the order to hold the quoted terms.
+#[utoipa::path(
+    post,
+    path = "/v1/quotes",
+    tag = "quotes",
+    request_body = QuoteRequest,
+    responses(
+        (status = 201, description = "Signed quote", body = Quote),
+        (status = 400, description = "Invalid request", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 422, description = "No FX rate or provider liquidity for the request", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_quote(
+    State(upstream): State<Arc<Upstream>>,
+    _principal: Principal,
+    Json(request): Json<QuoteRequest>,
+) -> Result<Response> {
+    Ok(upstream
+        .order_service(Method::POST, &["quotes"], Some(&request))
+        .await?
+        .into_response())
+}
+
+/// Load a quote referenced by an order, rejecting unknown or expired quotes
+pub async fn fetch_valid_quote(upstream: &Upstream, quote_id: Uuid) -> Result<Quote> {
+    let quote = upstream
+        .order_service::<()>(Method::GET, &["quotes", &quote_id.to_string()], None)
+        .await?
+        .json::<Quote>()
+        .ok_or_else(|| ApiError::InvalidRequest(format!("Unknown quote: {}", quote_id)))?;
+
+    if quote.is_expired() {
+        return Err(ApiError::InvalidRequest(format!("Quote {} has expired", quote_id)));
+    }
+    Ok(quote)
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use axum::extract::FromRef;
+
+use crate::{
+    auth::JwtAuth,
+    streaming::OrderEventHub,
+    upstream::Upstream,
+};
+
+/// Shared state handed to every gateway handler
+#[derive(Clone)]
+pub struct AppState {
+    pub hub: Arc<OrderEventHub>,
+    pub auth: Arc<JwtAuth>,
+    pub upstream: Arc<Upstream>,
+}
+
+impl AppState {
+    #[cfg(test)]
+    pub fn for_tests() -> Self {
+        Self::with_test_upstream("http://127.0.0.1:9")
+    }
+
+    /// Test state whose internal services are all served at `url`
+    #[cfg(test)]
+    pub fn with_test_upstream(url: &str) -> Self {
+        Self {
+            hub: Arc::new(OrderEventHub::new(64)),
+            auth: Arc::new(JwtAuth::new(b"test-secret")),
+            upstream: Arc::new(Upstream::new(crate::upstream::UpstreamConfig {
+                order_service_url: url.to_string(),
+                provider_service_url: url.to_string(),
+                ai_router_url: url.to_string(),
+                balance_service_url: url.to_string(),
+            })
+            .unwrap()),
+        }
+    }
+}
+
+/// Serve a stand-in for the internal services on a local port, returning
+/// its base URL
+#[cfg(test)]
+pub async fn serve_stub(app: axum::Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+impl FromRef<AppState> for Arc<OrderEventHub> {
+    fn from_ref(state: &AppState) -> Self {
+        state.hub.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<JwtAuth> {
+    fn from_ref(state: &AppState) -> Self {
+        state.auth.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<Upstream> {
+    fn from_ref(state: &AppState) -> Self {
+        state.upstream.clone()
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use shared_types::OrderStatusChangedEvent;
+use tokio::sync::broadcast;
+
+use crate::auth::Principal;
+
+/// Order status change tagged with a stream event ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamEvent {
+    /// Monotonic ID used for `Last-Event-ID` resumption
+    pub id: u64,
+    pub event: OrderStatusChangedEvent,
+}
+
+/// Which events a subscriber receives
+#[derive(Debug, Clone)]
+pub struct StreamFilter {
+    pub principal: Principal,
+    /// Restrict to a single order, or None for all of the principal's orders
+    pub order_id: Option<String>,
+}
+
+impl StreamFilter {
+    pub fn matches(&self, event: &OrderStatusChangedEvent) -> bool {
+        let order_matches = match &self.order_id {
+            Some(id) => id.eq_ignore_ascii_case(&event.order_id),
+            None => true,
+        };
+        order_matches && self.principal.can_view(event)
+    }
+}
+
+/// Events to replay on (re)connect before switching to live delivery
+pub struct Subscription {
+    pub replay: Vec<StreamEvent>,
+    /// True when the requested resume point fell out of the replay buffer;
+    /// the client must refetch order state because events were missed
+    pub resync_required: bool,
+    pub live: broadcast::Receiver<StreamEvent>,
+}
+
+struct Buffer {
+    next_id: u64,
+    events: VecDeque<StreamEvent>,
+}
+
+/// Fan-out point between the NATS bridge and WebSocket/SSE subscribers
+///
+/// Keeps a bounded buffer of recent events so clients reconnecting with a
+/// last event ID receive what they missed.
+pub struct OrderEventHub {
+    buffer: Mutex<Buffer>,
+    capacity: usize,
+    sender: broadcast::Sender<StreamEvent>,
+}
+
+impl OrderEventHub {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self {
+            buffer: Mutex::new(Buffer {
+                // Seed IDs from the clock so they keep increasing across gateway restarts
+                next_id: Utc::now().timestamp_micros().max(1) as u64,
+                events: VecDeque::with_capacity(capacity),
+            }),
+            capacity,
+            sender,
+        }
+    }
+
+    /// Assign an ID to an event, buffer it and broadcast it to live subscribers
+    pub fn publish(&self, event: OrderStatusChangedEvent) -> StreamEvent {
+        let mut buffer = self.buffer.lock().expect("event hub lock poisoned");
+        let stream_event = StreamEvent { id: buffer.next_id, event };
+        buffer.next_id += 1;
+
+        if buffer.events.len() == self.capacity {
+            buffer.events.pop_front();
+        }
+        buffer.events.push_back(stream_event.clone());
+
+        // No receivers is not an error; events stay buffered for replay
+        let _ = self.sender.send(stream_event.clone());
+        stream_event
+    }
+
+    /// Subscribe to live events, replaying buffered events after `last_event_id`
+    pub fn subscribe(&self, filter: &StreamFilter, last_event_id: Option<u64>) -> Subscription {
+        // Holding the lock while subscribing guarantees no event lands between
+        // the replay snapshot and the live receiver
+        let buffer = self.buffer.lock().expect("event hub lock poisoned");
+        let live = self.sender.subscribe();
+
+        let Some(last_event_id) = last_event_id else {
+            return Subscription { replay: Vec::new(), resync_required: false, live };
+        };
+
+        let oldest = buffer.events.front().map_or(buffer.next_id, |e| e.id);
+        let resync_required = last_event_id.saturating_add(1) < oldest;
+        let replay = buffer
+            .events
+            .iter()
+            .filter(|e| e.id > last_event_id && filter.matches(&e.event))
+            .cloned()
+            .collect();
+
+        Subscription { replay, resync_required, live }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+    use shared_types::OrderStatus;
+
+    fn event(order_id: &str, user: &str, status: OrderStatus) -> OrderStatusChangedEvent {
+        OrderStatusChangedEvent {
+            order_id: order_id.to_string(),
+            user_address: user.to_string(),
+            integrator_address: "0xintegrator".to_string(),
+            previous_status: None,
+            status,
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn user_filter(address: &str, order_id: Option<&str>) -> StreamFilter {
+        StreamFilter {
+            principal: Principal { address: address.to_string(), role: Role::User },
+            order_id: order_id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_replay_after_last_event_id_respects_filter() {
+        let hub = OrderEventHub::new(10);
+        let first = hub.publish(event("0xa", "0xalice", OrderStatus::Pending));
+        hub.publish(event("0xb", "0xbob", OrderStatus::Pending));
+        let third = hub.publish(event("0xa", "0xalice", OrderStatus::Accepted));
+
+        let subscription = hub.subscribe(&user_filter("0xALICE", None), Some(first.id));
+        assert!(!subscription.resync_required);
+        assert_eq!(subscription.replay.len(), 1);
+        assert_eq!(subscription.replay[0].id, third.id);
+
+        let subscription = hub.subscribe(&user_filter("0xalice", Some("0xb")), Some(first.id));
+        assert!(subscription.replay.is_empty());
+    }
+
+    #[test]
+    fn test_resync_when_resume_point_evicted() {
+        let hub = OrderEventHub::new(2);
+        let first = hub.publish(event("0xa", "0xalice", OrderStatus::Pending));
+        hub.publish(event("0xa", "0xalice", OrderStatus::Accepted));
+        hub.publish(event("0xa", "0xalice", OrderStatus::Fulfilled));
+        hub.publish(event("0xa", "0xalice", OrderStatus::Fulfilled));
+
+        let subscription = hub.subscribe(&user_filter("0xalice", None), Some(first.id));
+        assert!(subscription.resync_required);
+        assert_eq!(subscription.replay.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_live_events_follow_subscription() {
+        let hub = OrderEventHub::new(4);
+        let mut subscription = hub.subscribe(&user_filter("0xalice", None), None);
+        let published = hub.publish(event("0xa", "0xalice", OrderStatus::Pending));
+
+        let received = subscription.live.recv().await.unwrap();
+        assert_eq!(received.id, published.id);
+    }
+}
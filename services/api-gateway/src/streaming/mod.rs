@@ -0,0 +1,285 @@
+//! Real-time order status streaming over Server-Sent Events and WebSocket
+//!
+//! Events published on the NATS `order.*` subjects are bridged into an
+//! [`OrderEventHub`]; each connection receives the events of orders the caller
+//! is party to, optionally narrowed to a single order. Clients resume after a
+//! disconnect by sending the last event ID they processed.
+
+pub mod hub;
+
+use std::{convert::Infallible, sync::Arc};
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    http::HeaderMap,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Response,
+    },
+    routing::get,
+    Router,
+};
+use futures::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use shared_messaging::subjects;
+use shared_types::OrderStatusChangedEvent;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use crate::{auth::Principal, error::ErrorBody, state::AppState};
+pub use hub::{OrderEventHub, StreamEvent, StreamFilter, Subscription};
+
+/// Header browsers send when an EventSource reconnects
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StreamQuery {
+    /// Only stream events for this order
+    pub order_id: Option<String>,
+    /// Resume after this event ID (WebSocket clients; SSE uses `Last-Event-ID`)
+    pub last_event_id: Option<u64>,
+}
+
+/// Message delivered to stream subscribers
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamMessage {
+    /// Events were missed; the client should refetch current order state
+    Resync,
+    /// An order changed status
+    OrderStatus(StreamEvent),
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/v1/orders/events", get(sse_handler))
+        .route("/v1/orders/events/ws", get(ws_handler))
+}
+
+/// Bridge order lifecycle events from NATS into the hub
+pub async fn bridge_nats(client: async_nats::Client, hub: Arc<OrderEventHub>) -> anyhow::Result<()> {
+    let mut subscriber = client.subscribe(subjects::ORDER_ALL.to_string()).await?;
+    info!("Streaming bridge subscribed to {}", subjects::ORDER_ALL);
+
+    while let Some(message) = subscriber.next().await {
+        // Not every order.* subject carries a status transition
+        if let Some(event) = OrderStatusChangedEvent::from_message(&message.payload) {
+            hub.publish(event);
+        }
+    }
+
+    Ok(())
+}
+
+/// Replay, then live events for one subscriber
+pub fn stream_messages(subscription: Subscription, filter: StreamFilter) -> impl Stream<Item = StreamMessage> {
+    let Subscription { replay, resync_required, live } = subscription;
+    let last_seen = replay.last().map(|e| e.id).unwrap_or(0);
+
+    let head = resync_required
+        .then_some(StreamMessage::Resync)
+        .into_iter()
+        .chain(replay.into_iter().map(StreamMessage::OrderStatus));
+
+    let tail = stream::unfold((live, filter, last_seen), |(mut live, filter, last_seen)| async move {
+        loop {
+            match live.recv().await {
+                // Skip anything already delivered through the replay
+                Ok(event) if event.id > last_seen && filter.matches(&event.event) => {
+                    let id = event.id;
+                    return Some((StreamMessage::OrderStatus(event), (live, filter, id)));
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Stream subscriber lagged, {} events dropped", skipped);
+                    return Some((StreamMessage::Resync, (live, filter, last_seen)));
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    stream::iter(head).chain(tail)
+}
+
+fn subscribe(hub: &OrderEventHub, principal: Principal, query: StreamQuery, last_event_id: Option<u64>) -> impl Stream<Item = StreamMessage> {
+    let filter = StreamFilter {
+        principal,
+        order_id: query.order_id,
+    };
+    let subscription = hub.subscribe(&filter, last_event_id.or(query.last_event_id));
+    stream_messages(subscription, filter)
+}
+
+/// Stream status changes of the caller's orders as Server-Sent Events
+#[utoipa::path(
+    get,
+    path = "/v1/orders/events",
+    tag = "streaming",
+    params(
+        StreamQuery,
+        ("Last-Event-ID" = Option<u64>, Header, description = "Resume after this event ID"),
+    ),
+    responses(
+        (status = 200, description = "`order_status` events carrying the status change, or `resync` when events were missed", content_type = "text/event-stream", body = OrderStatusChangedEvent),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn sse_handler(
+    State(hub): State<Arc<OrderEventHub>>,
+    principal: Principal,
+    headers: HeaderMap,
+    Query(query): Query<StreamQuery>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let last_event_id = headers
+        .get(LAST_EVENT_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok());
+
+    let events = subscribe(&hub, principal, query, last_event_id).map(|message| {
+        let event = match &message {
+            StreamMessage::Resync => Event::default().event("resync").data("{}"),
+            StreamMessage::OrderStatus(e) => Event::default()
+                .id(e.id.to_string())
+                .event("order_status")
+                .json_data(&e.event)
+                .unwrap_or_else(|_| Event::default().event("resync").data("{}")),
+        };
+        Ok(event)
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// Stream status changes of the caller's orders over a WebSocket
+#[utoipa::path(
+    get,
+    path = "/v1/orders/events/ws",
+    tag = "streaming",
+    params(StreamQuery),
+    responses(
+        (status = 101, description = "Switching protocols; each text frame is a JSON message tagged `order_status` or `resync`"),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn ws_handler(
+    State(hub): State<Arc<OrderEventHub>>,
+    principal: Principal,
+    Query(query): Query<StreamQuery>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let messages = subscribe(&hub, principal, query, None);
+    upgrade.on_upgrade(move |socket| ws_session(socket, messages))
+}
+
+async fn ws_session(mut socket: WebSocket, messages: impl Stream<Item = StreamMessage>) {
+    let mut messages = std::pin::pin!(messages);
+
+    loop {
+        tokio::select! {
+            message = messages.next() => {
+                let Some(message) = message else { break };
+                let Ok(text) = serde_json::to_string(&message) else { continue };
+                if socket.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Pings are answered by axum; other client messages are ignored
+                Some(Ok(_)) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{JwtAuth, Role};
+    use axum::{body::Body, http::Request, http::StatusCode};
+    use chrono::Utc;
+    use shared_types::OrderStatus;
+    use tower::ServiceExt;
+
+    fn event(order_id: &str, status: OrderStatus) -> OrderStatusChangedEvent {
+        OrderStatusChangedEvent {
+            order_id: order_id.to_string(),
+            user_address: "0xalice".to_string(),
+            integrator_address: "0xintegrator".to_string(),
+            previous_status: None,
+            status,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sse_resumes_from_last_event_id() {
+        let state = AppState::for_tests();
+        let first = state.hub.publish(event("0xorder", OrderStatus::Pending));
+        let second = state.hub.publish(event("0xorder", OrderStatus::Accepted));
+        let token = state.auth.issue("0xalice", Role::User);
+
+        let response = router()
+            .with_state(state)
+            .oneshot(
+                Request::get("/v1/orders/events?order_id=0xorder")
+                    .header("authorization", format!("Bearer {}", token))
+                    .header(LAST_EVENT_ID_HEADER, first.id.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let mut body = response.into_body().into_data_stream();
+        let chunk = body.next().await.unwrap().unwrap();
+        let text = String::from_utf8_lossy(&chunk);
+        assert!(text.contains(&format!("id: {}", second.id)));
+        assert!(text.contains("\"status\":\"Accepted\""));
+        assert!(!text.contains(&format!("id: {}\n", first.id)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_requires_token() {
+        let state = AppState::for_tests();
+        let other = JwtAuth::new(b"someone-else").issue("0xalice", Role::User);
+
+        for uri in ["/v1/orders/events".to_string(), format!("/v1/orders/events?access_token={}", other)] {
+            let response = router()
+                .with_state(state.clone())
+                .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_live_stream_skips_other_users_orders() {
+        let hub = OrderEventHub::new(8);
+        let filter = StreamFilter {
+            principal: Principal { address: "0xalice".to_string(), role: Role::User },
+            order_id: None,
+        };
+        let messages = stream_messages(hub.subscribe(&filter, None), filter);
+        let mut messages = std::pin::pin!(messages);
+
+        let mut foreign = event("0xother", OrderStatus::Pending);
+        foreign.user_address = "0xbob".to_string();
+        hub.publish(foreign);
+        let mine = hub.publish(event("0xmine", OrderStatus::Pending));
+
+        match messages.next().await.unwrap() {
+            StreamMessage::OrderStatus(e) => assert_eq!(e.id, mine.id),
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use axum::{
+    body::Bytes,
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+};
+use anyhow::Context;
+use reqwest::{Client, Method, Url};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::error::{ApiError, Result};
+
+/// Base URLs of the internal services the gateway fronts
+#[derive(Debug, Clone)]
+pub struct UpstreamConfig {
+    pub order_service_url: String,
+    pub provider_service_url: String,
+    pub ai_router_url: String,
+    pub balance_service_url: String,
+}
+
+impl UpstreamConfig {
+    /// Load from `ORDER_SERVICE_URL`, `PROVIDER_SERVICE_URL`, `AI_ROUTER_URL`
+    /// and `BALANCE_SERVICE_URL`
+    pub fn from_env() -> Self {
+        Self {
+            order_service_url: std::env::var("ORDER_SERVICE_URL")
+                .unwrap_or_else(|_| "http://127.0.0.1:8001".to_string()),
+            provider_service_url: std::env::var("PROVIDER_SERVICE_URL")
+                .unwrap_or_else(|_| "http://127.0.0.1:8003".to_string()),
+            ai_router_url: std::env::var("AI_ROUTER_URL")
+                .unwrap_or_else(|_| "http://127.0.0.1:8002".to_string()),
+            balance_service_url: std::env::var("BALANCE_SERVICE_URL")
+                .unwrap_or_else(|_| "http://127.0.0.1:8004".to_string()),
+        }
+    }
+}
+
+/// Response from an internal service, passed back to the caller as-is
+#[derive(Debug)]
+pub struct UpstreamResponse {
+    pub status: StatusCode,
+    pub body: Bytes,
+}
+
+impl UpstreamResponse {
+    /// Decode a successful JSON body; None for non-2xx responses
+    pub fn json<T: DeserializeOwned>(&self) -> Option<T> {
+        if !self.status.is_success() {
+            return None;
+        }
+        serde_json::from_slice(&self.body).ok()
+    }
+}
+
+impl IntoResponse for UpstreamResponse {
+    fn into_response(self) -> Response {
+        (self.status, [(CONTENT_TYPE, "application/json")], self.body).into_response()
+    }
+}
+
+/// HTTP client for the internal services
+///
+/// Paths are given as segments, each percent-encoded on its own, so values
+/// taken from the caller cannot add segments or climb out of the route.
+pub struct Upstream {
+    client: Client,
+    order_service: Url,
+    provider_service: Url,
+    ai_router: Url,
+    balance_service: Url,
+}
+
+impl Upstream {
+    pub fn new(config: UpstreamConfig) -> anyhow::Result<Self> {
+        let parse = |name: &str, url: &str| {
+            Url::parse(url)
+                .ok()
+                .filter(|url| !url.cannot_be_a_base())
+                .with_context(|| format!("{} is not a valid base URL: {}", name, url))
+        };
+        Ok(Self {
+            client: Client::new(),
+            order_service: parse("ORDER_SERVICE_URL", &config.order_service_url)?,
+            provider_service: parse("PROVIDER_SERVICE_URL", &config.provider_service_url)?,
+            ai_router: parse("AI_ROUTER_URL", &config.ai_router_url)?,
+            balance_service: parse("BALANCE_SERVICE_URL", &config.balance_service_url)?,
+        })
+    }
+
+    pub async fn order_service<B: Serialize>(&self, method: Method, path: &[&str], body: Option<&B>) -> Result<UpstreamResponse> {
+        self.send(&self.order_service, method, path, body).await
+    }
+
+    pub async fn order_service_query<Q: Serialize>(&self, path: &[&str], query: &Q) -> Result<UpstreamResponse> {
+        self.get_query(&self.order_service, path, query).await
+    }
+
+    pub async fn provider_service<B: Serialize>(&self, method: Method, path: &[&str], body: Option<&B>) -> Result<UpstreamResponse> {
+        self.send(&self.provider_service, method, path, body).await
+    }
+
+    pub async fn ai_router<B: Serialize>(&self, method: Method, path: &[&str], body: Option<&B>) -> Result<UpstreamResponse> {
+        self.send(&self.ai_router, method, path, body).await
+    }
+
+    pub async fn ai_router_query<Q: Serialize>(&self, path: &[&str], query: &Q) -> Result<UpstreamResponse> {
+        self.get_query(&self.ai_router, path, query).await
+    }
+
+    pub async fn balance_service<B: Serialize>(&self, method: Method, path: &[&str], body: Option<&B>) -> Result<UpstreamResponse> {
+        self.send(&self.balance_service, method, path, body).await
+    }
+
+    async fn get_query<Q: Serialize>(&self, base: &Url, path: &[&str], query: &Q) -> Result<UpstreamResponse> {
+        Self::read(self.client.get(url(base, path)?).query(query).send().await?).await
+    }
+
+    async fn send<B: Serialize>(&self, base: &Url, method: Method, path: &[&str], body: Option<&B>) -> Result<UpstreamResponse> {
+        let mut request = self.client.request(method, url(base, path)?);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+
+        Self::read(request.send().await?).await
+    }
+
+    async fn read(response: reqwest::Response) -> Result<UpstreamResponse> {
+        // reqwest and axum are on different `http` major versions
+        let status = StatusCode::from_u16(response.status().as_u16()).unwrap_or(StatusCode::BAD_GATEWAY);
+        Ok(UpstreamResponse {
+            status,
+            body: response.bytes().await?,
+        })
+    }
+}
+
+/// `base` with each of `segments` appended as one encoded path segment
+fn url(base: &Url, segments: &[&str]) -> Result<Url> {
+    if let Some(segment) = segments.iter().find(|s| matches!(**s, "" | "." | "..")) {
+        return Err(ApiError::InvalidRequest(format!("Invalid path segment: {:?}", segment)));
+    }
+    let mut url = base.clone();
+    url.path_segments_mut()
+        .expect("base URLs are checked in Upstream::new")
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments_are_encoded_one_by_one() {
+        let base = Url::parse("http://provider-service:8003").unwrap();
+        let escaped = url(&base, &["providers", "0xabc", "intents", "../../0xvictim/intents/NGN"]).unwrap();
+        assert_eq!(
+            escaped.as_str(),
+            "http://provider-service:8003/providers/0xabc/intents/..%2F..%2F0xvictim%2Fintents%2FNGN"
+        );
+
+        let base = Url::parse("http://127.0.0.1:8001/internal/").unwrap();
+        assert_eq!(url(&base, &["orders", "a?b#c"]).unwrap().path(), "/internal/orders/a%3Fb%23c");
+
+        for segment in ["", ".", ".."] {
+            assert!(url(&base, &["orders", segment]).is_err());
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Webhook endpoints of the authenticated integrator, proxied to the order
+//! service
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use shared_types::{RegisterWebhookRequest, WebhookDeliveryAttempt, WebhookEndpoint, WebhookRegistration};
+use uuid::Uuid;
+
+use crate::{
+    auth::{Principal, Role},
+    error::{ApiError, ErrorBody, Result},
+    state::AppState,
+    upstream::Upstream,
+};
+
+#[derive(Debug, Serialize, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AttemptsQuery {
+    /// Maximum attempts returned, at most 200 (default 50)
+    pub limit: Option<i64>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/v1/webhooks", post(register_webhook).get(list_webhooks))
+        .route("/v1/webhooks/:id/enable", post(enable_webhook))
+        .route("/v1/webhooks/:id/attempts", get(list_attempts))
+        .route("/v1/webhooks/:id/deliveries/:delivery_id/redeliver", post(redeliver))
+}
+
+fn require_integrator(principal: &Principal) -> Result<()> {
+    if principal.role != Role::Integrator {
+        return Err(ApiError::Forbidden);
+    }
+    Ok(())
+}
+
+/// Register an endpoint for the authenticated integrator's order events
+///
+/// The signing secret is only returned in this response.
+#[utoipa::path(
+    post,
+    path = "/v1/webhooks",
+    tag = "webhooks",
+    request_body = RegisterWebhookRequest,
+    responses(
+        (status = 201, description = "Endpoint registered", body = WebhookRegistration),
+        (status = 400, description = "Invalid URL or event type", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only integrators register webhooks", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn register_webhook(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Json(request): Json<RegisterWebhookRequest>,
+) -> Result<Response> {
+    require_integrator(&principal)?;
+    Ok(upstream
+        .order_service(Method::POST, &["integrators", &principal.address, "webhooks"], Some(&request))
+        .await?
+        .into_response())
+}
+
+/// The authenticated integrator's webhook endpoints
+#[utoipa::path(
+    get,
+    path = "/v1/webhooks",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Registered endpoints", body = [WebhookEndpoint]),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only integrators register webhooks", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_webhooks(State(upstream): State<Arc<Upstream>>, principal: Principal) -> Result<Response> {
+    require_integrator(&principal)?;
+    Ok(upstream
+        .order_service::<()>(Method::GET, &["integrators", &principal.address, "webhooks"], None)
+        .await?
+        .into_response())
+}
+
+/// Re-enable an endpoint disabled after repeated failures
+#[utoipa::path(
+    post,
+    path = "/v1/webhooks/{id}/enable",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "Endpoint ID")),
+    responses(
+        (status = 204, description = "Endpoint enabled"),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only integrators register webhooks", body = ErrorBody),
+        (status = 404, description = "No such endpoint of the integrator", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn enable_webhook(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+) -> Result<Response> {
+    require_integrator(&principal)?;
+    Ok(upstream
+        .order_service::<()>(
+            Method::POST,
+            &["integrators", &principal.address, "webhooks", &id.to_string(), "enable"],
+            None,
+        )
+        .await?
+        .into_response())
+}
+
+/// Most recent delivery attempts made against an endpoint
+#[utoipa::path(
+    get,
+    path = "/v1/webhooks/{id}/attempts",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "Endpoint ID"), AttemptsQuery),
+    responses(
+        (status = 200, description = "Attempts, newest first", body = [WebhookDeliveryAttempt]),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only integrators register webhooks", body = ErrorBody),
+        (status = 404, description = "No such endpoint of the integrator", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_attempts(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    Query(query): Query<AttemptsQuery>,
+) -> Result<Response> {
+    require_integrator(&principal)?;
+    Ok(upstream
+        .order_service_query(&["integrators", &principal.address, "webhooks", &id.to_string(), "attempts"], &query)
+        .await?
+        .into_response())
+}
+
+/// Queue a fresh delivery of an event already sent to an endpoint
+#[utoipa::path(
+    post,
+    path = "/v1/webhooks/{id}/deliveries/{delivery_id}/redeliver",
+    tag = "webhooks",
+    params(
+        ("id" = Uuid, Path, description = "Endpoint ID"),
+        ("delivery_id" = i64, Path, description = "Delivery to send again"),
+    ),
+    responses(
+        (status = 202, description = "Delivery queued"),
+        (status = 400, description = "Endpoint is disabled", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "Only integrators register webhooks", body = ErrorBody),
+        (status = 404, description = "No such endpoint or delivery of the integrator", body = ErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn redeliver(
+    State(upstream): State<Arc<Upstream>>,
+    principal: Principal,
+    Path((id, delivery_id)): Path<(Uuid, i64)>,
+) -> Result<Response> {
+    require_integrator(&principal)?;
+    Ok(upstream
+        .order_service::<()>(
+            Method::POST,
+            &["integrators", &principal.address, "webhooks", &id.to_string(), "deliveries", &delivery_id.to_string(), "redeliver"],
+            None,
+        )
+        .await?
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{auth::Role, state::serve_stub};
+    use axum::{body::Body, http::Request, http::StatusCode};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_webhooks_are_scoped_to_the_integrator_token() {
+        let stub = Router::new().route(
+            "/integrators/:integrator/webhooks",
+            post(|Path(integrator): Path<String>, Json(request): Json<serde_json::Value>| async move {
+                Json(serde_json::json!({ "integrator": integrator, "request": request }))
+            }),
+        );
+        let state = AppState::with_test_upstream(&serve_stub(stub).await);
+        let body = r#"{"integrator_address":"0xsomeone-else","url":"https://example.com/hooks","event_types":["order.fulfilled"]}"#;
+        let register = |token: String| {
+            Request::post("/v1/webhooks")
+                .header("authorization", format!("Bearer {}", token))
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let user = state.auth.issue("0xalice", Role::User);
+        let response = router().with_state(state.clone()).oneshot(register(user)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let integrator = state.auth.issue("0xintegrator", Role::Integrator);
+        let response = router().with_state(state).oneshot(register(integrator)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let forwarded: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(forwarded["integrator"], "0xintegrator");
+        assert!(forwarded["request"].get("integrator_address").is_none());
+    }
+}
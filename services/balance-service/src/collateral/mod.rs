@@ -0,0 +1,78 @@
+//! Provider collateral
+//!
+//! Providers stake tokens on-chain; the indexer is to publish each deposit
+//! and withdrawal, and the balance service keeps the stake per provider and
+//! token, which the router checks against tier minimums.
+//!
+//! This is unfinished: the indexer does not publish stake transfers yet, so
+//! no stake is recorded. Locking collateral against assignments and slashing
+//! it for failures are not implemented; they need recorded stake first.
+
+pub mod routes;
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+use shared_database::{models::StakeTransferModel, StakeRepository};
+use shared_messaging::subjects;
+use shared_types::{helpers::hex_to_bytes, ProviderStake, StakeTransferEvent};
+use tracing::{debug, error, info};
+
+use crate::error::Result;
+use crate::reservations::parse_amount;
+
+pub struct CollateralService {
+    stakes: StakeRepository,
+    nats: async_nats::Client,
+}
+
+impl CollateralService {
+    pub fn new(stakes: StakeRepository, nats: async_nats::Client) -> Self {
+        Self { stakes, nats }
+    }
+
+    /// Apply a stake deposit or withdrawal seen on-chain
+    pub async fn record_transfer(&self, event: &StakeTransferEvent, kind: &str) -> Result<()> {
+        let mut transfer = StakeTransferModel::from_event(event, kind);
+        transfer.amount = parse_amount(&event.amount)?;
+        if self.stakes.record_transfer(&transfer).await? {
+            info!("Recorded stake {} of {} {} by {}", kind, transfer.amount, event.token, event.provider);
+        } else {
+            debug!("Stake {} {}:{} already recorded", kind, event.tx_hash, event.log_index);
+        }
+        Ok(())
+    }
+
+    /// A provider's stake in every token it has deposited
+    pub async fn stakes(&self, provider: &str) -> Result<Vec<ProviderStake>> {
+        Ok(self
+            .stakes
+            .list_for_provider(&hex_to_bytes(provider))
+            .await?
+            .iter()
+            .map(|model| model.to_domain())
+            .collect())
+    }
+
+    /// Consume stake deposits and withdrawals until the subscription closes
+    pub async fn run(self: Arc<Self>) -> anyhow::Result<()> {
+        let mut transfers = self.nats.subscribe(subjects::PROVIDER_STAKE_ALL.to_string()).await?;
+        info!("Balance Service subscribed to {}", subjects::PROVIDER_STAKE_ALL);
+
+        while let Some(message) = transfers.next().await {
+            let kind = match message.subject.as_str() {
+                subjects::PROVIDER_STAKE_DEPOSITED => "deposit",
+                subjects::PROVIDER_STAKE_WITHDRAWN => "withdrawal",
+                _ => continue,
+            };
+            let result = match serde_json::from_slice::<StakeTransferEvent>(&message.payload) {
+                Ok(event) => self.record_transfer(&event, kind).await,
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = result {
+                error!("Failed to handle {}: {}", message.subject, e);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    routing::get,
+    Json, Router,
+};
+use shared_types::{helpers::is_valid_address, ProviderStake};
+
+use super::CollateralService;
+use crate::error::{BalanceError, Result};
+
+/// Stake routes, for internal callers
+pub fn router(service: Arc<CollateralService>) -> Router {
+    Router::new()
+        .route("/providers/:address/stake", get(get_stake))
+        .with_state(service)
+}
+
+/// Staked collateral per token
+async fn get_stake(
+    State(service): State<Arc<CollateralService>>,
+    Path(address): Path<String>,
+) -> Result<Json<Vec<ProviderStake>>> {
+    if !is_valid_address(&address) {
+        return Err(BalanceError::InvalidRequest(format!("Invalid provider address: {}", address)));
+    }
+    Ok(Json(service.stakes(&address).await?))
+}
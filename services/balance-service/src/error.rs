@@ -0,0 +1,42 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use shared_database::DatabaseError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum BalanceError {
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, BalanceError>;
+
+impl IntoResponse for BalanceError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            BalanceError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+            BalanceError::Database(DatabaseError::NotFound(_)) => StatusCode::NOT_FOUND,
+            BalanceError::Database(_) | BalanceError::Redis(_) | BalanceError::Serialization(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+
+        if status.is_server_error() {
+            tracing::error!("Request failed: {}", self);
+        }
+
+        (status, Json(serde_json::json!({ "error": self.to_string() }))).into_response()
+    }
+}
@@ -1,5 +1,16 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{routing::get, Router};
+use shared_database::{DisputeRepository, OrderRepository, ProviderRepository, StakeRepository};
 use tracing::info;
 
+mod collateral;
+mod error;
+mod reservations;
+
+use collateral::CollateralService;
+use reservations::{ledger::Ledger, ReservationConfig, ReservationService};
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt::init();
@@ -7,5 +18,53 @@ async fn main() -> anyhow::Result<()> {
 
     info!("Balance Service starting...");
 
+    let pool = shared_database::initialize_database().await?;
+    let nats_url = std::env::var("NATS_URL").unwrap_or_else(|_| "nats://127.0.0.1:4222".to_string());
+    let nats = shared_messaging::connect_nats(&nats_url).await?;
+    let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+    let redis = redis::aio::ConnectionManager::new(redis::Client::open(redis_url)?).await?;
+
+    let collateral = Arc::new(CollateralService::new(StakeRepository::new(pool.clone()), nats.clone()));
+    let reservations = Arc::new(ReservationService::new(
+        Ledger::new(redis),
+        ProviderRepository::new(pool.clone()),
+        OrderRepository::new(pool.clone()),
+        DisputeRepository::new(pool),
+        nats,
+        ReservationConfig::from_env(),
+    ));
+    reservations.clone().spawn_maintenance();
+    let consumer = reservations.clone();
+    tokio::spawn(async move {
+        if let Err(e) = consumer.run().await {
+            tracing::error!("Order event consumer stopped: {}", e);
+        }
+    });
+    let stake_consumer = collateral.clone();
+    tokio::spawn(async move {
+        if let Err(e) = stake_consumer.run().await {
+            tracing::error!("Stake event consumer stopped: {}", e);
+        }
+    });
+
+    let app = Router::new()
+        .route("/health", get(health_check))
+        .merge(reservations::routes::router(reservations))
+        .merge(collateral::routes::router(collateral));
+
+    let port = std::env::var("BALANCE_SERVICE_PORT")
+        .ok()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(8004);
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    info!("Balance Service listening on {}", addr);
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await?;
+
     Ok(())
 }
+
+async fn health_check() -> &'static str {
+    "OK"
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeZone, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands, Script};
+use shared_types::{ProviderLiquidity, Reservation, ReservationState};
+
+use crate::error::Result;
+
+/// Reservations whose TTL has passed, scored by expiry in unix millis
+const EXPIRIES_KEY: &str = "balance:reservations:expiries";
+
+/// What [`Ledger::reserve`] did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReserveOutcome {
+    Reserved,
+    /// The assignment was already reserved for; nothing changed
+    Exists(ReservationState),
+    /// The provider's free liquidity is below the amount
+    Insufficient { free: String },
+    /// The provider's intent has not been loaded into the ledger
+    Unknown,
+}
+
+/// Liquidity reservations in Redis
+///
+/// Each provider's liquidity in a currency is a hash of `available` (its
+/// intent's amount at the last reconciliation), `reserved` and `committed`.
+/// Reservations move between them in Lua scripts, so concurrent
+/// assignments cannot both take the same liquidity.
+#[derive(Clone)]
+pub struct Ledger {
+    conn: ConnectionManager,
+    reserve: Script,
+    settle: Script,
+    reconcile: Script,
+}
+
+impl Ledger {
+    pub fn new(conn: ConnectionManager) -> Self {
+        Self {
+            conn,
+            reserve: Script::new(concat!(include_str!("lua/amounts.lua"), include_str!("lua/reserve.lua"))),
+            settle: Script::new(concat!(include_str!("lua/amounts.lua"), include_str!("lua/settle.lua"))),
+            reconcile: Script::new(concat!(include_str!("lua/amounts.lua"), include_str!("lua/reconcile.lua"))),
+        }
+    }
+
+    fn liquidity_key(provider: &str, currency: &str) -> String {
+        format!("balance:liquidity:{}:{}", provider.to_lowercase(), currency.to_uppercase())
+    }
+
+    fn reservation_key(id: &str) -> String {
+        format!("balance:reservation:{}", id)
+    }
+
+    fn order_key(order_id: &str) -> String {
+        format!("balance:order:{}", order_id.to_lowercase())
+    }
+
+    /// Hold a provider's liquidity for `reservation` until it expires
+    ///
+    /// The reservation record is kept for `retention` after it expires, so
+    /// late events for the order find it settled.
+    pub async fn reserve(&self, reservation: &Reservation, retention: Duration) -> Result<ReserveOutcome> {
+        let id = Reservation::id(&reservation.order_id, &reservation.provider);
+        let mut conn = self.conn.clone();
+        let (outcome, detail): (String, String) = self
+            .reserve
+            .key(Self::liquidity_key(&reservation.provider, &reservation.currency))
+            .key(Self::reservation_key(&id))
+            .key(Self::order_key(&reservation.order_id))
+            .key(EXPIRIES_KEY)
+            .arg(&id)
+            .arg(&reservation.order_id)
+            .arg(&reservation.provider)
+            .arg(&reservation.currency)
+            .arg(&reservation.amount)
+            .arg(reservation.created_at.timestamp_millis())
+            .arg(reservation.expires_at.timestamp_millis())
+            .arg(retention.as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await?;
+
+        Ok(match outcome.as_str() {
+            "reserved" => ReserveOutcome::Reserved,
+            "exists" => ReserveOutcome::Exists(detail.parse().unwrap_or(ReservationState::Reserved)),
+            "insufficient" => ReserveOutcome::Insufficient { free: detail },
+            _ => ReserveOutcome::Unknown,
+        })
+    }
+
+    /// Move a held reservation to `target`
+    ///
+    /// # Returns
+    /// * `Result<bool>` - False when the reservation was missing or already
+    ///   settled
+    pub async fn settle(&self, id: &str, target: ReservationState) -> Result<bool> {
+        let Some(reservation) = self.reservation(id).await? else {
+            return Ok(false);
+        };
+        let mut conn = self.conn.clone();
+        let (moved, _state): (i64, String) = self
+            .settle
+            .key(Self::liquidity_key(&reservation.provider, &reservation.currency))
+            .key(Self::reservation_key(id))
+            .key(EXPIRIES_KEY)
+            .arg(id)
+            .arg(target.as_str())
+            .invoke_async(&mut conn)
+            .await?;
+        Ok(moved == 1)
+    }
+
+    pub async fn reservation(&self, id: &str) -> Result<Option<Reservation>> {
+        let mut conn = self.conn.clone();
+        let fields: HashMap<String, String> = conn.hgetall(Self::reservation_key(id)).await?;
+        Ok(parse_reservation(&fields))
+    }
+
+    /// IDs of the reservations made for an order
+    pub async fn order_reservations(&self, order_id: &str) -> Result<Vec<String>> {
+        let mut conn = self.conn.clone();
+        Ok(conn.smembers(Self::order_key(order_id)).await?)
+    }
+
+    /// Held reservations whose TTL has passed, oldest first
+    pub async fn expired(&self, now: DateTime<Utc>, limit: isize) -> Result<Vec<String>> {
+        let mut conn = self.conn.clone();
+        Ok(conn
+            .zrangebyscore_limit(EXPIRIES_KEY, "-inf", now.timestamp_millis(), 0, limit)
+            .await?)
+    }
+
+    /// A provider's liquidity in one currency; None until its intent is loaded
+    pub async fn liquidity(&self, provider: &str, currency: &str) -> Result<Option<ProviderLiquidity>> {
+        let mut conn = self.conn.clone();
+        let fields: HashMap<String, String> = conn.hgetall(Self::liquidity_key(provider, currency)).await?;
+        let Some(available) = fields.get("available") else {
+            return Ok(None);
+        };
+        let amount = |name: &str| fields.get(name).and_then(|v| v.parse::<u128>().ok()).unwrap_or(0);
+        let (reserved, committed) = (amount("reserved"), amount("committed"));
+        let free = available.parse::<u128>().unwrap_or(0).saturating_sub(reserved + committed);
+        Ok(Some(ProviderLiquidity {
+            provider: provider.to_lowercase(),
+            currency: currency.to_uppercase(),
+            available: available.clone(),
+            reserved: reserved.to_string(),
+            committed: committed.to_string(),
+            free: free.to_string(),
+        }))
+    }
+
+    /// Load a provider's intent amount, unless reconciliation already has
+    pub async fn load(&self, provider: &str, currency: &str, available: &str) -> Result<()> {
+        let mut conn = self.conn.clone();
+        conn.hset_nx::<_, _, _, ()>(Self::liquidity_key(provider, currency), "available", available)
+            .await?;
+        Ok(())
+    }
+
+    /// Record that `applied` of the committed liquidity was deducted from the
+    /// intent, which now has `available`
+    pub async fn reconcile(&self, provider: &str, currency: &str, applied: &str, available: &str) -> Result<()> {
+        let mut conn = self.conn.clone();
+        self.reconcile
+            .key(Self::liquidity_key(provider, currency))
+            .arg(applied)
+            .arg(available)
+            .invoke_async::<_, i64>(&mut conn)
+            .await?;
+        Ok(())
+    }
+}
+
+fn parse_reservation(fields: &HashMap<String, String>) -> Option<Reservation> {
+    let millis = |name: &str| {
+        fields
+            .get(name)
+            .and_then(|v| v.parse::<i64>().ok())
+            .and_then(|ms| Utc.timestamp_millis_opt(ms).single())
+    };
+    Some(Reservation {
+        order_id: fields.get("order_id")?.clone(),
+        provider: fields.get("provider")?.clone(),
+        currency: fields.get("currency")?.clone(),
+        amount: fields.get("amount")?.clone(),
+        state: fields.get("state")?.parse().ok()?,
+        created_at: millis("created_at")?,
+        expires_at: millis("expires_at")?,
+    })
+}
@@ -0,0 +1,72 @@
+-- Token amounts are non-negative integers kept as decimal strings: in base
+-- units they overflow both Lua numbers and Redis integers.
+
+local function norm(a)
+    a = string.gsub(a or '0', '^0+', '')
+    if a == '' then
+        return '0'
+    end
+    return a
+end
+
+local function cmp(a, b)
+    a, b = norm(a), norm(b)
+    if #a ~= #b then
+        return #a < #b and -1 or 1
+    end
+    if a == b then
+        return 0
+    end
+    return a < b and -1 or 1
+end
+
+local function add(a, b)
+    a, b = norm(a), norm(b)
+    local out, carry = {}, 0
+    local i, j = #a, #b
+    while i > 0 or j > 0 or carry > 0 do
+        local d = carry
+        if i > 0 then
+            d = d + string.byte(a, i) - 48
+            i = i - 1
+        end
+        if j > 0 then
+            d = d + string.byte(b, j) - 48
+            j = j - 1
+        end
+        table.insert(out, 1, string.char(48 + d % 10))
+        carry = math.floor(d / 10)
+    end
+    return norm(table.concat(out))
+end
+
+-- a - b, floored at zero
+local function sub(a, b)
+    a, b = norm(a), norm(b)
+    if cmp(a, b) <= 0 then
+        return '0'
+    end
+    local out, borrow = {}, 0
+    local i, j = #a, #b
+    while i > 0 do
+        local d = string.byte(a, i) - 48 - borrow
+        if j > 0 then
+            d = d - (string.byte(b, j) - 48)
+            j = j - 1
+        end
+        if d < 0 then
+            d = d + 10
+            borrow = 1
+        else
+            borrow = 0
+        end
+        table.insert(out, 1, string.char(48 + d))
+        i = i - 1
+    end
+    return norm(table.concat(out))
+end
+
+local function field(key, name)
+    return redis.call('HGET', key, name) or '0'
+end
+
@@ -0,0 +1,9 @@
+-- Record that committed liquidity was deducted from the provider's intent
+--
+-- KEYS: liquidity
+-- ARGV: committed amount deducted, intent's available amount afterwards
+
+redis.call('HSET', KEYS[1],
+    'available', ARGV[2],
+    'committed', sub(field(KEYS[1], 'committed'), ARGV[1]))
+return 1
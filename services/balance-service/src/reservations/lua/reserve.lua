@@ -0,0 +1,34 @@
+-- Reserve liquidity for an assignment if the provider has enough free
+--
+-- KEYS: liquidity, reservation, order reservations, expiries
+-- ARGV: id, order_id, provider, currency, amount, created_at_ms,
+--       expires_at_ms, retention_ms
+-- Returns {outcome, detail}: {'reserved', free before}, {'exists', state},
+-- {'insufficient', free} or {'unknown', ''} when the intent is not loaded
+
+local state = redis.call('HGET', KEYS[2], 'state')
+if state then
+    return {'exists', state}
+end
+
+local available = redis.call('HGET', KEYS[1], 'available')
+if not available then
+    return {'unknown', ''}
+end
+
+local reserved = field(KEYS[1], 'reserved')
+local free = sub(available, add(reserved, field(KEYS[1], 'committed')))
+if cmp(free, ARGV[5]) < 0 then
+    return {'insufficient', free}
+end
+
+redis.call('HSET', KEYS[1], 'reserved', add(reserved, ARGV[5]))
+redis.call('HSET', KEYS[2],
+    'order_id', ARGV[2], 'provider', ARGV[3], 'currency', ARGV[4], 'amount', ARGV[5],
+    'state', 'reserved', 'created_at', ARGV[6], 'expires_at', ARGV[7])
+local retain_until = tonumber(ARGV[7]) + tonumber(ARGV[8])
+redis.call('PEXPIREAT', KEYS[2], retain_until)
+redis.call('SADD', KEYS[3], ARGV[1])
+redis.call('PEXPIREAT', KEYS[3], retain_until)
+redis.call('ZADD', KEYS[4], ARGV[7], ARGV[1])
+return {'reserved', free}
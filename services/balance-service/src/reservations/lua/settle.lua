@@ -0,0 +1,28 @@
+-- Move a reservation to committed, released or expired
+--
+-- Only held reservations move, except that an expired one can still be
+-- committed: the payout happened, so the liquidity is spent either way.
+--
+-- KEYS: liquidity, reservation, expiries
+-- ARGV: id, target state
+-- Returns {1, target} when it moved, else {0, current state or 'missing'}
+
+local state = redis.call('HGET', KEYS[2], 'state')
+if not state then
+    return {0, 'missing'}
+end
+
+local target = ARGV[2]
+local amount = redis.call('HGET', KEYS[2], 'amount')
+if state == 'reserved' then
+    redis.call('HSET', KEYS[1], 'reserved', sub(field(KEYS[1], 'reserved'), amount))
+elseif not (state == 'expired' and target == 'committed') then
+    return {0, state}
+end
+
+if target == 'committed' then
+    redis.call('HSET', KEYS[1], 'committed', add(field(KEYS[1], 'committed'), amount))
+end
+redis.call('HSET', KEYS[2], 'state', target)
+redis.call('ZREM', KEYS[3], ARGV[1])
+return {1, target}
@@ -0,0 +1,420 @@
+//! Provider liquidity reservations
+//!
+//! Assigning an order to a provider reserves the assigned amount of the
+//! provider's liquidity in the [`Ledger`]. The reservation is committed when
+//! the order is fulfilled, released when the provider fails it or the order
+//! ends unpaid, and expires on its own if nothing settles it within the TTL.
+//! An assignment the provider cannot cover is failed on `order.failed`, so
+//! the router moves the order to the next provider instead of
+//! over-committing this one.
+//!
+//! A dispute holds the order's settlement: a fulfilment that arrives while
+//! it is under review leaves the reservation in place, and settles when the
+//! dispute is released.
+//!
+//! Committed amounts are deducted from `provider_intents.available_amount`
+//! by periodic reconciliation, which also picks up intent changes the
+//! provider made in the meantime.
+
+pub mod ledger;
+pub mod routes;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use futures::StreamExt;
+use serde::Deserialize;
+use shared_database::{
+    models::SettlementHoldModel, DisputeRepository, OrderRepository, ProviderRepository,
+};
+use shared_messaging::subjects;
+use shared_types::{
+    helpers::{bytes_to_hex, hex_to_bytes},
+    DisputeOutcome, DisputeResolvedEvent, OrderAssignedEvent, OrderFailedEvent, OrderRefundRequestedEvent,
+    ProviderLiquidity, Reservation, ReservationState,
+};
+use tracing::{debug, error, info, warn};
+
+use crate::error::{BalanceError, Result};
+use ledger::{Ledger, ReserveOutcome};
+
+/// Expired reservations released per sweep
+const SWEEP_BATCH: isize = 100;
+
+/// Reservation settings, loaded from the environment
+#[derive(Debug, Clone)]
+pub struct ReservationConfig {
+    /// How long a reservation holds liquidity without being settled
+    pub ttl: Duration,
+    /// How long settled reservations are kept to answer late events
+    pub retention: Duration,
+    pub sweep_interval: Duration,
+    pub reconcile_interval: Duration,
+}
+
+impl Default for ReservationConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(1800),
+            retention: Duration::from_secs(86400),
+            sweep_interval: Duration::from_secs(15),
+            reconcile_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+impl ReservationConfig {
+    /// Load `BALANCE_RESERVATION_TTL_SECS`, `BALANCE_RESERVATION_RETENTION_SECS`,
+    /// `BALANCE_SWEEP_INTERVAL_SECS` and `BALANCE_RECONCILE_INTERVAL_SECS`,
+    /// falling back to defaults
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let env_secs = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs)
+        };
+
+        Self {
+            ttl: env_secs("BALANCE_RESERVATION_TTL_SECS").unwrap_or(defaults.ttl),
+            retention: env_secs("BALANCE_RESERVATION_RETENTION_SECS").unwrap_or(defaults.retention),
+            sweep_interval: env_secs("BALANCE_SWEEP_INTERVAL_SECS").unwrap_or(defaults.sweep_interval),
+            reconcile_interval: env_secs("BALANCE_RECONCILE_INTERVAL_SECS").unwrap_or(defaults.reconcile_interval),
+        }
+    }
+}
+
+/// Payload fields shared by the order events that end an order
+#[derive(Debug, Deserialize)]
+struct OrderRef {
+    order_id: String,
+}
+
+pub struct ReservationService {
+    ledger: Ledger,
+    providers: ProviderRepository,
+    orders: OrderRepository,
+    disputes: DisputeRepository,
+    nats: async_nats::Client,
+    config: ReservationConfig,
+}
+
+impl ReservationService {
+    pub fn new(
+        ledger: Ledger,
+        providers: ProviderRepository,
+        orders: OrderRepository,
+        disputes: DisputeRepository,
+        nats: async_nats::Client,
+        config: ReservationConfig,
+    ) -> Self {
+        Self { ledger, providers, orders, disputes, nats, config }
+    }
+
+    /// Reserve liquidity for an assignment, failing the assignment when the
+    /// provider cannot cover it
+    pub async fn handle_assigned(&self, event: &OrderAssignedEvent) -> Result<()> {
+        let order = self.orders.get_by_order_id(&hex_to_bytes(&event.order_id)).await?;
+        let currency = order
+            .currency
+            .ok_or_else(|| BalanceError::InvalidRequest(format!("Order {} has no currency", event.order_id)))?;
+
+        match self.reserve(&event.order_id, &event.provider, &currency, &event.amount).await? {
+            ReserveOutcome::Reserved => {
+                info!("Reserved {} {} of {} for order {}", event.amount, currency, event.provider, event.order_id);
+            }
+            ReserveOutcome::Exists(state) => {
+                debug!("Order {} already has a {:?} reservation with {}", event.order_id, state, event.provider);
+            }
+            ReserveOutcome::Insufficient { free } => {
+                self.reject(event, &format!("insufficient liquidity: {} free of {} assigned", free, event.amount))
+                    .await;
+            }
+            ReserveOutcome::Unknown => {
+                self.reject(event, &format!("no {} intent to reserve liquidity from", currency)).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reserve `amount`, loading the provider's intent into the ledger the
+    /// first time it is reserved from
+    pub async fn reserve(&self, order_id: &str, provider: &str, currency: &str, amount: &str) -> Result<ReserveOutcome> {
+        let now = Utc::now();
+        let reservation = Reservation {
+            order_id: order_id.to_lowercase(),
+            provider: provider.to_lowercase(),
+            currency: currency.to_uppercase(),
+            amount: parse_amount(amount)?,
+            state: ReservationState::Reserved,
+            created_at: now,
+            expires_at: now + chrono::Duration::from_std(self.config.ttl).unwrap_or_default(),
+        };
+        let reserve = || self.ledger.reserve(&reservation, self.config.retention);
+
+        let outcome = reserve().await?;
+        if outcome != ReserveOutcome::Unknown {
+            return Ok(outcome);
+        }
+        let Some(intent) = self.providers.get_intent(&hex_to_bytes(provider), currency).await? else {
+            return Ok(ReserveOutcome::Unknown);
+        };
+        self.ledger
+            .load(provider, currency, &parse_amount(&intent.available_amount).unwrap_or_else(|_| "0".to_string()))
+            .await?;
+        reserve().await
+    }
+
+    async fn reject(&self, event: &OrderAssignedEvent, reason: &str) {
+        warn!("Failing assignment of order {} to {}: {}", event.order_id, event.provider, reason);
+        let failed = OrderFailedEvent {
+            order_id: event.order_id.clone(),
+            provider: event.provider.clone(),
+            reason: reason.to_string(),
+            timestamp: Utc::now(),
+        };
+        if let Err(e) = shared_messaging::publish_event(&self.nats, subjects::ORDER_FAILED, &failed).await {
+            error!("Failed to publish {} for order {}: {}", subjects::ORDER_FAILED, event.order_id, e);
+        }
+    }
+
+    /// Give back a provider's reservation on an order
+    pub async fn release(&self, order_id: &str, provider: &str) -> Result<bool> {
+        let released = self
+            .ledger
+            .settle(&Reservation::id(order_id, provider), ReservationState::Released)
+            .await?;
+        if released {
+            info!("Released reservation of {} for order {}", provider, order_id);
+        }
+        Ok(released)
+    }
+
+    /// Settle every reservation of an order as `target`
+    ///
+    /// # Returns
+    /// * `Result<usize>` - Reservations that moved
+    pub async fn settle_order(&self, order_id: &str, target: ReservationState) -> Result<usize> {
+        let mut settled = 0;
+        for id in self.ledger.order_reservations(order_id).await? {
+            if self.ledger.settle(&id, target).await? {
+                settled += 1;
+            }
+        }
+        if settled > 0 {
+            info!("Settled {} reservations of order {} as {:?}", settled, order_id, target);
+        }
+        Ok(settled)
+    }
+
+    /// Release reservations whose TTL passed without a settlement
+    pub async fn sweep(&self) -> Result<usize> {
+        let mut expired = 0;
+        for id in self.ledger.expired(Utc::now(), SWEEP_BATCH).await? {
+            if self.ledger.settle(&id, ReservationState::Expired).await? {
+                warn!("Reservation {} expired unsettled; its liquidity is free again", id);
+                expired += 1;
+            }
+        }
+        Ok(expired)
+    }
+
+    /// Deduct committed liquidity from each provider's intent and refresh the
+    /// ledger's view of what the intent holds
+    pub async fn reconcile(&self) -> Result<()> {
+        for intent in self.providers.list_intents().await? {
+            let provider = bytes_to_hex(&intent.provider);
+            let Some(liquidity) = self.ledger.liquidity(&provider, &intent.currency).await? else {
+                continue;
+            };
+
+            let (applied, available) = if liquidity.committed != "0" {
+                match self
+                    .providers
+                    .deduct_available(&intent.provider, &intent.currency, &liquidity.committed)
+                    .await?
+                {
+                    Some(available) => (liquidity.committed.clone(), available),
+                    None => continue,
+                }
+            } else {
+                ("0".to_string(), intent.available_amount.clone())
+            };
+            let available = parse_amount(&available).unwrap_or_else(|_| "0".to_string());
+            self.ledger.reconcile(&provider, &intent.currency, &applied, &available).await?;
+            if applied != "0" {
+                info!(
+                    "Deducted {} {} paid out by {} from its intent; {} available",
+                    applied, intent.currency, provider, available
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// A provider's liquidity in each currency it has an intent in
+    pub async fn liquidity(&self, provider: &str) -> Result<Vec<ProviderLiquidity>> {
+        let mut balances = Vec::new();
+        for intent in self.providers.list_provider_intents(&hex_to_bytes(provider), false).await? {
+            let liquidity = match self.ledger.liquidity(provider, &intent.currency).await? {
+                Some(liquidity) => liquidity,
+                None => {
+                    let available = parse_amount(&intent.available_amount).unwrap_or_else(|_| "0".to_string());
+                    ProviderLiquidity {
+                        provider: provider.to_lowercase(),
+                        currency: intent.currency.to_uppercase(),
+                        available: available.clone(),
+                        reserved: "0".to_string(),
+                        committed: "0".to_string(),
+                        free: available,
+                    }
+                }
+            };
+            balances.push(liquidity);
+        }
+        Ok(balances)
+    }
+
+    /// Consume order events until the subscription closes
+    pub async fn run(self: Arc<Self>) -> anyhow::Result<()> {
+        let mut orders = self.nats.subscribe(subjects::ORDER_ALL.to_string()).await?;
+        info!("Balance Service subscribed to {}", subjects::ORDER_ALL);
+
+        while let Some(message) = orders.next().await {
+            if let Err(e) = self.handle_order_message(message.subject.as_str(), &message.payload).await {
+                error!("Failed to handle {}: {}", message.subject, e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_order_message(&self, subject: &str, payload: &[u8]) -> Result<()> {
+        match subject {
+            subjects::ORDER_ASSIGNED => {
+                let event: OrderAssignedEvent = serde_json::from_slice(payload)?;
+                self.handle_assigned(&event).await
+            }
+            subjects::ORDER_FAILED => {
+                let event: OrderFailedEvent = serde_json::from_slice(payload)?;
+                self.release(&event.order_id, &event.provider).await.map(|_| ())
+            }
+            subjects::ORDER_FULFILLED => {
+                let event: OrderRef = serde_json::from_slice(payload)?;
+                self.handle_fulfilled(&event.order_id).await
+            }
+            subjects::ORDER_REFUND_REQUESTED => {
+                let event: OrderRefundRequestedEvent = serde_json::from_slice(payload)?;
+                if event.leg.is_some() {
+                    return self.release_leg(&event).await;
+                }
+                self.settle_order(&event.order_id, ReservationState::Released).await.map(|_| ())
+            }
+            subjects::ORDER_REFUNDED | subjects::ORDER_EXPIRED => {
+                let event: OrderRef = serde_json::from_slice(payload)?;
+                self.settle_order(&event.order_id, ReservationState::Released).await.map(|_| ())
+            }
+            subjects::ORDER_DISPUTE_RESOLVED => {
+                let event: DisputeResolvedEvent = serde_json::from_slice(payload)?;
+                match event.outcome {
+                    DisputeOutcome::Release => self.settle_held_fulfilment(&event.order_id).await,
+                    DisputeOutcome::Refund => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Release what the providers of a refunded leg still hold; the order's
+    /// other legs keep theirs
+    async fn release_leg(&self, event: &OrderRefundRequestedEvent) -> Result<()> {
+        for provider in &event.failed_providers {
+            self.release(&event.order_id, provider).await?;
+        }
+        Ok(())
+    }
+
+    /// Commit a fulfilled order's reservations, unless a dispute holds its
+    /// settlement
+    async fn handle_fulfilled(&self, order_id: &str) -> Result<()> {
+        if let Some(model) = self.disputes.settlement_hold(&hex_to_bytes(order_id)).await? {
+            let mut hold = model.to_domain();
+            // A release that lands in between fails the save; settle then
+            if hold.hold_fulfilment(Utc::now())
+                && self.disputes.save_settlement_hold(&SettlementHoldModel::from_domain(&hold)).await?
+            {
+                info!("Order {} fulfilled under dispute {}; settlement held", order_id, hold.dispute_id);
+                return Ok(());
+            }
+        }
+        self.settle_fulfilled(order_id).await
+    }
+
+    /// Settle the fulfilment a released dispute held, if there was one
+    async fn settle_held_fulfilment(&self, order_id: &str) -> Result<()> {
+        let Some(model) = self.disputes.settlement_hold(&hex_to_bytes(order_id)).await? else {
+            return Ok(());
+        };
+        let mut hold = model.to_domain();
+        if !hold.take_held_fulfilment(Utc::now()) {
+            return Ok(());
+        }
+        // Settling again on a redelivery moves nothing
+        self.settle_fulfilled(order_id).await?;
+        self.disputes.save_settlement_hold(&SettlementHoldModel::from_domain(&hold)).await?;
+        info!("Dispute {} released; settled held fulfilment of order {}", hold.dispute_id, order_id);
+        Ok(())
+    }
+
+    async fn settle_fulfilled(&self, order_id: &str) -> Result<()> {
+        self.settle_order(order_id, ReservationState::Committed).await.map(|_| ())
+    }
+
+    /// Release expired reservations and reconcile intents in the background
+    pub fn spawn_maintenance(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut sweep = tokio::time::interval(self.config.sweep_interval);
+            let mut reconcile = tokio::time::interval(self.config.reconcile_interval);
+            loop {
+                tokio::select! {
+                    _ = sweep.tick() => {
+                        if let Err(e) = self.sweep().await {
+                            warn!("Reservation sweep failed: {}", e);
+                        }
+                    }
+                    _ = reconcile.tick() => {
+                        if let Err(e) = self.reconcile().await {
+                            warn!("Liquidity reconciliation failed: {}", e);
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Amount in base units, without leading zeros; the ledger's scripts only
+/// handle plain non-negative integers
+pub fn parse_amount(amount: &str) -> Result<String> {
+    amount
+        .trim()
+        .parse::<u128>()
+        .map(|amount| amount.to_string())
+        .map_err(|_| BalanceError::InvalidRequest(format!("Invalid token amount: {}", amount)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(parse_amount("000150000000000000000000").unwrap(), "150000000000000000000");
+        assert_eq!(parse_amount(" 42 ").unwrap(), "42");
+        assert!(parse_amount("1.5").is_err());
+        assert!(parse_amount("-1").is_err());
+        assert_eq!(Reservation::id("0xABC", "0xDEF"), "0xabc:0xdef");
+    }
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    routing::get,
+    Json, Router,
+};
+use shared_types::{helpers::is_valid_address, ProviderLiquidity};
+
+use super::ReservationService;
+use crate::error::{BalanceError, Result};
+
+/// Liquidity routes, for internal callers
+pub fn router(service: Arc<ReservationService>) -> Router {
+    Router::new()
+        .route("/providers/:address/liquidity", get(get_liquidity))
+        .with_state(service)
+}
+
+/// Available, reserved, committed and free liquidity per currency
+async fn get_liquidity(
+    State(service): State<Arc<ReservationService>>,
+    Path(address): Path<String>,
+) -> Result<Json<Vec<ProviderLiquidity>>> {
+    if !is_valid_address(&address) {
+        return Err(BalanceError::InvalidRequest(format!("Invalid provider address: {}", address)));
+    }
+    Ok(Json(service.liquidity(&address).await?))
+}
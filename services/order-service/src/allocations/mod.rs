@@ -0,0 +1,120 @@
+//! Settlement of split orders
+//!
+//! Orders too large for any one provider are split by the router into legs,
+//! each paid out by its own provider. Fills are recorded per leg; the parent
+//! order moves to partially fulfilled on the first payout and to fulfilled
+//! once every leg has settled, publishing the transition like any other
+//! status change. A failed leg is closed and its unpaid amount refunded
+//! through `order.refund_requested`, like a leg the router gave up on.
+
+pub mod routes;
+
+use chrono::Utc;
+use shared_database::{
+    models::{hex_to_bytes, OrderAllocationModel},
+    AllocationRepository, OrderRepository,
+};
+use shared_messaging::subjects;
+use shared_types::{
+    helpers::bytes_to_hex, split_order_status, OrderAllocation, OrderRefundRequestedEvent, OrderStatus,
+    OrderStatusChangedEvent, RecordFillRequest,
+};
+use tracing::warn;
+
+use crate::error::{OrderServiceError, Result};
+
+pub struct AllocationService {
+    allocations: AllocationRepository,
+    orders: OrderRepository,
+    nats: async_nats::Client,
+}
+
+impl AllocationService {
+    pub fn new(allocations: AllocationRepository, orders: OrderRepository, nats: async_nats::Client) -> Self {
+        Self { allocations, orders, nats }
+    }
+
+    /// Legs of an order, in leg order; empty for orders that were not split
+    pub async fn list(&self, order_id: &str) -> Result<Vec<OrderAllocation>> {
+        let allocations = self.allocations.list_for_order(&hex_to_bytes(order_id)).await?;
+        Ok(allocations.iter().map(OrderAllocationModel::to_domain).collect())
+    }
+
+    /// Record a payout on one leg and roll the order status forward
+    pub async fn record_fill(&self, order_id: &str, leg: u32, request: &RecordFillRequest) -> Result<OrderAllocation> {
+        let mut legs = self.list(order_id).await?;
+        let index = legs
+            .iter()
+            .position(|allocation| allocation.leg == leg)
+            .ok_or_else(|| OrderServiceError::InvalidRequest(format!("Order {} has no leg {}", order_id, leg)))?;
+        let previous_filled_amount = legs[index].filled_amount.clone();
+        legs[index].record_fill(&request.amount, request.tx_hash.clone())?;
+
+        let order = self.orders.get_by_order_id(&hex_to_bytes(order_id)).await?;
+        let previous_status = order.status.parse::<OrderStatus>().ok();
+        let status = split_order_status(&legs).filter(|status| Some(*status) != previous_status);
+
+        let model = OrderAllocationModel::from_domain(&legs[index]);
+        let stored = self
+            .allocations
+            .record_fill(&model, &previous_filled_amount, status.map(|s| s.as_str()))
+            .await?;
+        if !stored {
+            return Err(OrderServiceError::Conflict(format!(
+                "Leg {} of order {} changed concurrently; retry the fill",
+                leg, order_id
+            )));
+        }
+
+        if let Some(status) = status {
+            let event = OrderStatusChangedEvent {
+                order_id: order_id.to_lowercase(),
+                user_address: bytes_to_hex(&order.user_address),
+                integrator_address: bytes_to_hex(&order.integrator_address),
+                previous_status,
+                status,
+                timestamp: Utc::now(),
+            };
+            let subject = match status {
+                OrderStatus::Fulfilled => subjects::ORDER_FULFILLED,
+                _ => subjects::ORDER_PARTIALLY_FULFILLED,
+            };
+            if let Err(e) = shared_messaging::publish_event(&self.nats, subject, &event).await {
+                warn!("Failed to publish {} for order {}: {}", subject, order_id, e);
+            }
+        }
+
+        Ok(legs.swap_remove(index))
+    }
+
+    /// Close a leg its provider could not pay out and request the refund of
+    /// what it did not pay, which closes the order once no leg is left open
+    pub async fn fail_leg(&self, order_id: &str, leg: u32) -> Result<()> {
+        let failed = self
+            .list(order_id)
+            .await?
+            .into_iter()
+            .find(|allocation| allocation.leg == leg)
+            .ok_or_else(|| OrderServiceError::InvalidRequest(format!("Order {} has no leg {}", order_id, leg)))?;
+        if !self.allocations.mark_failed(&hex_to_bytes(order_id), leg as i32).await? {
+            return Err(OrderServiceError::Conflict(format!(
+                "Leg {} of order {} is not open",
+                leg, order_id
+            )));
+        }
+
+        let event = OrderRefundRequestedEvent {
+            order_id: order_id.to_lowercase(),
+            attempts: 0,
+            failed_providers: vec![failed.provider.clone()],
+            reason: format!("Leg {} failed", leg),
+            leg: Some(leg),
+            amount: Some(failed.remaining().to_string()),
+            timestamp: Utc::now(),
+        };
+        if let Err(e) = shared_messaging::publish_event(&self.nats, subjects::ORDER_REFUND_REQUESTED, &event).await {
+            warn!("Failed to publish {} for order {}: {}", subjects::ORDER_REFUND_REQUESTED, order_id, e);
+        }
+        Ok(())
+    }
+}
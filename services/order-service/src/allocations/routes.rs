@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use shared_types::{OrderAllocation, RecordFillRequest};
+
+use super::AllocationService;
+use crate::error::Result;
+
+/// Split-order leg routes
+pub fn router(service: Arc<AllocationService>) -> Router {
+    Router::new()
+        .route("/orders/:order_id/allocations", get(list_allocations))
+        .route("/orders/:order_id/allocations/:leg/fills", post(record_fill))
+        .route("/orders/:order_id/allocations/:leg/fail", post(fail_leg))
+        .with_state(service)
+}
+
+async fn list_allocations(
+    State(service): State<Arc<AllocationService>>,
+    Path(order_id): Path<String>,
+) -> Result<Json<Vec<OrderAllocation>>> {
+    Ok(Json(service.list(&order_id).await?))
+}
+
+async fn record_fill(
+    State(service): State<Arc<AllocationService>>,
+    Path((order_id, leg)): Path<(String, u32)>,
+    Json(request): Json<RecordFillRequest>,
+) -> Result<Json<OrderAllocation>> {
+    Ok(Json(service.record_fill(&order_id, leg, &request).await?))
+}
+
+async fn fail_leg(
+    State(service): State<Arc<AllocationService>>,
+    Path((order_id, leg)): Path<(String, u32)>,
+) -> Result<StatusCode> {
+    service.fail_leg(&order_id, leg).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
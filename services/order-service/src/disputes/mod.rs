@@ -0,0 +1,402 @@
+//! Disputes over payouts users say they never received
+//!
+//! A user may dispute an accepted or fulfilled order for a while after it
+//! last changed. Opening a dispute publishes `order.disputed`, and the
+//! order's settlement is held while the dispute is active. The provider
+//! answers with its signed payment proof and supporting evidence; proofs
+//! not signed by the provider of the accepted proposal, or for a payment
+//! another dispute was already answered with, are rejected. An admin
+//! resolves the dispute: a release lets settlement proceed, a refund returns
+//! the escrow to the user and counts against the provider's reputation.
+
+pub mod routes;
+
+use chrono::{DateTime, Duration, Utc};
+use shared_database::{
+    models::{hex_to_bytes, DisputeModel},
+    DatabaseError, DisputeRepository, OrderRepository, ProposalRepository,
+};
+use shared_messaging::subjects;
+use shared_types::{
+    helpers::bytes_to_hex, Dispute, DisputeOutcome, DisputeResolvedEvent, DisputeStatus, OpenDisputeRequest,
+    OrderDisputedEvent, OrderRefundRequestedEvent, OrderStatus, PaymentProof, ProofDomain, ProofError, ProofFreshness,
+    ProofVerifier, Proposal, ResolveDisputeRequest, SubmitEvidenceRequest, TypesError,
+};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::error::{OrderServiceError, Result};
+
+/// Dispute settings, loaded from the environment
+#[derive(Debug, Clone)]
+pub struct DisputeConfig {
+    /// How long after an order last changed it may still be disputed
+    pub window: Duration,
+    /// Escrow contract payment proofs are signed for; without it no
+    /// evidence can be checked, so none is accepted
+    pub proof_domain: Option<ProofDomain>,
+}
+
+impl Default for DisputeConfig {
+    fn default() -> Self {
+        Self { window: Duration::hours(72), proof_domain: None }
+    }
+}
+
+impl DisputeConfig {
+    /// Load `DISPUTE_WINDOW_SECS`, falling back to 72 hours, and the proof
+    /// domain from `ESCROW_CHAIN_ID` and `ESCROW_CONTRACT_ADDRESS`
+    pub fn from_env() -> Self {
+        let window = std::env::var("DISPUTE_WINDOW_SECS")
+            .ok()
+            .and_then(|v| v.parse::<i64>().ok())
+            .filter(|secs| *secs > 0)
+            .map(Duration::seconds)
+            .unwrap_or_else(|| Self::default().window);
+        let chain_id = std::env::var("ESCROW_CHAIN_ID").ok().and_then(|v| v.parse::<u64>().ok());
+        let contract = std::env::var("ESCROW_CONTRACT_ADDRESS").ok().filter(|v| !v.trim().is_empty());
+        let proof_domain = match (chain_id, contract) {
+            (Some(chain_id), Some(contract)) => match ProofDomain::parse(chain_id, &contract) {
+                Ok(domain) => Some(domain),
+                Err(e) => {
+                    warn!("Ignoring escrow proof domain: {}", e);
+                    None
+                }
+            },
+            _ => None,
+        };
+        Self { window, proof_domain }
+    }
+
+    /// How old a proof may be when its dispute opens: the order may be
+    /// disputed for the window after it settled, and the payout precedes
+    /// settlement by at most the usual proof age
+    fn proof_freshness(&self) -> ProofFreshness {
+        let freshness = ProofFreshness::default();
+        ProofFreshness { max_age: freshness.max_age + self.window, ..freshness }
+    }
+}
+
+pub struct DisputeService {
+    disputes: DisputeRepository,
+    orders: OrderRepository,
+    proposals: ProposalRepository,
+    verifier: Option<ProofVerifier>,
+    nats: async_nats::Client,
+    config: DisputeConfig,
+}
+
+impl DisputeService {
+    pub fn new(
+        disputes: DisputeRepository,
+        orders: OrderRepository,
+        proposals: ProposalRepository,
+        nats: async_nats::Client,
+        config: DisputeConfig,
+    ) -> Self {
+        let verifier = config
+            .proof_domain
+            .clone()
+            .map(|domain| ProofVerifier::new(domain, config.proof_freshness()));
+        Self { disputes, orders, proposals, verifier, nats, config }
+    }
+
+    /// Open a dispute on an order for its user and hold its settlement
+    pub async fn open(&self, order_id: &str, user_address: &str, request: &OpenDisputeRequest) -> Result<Dispute> {
+        let reason = request.reason.trim();
+        if reason.is_empty() {
+            return Err(OrderServiceError::InvalidRequest("reason is required".to_string()));
+        }
+
+        let order_key = hex_to_bytes(order_id);
+        let order = self
+            .orders
+            .find(&order_key)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Order {}", order_id)))?;
+        if !bytes_to_hex(&order.user_address).eq_ignore_ascii_case(user_address) {
+            return Err(OrderServiceError::Forbidden(format!(
+                "Only the user of order {} can dispute it",
+                order_id
+            )));
+        }
+        let now = Utc::now();
+        let disputable = order.status.parse::<OrderStatus>().ok()
+            .is_some_and(|status| Dispute::can_open(status, order.updated_at, self.config.window, now));
+        if !disputable {
+            return Err(OrderServiceError::Conflict(format!(
+                "Order {} is {} and can no longer be disputed",
+                order_id, order.status
+            )));
+        }
+        if self.disputes.active_for_order(&order_key).await?.is_some() {
+            return Err(OrderServiceError::Conflict(format!(
+                "Order {} already has a dispute under review",
+                order_id
+            )));
+        }
+        let proposal = self
+            .proposals
+            .get_accepted_for_order(&order_key)
+            .await?
+            .ok_or_else(|| OrderServiceError::Conflict(format!("Order {} has no accepted proposal", order_id)))?;
+
+        let dispute = Dispute {
+            id: Uuid::new_v4(),
+            order_id: bytes_to_hex(&order.order_id),
+            user_address: bytes_to_hex(&order.user_address),
+            provider: bytes_to_hex(&proposal.provider),
+            reason: reason.to_string(),
+            status: DisputeStatus::Open,
+            proof: None,
+            outcome: None,
+            resolution_note: None,
+            resolved_by: None,
+            opened_at: now,
+            evidence_submitted_at: None,
+            resolved_at: None,
+        };
+        // Lost a race with another dispute on the same order
+        if !self.disputes.create(&DisputeModel::from_domain(&dispute)).await? {
+            return Err(OrderServiceError::Conflict(format!(
+                "Order {} already has a dispute under review",
+                order_id
+            )));
+        }
+        info!("Dispute {} opened on order {} against {}", dispute.id, dispute.order_id, dispute.provider);
+
+        let event = OrderDisputedEvent {
+            order_id: dispute.order_id.clone(),
+            dispute_id: dispute.id,
+            provider: dispute.provider.clone(),
+            reason: dispute.reason.clone(),
+            timestamp: now,
+        };
+        self.publish(subjects::ORDER_DISPUTED, &dispute, &event).await;
+        Ok(dispute)
+    }
+
+    /// Record the provider's answer: its payment proof, with the evidence
+    /// stored in the proof's metadata
+    pub async fn submit_evidence(&self, id: Uuid, provider: &str, request: SubmitEvidenceRequest) -> Result<Dispute> {
+        let dispute = self.get(id).await?;
+        if !dispute.provider.eq_ignore_ascii_case(provider) {
+            return Err(OrderServiceError::Forbidden(format!("Dispute {} is not against {}", id, provider)));
+        }
+        let verifier = self
+            .verifier
+            .as_ref()
+            .ok_or_else(|| OrderServiceError::Internal("Payment proof verification is not configured".to_string()))?;
+        let proposal = self
+            .proposals
+            .get_accepted_for_order(&hex_to_bytes(&dispute.order_id))
+            .await?
+            .ok_or_else(|| OrderServiceError::Conflict(format!("Order {} has no accepted proposal", dispute.order_id)))?
+            .to_domain();
+        let reference = request.proof.transaction_reference.trim();
+        if self.disputes.proof_reference_used(id, reference).await? {
+            return Err(OrderServiceError::Conflict(format!(
+                "Payment {} already answered another dispute",
+                reference
+            )));
+        }
+        // Evidence may be replaced, so the proof already on the dispute is
+        // not a replay of itself
+        let replacing = dispute
+            .proof
+            .as_ref()
+            .is_some_and(|proof| proof.transaction_reference.trim() == reference);
+        check_proof(verifier, &request.proof, &proposal, dispute.opened_at, replacing)?;
+
+        let proof = serde_json::to_value(request.proof.with_evidence(request.evidence)).map_err(TypesError::from)?;
+        let updated = self
+            .disputes
+            .submit_evidence(id, &hex_to_bytes(provider), &proof)
+            .await?
+            .ok_or_else(|| OrderServiceError::Conflict(format!("Dispute {} is already resolved", id)))?;
+        Ok(updated.to_domain())
+    }
+
+    /// Decide a dispute and let the held settlement proceed
+    pub async fn resolve(&self, id: Uuid, admin: &str, request: &ResolveDisputeRequest) -> Result<Dispute> {
+        let note = request.note.as_deref().map(str::trim).filter(|note| !note.is_empty());
+        let refund = request.outcome == DisputeOutcome::Refund;
+        let Some(resolved) = self
+            .disputes
+            .resolve(id, request.outcome.as_str(), note, &hex_to_bytes(admin), refund)
+            .await?
+        else {
+            // Either unknown, or resolved already
+            self.get(id).await?;
+            return Err(OrderServiceError::Conflict(format!("Dispute {} is already resolved", id)));
+        };
+        let dispute = resolved.to_domain();
+        info!("Dispute {} on order {} resolved: {:?}", dispute.id, dispute.order_id, request.outcome);
+
+        let now = Utc::now();
+        let event = DisputeResolvedEvent {
+            order_id: dispute.order_id.clone(),
+            dispute_id: dispute.id,
+            provider: dispute.provider.clone(),
+            outcome: request.outcome,
+            timestamp: now,
+        };
+        self.publish(subjects::ORDER_DISPUTE_RESOLVED, &dispute, &event).await;
+        if refund {
+            let event = OrderRefundRequestedEvent {
+                order_id: dispute.order_id.clone(),
+                attempts: 1,
+                failed_providers: vec![dispute.provider.clone()],
+                reason: format!("Dispute {} resolved in the user's favour", dispute.id),
+                leg: None,
+                amount: None,
+                timestamp: now,
+            };
+            self.publish(subjects::ORDER_REFUND_REQUESTED, &dispute, &event).await;
+        }
+        Ok(dispute)
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<Dispute> {
+        let dispute = self
+            .disputes
+            .get(id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Dispute {}", id)))?;
+        Ok(dispute.to_domain())
+    }
+
+    /// Disputes on an order, newest first
+    pub async fn list_for_order(&self, order_id: &str) -> Result<Vec<Dispute>> {
+        let disputes = self.disputes.list_for_order(&hex_to_bytes(order_id)).await?;
+        Ok(disputes.iter().map(DisputeModel::to_domain).collect())
+    }
+
+    /// Disputes, oldest first, optionally only those in a status or against
+    /// a provider
+    pub async fn list(&self, status: Option<DisputeStatus>, provider: Option<&str>, limit: i64) -> Result<Vec<Dispute>> {
+        let provider = provider.map(hex_to_bytes);
+        let disputes = self
+            .disputes
+            .list(status.map(|s| s.as_str()), provider.as_deref(), limit)
+            .await?;
+        Ok(disputes.iter().map(DisputeModel::to_domain).collect())
+    }
+
+    async fn publish<T: serde::Serialize>(&self, subject: &str, dispute: &Dispute, event: &T) {
+        if let Err(e) = shared_messaging::publish_event(&self.nats, subject, event).await {
+            warn!("Failed to publish {} for dispute {}: {}", subject, dispute.id, e);
+        }
+    }
+}
+
+/// Verify a proof answering a dispute opened at `opened_at` against the
+/// order's accepted proposal; `replacing` allows the reference of the proof
+/// the dispute was already answered with
+fn check_proof(
+    verifier: &ProofVerifier,
+    proof: &PaymentProof,
+    proposal: &Proposal,
+    opened_at: DateTime<Utc>,
+    replacing: bool,
+) -> Result<()> {
+    match verifier.verify(proof, proposal, opened_at) {
+        Ok(_) => Ok(()),
+        Err(ProofError::Replayed(_)) if replacing => Ok(()),
+        Err(ProofError::Replayed(reference)) => Err(OrderServiceError::Conflict(format!(
+            "Payment {} already answered a dispute",
+            reference
+        ))),
+        Err(e) => Err(OrderServiceError::InvalidRequest(format!("Invalid payment proof: {}", e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::types::Address;
+    use shared_types::ProposalStatus;
+
+    #[test]
+    fn test_dispute_window_from_env() {
+        std::env::set_var("DISPUTE_WINDOW_SECS", "3600");
+        assert_eq!(DisputeConfig::from_env().window, Duration::hours(1));
+        std::env::set_var("DISPUTE_WINDOW_SECS", "0");
+        assert_eq!(DisputeConfig::from_env().window, Duration::hours(72));
+        std::env::remove_var("DISPUTE_WINDOW_SECS");
+    }
+
+    fn signed_proof(wallet: &LocalWallet, domain: &ProofDomain, paid_at: DateTime<Utc>, reference: &str) -> PaymentProof {
+        let mut proof = PaymentProof {
+            proposal_id: format!("0x{}", "ab".repeat(32)),
+            provider: format!("{:?}", wallet.address()),
+            transaction_reference: reference.to_string(),
+            timestamp: paid_at,
+            amount: "150000.00".to_string(),
+            currency: "NGN".to_string(),
+            signature: String::new(),
+            metadata: serde_json::Value::Null,
+        };
+        proof.signature = format!("0x{}", wallet.sign_hash(proof.signing_hash(domain).unwrap()).unwrap());
+        proof
+    }
+
+    #[test]
+    fn test_evidence_needs_a_proof_signed_by_the_proposal_provider() {
+        let provider = LocalWallet::from_str("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318").unwrap();
+        let forger = LocalWallet::from_str("0123456789012345678901234567890123456789012345678901234567890123").unwrap();
+        let domain = ProofDomain::new(8453, Address::repeat_byte(0x11));
+        let config = DisputeConfig { proof_domain: Some(domain.clone()), ..DisputeConfig::default() };
+        let verifier = ProofVerifier::new(domain.clone(), config.proof_freshness());
+
+        // Paid two days before the dispute, within the window
+        let opened_at = Utc::now();
+        let paid_at = opened_at - Duration::days(2);
+        let proof = signed_proof(&provider, &domain, paid_at, "TXN-001");
+        let proposal = Proposal {
+            proposal_id: proof.proposal_id.clone(),
+            order_id: format!("0x{}", "cd".repeat(32)),
+            provider: proof.provider.clone(),
+            proposed_fee_bps: 50,
+            status: ProposalStatus::Accepted,
+            created_at: paid_at,
+            deadline: paid_at + Duration::minutes(10),
+            accepted_at: Some(paid_at),
+            executed_at: None,
+            tx_hash: None,
+        };
+
+        // Signed by someone else while naming the provider
+        let mut forged = signed_proof(&forger, &domain, paid_at, "TXN-002");
+        forged.provider = proof.provider.clone();
+        assert!(matches!(
+            check_proof(&verifier, &forged, &proposal, opened_at, false),
+            Err(OrderServiceError::InvalidRequest(_))
+        ));
+        // Signed, then altered
+        let mut altered = signed_proof(&provider, &domain, paid_at, "TXN-003");
+        altered.amount = "1500000.00".to_string();
+        assert!(matches!(
+            check_proof(&verifier, &altered, &proposal, opened_at, false),
+            Err(OrderServiceError::InvalidRequest(_))
+        ));
+
+        // Paid longer ago than the window allows
+        let stale = signed_proof(&provider, &domain, opened_at - Duration::days(5), "TXN-004");
+        assert!(matches!(
+            check_proof(&verifier, &stale, &proposal, opened_at, false),
+            Err(OrderServiceError::InvalidRequest(_))
+        ));
+
+        assert!(check_proof(&verifier, &proof, &proposal, opened_at, false).is_ok());
+        // Answering the same dispute again replaces the evidence
+        assert!(check_proof(&verifier, &proof, &proposal, opened_at, true).is_ok());
+        assert!(matches!(
+            check_proof(&verifier, &proof, &proposal, opened_at, false),
+            Err(OrderServiceError::Conflict(_))
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use shared_types::{Dispute, DisputeStatus, OpenDisputeRequest, ResolveDisputeRequest, SubmitEvidenceRequest};
+use uuid::Uuid;
+
+use super::DisputeService;
+use crate::error::Result;
+
+/// Largest page of disputes returned by the listing
+const MAX_DISPUTES_PAGE: i64 = 200;
+
+/// Dispute opened on behalf of an order's user
+#[derive(Debug, Deserialize)]
+pub struct OpenDisputeBody {
+    pub user_address: String,
+    #[serde(flatten)]
+    pub request: OpenDisputeRequest,
+}
+
+/// Evidence submitted on behalf of the disputed provider
+#[derive(Debug, Deserialize)]
+pub struct SubmitEvidenceBody {
+    pub provider: String,
+    #[serde(flatten)]
+    pub request: SubmitEvidenceRequest,
+}
+
+/// Resolution made by an admin
+#[derive(Debug, Deserialize)]
+pub struct ResolveDisputeBody {
+    pub resolved_by: String,
+    #[serde(flatten)]
+    pub request: ResolveDisputeRequest,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListDisputesQuery {
+    pub status: Option<DisputeStatus>,
+    pub provider: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Dispute routes
+pub fn router(service: Arc<DisputeService>) -> Router {
+    Router::new()
+        .route("/orders/:order_id/disputes", post(open_dispute).get(list_order_disputes))
+        .route("/disputes", get(list_disputes))
+        .route("/disputes/:id", get(get_dispute))
+        .route("/disputes/:id/evidence", post(submit_evidence))
+        .route("/disputes/:id/resolve", post(resolve_dispute))
+        .with_state(service)
+}
+
+async fn open_dispute(
+    State(service): State<Arc<DisputeService>>,
+    Path(order_id): Path<String>,
+    Json(body): Json<OpenDisputeBody>,
+) -> Result<(StatusCode, Json<Dispute>)> {
+    let dispute = service.open(&order_id, &body.user_address, &body.request).await?;
+    Ok((StatusCode::CREATED, Json(dispute)))
+}
+
+async fn list_order_disputes(
+    State(service): State<Arc<DisputeService>>,
+    Path(order_id): Path<String>,
+) -> Result<Json<Vec<Dispute>>> {
+    Ok(Json(service.list_for_order(&order_id).await?))
+}
+
+async fn list_disputes(
+    State(service): State<Arc<DisputeService>>,
+    Query(query): Query<ListDisputesQuery>,
+) -> Result<Json<Vec<Dispute>>> {
+    let limit = query.limit.unwrap_or(50).clamp(1, MAX_DISPUTES_PAGE);
+    Ok(Json(service.list(query.status, query.provider.as_deref(), limit).await?))
+}
+
+async fn get_dispute(State(service): State<Arc<DisputeService>>, Path(id): Path<Uuid>) -> Result<Json<Dispute>> {
+    Ok(Json(service.get(id).await?))
+}
+
+async fn submit_evidence(
+    State(service): State<Arc<DisputeService>>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<SubmitEvidenceBody>,
+) -> Result<Json<Dispute>> {
+    Ok(Json(service.submit_evidence(id, &body.provider, body.request).await?))
+}
+
+async fn resolve_dispute(
+    State(service): State<Arc<DisputeService>>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<ResolveDisputeBody>,
+) -> Result<Json<Dispute>> {
+    Ok(Json(service.resolve(id, &body.resolved_by, &body.request).await?))
+}
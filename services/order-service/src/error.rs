@@ -0,0 +1,61 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use shared_database::DatabaseError;
+use shared_fx::FxError;
+use shared_types::TypesError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum OrderServiceError {
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("{0}")]
+    NoLiquidity(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Internal error: {0}")]
+    Internal(String),
+
+    #[error(transparent)]
+    Types(#[from] TypesError),
+
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+
+    #[error(transparent)]
+    Fx(#[from] FxError),
+}
+
+pub type Result<T> = std::result::Result<T, OrderServiceError>;
+
+impl IntoResponse for OrderServiceError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            OrderServiceError::InvalidRequest(_) | OrderServiceError::Types(_) => StatusCode::BAD_REQUEST,
+            OrderServiceError::NoLiquidity(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            OrderServiceError::Forbidden(_) => StatusCode::FORBIDDEN,
+            OrderServiceError::Conflict(_) => StatusCode::CONFLICT,
+            OrderServiceError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            OrderServiceError::Database(DatabaseError::NotFound(_)) => StatusCode::NOT_FOUND,
+            OrderServiceError::Database(DatabaseError::DuplicateEntry(_)) => StatusCode::CONFLICT,
+            OrderServiceError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            OrderServiceError::Fx(FxError::NoRate(_)) => StatusCode::UNPROCESSABLE_ENTITY,
+            OrderServiceError::Fx(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        if status.is_server_error() {
+            tracing::error!("Request failed: {}", self);
+        }
+
+        (status, Json(serde_json::json!({ "error": self.to_string() }))).into_response()
+    }
+}
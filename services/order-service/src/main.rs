@@ -1,5 +1,28 @@
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use axum::{routing::get, Router};
+use shared_database::{
+    AllocationRepository, DisputeRepository, FxRateRepository, OrderRepository, ProposalRepository, ProviderRepository,
+    QuoteRepository, TierLimitsRepository, TierLimitsStore, WebhookRepository,
+};
+use shared_fx::FxOracle;
 use tracing::info;
 
+mod allocations;
+mod disputes;
+mod error;
+mod orders;
+mod quotes;
+mod tiers;
+mod webhooks;
+
+use allocations::AllocationService;
+use disputes::{DisputeConfig, DisputeService};
+use orders::{OrderConfig, OrderService};
+use quotes::{QuoteConfig, QuoteService};
+use tiers::TierService;
+use webhooks::{WebhookConfig, WebhookWorker};
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt::init();
@@ -7,5 +30,112 @@ async fn main() -> anyhow::Result<()> {
 
     info!("Order Service starting...");
 
+    let pool = shared_database::initialize_database().await?;
+    let nats_url = std::env::var("NATS_URL").unwrap_or_else(|_| "nats://127.0.0.1:4222".to_string());
+    let nats = shared_messaging::connect_nats(&nats_url).await?;
+
+    let allocation_service = Arc::new(AllocationService::new(
+        AllocationRepository::new(pool.clone()),
+        OrderRepository::new(pool.clone()),
+        nats.clone(),
+    ));
+    let dispute_config = DisputeConfig::from_env();
+    if dispute_config.proof_domain.is_none() {
+        tracing::warn!("ESCROW_CHAIN_ID or ESCROW_CONTRACT_ADDRESS not set; dispute evidence will be refused");
+    }
+    let dispute_service = Arc::new(DisputeService::new(
+        DisputeRepository::new(pool.clone()),
+        OrderRepository::new(pool.clone()),
+        ProposalRepository::new(pool.clone()),
+        nats.clone(),
+        dispute_config,
+    ));
+
+    let webhook_repo = Arc::new(WebhookRepository::new(pool.clone()));
+    let webhook_worker = Arc::new(WebhookWorker::new(webhook_repo.clone(), WebhookConfig::from_env()));
+    tokio::spawn(webhook_worker.clone().run_deliveries());
+    let events = nats.clone();
+    tokio::spawn(async move {
+        if let Err(e) = webhook_worker.consume_order_events(events).await {
+            tracing::error!("Webhook event consumer stopped: {}", e);
+        }
+    });
+
+    let oracle = Arc::new(FxOracle::from_env(connect_redis().await)?);
+    let quote_service = Arc::new(QuoteService::new(
+        ProviderRepository::new(pool.clone()),
+        QuoteRepository::new(pool.clone()),
+        FxRateRepository::new(pool.clone()),
+        oracle,
+        QuoteConfig::from_env()?,
+    ));
+
+    let tier_limits = Arc::new(TierLimitsStore::new(TierLimitsRepository::new(pool.clone())));
+    info!("Loaded tier limits for {} tokens", tier_limits.reload().await?);
+    let refresh_secs = std::env::var("TIER_LIMITS_REFRESH_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(30u64);
+    tier_limits.clone().spawn_refresh(Duration::from_secs(refresh_secs.max(1)));
+    let tier_service = Arc::new(TierService::new(tier_limits));
+    let order_service = Arc::new(OrderService::new(
+        pool.clone(),
+        tier_service.clone(),
+        quote_service.clone(),
+        nats.clone(),
+        OrderConfig::from_env(),
+    ));
+    order_service.clone().spawn_expiry_sweep();
+    let refunds = order_service.clone();
+    let refund_events = nats.clone();
+    tokio::spawn(async move {
+        if let Err(e) = refunds.consume_refund_requests(refund_events).await {
+            tracing::error!("Refund request consumer stopped: {}", e);
+        }
+    });
+
+    let app = Router::new()
+        .route("/health", get(health_check))
+        .merge(orders::routes::router(order_service))
+        .merge(webhooks::routes::router(webhook_repo))
+        .merge(quotes::routes::router(quote_service))
+        .merge(allocations::routes::router(allocation_service))
+        .merge(disputes::routes::router(dispute_service))
+        .merge(tiers::routes::router(tier_service));
+
+    let port = std::env::var("ORDER_SERVICE_PORT")
+        .ok()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(8001);
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    info!("Order Service listening on {}", addr);
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await?;
+
     Ok(())
 }
+
+/// Redis backs the FX cache and provider-submitted rates; without it the
+/// oracle still works from its other sources
+async fn connect_redis() -> Option<redis::aio::ConnectionManager> {
+    let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+    let client = match redis::Client::open(url) {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::warn!("Invalid REDIS_URL, FX cache disabled: {}", e);
+            return None;
+        }
+    };
+    match redis::aio::ConnectionManager::new(client).await {
+        Ok(conn) => Some(conn),
+        Err(e) => {
+            tracing::warn!("Redis unavailable, FX cache disabled: {}", e);
+            None
+        }
+    }
+}
+
+async fn health_check() -> &'static str {
+    "OK"
+}
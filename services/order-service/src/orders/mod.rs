@@ -0,0 +1,669 @@
+//! Orders submitted through the API
+//!
+//! The gateway forwards a user's order with the user's address. It is stored
+//! pending in the tier its amount falls in, charged the integrator's
+//! configured fee, and published on `order.pending` for the router. An order
+//! no provider takes expires `ORDER_TTL_SECS` after it was created, which a
+//! sweep every `ORDER_EXPIRY_SWEEP_SECS` records and publishes on
+//! `order.expired`.
+//!
+//! A refund requested by the router or a dispute closes an order that has
+//! nothing escrowed on-chain as refunded, published on `order.refunded`.
+//! Escrowed orders are refunded by the Settlement Service, which publishes
+//! the same event once the escrow is returned. A refund requested for one
+//! leg of a split order only closes the order once no leg is left open: it
+//! is refunded if every leg was given up without a payout and fulfilled,
+//! published on `order.fulfilled`, if the other legs paid out.
+//!
+//! An order referencing a quote is only created while the quote is unexpired,
+//! correctly signed and issued for the order's terms. The order keeps the
+//! quote's ID and the quote cannot back another order. The FX rate the quote
+//! was priced with is linked to the order for audits.
+//!
+//! A create carrying an idempotency key is stored once per user and key:
+//! repeating it returns the order it first created, and reusing the key for
+//! different terms is refused.
+//!
+//! Orders are listed newest first for their user or integrator, a page at a
+//! time. `next_cursor` holds the creation time and row id of a page's last
+//! order, so pages stay stable while new orders arrive.
+
+pub mod routes;
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use futures::StreamExt;
+use shared_database::{
+    models::{hex_to_bytes, FxRateRecordModel, OrderAllocationModel, OrderModel, ProposalModel},
+    AllocationRepository, DatabaseError, FxRateRepository, OrderRepository, ProposalRepository,
+};
+use shared_messaging::subjects;
+use shared_types::{
+    helpers::is_valid_address, split_order_status, CreateOrderRequest, Currency, FxRateSnapshot, Order, OrderRefundRequestedEvent,
+    OrderStatus, Page, Proposal, Quote,
+};
+use sqlx::PgPool;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::error::{OrderServiceError, Result};
+use crate::quotes::QuoteService;
+use crate::tiers::TierService;
+
+/// Orders expired per sweep query
+const EXPIRY_BATCH: i64 = 100;
+
+/// Statuses a refund request can close an order from
+const REFUNDABLE: [&str; 4] = ["PENDING", "ACCEPTED", "PARTIALLY_FULFILLED", "FULFILLED"];
+
+/// Statuses a split order can be fulfilled from once its last leg closes
+const FULFILLABLE: [&str; 2] = ["ACCEPTED", "PARTIALLY_FULFILLED"];
+
+/// Order settings, loaded from the environment
+#[derive(Debug, Clone)]
+pub struct OrderConfig {
+    /// How long an order waits for a provider before it expires
+    pub ttl: Duration,
+    /// How often overdue orders are expired
+    pub expiry_sweep_interval: std::time::Duration,
+}
+
+impl Default for OrderConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::hours(1),
+            expiry_sweep_interval: std::time::Duration::from_secs(60),
+        }
+    }
+}
+
+impl OrderConfig {
+    /// Load `ORDER_TTL_SECS` and `ORDER_EXPIRY_SWEEP_SECS`, falling back to
+    /// one hour and one minute
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let env_secs = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|secs| *secs > 0)
+        };
+        Self {
+            ttl: env_secs("ORDER_TTL_SECS").map(Duration::seconds).unwrap_or(defaults.ttl),
+            expiry_sweep_interval: env_secs("ORDER_EXPIRY_SWEEP_SECS")
+                .map(|secs| std::time::Duration::from_secs(secs as u64))
+                .unwrap_or(defaults.expiry_sweep_interval),
+        }
+    }
+}
+
+pub struct OrderService {
+    orders: OrderRepository,
+    proposals: ProposalRepository,
+    allocations: AllocationRepository,
+    fx_history: FxRateRepository,
+    tiers: Arc<TierService>,
+    quotes: Arc<QuoteService>,
+    pool: PgPool,
+    nats: async_nats::Client,
+    config: OrderConfig,
+}
+
+impl OrderService {
+    pub fn new(
+        pool: PgPool,
+        tiers: Arc<TierService>,
+        quotes: Arc<QuoteService>,
+        nats: async_nats::Client,
+        config: OrderConfig,
+    ) -> Self {
+        Self {
+            orders: OrderRepository::new(pool.clone()),
+            proposals: ProposalRepository::new(pool.clone()),
+            allocations: AllocationRepository::new(pool.clone()),
+            fx_history: FxRateRepository::new(pool.clone()),
+            tiers,
+            quotes,
+            pool,
+            nats,
+            config,
+        }
+    }
+
+    /// Store a pending order for a user and hand it to the router, or return
+    /// the order the user already created with `idempotency_key`
+    pub async fn create(
+        &self,
+        user_address: &str,
+        request: &CreateOrderRequest,
+        idempotency_key: Option<&str>,
+    ) -> Result<Order> {
+        validate(user_address, request)?;
+        if let Some(key) = idempotency_key {
+            validate_idempotency_key(key)?;
+            if let Some(existing) = self.find_repeat(user_address, request, key).await? {
+                return Ok(existing);
+            }
+        }
+        if let Some(quote_id) = request.quote_id {
+            // Signature is checked as the quote is loaded
+            check_quote(&self.quotes.get(quote_id).await?, request)?;
+        }
+        let tier = self.tiers.classify(&request.token, &request.currency, &request.amount)?;
+
+        let now = Utc::now();
+        let model = OrderModel {
+            id: 0,
+            order_id: new_order_id(),
+            user_address: hex_to_bytes(user_address),
+            token: hex_to_bytes(&request.token),
+            amount: request.amount.clone(),
+            refund_address: hex_to_bytes(&request.refund_address),
+            integrator_address: hex_to_bytes(&request.integrator_address),
+            integrator_fee: Vec::new(),
+            status: OrderStatus::Pending.as_str().to_string(),
+            tier: Some(tier.as_str().to_string()),
+            currency: Some(Currency::from(request.currency.as_str()).as_str()),
+            // Not escrowed on-chain yet
+            block_number: 0,
+            tx_hash: Vec::new(),
+            created_at: now,
+            expires_at: Some(now + self.config.ttl),
+            updated_at: now,
+        };
+        let Some(stored) = self
+            .orders
+            .insert(&model, idempotency_key, request.quote_id)
+            .await? else {
+            // Another request with the same key created it first
+            return self
+                .find_repeat(user_address, request, idempotency_key.unwrap_or_default())
+                .await?
+                .ok_or_else(|| OrderServiceError::Conflict("Order creation is already in progress".to_string()));
+        };
+        let order = self.to_domain(&stored).await?;
+        info!("Order {} created in tier {} for {}", order.order_id, tier.as_str(), order.user_address);
+
+        if let Some(quote_id) = request.quote_id {
+            if let Err(e) = self.fx_history.link_order(quote_id, &stored.order_id).await {
+                warn!("Failed to link FX rates of quote {} to order {}: {}", quote_id, order.order_id, e);
+            }
+        }
+
+        if let Err(e) = shared_messaging::publish_event(&self.nats, subjects::ORDER_PENDING, &order).await {
+            warn!("Failed to publish {} for order {}: {}", subjects::ORDER_PENDING, order.order_id, e);
+        }
+        Ok(order)
+    }
+
+    /// The order an earlier create with `idempotency_key` stored, provided
+    /// it was for the same terms
+    async fn find_repeat(&self, user_address: &str, request: &CreateOrderRequest, idempotency_key: &str) -> Result<Option<Order>> {
+        let Some(existing) = self
+            .orders
+            .find_by_idempotency_key(&hex_to_bytes(user_address), idempotency_key)
+            .await?
+        else {
+            return Ok(None);
+        };
+        if !same_terms(&existing, request) {
+            return Err(OrderServiceError::Conflict(
+                "Idempotency key was already used for a different order".to_string(),
+            ));
+        }
+        Ok(Some(self.to_domain(&existing).await?))
+    }
+
+    pub async fn get(&self, order_id: &str) -> Result<Order> {
+        let order = self
+            .orders
+            .find(&hex_to_bytes(order_id))
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Order {}", order_id)))?;
+        self.to_domain(&order).await
+    }
+
+    /// One page of the orders of a user and/or integrator, newest first
+    pub async fn list(
+        &self,
+        user_address: Option<&str>,
+        integrator_address: Option<&str>,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> Result<Page<Order>> {
+        if user_address.is_none() && integrator_address.is_none() {
+            return Err(OrderServiceError::InvalidRequest(
+                "user_address or integrator_address is required".to_string(),
+            ));
+        }
+        let after = cursor.map(OrderCursor::decode).transpose()?;
+        let user_address = user_address.map(hex_to_bytes);
+        let integrator_address = integrator_address.map(hex_to_bytes);
+
+        // One extra row tells whether another page follows
+        let mut models = self
+            .orders
+            .list_page(
+                user_address.as_deref(),
+                integrator_address.as_deref(),
+                after.map(|cursor| (cursor.created_at, cursor.id)),
+                limit as i64 + 1,
+            )
+            .await?;
+        let next_cursor = split_page(&mut models, limit);
+
+        let mut data = Vec::with_capacity(models.len());
+        for model in &models {
+            data.push(self.to_domain(model).await?);
+        }
+        Ok(Page { data, next_cursor })
+    }
+
+    /// Proposals made for an order, newest first
+    pub async fn list_proposals(&self, order_id: &str) -> Result<Vec<Proposal>> {
+        let order = self.get(order_id).await?;
+        let proposals = self.proposals.list_for_order(&hex_to_bytes(&order.order_id)).await?;
+        Ok(proposals.iter().map(ProposalModel::to_domain).collect())
+    }
+
+    /// FX rates recorded for an order, oldest first
+    pub async fn rate_history(&self, order_id: &str) -> Result<Vec<FxRateSnapshot>> {
+        let order = self.get(order_id).await?;
+        let records = self.fx_history.list_for_order(&hex_to_bytes(&order.order_id)).await?;
+        Ok(records.iter().map(FxRateRecordModel::to_domain).collect())
+    }
+
+    /// Expire pending orders past their deadline and publish `order.expired`
+    /// for each
+    ///
+    /// # Returns
+    /// * `Result<usize>` - Orders expired
+    pub async fn expire_overdue(&self) -> Result<usize> {
+        let mut expired = 0;
+        loop {
+            let batch = self.orders.expire_overdue(EXPIRY_BATCH).await?;
+            for order in &batch {
+                let event = order.status_changed(Some(OrderStatus::Pending));
+                info!("Order {} expired without a provider", event.order_id);
+                self.publish(subjects::ORDER_EXPIRED, &event).await;
+            }
+            expired += batch.len();
+            if batch.len() < EXPIRY_BATCH as usize {
+                return Ok(expired);
+            }
+        }
+    }
+
+    /// Close an order whose refund, or the refund of leg `leg`, was
+    /// requested, unless its escrow has to be returned on-chain first
+    ///
+    /// A leg refund that leaves every leg closed fulfils the order if the
+    /// other legs paid anything out and refunds it if none did.
+    pub async fn refund(&self, order_id: &str, leg: Option<u32>) -> Result<()> {
+        let order_key = hex_to_bytes(order_id);
+        if leg.is_some() {
+            let legs = self.allocations.list_for_order(&order_key).await?;
+            match split_status(&legs) {
+                Some(OrderStatus::Refunded) => {}
+                Some(OrderStatus::Fulfilled) => return self.fulfil_split(order_id).await,
+                _ => return Ok(()),
+            }
+        }
+        let order = self
+            .orders
+            .find(&order_key)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Order {}", order_id)))?;
+        if order.block_number != 0 {
+            return Ok(());
+        }
+
+        let Some((order, previous)) = self
+            .orders
+            .transition_status(&order_key, &REFUNDABLE, OrderStatus::Refunded.as_str())
+            .await?
+        else {
+            return Ok(());
+        };
+        let event = order.status_changed(previous.parse().ok());
+        info!("Order {} refunded from {}", event.order_id, previous);
+        self.publish(subjects::ORDER_REFUNDED, &event).await;
+        Ok(())
+    }
+
+    /// Fulfil a split order whose last open leg failed after the others
+    /// paid out
+    async fn fulfil_split(&self, order_id: &str) -> Result<()> {
+        let Some((order, previous)) = self
+            .orders
+            .transition_status(&hex_to_bytes(order_id), &FULFILLABLE, OrderStatus::Fulfilled.as_str())
+            .await?
+        else {
+            return Ok(());
+        };
+        let event = order.status_changed(previous.parse().ok());
+        info!("Split order {} fulfilled from {}", event.order_id, previous);
+        self.publish(subjects::ORDER_FULFILLED, &event).await;
+        Ok(())
+    }
+
+    /// Expire overdue orders in the background
+    pub fn spawn_expiry_sweep(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.config.expiry_sweep_interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.expire_overdue().await {
+                    warn!("Order expiry sweep failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Close orders as their refunds are requested, until the subscription
+    /// closes
+    pub async fn consume_refund_requests(self: Arc<Self>, client: async_nats::Client) -> anyhow::Result<()> {
+        let mut subscriber = client.subscribe(subjects::ORDER_REFUND_REQUESTED.to_string()).await?;
+        info!("Order Service subscribed to {}", subjects::ORDER_REFUND_REQUESTED);
+
+        while let Some(message) = subscriber.next().await {
+            match serde_json::from_slice::<OrderRefundRequestedEvent>(&message.payload) {
+                Ok(event) => {
+                    if let Err(e) = self.refund(&event.order_id, event.leg).await {
+                        error!("Failed to refund order {}: {}", event.order_id, e);
+                    }
+                }
+                Err(e) => warn!("Invalid {} payload: {}", subjects::ORDER_REFUND_REQUESTED, e),
+            }
+        }
+        Ok(())
+    }
+
+    async fn publish<T: serde::Serialize>(&self, subject: &str, event: &T) {
+        if let Err(e) = shared_messaging::publish_event(&self.nats, subject, event).await {
+            warn!("Failed to publish {}: {}", subject, e);
+        }
+    }
+
+    async fn to_domain(&self, order: &OrderModel) -> Result<Order> {
+        Ok(order.to_domain(&self.pool).await.map_err(DatabaseError::from)?)
+    }
+}
+
+fn validate(user_address: &str, request: &CreateOrderRequest) -> Result<()> {
+    for (field, address) in [
+        ("user_address", user_address),
+        ("token", &request.token),
+        ("refund_address", &request.refund_address),
+        ("integrator_address", &request.integrator_address),
+    ] {
+        if !is_valid_address(address) {
+            return Err(OrderServiceError::InvalidRequest(format!("{} is not a valid address", field)));
+        }
+    }
+    match request.amount.parse::<u128>() {
+        Ok(amount) if amount > 0 => {}
+        _ => return Err(OrderServiceError::InvalidRequest("amount must be a positive integer".to_string())),
+    }
+    if !shared_utils::validate_currency(&request.currency) {
+        return Err(OrderServiceError::InvalidRequest(format!("Unsupported currency: {}", request.currency)));
+    }
+    Ok(())
+}
+
+/// Order status the stored legs of a split order imply
+fn split_status(legs: &[OrderAllocationModel]) -> Option<OrderStatus> {
+    let legs: Vec<_> = legs.iter().map(OrderAllocationModel::to_domain).collect();
+    split_order_status(&legs)
+}
+
+/// Reject a quote an order cannot be created from
+fn check_quote(quote: &Quote, request: &CreateOrderRequest) -> Result<()> {
+    if quote.is_expired() {
+        return Err(OrderServiceError::InvalidRequest(format!("Quote {} has expired", quote.quote_id)));
+    }
+    if !quote.matches_order(request) {
+        return Err(OrderServiceError::InvalidRequest(format!(
+            "Order does not match the terms of quote {}",
+            quote.quote_id
+        )));
+    }
+    Ok(())
+}
+
+/// Longest idempotency key accepted
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+fn validate_idempotency_key(key: &str) -> Result<()> {
+    if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN || !key.bytes().all(|b| b.is_ascii_graphic()) {
+        return Err(OrderServiceError::InvalidRequest(format!(
+            "Idempotency key must be 1 to {} printable ASCII characters",
+            MAX_IDEMPOTENCY_KEY_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Whether a stored order was created from the same terms as `request`
+fn same_terms(order: &OrderModel, request: &CreateOrderRequest) -> bool {
+    order.token == hex_to_bytes(&request.token)
+        && order.amount == request.amount
+        && order.refund_address == hex_to_bytes(&request.refund_address)
+        && order.integrator_address == hex_to_bytes(&request.integrator_address)
+        && order.currency.as_deref() == Some(Currency::from(request.currency.as_str()).as_str().as_str())
+}
+
+/// Position of an order in the newest-first listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct OrderCursor {
+    created_at: DateTime<Utc>,
+    id: i32,
+}
+
+impl OrderCursor {
+    fn of(order: &OrderModel) -> Self {
+        Self { created_at: order.created_at, id: order.id }
+    }
+
+    /// `<created_at in microseconds>.<id>`, the precision Postgres stores
+    fn encode(&self) -> String {
+        format!("{}.{}", self.created_at.timestamp_micros(), self.id)
+    }
+
+    fn decode(cursor: &str) -> Result<Self> {
+        cursor
+            .split_once('.')
+            .and_then(|(micros, id)| {
+                Some(Self {
+                    created_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+                    id: id.parse().ok()?,
+                })
+            })
+            .ok_or_else(|| OrderServiceError::InvalidRequest(format!("Invalid cursor: {}", cursor)))
+    }
+}
+
+/// Trim a page fetched with one extra row to `limit`, returning the cursor
+/// of the next page if that row was there
+fn split_page(models: &mut Vec<OrderModel>, limit: usize) -> Option<String> {
+    if models.len() <= limit {
+        return None;
+    }
+    models.truncate(limit);
+    models.last().map(|model| OrderCursor::of(model).encode())
+}
+
+/// Random bytes32 identifier for an order created off-chain
+fn new_order_id() -> Vec<u8> {
+    [Uuid::new_v4().into_bytes(), Uuid::new_v4().into_bytes()].concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_validation() {
+        let user = format!("0x{}", "d".repeat(40));
+        let mut request = CreateOrderRequest {
+            token: format!("0x{}", "a".repeat(40)),
+            amount: "1000000".to_string(),
+            currency: "NGN".to_string(),
+            refund_address: format!("0x{}", "b".repeat(40)),
+            integrator_address: format!("0x{}", "c".repeat(40)),
+            quote_id: None,
+        };
+        assert!(validate(&user, &request).is_ok());
+        assert!(validate("0x1234", &request).is_err());
+
+        request.amount = "-5".to_string();
+        assert!(validate(&user, &request).is_err());
+
+        request.amount = "10".to_string();
+        request.currency = "XYZ".to_string();
+        assert!(validate(&user, &request).is_err());
+    }
+
+    fn model(id: i32, created_at: DateTime<Utc>) -> OrderModel {
+        OrderModel {
+            id,
+            order_id: new_order_id(),
+            user_address: vec![0xd; 20],
+            token: vec![0xa; 20],
+            amount: "1000000".to_string(),
+            refund_address: vec![0xb; 20],
+            integrator_address: vec![0xc; 20],
+            integrator_fee: 50i32.to_be_bytes().to_vec(),
+            status: "PENDING".to_string(),
+            tier: Some("ALPHA".to_string()),
+            currency: Some("NGN".to_string()),
+            block_number: 0,
+            tx_hash: Vec::new(),
+            created_at,
+            expires_at: None,
+            updated_at: created_at,
+        }
+    }
+
+    #[test]
+    fn test_idempotent_repeats_need_the_same_terms() {
+        let stored = model(1, Utc::now());
+        let mut request = CreateOrderRequest {
+            token: format!("0x{}", "0a".repeat(20)),
+            amount: "1000000".to_string(),
+            currency: "NGN".to_string(),
+            refund_address: format!("0x{}", "0b".repeat(20)),
+            integrator_address: format!("0x{}", "0c".repeat(20)),
+            quote_id: None,
+        };
+        assert!(same_terms(&stored, &request));
+
+        request.amount = "2000000".to_string();
+        assert!(!same_terms(&stored, &request));
+
+        assert!(validate_idempotency_key("3f0e1c2a-6b1d-4c8e-9a57-0d2c4b9e7f11").is_ok());
+        for invalid in ["", "has space", &"k".repeat(256)] {
+            assert!(validate_idempotency_key(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn test_orders_need_an_unexpired_matching_quote() {
+        let integrator = format!("0x{}", "c".repeat(40));
+        let mut quote = Quote {
+            quote_id: Uuid::new_v4(),
+            token: format!("0x{}", "a".repeat(40)),
+            amount: "1000000".to_string(),
+            currency: Currency::NGN,
+            integrator_address: Some(integrator.clone()),
+            rate: "1550".to_string(),
+            provider_fee_bps: 20,
+            integrator_fee_bps: 50,
+            protocol_fee_bps: 30,
+            fiat_amount: "1534.5".to_string(),
+            created_at: Utc::now(),
+            expires_at: Utc::now() + Duration::minutes(1),
+            signature: String::new(),
+        };
+        let mut request = CreateOrderRequest {
+            token: quote.token.clone(),
+            amount: "1000000".to_string(),
+            currency: "NGN".to_string(),
+            refund_address: format!("0x{}", "b".repeat(40)),
+            integrator_address: integrator,
+            quote_id: Some(quote.quote_id),
+        };
+        assert!(check_quote(&quote, &request).is_ok());
+
+        request.amount = "2000000".to_string();
+        assert!(check_quote(&quote, &request).is_err());
+
+        request.amount = "1000000".to_string();
+        quote.expires_at = Utc::now() - Duration::seconds(1);
+        assert!(check_quote(&quote, &request).is_err());
+    }
+
+    #[test]
+    fn test_leg_refunds_close_the_order_once_every_leg_is_closed() {
+        let leg = |leg: i32, status: &str, filled_amount: &str| OrderAllocationModel {
+            id: Uuid::new_v4(),
+            order_id: vec![0x1; 32],
+            leg,
+            provider: vec![leg as u8; 20],
+            amount: "500".to_string(),
+            filled_amount: filled_amount.to_string(),
+            status: status.to_string(),
+            tx_hash: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            settled_at: None,
+        };
+        assert_eq!(split_status(&[]), None);
+        assert_eq!(split_status(&[leg(1, "FAILED", "0"), leg(2, "PENDING", "0")]), None);
+        assert_eq!(
+            split_status(&[leg(1, "SETTLED", "500"), leg(2, "PENDING", "0")]),
+            Some(OrderStatus::PartiallyFulfilled)
+        );
+        assert_eq!(
+            split_status(&[leg(1, "FAILED", "0"), leg(2, "FAILED", "200")]),
+            Some(OrderStatus::Fulfilled)
+        );
+        assert_eq!(
+            split_status(&[leg(1, "SETTLED", "500"), leg(2, "FAILED", "0")]),
+            Some(OrderStatus::Fulfilled)
+        );
+        assert_eq!(
+            split_status(&[leg(1, "FAILED", "0"), leg(2, "FAILED", "0")]),
+            Some(OrderStatus::Refunded)
+        );
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = OrderCursor { created_at: DateTime::from_timestamp_micros(1_760_000_000_123_456).unwrap(), id: 42 };
+        assert_eq!(OrderCursor::decode(&cursor.encode()).unwrap(), cursor);
+        for invalid in ["", "42", "abc.1", "1760000000123456.x"] {
+            assert!(OrderCursor::decode(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn test_split_page() {
+        // Newest first, at the precision Postgres stores
+        let now = DateTime::from_timestamp_micros(Utc::now().timestamp_micros()).unwrap();
+        let mut models: Vec<_> = (0..3).map(|i| model(3 - i, now - Duration::seconds(i as i64))).collect();
+        assert_eq!(split_page(&mut models.clone(), 3), None);
+
+        let next = split_page(&mut models, 2).unwrap();
+        assert_eq!(models.len(), 2);
+        assert_eq!(OrderCursor::decode(&next).unwrap(), OrderCursor::of(&models[1]));
+    }
+
+    #[test]
+    fn test_order_ids_are_bytes32() {
+        let id = new_order_id();
+        assert_eq!(id.len(), 32);
+        assert_ne!(id, new_order_id());
+    }
+}
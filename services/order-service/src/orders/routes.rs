@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use shared_types::{CreateOrderRequest, FxRateSnapshot, Order, Page, Proposal};
+
+use super::OrderService;
+use crate::error::Result;
+
+/// Largest page of orders returned by the listing
+const MAX_ORDERS_PAGE: usize = 100;
+
+/// Order created on behalf of the user authenticated by the gateway
+#[derive(Debug, Deserialize)]
+pub struct CreateOrderBody {
+    pub user_address: String,
+    /// `Idempotency-Key` the caller sent, if any
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    #[serde(flatten)]
+    pub request: CreateOrderRequest,
+}
+
+/// Orders of a user and/or integrator, as forwarded by the gateway
+#[derive(Debug, Deserialize)]
+pub struct ListOrdersQuery {
+    pub user_address: Option<String>,
+    pub integrator_address: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// Order routes
+pub fn router(service: Arc<OrderService>) -> Router {
+    Router::new()
+        .route("/orders", post(create_order).get(list_orders))
+        .route("/orders/:order_id", get(get_order))
+        .route("/orders/:order_id/proposals", get(list_proposals))
+        .route("/orders/:order_id/rates", get(list_rates))
+        .with_state(service)
+}
+
+async fn create_order(
+    State(service): State<Arc<OrderService>>,
+    Json(body): Json<CreateOrderBody>,
+) -> Result<(StatusCode, Json<Order>)> {
+    let order = service
+        .create(&body.user_address, &body.request, body.idempotency_key.as_deref())
+        .await?;
+    Ok((StatusCode::CREATED, Json(order)))
+}
+
+async fn list_orders(
+    State(service): State<Arc<OrderService>>,
+    Query(query): Query<ListOrdersQuery>,
+) -> Result<Json<Page<Order>>> {
+    let limit = query.limit.unwrap_or(20).clamp(1, MAX_ORDERS_PAGE);
+    let page = service
+        .list(
+            query.user_address.as_deref(),
+            query.integrator_address.as_deref(),
+            query.cursor.as_deref(),
+            limit,
+        )
+        .await?;
+    Ok(Json(page))
+}
+
+async fn get_order(State(service): State<Arc<OrderService>>, Path(order_id): Path<String>) -> Result<Json<Order>> {
+    Ok(Json(service.get(&order_id).await?))
+}
+
+async fn list_proposals(
+    State(service): State<Arc<OrderService>>,
+    Path(order_id): Path<String>,
+) -> Result<Json<Vec<Proposal>>> {
+    Ok(Json(service.list_proposals(&order_id).await?))
+}
+
+async fn list_rates(
+    State(service): State<Arc<OrderService>>,
+    Path(order_id): Path<String>,
+) -> Result<Json<Vec<FxRateSnapshot>>> {
+    Ok(Json(service.rate_history(&order_id).await?))
+}
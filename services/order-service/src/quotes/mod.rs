@@ -0,0 +1,218 @@
+//! Pre-order payout quotes
+//!
+//! A quote prices an order against the cheapest provider able to fill it,
+//! applies provider, integrator and protocol fees along with the FX oracle
+//! rate, and is signed and stored so the order can later reference it by ID.
+//! The rate used is recorded in the FX history for audits.
+//!
+//! An order created from a quote must be placed before the quote expires,
+//! with the token, amount, currency and integrator it was priced for, and
+//! each quote backs at most one order.
+
+pub mod pricing;
+pub mod routes;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use shared_database::{
+    models::{hex_to_bytes, FxRateRecordModel, QuoteModel},
+    FxRateRepository, ProviderRepository, QuoteRepository,
+};
+use shared_fx::{FxOracle, FxPair, USD};
+use shared_types::{helpers::is_valid_address, Currency, FxRateSnapshot, Quote, QuoteRequest};
+use uuid::Uuid;
+
+use crate::error::{OrderServiceError, Result};
+
+/// Fee applied when an integrator has not configured one (matches order creation)
+const DEFAULT_INTEGRATOR_FEE_BPS: u64 = 50;
+
+/// Decimals assumed for tokens missing from `TOKEN_DECIMALS`
+const DEFAULT_TOKEN_DECIMALS: u32 = 18;
+
+/// Quote pricing configuration, loaded from the environment
+#[derive(Debug, Clone)]
+pub struct QuoteConfig {
+    /// How long a quote can be referenced after it is issued
+    pub ttl: Duration,
+    /// Protocol fee in basis points
+    pub protocol_fee_bps: u64,
+    /// Secret quotes are signed with
+    pub signing_secret: Vec<u8>,
+    /// Token decimals keyed by lowercase token address
+    pub token_decimals: HashMap<String, u32>,
+}
+
+impl QuoteConfig {
+    /// Load quote configuration; `QUOTE_SIGNING_SECRET` is required
+    pub fn from_env() -> anyhow::Result<Self> {
+        let signing_secret = shared_utils::signing::required_secret("QUOTE_SIGNING_SECRET")?;
+        let env_u64 = |key: &str| std::env::var(key).ok().and_then(|v| v.parse::<u64>().ok());
+
+        let token_decimals = std::env::var("TOKEN_DECIMALS")
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|entry| {
+                let (token, decimals) = entry.trim().split_once('=')?;
+                Some((token.trim().to_lowercase(), decimals.trim().parse().ok()?))
+            })
+            .collect();
+
+        Ok(Self {
+            ttl: Duration::from_secs(env_u64("QUOTE_TTL_SECS").unwrap_or(60)),
+            protocol_fee_bps: env_u64("PROTOCOL_FEE_BPS").unwrap_or(30),
+            signing_secret: signing_secret.into_bytes(),
+            token_decimals,
+        })
+    }
+
+    fn decimals_for(&self, token: &str) -> u32 {
+        self.token_decimals
+            .get(&token.to_lowercase())
+            .copied()
+            .unwrap_or(DEFAULT_TOKEN_DECIMALS)
+    }
+}
+
+/// Issues and loads signed quotes
+pub struct QuoteService {
+    providers: ProviderRepository,
+    quotes: QuoteRepository,
+    fx_history: FxRateRepository,
+    oracle: Arc<FxOracle>,
+    config: QuoteConfig,
+}
+
+impl QuoteService {
+    pub fn new(
+        providers: ProviderRepository,
+        quotes: QuoteRepository,
+        fx_history: FxRateRepository,
+        oracle: Arc<FxOracle>,
+        config: QuoteConfig,
+    ) -> Self {
+        Self { providers, quotes, fx_history, oracle, config }
+    }
+
+    /// Price a request and store the signed quote
+    pub async fn create(&self, request: &QuoteRequest) -> Result<Quote> {
+        if !is_valid_address(&request.token) {
+            return Err(OrderServiceError::InvalidRequest(format!("Invalid token address: {}", request.token)));
+        }
+        if let Some(integrator) = &request.integrator_address {
+            if !is_valid_address(integrator) {
+                return Err(OrderServiceError::InvalidRequest(format!("Invalid integrator address: {}", integrator)));
+            }
+        }
+        let amount = match request.amount.parse::<u128>() {
+            Ok(amount) if amount > 0 => amount,
+            _ => return Err(OrderServiceError::InvalidRequest("amount must be a positive integer".to_string())),
+        };
+        if !shared_utils::validate_currency(&request.currency) {
+            return Err(OrderServiceError::InvalidRequest(format!("Unsupported currency: {}", request.currency)));
+        }
+
+        // Supported tokens are USD stablecoins, so USD/<currency> prices them
+        let fx = self.oracle.rate(&FxPair::new(USD, &request.currency)).await?;
+        if fx.stale {
+            return Err(OrderServiceError::NoLiquidity(format!("FX rate for {} is stale", fx.pair)));
+        }
+        let rate = fx.rate;
+
+        let intents = self
+            .providers
+            .get_eligible_providers(&request.currency, &request.amount)
+            .await?;
+        let provider = pricing::select_provider(&intents, amount).ok_or_else(|| {
+            OrderServiceError::NoLiquidity(format!("No provider can fill {} for {}", request.amount, request.currency))
+        })?;
+
+        let integrator_fee_bps = match &request.integrator_address {
+            Some(integrator) => self
+                .quotes
+                .get_integrator_fee_bps(&hex_to_bytes(integrator))
+                .await?
+                .map(|fee| fee.max(0) as u64)
+                .unwrap_or(DEFAULT_INTEGRATOR_FEE_BPS),
+            // An estimate only: orders need a quote issued for their integrator
+            None => DEFAULT_INTEGRATOR_FEE_BPS,
+        };
+        let provider_fee_bps = provider.min_fee_bps as u64;
+        let total_fee_bps = provider_fee_bps + integrator_fee_bps + self.config.protocol_fee_bps;
+
+        let fiat_amount = pricing::fiat_payout(amount, self.config.decimals_for(&request.token), rate, total_fee_bps)
+            .ok_or_else(|| OrderServiceError::InvalidRequest("Amount cannot be quoted".to_string()))?;
+
+        let created_at = Utc::now();
+        let mut quote = Quote {
+            quote_id: Uuid::new_v4(),
+            token: request.token.to_lowercase(),
+            amount: request.amount.clone(),
+            currency: Currency::from(request.currency.as_str()),
+            integrator_address: request.integrator_address.as_ref().map(|a| a.to_lowercase()),
+            rate: rate.to_string(),
+            provider_fee_bps,
+            integrator_fee_bps,
+            protocol_fee_bps: self.config.protocol_fee_bps,
+            fiat_amount: fiat_amount.to_string(),
+            created_at,
+            expires_at: created_at + chrono::Duration::from_std(self.config.ttl).unwrap_or_default(),
+            signature: String::new(),
+        };
+        quote.signature = pricing::sign_quote(&self.config.signing_secret, &quote);
+
+        self.quotes
+            .create(&QuoteModel {
+                quote_id: quote.quote_id,
+                token: hex_to_bytes(&quote.token),
+                amount: quote.amount.clone(),
+                currency: request.currency.clone(),
+                integrator_address: quote.integrator_address.as_deref().map(hex_to_bytes),
+                provider: provider.provider.clone(),
+                rate: quote.rate.clone(),
+                provider_fee_bps: provider_fee_bps as i32,
+                integrator_fee_bps: integrator_fee_bps as i32,
+                protocol_fee_bps: quote.protocol_fee_bps as i32,
+                fiat_amount: quote.fiat_amount.clone(),
+                signature: quote.signature.clone(),
+                created_at: quote.created_at,
+                expires_at: quote.expires_at,
+            })
+            .await?;
+
+        self.fx_history
+            .record(&FxRateRecordModel {
+                id: 0,
+                quote_id: Some(quote.quote_id),
+                order_id: None,
+                pair: fx.pair.to_string(),
+                rate: fx.rate.to_string(),
+                sources: fx.sources.clone(),
+                rejected_count: fx.rejected as i32,
+                stale: fx.stale,
+                observed_at: fx.observed_at,
+                recorded_at: created_at,
+            })
+            .await?;
+
+        Ok(quote)
+    }
+
+    /// Load a quote, rejecting it if its terms no longer match the signature
+    pub async fn get(&self, quote_id: Uuid) -> Result<Quote> {
+        let quote = self.quotes.get(quote_id).await?.to_domain();
+        if pricing::sign_quote(&self.config.signing_secret, &quote) != quote.signature {
+            return Err(OrderServiceError::Internal(format!("Signature mismatch for quote {}", quote_id)));
+        }
+        Ok(quote)
+    }
+
+    /// FX rates recorded when pricing a quote
+    pub async fn rate_history(&self, quote_id: Uuid) -> Result<Vec<FxRateSnapshot>> {
+        let records = self.fx_history.list_for_quote(quote_id).await?;
+        Ok(records.iter().map(FxRateRecordModel::to_domain).collect())
+    }
+}
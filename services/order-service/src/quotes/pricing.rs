@@ -0,0 +1,112 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use shared_database::models::ProviderIntentModel;
+use shared_types::Quote;
+use shared_utils::signing::hmac_sha256_hex;
+
+/// Basis points in 100%
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Cheapest eligible provider with enough liquidity for the amount
+///
+/// `intents` are expected sorted by `min_fee_bps` ascending, as returned by
+/// `ProviderRepository::get_eligible_providers`. Available amounts are
+/// re-checked numerically since the database compares them as text.
+pub fn select_provider(intents: &[ProviderIntentModel], amount: u128) -> Option<&ProviderIntentModel> {
+    intents.iter().find(|intent| {
+        intent.min_fee_bps >= 0
+            && intent
+                .available_amount
+                .parse::<u128>()
+                .map(|available| available >= amount)
+                .unwrap_or(false)
+    })
+}
+
+/// Fiat received for `amount` (in smallest token units) after all fees
+///
+/// # Arguments
+/// * `amount` - Token amount in smallest unit
+/// * `decimals` - Token decimals
+/// * `rate` - Fiat per whole token
+/// * `total_fee_bps` - Provider, integrator and protocol fees combined
+///
+/// # Returns
+/// * `Option<Decimal>` - Payout rounded down to 2 decimal places, None if fees
+///   consume the whole amount or the amount cannot be represented
+pub fn fiat_payout(amount: u128, decimals: u32, rate: Decimal, total_fee_bps: u64) -> Option<Decimal> {
+    if total_fee_bps >= BPS_DENOMINATOR {
+        return None;
+    }
+
+    let tokens = Decimal::try_from_i128_with_scale(i128::try_from(amount).ok()?, decimals).ok()?;
+    let gross = tokens.checked_mul(rate)?;
+    let net = gross
+        .checked_mul(Decimal::from(BPS_DENOMINATOR - total_fee_bps))?
+        .checked_div(Decimal::from(BPS_DENOMINATOR))?;
+
+    Some(net.round_dp_with_strategy(2, RoundingStrategy::ToZero))
+}
+
+/// HMAC over every quote term, so a stored or client-held quote cannot be altered
+pub fn sign_quote(secret: &[u8], quote: &Quote) -> String {
+    let message = [
+        quote.quote_id.to_string(),
+        quote.token.to_lowercase(),
+        quote.amount.clone(),
+        quote.currency.as_str(),
+        quote.integrator_address.as_deref().unwrap_or("").to_lowercase(),
+        quote.rate.clone(),
+        quote.provider_fee_bps.to_string(),
+        quote.integrator_fee_bps.to_string(),
+        quote.protocol_fee_bps.to_string(),
+        quote.fiat_amount.clone(),
+        quote.expires_at.timestamp().to_string(),
+    ]
+    .join("|");
+    hmac_sha256_hex(secret, message.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::str::FromStr;
+
+    fn intent(available: &str, fee: i32) -> ProviderIntentModel {
+        ProviderIntentModel {
+            id: fee,
+            provider: vec![fee as u8],
+            currency: "NGN".to_string(),
+            available_amount: available.to_string(),
+            min_fee_bps: fee,
+            max_fee_bps: fee + 50,
+            commitment_window: 300,
+            is_active: true,
+            expires_at: Utc::now(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_select_provider_compares_amounts_numerically() {
+        // "900" > "1000" as text, but cannot fill a 1000 unit order
+        let intents = vec![intent("900", 10), intent("5000", 20)];
+        assert_eq!(select_provider(&intents, 1000).unwrap().min_fee_bps, 20);
+        assert!(select_provider(&intents, 10_000).is_none());
+    }
+
+    #[test]
+    fn test_fiat_payout_applies_fees_and_rounds_down() {
+        let rate = Decimal::from_str("1550.50").unwrap();
+
+        // 100 USDC (6 decimals) at 1550.50, minus 1% total fees
+        let payout = fiat_payout(100_000_000, 6, rate, 100).unwrap();
+        assert_eq!(payout, Decimal::from_str("153499.50").unwrap());
+
+        let payout = fiat_payout(1, 6, rate, 0).unwrap();
+        assert_eq!(payout, Decimal::from_str("0.00").unwrap());
+
+        assert!(fiat_payout(100_000_000, 6, rate, 10_000).is_none());
+    }
+}
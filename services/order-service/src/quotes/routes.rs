@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use shared_types::{FxRateSnapshot, Quote, QuoteRequest};
+use uuid::Uuid;
+
+use super::QuoteService;
+use crate::error::Result;
+
+/// Quote routes
+pub fn router(service: Arc<QuoteService>) -> Router {
+    Router::new()
+        .route("/quotes", post(create_quote))
+        .route("/quotes/:quote_id", get(get_quote))
+        .route("/quotes/:quote_id/rates", get(get_quote_rates))
+        .with_state(service)
+}
+
+async fn create_quote(
+    State(service): State<Arc<QuoteService>>,
+    Json(request): Json<QuoteRequest>,
+) -> Result<(StatusCode, Json<Quote>)> {
+    let quote = service.create(&request).await?;
+    Ok((StatusCode::CREATED, Json(quote)))
+}
+
+async fn get_quote(
+    State(service): State<Arc<QuoteService>>,
+    Path(quote_id): Path<Uuid>,
+) -> Result<Json<Quote>> {
+    Ok(Json(service.get(quote_id).await?))
+}
+
+/// FX rates a quote was priced with, for audits
+async fn get_quote_rates(
+    State(service): State<Arc<QuoteService>>,
+    Path(quote_id): Path<Uuid>,
+) -> Result<Json<Vec<FxRateSnapshot>>> {
+    Ok(Json(service.rate_history(quote_id).await?))
+}
@@ -0,0 +1,60 @@
+//! Per-token tier limits
+//!
+//! Limits are stored per token, optionally overridden per currency, in whole
+//! tokens. They are cached by a [`TierLimitsStore`] that reloads every
+//! `TIER_LIMITS_REFRESH_SECS`; changes made through this service are applied
+//! to its own cache immediately and reach other instances on their next
+//! reload.
+
+pub mod routes;
+
+use std::sync::Arc;
+
+use shared_database::{models::TierLimitsModel, TierLimitsStore};
+use shared_types::{helpers::is_valid_address, Currency, OrderTier, TierLimits};
+
+use crate::error::{OrderServiceError, Result};
+
+pub struct TierService {
+    store: Arc<TierLimitsStore>,
+}
+
+impl TierService {
+    pub fn new(store: Arc<TierLimitsStore>) -> Self {
+        Self { store }
+    }
+
+    /// Limits currently in effect
+    pub fn list(&self) -> Vec<TierLimits> {
+        self.store.current().list()
+    }
+
+    /// Tier an order of `amount` base units of `token` would fall in
+    pub fn classify(&self, token: &str, currency: &str, amount: &str) -> Result<OrderTier> {
+        Ok(self.store.current().classify(token, &Currency::from(currency), amount)?)
+    }
+
+    /// Validate and store a token's limits, then reload the cache
+    pub async fn upsert(&self, limits: TierLimits) -> Result<TierLimits> {
+        if !is_valid_address(&limits.token) {
+            return Err(OrderServiceError::InvalidRequest(format!("Invalid token address: {}", limits.token)));
+        }
+        if let Some(currency) = &limits.currency {
+            if !shared_utils::validate_currency(&currency.as_str()) {
+                return Err(OrderServiceError::InvalidRequest(format!(
+                    "Unsupported currency: {}",
+                    currency.as_str()
+                )));
+            }
+        }
+        limits.validate()?;
+
+        let stored = self
+            .store
+            .repository()
+            .upsert(&TierLimitsModel::from_domain(&limits))
+            .await?;
+        self.store.reload().await?;
+        Ok(stored.to_domain()?)
+    }
+}
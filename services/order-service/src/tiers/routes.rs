@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    routing::{get, put},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use shared_types::{OrderTier, TierLimits};
+
+use super::TierService;
+use crate::error::Result;
+
+/// Tier limit routes
+pub fn router(service: Arc<TierService>) -> Router {
+    Router::new()
+        .route("/tier-limits", get(list_limits))
+        .route("/tier-limits/classify", get(classify))
+        .route("/admin/tier-limits", put(upsert_limits))
+        .with_state(service)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClassifyQuery {
+    pub token: String,
+    pub currency: String,
+    /// Amount in base units
+    pub amount: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClassifyResponse {
+    pub tier: OrderTier,
+}
+
+async fn list_limits(State(service): State<Arc<TierService>>) -> Json<Vec<TierLimits>> {
+    Json(service.list())
+}
+
+async fn classify(
+    State(service): State<Arc<TierService>>,
+    Query(query): Query<ClassifyQuery>,
+) -> Result<Json<ClassifyResponse>> {
+    let tier = service.classify(&query.token, &query.currency, &query.amount)?;
+    Ok(Json(ClassifyResponse { tier }))
+}
+
+async fn upsert_limits(
+    State(service): State<Arc<TierService>>,
+    Json(limits): Json<TierLimits>,
+) -> Result<Json<TierLimits>> {
+    Ok(Json(service.upsert(limits).await?))
+}
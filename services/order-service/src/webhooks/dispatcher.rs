@@ -0,0 +1,162 @@
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use shared_utils::signing::{sign_payload, SIGNATURE_HEADER};
+use uuid::Uuid;
+
+/// Header naming the event type of a webhook payload
+pub const EVENT_HEADER: &str = "X-PayNode-Event";
+/// Header carrying the event ID, stable across retries
+pub const EVENT_ID_HEADER: &str = "X-PayNode-Event-Id";
+
+/// Result of a single HTTP delivery attempt
+#[derive(Debug, Clone)]
+pub struct DeliveryOutcome {
+    /// HTTP status returned by the endpoint, if a response was received
+    pub status_code: Option<u16>,
+    /// Transport error or non-2xx summary
+    pub error: Option<String>,
+    /// Wall-clock time spent on the attempt
+    pub duration: Duration,
+}
+
+impl DeliveryOutcome {
+    /// Only 2xx responses count as delivered
+    pub fn is_success(&self) -> bool {
+        matches!(self.status_code, Some(200..=299))
+    }
+}
+
+/// Signs and POSTs webhook payloads
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    client: reqwest::Client,
+}
+
+impl WebhookDispatcher {
+    pub fn new(timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .user_agent("PayNode-Webhooks/1.0")
+            .build()
+            .expect("webhook HTTP client configuration is valid");
+
+        Self { client }
+    }
+
+    /// Deliver a serialized payload to an endpoint
+    ///
+    /// # Arguments
+    /// * `url` - Endpoint URL
+    /// * `secret` - Endpoint signing secret
+    /// * `event_id` - Event ID sent in the `X-PayNode-Event-Id` header
+    /// * `event_type` - Event name sent in the `X-PayNode-Event` header
+    /// * `body` - Exact JSON bytes to send and sign
+    pub async fn send(
+        &self,
+        url: &str,
+        secret: &str,
+        event_id: Uuid,
+        event_type: &str,
+        body: Vec<u8>,
+    ) -> DeliveryOutcome {
+        let signature = sign_payload(secret.as_bytes(), Utc::now().timestamp(), &body);
+        let started = Instant::now();
+
+        let result = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header(EVENT_HEADER, event_type)
+            .header(EVENT_ID_HEADER, event_id.to_string())
+            .body(body)
+            .send()
+            .await;
+
+        let duration = started.elapsed();
+        match result {
+            Ok(response) => {
+                let status = response.status();
+                DeliveryOutcome {
+                    status_code: Some(status.as_u16()),
+                    error: (!status.is_success()).then(|| format!("Endpoint responded with {}", status)),
+                    duration,
+                }
+            }
+            Err(e) => DeliveryOutcome {
+                status_code: None,
+                error: Some(e.to_string()),
+                duration,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Bytes, extract::State, http::HeaderMap, http::StatusCode, routing::post, Router};
+    use shared_utils::signing::verify_payload;
+    use std::sync::{Arc, Mutex};
+
+    type Captured = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+    /// Spawns a local endpoint that records requests and answers with `status`
+    async fn spawn_stub(status: StatusCode) -> (String, Captured) {
+        let captured: Captured = Arc::default();
+        let app = Router::new()
+            .route(
+                "/hook",
+                post(|State((captured, status)): State<(Captured, StatusCode)>, headers: HeaderMap, body: Bytes| async move {
+                    captured.lock().unwrap().push((headers, body));
+                    status
+                }),
+            )
+            .with_state((captured.clone(), status));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (format!("http://{}/hook", addr), captured)
+    }
+
+    #[tokio::test]
+    async fn test_delivery_is_signed() {
+        let (url, captured) = spawn_stub(StatusCode::OK).await;
+        let dispatcher = WebhookDispatcher::new(Duration::from_secs(5));
+        let event_id = Uuid::new_v4();
+
+        let outcome = dispatcher
+            .send(&url, "whsec_test", event_id, "order.fulfilled", br#"{"id":1}"#.to_vec())
+            .await;
+        assert!(outcome.is_success());
+        assert_eq!(outcome.error, None);
+
+        let requests = captured.lock().unwrap();
+        let (headers, body) = &requests[0];
+        assert_eq!(headers[EVENT_HEADER], "order.fulfilled");
+        assert_eq!(headers[EVENT_ID_HEADER], event_id.to_string().as_str());
+
+        let signature = headers[SIGNATURE_HEADER].to_str().unwrap();
+        assert!(verify_payload(b"whsec_test", signature, body, Utc::now().timestamp(), 60).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_non_2xx_and_unreachable_are_failures() {
+        let (url, _) = spawn_stub(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let dispatcher = WebhookDispatcher::new(Duration::from_secs(5));
+
+        let outcome = dispatcher.send(&url, "s", Uuid::new_v4(), "order.created", b"{}".to_vec()).await;
+        assert!(!outcome.is_success());
+        assert_eq!(outcome.status_code, Some(500));
+
+        let outcome = dispatcher
+            .send("http://127.0.0.1:1/hook", "s", Uuid::new_v4(), "order.created", b"{}".to_vec())
+            .await;
+        assert!(!outcome.is_success());
+        assert_eq!(outcome.status_code, None);
+        assert!(outcome.error.is_some());
+    }
+}
@@ -0,0 +1,77 @@
+//! Outbound webhook delivery to integrators
+//!
+//! Order status events arriving on NATS are fanned out to every subscribed
+//! endpoint, signed with the endpoint secret and retried with exponential
+//! backoff. Every attempt is recorded; endpoints that keep failing are disabled.
+
+pub mod dispatcher;
+pub mod routes;
+pub mod worker;
+
+use std::time::Duration;
+
+use shared_utils::retry::Backoff;
+
+pub use dispatcher::{DeliveryOutcome, WebhookDispatcher};
+pub use worker::WebhookWorker;
+
+/// Webhook delivery tuning, loaded from the environment
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    /// Attempts per delivery before it is marked exhausted
+    pub max_attempts: u32,
+    /// Consecutive failed attempts before an endpoint is disabled
+    pub disable_threshold: u32,
+    /// Per-request HTTP timeout
+    pub request_timeout: Duration,
+    /// Retry schedule between attempts
+    pub backoff: Backoff,
+    /// How often the worker polls for due deliveries
+    pub poll_interval: Duration,
+    /// Maximum deliveries claimed per poll
+    pub batch_size: i64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 8,
+            disable_threshold: 20,
+            request_timeout: Duration::from_secs(10),
+            backoff: Backoff::new(Duration::from_secs(30), Duration::from_secs(3600), 2),
+            poll_interval: Duration::from_secs(2),
+            batch_size: 50,
+        }
+    }
+}
+
+impl WebhookConfig {
+    /// Load webhook configuration, falling back to defaults for unset variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let env_u64 = |key: &str| std::env::var(key).ok().and_then(|v| v.parse::<u64>().ok());
+
+        Self {
+            max_attempts: env_u64("WEBHOOK_MAX_ATTEMPTS")
+                .map(|v| v as u32)
+                .unwrap_or(defaults.max_attempts),
+            disable_threshold: env_u64("WEBHOOK_DISABLE_THRESHOLD")
+                .map(|v| v as u32)
+                .unwrap_or(defaults.disable_threshold),
+            request_timeout: env_u64("WEBHOOK_TIMEOUT_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.request_timeout),
+            backoff: Backoff::new(
+                env_u64("WEBHOOK_BACKOFF_INITIAL_SECS")
+                    .map(Duration::from_secs)
+                    .unwrap_or(defaults.backoff.initial),
+                env_u64("WEBHOOK_BACKOFF_MAX_SECS")
+                    .map(Duration::from_secs)
+                    .unwrap_or(defaults.backoff.max),
+                defaults.backoff.multiplier,
+            ),
+            poll_interval: defaults.poll_interval,
+            batch_size: defaults.batch_size,
+        }
+    }
+}
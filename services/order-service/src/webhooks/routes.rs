@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use shared_database::{
+    models::{hex_to_bytes, WebhookEndpointModel},
+    DatabaseError, WebhookRepository,
+};
+use shared_types::{
+    helpers::is_valid_address, RegisterWebhookRequest, WebhookDeliveryAttempt, WebhookEndpoint,
+    WebhookRegistration,
+};
+use uuid::Uuid;
+
+use crate::error::{OrderServiceError, Result};
+
+/// Maximum number of attempts returned by the attempts listing
+const MAX_ATTEMPTS_PAGE: i64 = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct ListAttemptsQuery {
+    pub limit: Option<i64>,
+}
+
+/// Webhook management routes, scoped to the integrator in the path
+///
+/// Only the api-gateway calls these, with the integrator taken from the
+/// caller's token. Endpoints of other integrators read as not found.
+pub fn router(repo: Arc<WebhookRepository>) -> Router {
+    Router::new()
+        .route("/integrators/:integrator/webhooks", post(register_webhook).get(list_webhooks))
+        .route("/integrators/:integrator/webhooks/:id/enable", post(enable_webhook))
+        .route("/integrators/:integrator/webhooks/:id/attempts", get(list_attempts))
+        .route(
+            "/integrators/:integrator/webhooks/:id/deliveries/:delivery_id/redeliver",
+            post(redeliver),
+        )
+        .with_state(repo)
+}
+
+fn integrator_key(integrator: &str) -> Result<Vec<u8>> {
+    if !is_valid_address(integrator) {
+        return Err(OrderServiceError::InvalidRequest(format!("Invalid integrator address: {}", integrator)));
+    }
+    Ok(hex_to_bytes(integrator))
+}
+
+/// An endpoint, if `integrator` registered it
+async fn owned_endpoint(repo: &WebhookRepository, integrator: &str, id: Uuid) -> Result<WebhookEndpointModel> {
+    let integrator = integrator_key(integrator)?;
+    let endpoint = repo.get_endpoint(id).await?;
+    if endpoint.integrator_address != integrator {
+        return Err(DatabaseError::NotFound(format!("webhook endpoint {}", id)).into());
+    }
+    Ok(endpoint)
+}
+
+async fn register_webhook(
+    State(repo): State<Arc<WebhookRepository>>,
+    Path(integrator): Path<String>,
+    Json(request): Json<RegisterWebhookRequest>,
+) -> Result<(StatusCode, Json<WebhookRegistration>)> {
+    let integrator = integrator_key(&integrator)?;
+    let event_types = request.parsed_event_types()?;
+
+    let now = chrono::Utc::now();
+    let model = WebhookEndpointModel {
+        id: Uuid::new_v4(),
+        integrator_address: integrator,
+        url: request.url,
+        secret: generate_secret(),
+        event_types: event_types.iter().map(|e| e.as_str().to_string()).collect(),
+        is_active: true,
+        consecutive_failures: 0,
+        disabled_at: None,
+        created_at: now,
+        updated_at: now,
+    };
+    repo.create_endpoint(&model).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(WebhookRegistration {
+            endpoint: model.to_domain(),
+            secret: model.secret,
+        }),
+    ))
+}
+
+async fn list_webhooks(
+    State(repo): State<Arc<WebhookRepository>>,
+    Path(integrator): Path<String>,
+) -> Result<Json<Vec<WebhookEndpoint>>> {
+    let endpoints = repo.list_endpoints(&integrator_key(&integrator)?).await?;
+    Ok(Json(endpoints.iter().map(WebhookEndpointModel::to_domain).collect()))
+}
+
+async fn enable_webhook(
+    State(repo): State<Arc<WebhookRepository>>,
+    Path((integrator, id)): Path<(String, Uuid)>,
+) -> Result<StatusCode> {
+    owned_endpoint(&repo, &integrator, id).await?;
+    repo.set_endpoint_active(id, true).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_attempts(
+    State(repo): State<Arc<WebhookRepository>>,
+    Path((integrator, id)): Path<(String, Uuid)>,
+    Query(query): Query<ListAttemptsQuery>,
+) -> Result<Json<Vec<WebhookDeliveryAttempt>>> {
+    owned_endpoint(&repo, &integrator, id).await?;
+    let limit = query.limit.unwrap_or(50).clamp(1, MAX_ATTEMPTS_PAGE);
+    let attempts = repo.list_attempts(id, limit).await?;
+    Ok(Json(attempts.iter().map(|a| a.to_domain()).collect()))
+}
+
+/// Manually queue a fresh delivery of an event, with a full retry budget
+async fn redeliver(
+    State(repo): State<Arc<WebhookRepository>>,
+    Path((integrator, id, delivery_id)): Path<(String, Uuid, i64)>,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    let endpoint = owned_endpoint(&repo, &integrator, id).await?;
+    if !endpoint.is_active {
+        return Err(OrderServiceError::InvalidRequest(
+            "Endpoint is disabled; enable it before redelivering".to_string(),
+        ));
+    }
+
+    let new_delivery_id = repo.redeliver(id, delivery_id).await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({ "delivery_id": new_delivery_id })),
+    ))
+}
+
+/// Random signing secret shown to the integrator once at registration
+fn generate_secret() -> String {
+    format!("whsec_{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
@@ -0,0 +1,287 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use async_nats::Message;
+use futures::{Stream, StreamExt};
+use shared_database::{
+    models::{hex_to_bytes, DueWebhookDelivery, WebhookEventModel},
+    WebhookRepository,
+};
+use shared_messaging::subjects;
+use shared_types::{OrderStatusChangedEvent, WebhookEventType, WebhookPayload};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use super::{DeliveryOutcome, WebhookConfig, WebhookDispatcher};
+use crate::error::Result;
+
+/// What happens to a delivery after an attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttemptResolution {
+    Succeeded,
+    Retry { at: DateTime<Utc> },
+    Exhausted,
+}
+
+impl AttemptResolution {
+    /// Delivery status stored in `webhook_deliveries.status`
+    pub fn status(&self) -> &'static str {
+        match self {
+            AttemptResolution::Succeeded => "SUCCEEDED",
+            AttemptResolution::Retry { .. } => "PENDING",
+            AttemptResolution::Exhausted => "EXHAUSTED",
+        }
+    }
+}
+
+/// Decides the next step for a delivery given the outcome of attempt number `attempt` (1-based)
+pub fn resolve_attempt(
+    outcome: &DeliveryOutcome,
+    attempt: u32,
+    config: &WebhookConfig,
+    now: DateTime<Utc>,
+) -> AttemptResolution {
+    if outcome.is_success() {
+        AttemptResolution::Succeeded
+    } else if attempt >= config.max_attempts {
+        AttemptResolution::Exhausted
+    } else {
+        let delay = chrono::Duration::from_std(config.backoff.delay_for(attempt))
+            .unwrap_or_else(|_| chrono::Duration::hours(1));
+        AttemptResolution::Retry { at: now + delay }
+    }
+}
+
+/// Status changes carried by `order.*` messages, including the new orders
+/// published on `order.pending`
+pub fn status_changes(messages: impl Stream<Item = Message>) -> impl Stream<Item = OrderStatusChangedEvent> {
+    // Not every order.* subject carries a status transition
+    messages.filter_map(|message| async move { OrderStatusChangedEvent::from_message(&message.payload) })
+}
+
+/// Fans order events out to subscribed endpoints and delivers them
+pub struct WebhookWorker {
+    repo: Arc<WebhookRepository>,
+    dispatcher: WebhookDispatcher,
+    config: WebhookConfig,
+}
+
+impl WebhookWorker {
+    pub fn new(repo: Arc<WebhookRepository>, config: WebhookConfig) -> Self {
+        Self {
+            repo,
+            dispatcher: WebhookDispatcher::new(config.request_timeout),
+            config,
+        }
+    }
+
+    /// Record an order status change and queue deliveries for every subscribed endpoint
+    pub async fn enqueue(&self, event: &OrderStatusChangedEvent) -> Result<()> {
+        let event_type = WebhookEventType::from_status(event.status);
+        let integrator = hex_to_bytes(&event.integrator_address);
+
+        let endpoints = self
+            .repo
+            .get_subscribed_endpoints(&integrator, event_type.as_str())
+            .await?;
+        if endpoints.is_empty() {
+            return Ok(());
+        }
+
+        let payload = WebhookPayload {
+            id: Uuid::new_v4(),
+            event_type,
+            created_at: Utc::now(),
+            data: event.clone(),
+        };
+        let model = WebhookEventModel {
+            id: payload.id,
+            integrator_address: integrator,
+            order_id: hex_to_bytes(&event.order_id),
+            event_type: event_type.as_str().to_string(),
+            payload: serde_json::to_string(&payload).map_err(shared_types::TypesError::from)?,
+            created_at: payload.created_at,
+        };
+        let endpoint_ids: Vec<Uuid> = endpoints.iter().map(|e| e.id).collect();
+
+        self.repo.create_event_with_deliveries(&model, &endpoint_ids).await?;
+        info!(
+            "Queued {} webhook deliveries for order {} ({})",
+            endpoint_ids.len(),
+            event.order_id,
+            event_type.as_str()
+        );
+        Ok(())
+    }
+
+    /// Subscribe to every order lifecycle subject and enqueue webhook events
+    pub async fn consume_order_events(self: Arc<Self>, client: async_nats::Client) -> anyhow::Result<()> {
+        let subscriber = client.subscribe(subjects::ORDER_ALL.to_string()).await?;
+        info!("Webhook worker subscribed to {}", subjects::ORDER_ALL);
+
+        let mut events = std::pin::pin!(status_changes(subscriber));
+        while let Some(event) = events.next().await {
+            if let Err(e) = self.enqueue(&event).await {
+                error!("Failed to enqueue webhooks for order {}: {}", event.order_id, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Poll for due deliveries until the process exits
+    pub async fn run_deliveries(self: Arc<Self>) {
+        let lease_seconds = self.config.request_timeout.as_secs() as i64 * 3;
+        let mut interval = tokio::time::interval(self.config.poll_interval);
+
+        loop {
+            interval.tick().await;
+
+            let due = match self.repo.claim_due_deliveries(self.config.batch_size, lease_seconds).await {
+                Ok(due) => due,
+                Err(e) => {
+                    error!("Failed to claim webhook deliveries: {}", e);
+                    continue;
+                }
+            };
+
+            let deliveries = due.into_iter().map(|delivery| {
+                let worker = self.clone();
+                async move {
+                    let delivery_id = delivery.delivery_id;
+                    if let Err(e) = worker.process(delivery).await {
+                        error!("Failed to process webhook delivery {}: {}", delivery_id, e);
+                    }
+                }
+            });
+            futures::future::join_all(deliveries).await;
+        }
+    }
+
+    /// Perform one attempt for a claimed delivery and persist the result
+    async fn process(&self, delivery: DueWebhookDelivery) -> Result<()> {
+        let attempt = delivery.attempts.max(0) as u32 + 1;
+        let body = delivery.payload.into_bytes();
+
+        let outcome = self
+            .dispatcher
+            .send(&delivery.url, &delivery.secret, delivery.event_id, &delivery.event_type, body)
+            .await;
+        let resolution = resolve_attempt(&outcome, attempt, &self.config, Utc::now());
+        let status_code = outcome.status_code.map(i32::from);
+
+        self.repo
+            .record_attempt(
+                delivery.delivery_id,
+                attempt as i32,
+                status_code,
+                outcome.error.as_deref(),
+                outcome.is_success(),
+                outcome.duration.as_millis() as i64,
+            )
+            .await?;
+
+        let next_attempt_at = match resolution {
+            AttemptResolution::Retry { at } => at,
+            _ => Utc::now(),
+        };
+        self.repo
+            .update_delivery(
+                delivery.delivery_id,
+                resolution.status(),
+                attempt as i32,
+                next_attempt_at,
+                status_code,
+                outcome.error.as_deref(),
+            )
+            .await?;
+
+        if outcome.is_success() {
+            self.repo.reset_endpoint_failures(delivery.endpoint_id).await?;
+        } else if self
+            .repo
+            .record_endpoint_failure(delivery.endpoint_id, self.config.disable_threshold as i32)
+            .await?
+        {
+            warn!(
+                "Disabled webhook endpoint {} after {} consecutive failures",
+                delivery.endpoint_id, self.config.disable_threshold
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_types::{Currency, Order, OrderTier};
+    use std::time::Duration;
+
+    fn outcome(status_code: Option<u16>) -> DeliveryOutcome {
+        DeliveryOutcome {
+            status_code,
+            error: None,
+            duration: Duration::from_millis(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_new_orders_queue_order_created() {
+        // What `OrderService::create` publishes on order.pending
+        let order = Order::new(
+            "0xorder".to_string(),
+            "0xuser".to_string(),
+            "0xtoken".to_string(),
+            "1000000".to_string(),
+            "0xrefund".to_string(),
+            "0xintegrator".to_string(),
+            50,
+            Currency::NGN,
+            OrderTier::Alpha,
+            Utc::now() + chrono::Duration::hours(1),
+            0,
+            String::new(),
+        );
+        let message = |subject: &str, payload: Vec<u8>| Message {
+            subject: subject.into(),
+            reply: None,
+            length: payload.len(),
+            payload: payload.into(),
+            headers: None,
+            status: None,
+            description: None,
+        };
+        let messages = futures::stream::iter(vec![
+            message(subjects::ORDER_PENDING, serde_json::to_vec(&order).unwrap()),
+            message(subjects::ORDER_FAILED, br#"{"order_id":"0xorder","provider":"0xp"}"#.to_vec()),
+        ]);
+
+        let events: Vec<_> = status_changes(messages).collect().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].order_id, "0xorder");
+        assert_eq!(events[0].integrator_address, "0xintegrator");
+        assert_eq!(WebhookEventType::from_status(events[0].status), WebhookEventType::OrderCreated);
+    }
+
+    #[test]
+    fn test_attempt_resolution() {
+        let config = WebhookConfig::default();
+        let now = Utc::now();
+
+        assert_eq!(resolve_attempt(&outcome(Some(204)), 3, &config, now), AttemptResolution::Succeeded);
+        assert_eq!(
+            resolve_attempt(&outcome(Some(500)), 1, &config, now),
+            AttemptResolution::Retry { at: now + chrono::Duration::seconds(30) }
+        );
+        assert_eq!(
+            resolve_attempt(&outcome(None), 3, &config, now),
+            AttemptResolution::Retry { at: now + chrono::Duration::seconds(120) }
+        );
+        assert_eq!(
+            resolve_attempt(&outcome(Some(410)), config.max_attempts, &config, now),
+            AttemptResolution::Exhausted
+        );
+    }
+}
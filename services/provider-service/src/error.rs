@@ -0,0 +1,79 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use shared_database::DatabaseError;
+use shared_fx::FxError;
+use shared_types::TypesError;
+use thiserror::Error;
+
+use crate::payouts::PayoutError;
+
+#[derive(Error, Debug)]
+pub enum ProviderServiceError {
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error(transparent)]
+    Types(#[from] TypesError),
+
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+
+    #[error(transparent)]
+    Payout(#[from] PayoutError),
+
+    #[error(transparent)]
+    Fx(#[from] FxError),
+
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, ProviderServiceError>;
+
+impl IntoResponse for ProviderServiceError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            ProviderServiceError::InvalidRequest(_) | ProviderServiceError::Types(_) => StatusCode::BAD_REQUEST,
+            ProviderServiceError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ProviderServiceError::Conflict(_) => StatusCode::CONFLICT,
+            ProviderServiceError::Payout(e) => match e {
+                PayoutError::Unsupported(_)
+                | PayoutError::InvalidRecipient(_)
+                | PayoutError::InvalidAmount(_)
+                | PayoutError::InvalidWebhook(_) => StatusCode::BAD_REQUEST,
+                PayoutError::InvalidSignature(_) => StatusCode::UNAUTHORIZED,
+                PayoutError::NotFound(_) => StatusCode::NOT_FOUND,
+                PayoutError::Rejected { .. } | PayoutError::NameMismatch { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+                PayoutError::Unavailable { .. } => StatusCode::BAD_GATEWAY,
+            },
+            ProviderServiceError::NotFound(_) | ProviderServiceError::Database(DatabaseError::NotFound(_)) => {
+                StatusCode::NOT_FOUND
+            }
+            ProviderServiceError::Database(_)
+            | ProviderServiceError::Fx(_)
+            | ProviderServiceError::Redis(_)
+            | ProviderServiceError::Serialization(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        if status.is_server_error() {
+            tracing::error!("Request failed: {}", self);
+        }
+
+        (status, Json(serde_json::json!({ "error": self.to_string() }))).into_response()
+    }
+}
@@ -0,0 +1,298 @@
+//! Provider heartbeats and health
+//!
+//! Providers send a [`ProviderHeartbeat`] every interval, over HTTP or on the
+//! `provider.heartbeat` NATS subject, signed like outbound webhooks: an
+//! `X-PayNode-Signature` of `t=<unix>,v1=<hmac>` over `"{t}.{body}"`. Each
+//! provider signs with its own key, derived from the service secret so no
+//! per-provider secrets need storing.
+//!
+//! Heartbeats are kept in Redis for a rolling window and summarized into a
+//! [`ProviderHealth`] that the router reads as scoring features. Providers
+//! that report offline, or go quiet for longer than the timeout, have their
+//! live intents paused; the intents resume on their next healthy heartbeat.
+
+pub mod routes;
+pub mod store;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use shared_messaging::subjects;
+use shared_types::{helpers::is_valid_address, HeartbeatSample, ProviderHealth, ProviderHeartbeat};
+use shared_utils::signing::{hmac_sha256_hex, required_secret, verify_payload, SIGNATURE_HEADER};
+use tracing::{info, warn};
+
+use crate::error::{ProviderServiceError, Result};
+use crate::intents::IntentService;
+use store::HealthStore;
+
+/// Heartbeat settings, loaded from the environment
+#[derive(Debug, Clone)]
+pub struct HealthConfig {
+    /// Service secret provider heartbeat keys are derived from
+    pub secret: String,
+    /// How often providers are expected to send a heartbeat
+    pub interval: Duration,
+    /// Silence after which a provider is considered offline
+    pub timeout: Duration,
+    /// Span of heartbeats uptime and latency are computed over
+    pub window: Duration,
+    /// Maximum age (or clock skew) of a heartbeat signature
+    pub signature_tolerance: Duration,
+}
+
+impl HealthConfig {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self {
+            secret: secret.into(),
+            interval: Duration::from_secs(30),
+            timeout: Duration::from_secs(120),
+            window: Duration::from_secs(3600),
+            signature_tolerance: Duration::from_secs(300),
+        }
+    }
+
+    /// Load from the environment; `PROVIDER_HEARTBEAT_SECRET` is required,
+    /// `PROVIDER_HEARTBEAT_{INTERVAL,TIMEOUT,TOLERANCE}_SECS` and
+    /// `PROVIDER_HEALTH_WINDOW_SECS` fall back to defaults
+    pub fn from_env() -> anyhow::Result<Self> {
+        let secret = required_secret("PROVIDER_HEARTBEAT_SECRET")?;
+        let defaults = Self::new(secret);
+        let env_secs = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs)
+        };
+
+        let config = Self {
+            interval: env_secs("PROVIDER_HEARTBEAT_INTERVAL_SECS").unwrap_or(defaults.interval),
+            timeout: env_secs("PROVIDER_HEARTBEAT_TIMEOUT_SECS").unwrap_or(defaults.timeout),
+            window: env_secs("PROVIDER_HEALTH_WINDOW_SECS").unwrap_or(defaults.window),
+            signature_tolerance: env_secs("PROVIDER_HEARTBEAT_TOLERANCE_SECS").unwrap_or(defaults.signature_tolerance),
+            ..defaults
+        };
+        if config.timeout < config.interval || config.window < config.timeout {
+            anyhow::bail!("Heartbeat settings must satisfy interval <= timeout <= window");
+        }
+        Ok(config)
+    }
+
+    /// Key a provider signs its heartbeats with
+    pub fn heartbeat_key(&self, provider: &str) -> String {
+        hmac_sha256_hex(self.secret.as_bytes(), provider.to_lowercase().as_bytes())
+    }
+
+    /// Check a heartbeat's signature and parse it
+    ///
+    /// # Arguments
+    /// * `signature` - Value of the `X-PayNode-Signature` header
+    /// * `body` - Raw heartbeat exactly as received
+    /// * `now` - Current unix time in seconds
+    pub fn verify(&self, signature: Option<&str>, body: &[u8], now: i64) -> Result<ProviderHeartbeat> {
+        let heartbeat: ProviderHeartbeat = serde_json::from_slice(body)
+            .map_err(|e| ProviderServiceError::InvalidRequest(format!("Invalid heartbeat: {}", e)))?;
+        if !is_valid_address(&heartbeat.provider) {
+            return Err(ProviderServiceError::InvalidRequest(format!(
+                "Invalid provider address: {}",
+                heartbeat.provider
+            )));
+        }
+
+        let signature =
+            signature.ok_or_else(|| ProviderServiceError::Unauthorized(format!("Missing {} header", SIGNATURE_HEADER)))?;
+        let key = self.heartbeat_key(&heartbeat.provider);
+        verify_payload(key.as_bytes(), signature, body, now, self.signature_tolerance.as_secs() as i64)
+            .map_err(|e| ProviderServiceError::Unauthorized(e.to_string()))?;
+        Ok(heartbeat)
+    }
+
+    fn summarize(&self, provider: &str, samples: &[HeartbeatSample], now: DateTime<Utc>) -> ProviderHealth {
+        let duration = |d: Duration| chrono::Duration::from_std(d).unwrap_or(chrono::Duration::MAX);
+        ProviderHealth::summarize(
+            provider,
+            samples,
+            now,
+            duration(self.window),
+            duration(self.interval),
+            duration(self.timeout),
+        )
+    }
+}
+
+/// Records heartbeats and pauses the intents of providers that go down
+pub struct HealthMonitor {
+    store: HealthStore,
+    intents: Arc<IntentService>,
+    config: HealthConfig,
+}
+
+impl HealthMonitor {
+    pub fn new(store: HealthStore, intents: Arc<IntentService>, config: HealthConfig) -> Self {
+        Self { store, intents, config }
+    }
+
+    pub fn config(&self) -> &HealthConfig {
+        &self.config
+    }
+
+    /// Verify and record a signed heartbeat
+    ///
+    /// # Arguments
+    /// * `provider` - Provider the heartbeat must come from, when known from
+    ///   the transport (the HTTP path)
+    /// * `signature` - Value of the `X-PayNode-Signature` header
+    /// * `body` - Raw heartbeat exactly as received
+    ///
+    /// # Returns
+    /// * `Result<ProviderHealth>` - The provider's health including this
+    ///   heartbeat
+    pub async fn record(&self, provider: Option<&str>, signature: Option<&str>, body: &[u8]) -> Result<ProviderHealth> {
+        let now = Utc::now();
+        let heartbeat = self.config.verify(signature, body, now.timestamp())?;
+        let address = heartbeat.provider.to_lowercase();
+        if provider.is_some_and(|p| !p.eq_ignore_ascii_case(&address)) {
+            return Err(ProviderServiceError::InvalidRequest(
+                "Heartbeat provider does not match the path".to_string(),
+            ));
+        }
+
+        let sample = HeartbeatSample {
+            received_at: now,
+            status: heartbeat.status,
+            latency_ms: heartbeat.latency_ms,
+        };
+        self.store.push(&address, &sample, self.config.window).await?;
+        let health = self.refresh(&address, now).await?;
+
+        if heartbeat.status.is_up() {
+            self.resume(&address).await?;
+        } else {
+            self.suspend(&address, "reported offline").await?;
+        }
+        Ok(health)
+    }
+
+    /// Latest health of a provider
+    pub async fn health(&self, provider: &str) -> Result<ProviderHealth> {
+        self.store
+            .health(provider)
+            .await?
+            .ok_or_else(|| ProviderServiceError::NotFound(format!("No heartbeats from provider {}", provider)))
+    }
+
+    /// Recompute every tracked provider's health and pause the intents of
+    /// those that stopped sending heartbeats
+    pub async fn sweep(&self) -> Result<()> {
+        let now = Utc::now();
+        for provider in self.store.providers().await? {
+            let health = self.refresh(&provider, now).await?;
+            if health.status.is_up() {
+                continue;
+            }
+            self.suspend(&provider, "heartbeats stopped").await?;
+            if health.heartbeats == 0 {
+                self.store.forget(&provider).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sweep every heartbeat interval until the process exits
+    pub fn spawn_sweeper(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.config.interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.sweep().await {
+                    warn!("Provider health sweep failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Record heartbeats published on NATS
+    pub async fn run(&self, nats: async_nats::Client) -> anyhow::Result<()> {
+        let mut subscriber = nats.subscribe(subjects::PROVIDER_HEARTBEAT.to_string()).await?;
+        info!("Listening for heartbeats on {}", subjects::PROVIDER_HEARTBEAT);
+
+        while let Some(message) = subscriber.next().await {
+            let signature = message
+                .headers
+                .as_ref()
+                .and_then(|headers| headers.get(SIGNATURE_HEADER))
+                .map(|value| value.as_ref());
+            if let Err(e) = self.record(None, signature, &message.payload).await {
+                warn!("Rejected heartbeat: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn refresh(&self, provider: &str, now: DateTime<Utc>) -> Result<ProviderHealth> {
+        let since = now - chrono::Duration::from_std(self.config.window).unwrap_or(chrono::Duration::MAX);
+        let samples = self.store.samples(provider, since).await?;
+        let health = self.config.summarize(provider, &samples, now);
+        self.store.put_health(&health).await?;
+        Ok(health)
+    }
+
+    /// Pause a provider's live intents, remembering which so they can be
+    /// resumed
+    async fn suspend(&self, provider: &str, reason: &str) -> Result<()> {
+        for intent in self.intents.list_active(provider).await? {
+            let currency = intent.currency.as_str();
+            self.intents.set_active(provider, &currency, false).await?;
+            self.store.mark_suspended(provider, &currency).await?;
+            warn!("Paused {} intent of provider {}: {}", currency, provider, reason);
+        }
+        Ok(())
+    }
+
+    /// Resume the intents paused by [`Self::suspend`]
+    async fn resume(&self, provider: &str) -> Result<()> {
+        for currency in self.store.take_suspended(provider).await? {
+            match self.intents.set_active(provider, &currency, true).await {
+                Ok(_) => info!("Resumed {} intent of provider {}", currency, provider),
+                Err(ProviderServiceError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_utils::signing::sign_payload;
+
+    #[test]
+    fn test_heartbeat_signatures() {
+        let config = HealthConfig::new("heartbeat-secret");
+        let provider = "0x742d35cc6634c0532925a3b844bc9e7595f0beb0";
+        let body = format!(r#"{{"providerId":"{}","status":"online","balance":"120000","latency_ms":90}}"#, provider);
+        let now = 1_700_000_000;
+
+        // Keys do not depend on address case
+        let key = config.heartbeat_key(&format!("0x{}", provider[2..].to_uppercase()));
+        let signature = sign_payload(key.as_bytes(), now, body.as_bytes());
+        let heartbeat = config.verify(Some(&signature), body.as_bytes(), now + 5).unwrap();
+        assert_eq!(heartbeat.provider, provider);
+        assert_eq!(heartbeat.latency_ms, 90);
+
+        // Another provider's key, a stale signature or none at all
+        let other_key = config.heartbeat_key("0x0000000000000000000000000000000000000001");
+        let other = sign_payload(other_key.as_bytes(), now, body.as_bytes());
+        assert!(matches!(config.verify(Some(&other), body.as_bytes(), now), Err(ProviderServiceError::Unauthorized(_))));
+        assert!(matches!(
+            config.verify(Some(&signature), body.as_bytes(), now + 3600),
+            Err(ProviderServiceError::Unauthorized(_))
+        ));
+        assert!(matches!(config.verify(None, body.as_bytes(), now), Err(ProviderServiceError::Unauthorized(_))));
+        assert!(matches!(config.verify(Some(&signature), b"{}", now), Err(ProviderServiceError::InvalidRequest(_))));
+    }
+}
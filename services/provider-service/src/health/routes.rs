@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::HeaderMap,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Serialize;
+use shared_types::ProviderHealth;
+use shared_utils::signing::SIGNATURE_HEADER;
+
+use super::HealthMonitor;
+use crate::error::Result;
+
+/// Key a provider signs its heartbeats with
+#[derive(Debug, Serialize)]
+pub struct HeartbeatKey {
+    pub provider: String,
+    pub key: String,
+}
+
+/// Heartbeat and health routes
+pub fn router(monitor: Arc<HealthMonitor>) -> Router {
+    Router::new()
+        .route("/providers/:provider/heartbeats", post(record_heartbeat))
+        .route("/providers/:provider/health", get(get_health))
+        .route("/providers/:provider/heartbeat-key", get(get_heartbeat_key))
+        .with_state(monitor)
+}
+
+async fn record_heartbeat(
+    State(monitor): State<Arc<HealthMonitor>>,
+    Path(provider): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<ProviderHealth>> {
+    let signature = headers.get(SIGNATURE_HEADER).and_then(|value| value.to_str().ok());
+    Ok(Json(monitor.record(Some(&provider), signature, &body).await?))
+}
+
+async fn get_health(
+    State(monitor): State<Arc<HealthMonitor>>,
+    Path(provider): Path<String>,
+) -> Result<Json<ProviderHealth>> {
+    Ok(Json(monitor.health(&provider).await?))
+}
+
+/// Reached through the gateway, which only lets providers fetch their own key
+async fn get_heartbeat_key(
+    State(monitor): State<Arc<HealthMonitor>>,
+    Path(provider): Path<String>,
+) -> Json<HeartbeatKey> {
+    Json(HeartbeatKey {
+        key: monitor.config().heartbeat_key(&provider),
+        provider: provider.to_lowercase(),
+    })
+}
@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands};
+use shared_types::{HeartbeatSample, ProviderHealth};
+
+use crate::error::Result;
+
+/// Providers that have sent a heartbeat within the window
+const PROVIDERS_KEY: &str = "provider:health:providers";
+
+/// Rolling heartbeat history and health snapshots in Redis
+///
+/// Each provider's heartbeats are kept in a sorted set scored by arrival
+/// time and trimmed to the window on every write. The computed
+/// [`ProviderHealth`] is stored under [`ProviderHealth::key`] for the router
+/// to read.
+#[derive(Clone)]
+pub struct HealthStore {
+    conn: ConnectionManager,
+}
+
+impl HealthStore {
+    pub fn new(conn: ConnectionManager) -> Self {
+        Self { conn }
+    }
+
+    fn samples_key(provider: &str) -> String {
+        format!("provider:heartbeats:{}", provider)
+    }
+
+    fn suspended_key(provider: &str) -> String {
+        format!("provider:health:suspended:{}", provider)
+    }
+
+    /// Append a heartbeat and drop those older than `window`
+    pub async fn push(&self, provider: &str, sample: &HeartbeatSample, window: Duration) -> Result<()> {
+        let value = serde_json::to_string(sample)?;
+        let key = Self::samples_key(provider);
+        let cutoff = sample.received_at.timestamp_millis() - window.as_millis() as i64;
+
+        let mut conn = self.conn.clone();
+        redis::pipe()
+            .zadd(&key, value, sample.received_at.timestamp_millis())
+            .ignore()
+            .zrembyscore(&key, "-inf", format!("({}", cutoff))
+            .ignore()
+            .expire(&key, window.as_secs() as i64)
+            .ignore()
+            .sadd(PROVIDERS_KEY, provider)
+            .ignore()
+            .query_async::<_, ()>(&mut conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Heartbeats received after `since`, oldest first
+    pub async fn samples(&self, provider: &str, since: DateTime<Utc>) -> Result<Vec<HeartbeatSample>> {
+        let mut conn = self.conn.clone();
+        let values: Vec<String> = conn
+            .zrangebyscore(Self::samples_key(provider), format!("({}", since.timestamp_millis()), "+inf")
+            .await?;
+        Ok(values.iter().filter_map(|v| serde_json::from_str(v).ok()).collect())
+    }
+
+    pub async fn put_health(&self, health: &ProviderHealth) -> Result<()> {
+        let value = serde_json::to_string(health)?;
+        let mut conn = self.conn.clone();
+        conn.set::<_, _, ()>(ProviderHealth::key(&health.provider), value).await?;
+        Ok(())
+    }
+
+    pub async fn health(&self, provider: &str) -> Result<Option<ProviderHealth>> {
+        let mut conn = self.conn.clone();
+        let value: Option<String> = conn.get(ProviderHealth::key(provider)).await?;
+        Ok(value.and_then(|v| serde_json::from_str(&v).ok()))
+    }
+
+    /// Providers with heartbeats on record
+    pub async fn providers(&self) -> Result<Vec<String>> {
+        let mut conn = self.conn.clone();
+        Ok(conn.smembers(PROVIDERS_KEY).await?)
+    }
+
+    /// Stop tracking a provider whose heartbeats have all aged out
+    pub async fn forget(&self, provider: &str) -> Result<()> {
+        let mut conn = self.conn.clone();
+        conn.srem::<_, _, ()>(PROVIDERS_KEY, provider).await?;
+        Ok(())
+    }
+
+    /// Remember that a provider's intent in `currency` was paused for health
+    pub async fn mark_suspended(&self, provider: &str, currency: &str) -> Result<()> {
+        let mut conn = self.conn.clone();
+        conn.sadd::<_, _, ()>(Self::suspended_key(provider), currency).await?;
+        Ok(())
+    }
+
+    /// Currencies paused for health, clearing the record
+    pub async fn take_suspended(&self, provider: &str) -> Result<Vec<String>> {
+        let key = Self::suspended_key(provider);
+        let mut conn = self.conn.clone();
+        let (currencies,): (Vec<String>,) = redis::pipe()
+            .atomic()
+            .smembers(&key)
+            .del(&key)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+        Ok(currencies)
+    }
+}
@@ -0,0 +1,261 @@
+//! Provider intents
+//!
+//! A provider offers liquidity in a currency by publishing an intent: how
+//! much it has available, the fee range it accepts and how long it commits
+//! to honour proposals. Publishing again replaces the intent and renews its
+//! expiry; intents can also be edited in place, paused and resumed. Every
+//! change is published as a [`ProviderIntentEvent`] so the router sees it
+//! without polling.
+
+pub mod routes;
+
+use std::time::Duration;
+
+use chrono::Utc;
+use shared_database::{
+    models::{hex_to_bytes, ProviderIntentModel},
+    ProviderRepository,
+};
+use shared_messaging::subjects;
+use shared_types::{
+    helpers::{bytes_to_hex, is_valid_address},
+    ProviderIntent, ProviderIntentEvent, ProviderReputation, RegisterProviderRequest,
+    UpdateIntentRequest,
+};
+use tracing::{error, info};
+
+use crate::error::{ProviderServiceError, Result};
+
+/// Bounds on intents, loaded from the environment
+#[derive(Debug, Clone)]
+pub struct IntentConfig {
+    /// Shortest commitment window accepted
+    pub min_commitment: Duration,
+    /// Longest commitment window accepted
+    pub max_commitment: Duration,
+    /// How long a published intent stays live without being renewed
+    pub ttl: Duration,
+}
+
+impl Default for IntentConfig {
+    fn default() -> Self {
+        Self {
+            min_commitment: Duration::from_secs(60),
+            max_commitment: Duration::from_secs(3600),
+            ttl: Duration::from_secs(24 * 3600),
+        }
+    }
+}
+
+impl IntentConfig {
+    /// Load from `PROVIDER_MIN_COMMITMENT_SECS`, `PROVIDER_MAX_COMMITMENT_SECS`
+    /// and `PROVIDER_INTENT_TTL_SECS`, falling back to defaults
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let env_secs = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(Duration::from_secs)
+        };
+
+        Self {
+            min_commitment: env_secs("PROVIDER_MIN_COMMITMENT_SECS").unwrap_or(defaults.min_commitment),
+            max_commitment: env_secs("PROVIDER_MAX_COMMITMENT_SECS").unwrap_or(defaults.max_commitment),
+            ttl: env_secs("PROVIDER_INTENT_TTL_SECS").unwrap_or(defaults.ttl),
+        }
+    }
+
+    /// Check the terms of an intent
+    pub fn validate(&self, request: &RegisterProviderRequest) -> Result<()> {
+        if !shared_utils::validate_currency(&request.currency) {
+            return Err(ProviderServiceError::InvalidRequest(format!("Unsupported currency: {}", request.currency)));
+        }
+        if request.available_amount.parse::<u128>().is_err() {
+            return Err(ProviderServiceError::InvalidRequest(
+                "available_amount must be a non-negative integer".to_string(),
+            ));
+        }
+        if request.min_fee_bps > request.max_fee_bps || request.max_fee_bps > 10_000 {
+            return Err(ProviderServiceError::InvalidRequest(
+                "Fee range must satisfy min_fee_bps <= max_fee_bps <= 10000".to_string(),
+            ));
+        }
+        let window = request.commitment_window_seconds;
+        if window < self.min_commitment.as_secs() || window > self.max_commitment.as_secs() {
+            return Err(ProviderServiceError::InvalidRequest(format!(
+                "commitment_window_seconds must be between {} and {}",
+                self.min_commitment.as_secs(),
+                self.max_commitment.as_secs()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Publishes and manages provider intents
+pub struct IntentService {
+    providers: ProviderRepository,
+    nats: async_nats::Client,
+    config: IntentConfig,
+}
+
+impl IntentService {
+    pub fn new(providers: ProviderRepository, nats: async_nats::Client, config: IntentConfig) -> Self {
+        Self { providers, nats, config }
+    }
+
+    /// Publish or replace a provider's intent in a currency, renewing its
+    /// expiry and resuming it if paused
+    ///
+    /// # Returns
+    /// * `Result<(ProviderIntent, bool)>` - The stored intent and whether it
+    ///   is new
+    pub async fn publish(&self, provider: &str, request: &RegisterProviderRequest) -> Result<(ProviderIntent, bool)> {
+        let provider = validate_provider(provider)?;
+        self.config.validate(request)?;
+
+        let created = self.providers.get_intent(&provider, &request.currency).await?.is_none();
+        let intent = self.store(&provider, request, true).await?;
+        info!(
+            "Provider {} {} its {} intent",
+            intent.provider,
+            if created { "published" } else { "replaced" },
+            request.currency
+        );
+        Ok((intent, created))
+    }
+
+    /// Change some terms of an existing intent
+    pub async fn update(&self, provider: &str, currency: &str, request: &UpdateIntentRequest) -> Result<ProviderIntent> {
+        let provider = validate_provider(provider)?;
+        let current = self.existing(&provider, currency).await?.to_domain();
+
+        let merged = RegisterProviderRequest {
+            currency: currency.to_string(),
+            available_amount: request.available_amount.clone().unwrap_or(current.available_amount),
+            min_fee_bps: request.min_fee_bps.unwrap_or(current.min_fee_bps),
+            max_fee_bps: request.max_fee_bps.unwrap_or(current.max_fee_bps),
+            commitment_window_seconds: request
+                .commitment_window_seconds
+                .unwrap_or(current.commitment_window_seconds),
+        };
+        self.config.validate(&merged)?;
+        self.store(&provider, &merged, current.is_active).await
+    }
+
+    /// Pause or resume an intent; paused intents are not routed to
+    pub async fn set_active(&self, provider: &str, currency: &str, is_active: bool) -> Result<ProviderIntent> {
+        let provider = validate_provider(provider)?;
+        let intent = self
+            .providers
+            .set_intent_active(&provider, currency, is_active)
+            .await?
+            .ok_or_else(|| not_found(&provider, currency))?
+            .to_domain();
+
+        info!(
+            "Provider {} {} its {} intent",
+            intent.provider,
+            if is_active { "resumed" } else { "paused" },
+            currency
+        );
+        self.announce(&intent).await;
+        Ok(intent)
+    }
+
+    /// A provider's live intents
+    pub async fn list_active(&self, provider: &str) -> Result<Vec<ProviderIntent>> {
+        let provider = validate_provider(provider)?;
+        let intents = self.providers.list_provider_intents(&provider, true).await?;
+        Ok(intents.iter().map(ProviderIntentModel::to_domain).collect())
+    }
+
+    /// A provider's track record; providers without one yet get a fresh record
+    pub async fn reputation(&self, provider: &str) -> Result<ProviderReputation> {
+        let address = validate_provider(provider)?;
+        Ok(match self.providers.get_reputation(&address).await? {
+            Some(reputation) => reputation.to_domain(),
+            None => ProviderReputation::new(provider.to_lowercase()),
+        })
+    }
+
+    async fn existing(&self, provider: &[u8], currency: &str) -> Result<ProviderIntentModel> {
+        self.providers
+            .get_intent(provider, currency)
+            .await?
+            .ok_or_else(|| not_found(provider, currency))
+    }
+
+    async fn store(&self, provider: &[u8], request: &RegisterProviderRequest, is_active: bool) -> Result<ProviderIntent> {
+        let now = Utc::now();
+        let model = ProviderIntentModel {
+            id: 0,
+            provider: provider.to_vec(),
+            currency: request.currency.clone(),
+            available_amount: request.available_amount.clone(),
+            min_fee_bps: request.min_fee_bps as i32,
+            max_fee_bps: request.max_fee_bps as i32,
+            commitment_window: request.commitment_window_seconds as i64,
+            is_active,
+            expires_at: now + chrono::Duration::from_std(self.config.ttl).unwrap_or_default(),
+            created_at: now,
+            updated_at: now,
+        };
+        self.providers.upsert_intent(&model).await?;
+
+        let intent = self.existing(provider, &request.currency).await?.to_domain();
+        self.announce(&intent).await;
+        Ok(intent)
+    }
+
+    async fn announce(&self, intent: &ProviderIntent) {
+        let event = ProviderIntentEvent::from_intent(intent);
+        if let Err(e) = shared_messaging::publish_event(&self.nats, subjects::PROVIDER_INTENT_UPDATED, &event).await {
+            error!("Failed to publish {}: {}", subjects::PROVIDER_INTENT_UPDATED, e);
+        }
+    }
+}
+
+fn validate_provider(provider: &str) -> Result<Vec<u8>> {
+    if !is_valid_address(provider) {
+        return Err(ProviderServiceError::InvalidRequest(format!("Invalid provider address: {}", provider)));
+    }
+    Ok(hex_to_bytes(provider))
+}
+
+fn not_found(provider: &[u8], currency: &str) -> ProviderServiceError {
+    ProviderServiceError::NotFound(format!("No {} intent for provider {}", currency, bytes_to_hex(provider)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(min_fee_bps: u64, max_fee_bps: u64, window: u64) -> RegisterProviderRequest {
+        RegisterProviderRequest {
+            currency: "NGN".to_string(),
+            available_amount: "5000000000".to_string(),
+            min_fee_bps,
+            max_fee_bps,
+            commitment_window_seconds: window,
+        }
+    }
+
+    #[test]
+    fn test_intent_validation() {
+        let config = IntentConfig::default();
+        assert!(config.validate(&request(100, 300, 300)).is_ok());
+        assert!(config.validate(&request(300, 300, 300)).is_ok());
+
+        assert!(config.validate(&request(400, 300, 300)).is_err()); // min above max
+        assert!(config.validate(&request(100, 10_001, 300)).is_err());
+        assert!(config.validate(&request(100, 300, 10)).is_err()); // window too short
+        assert!(config.validate(&request(100, 300, 7200)).is_err()); // window too long
+
+        let unsupported = RegisterProviderRequest { currency: "XYZ".to_string(), ..request(100, 300, 300) };
+        assert!(config.validate(&unsupported).is_err());
+        let fractional = RegisterProviderRequest { available_amount: "1.5".to_string(), ..request(100, 300, 300) };
+        assert!(config.validate(&fractional).is_err());
+    }
+}
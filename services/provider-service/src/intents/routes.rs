@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post, put},
+    Json, Router,
+};
+use shared_types::{ProviderIntent, ProviderReputation, RegisterProviderRequest, UpdateIntentRequest};
+
+use super::IntentService;
+use crate::error::Result;
+
+/// Provider intent and reputation routes; the gateway authenticates the
+/// provider and passes its address in the path
+pub fn router(service: Arc<IntentService>) -> Router {
+    Router::new()
+        .route("/providers/:provider/intents", post(publish_intent).get(list_intents))
+        .route("/providers/:provider/intents/:currency", put(update_intent))
+        .route("/providers/:provider/intents/:currency/pause", post(pause_intent))
+        .route("/providers/:provider/intents/:currency/resume", post(resume_intent))
+        .route("/providers/:provider/reputation", get(get_reputation))
+        .with_state(service)
+}
+
+async fn publish_intent(
+    State(service): State<Arc<IntentService>>,
+    Path(provider): Path<String>,
+    Json(request): Json<RegisterProviderRequest>,
+) -> Result<(StatusCode, Json<ProviderIntent>)> {
+    let (intent, created) = service.publish(&provider, &request).await?;
+    let status = if created { StatusCode::CREATED } else { StatusCode::OK };
+    Ok((status, Json(intent)))
+}
+
+async fn list_intents(
+    State(service): State<Arc<IntentService>>,
+    Path(provider): Path<String>,
+) -> Result<Json<Vec<ProviderIntent>>> {
+    Ok(Json(service.list_active(&provider).await?))
+}
+
+async fn update_intent(
+    State(service): State<Arc<IntentService>>,
+    Path((provider, currency)): Path<(String, String)>,
+    Json(request): Json<UpdateIntentRequest>,
+) -> Result<Json<ProviderIntent>> {
+    Ok(Json(service.update(&provider, &currency, &request).await?))
+}
+
+async fn pause_intent(
+    State(service): State<Arc<IntentService>>,
+    Path((provider, currency)): Path<(String, String)>,
+) -> Result<Json<ProviderIntent>> {
+    Ok(Json(service.set_active(&provider, &currency, false).await?))
+}
+
+async fn resume_intent(
+    State(service): State<Arc<IntentService>>,
+    Path((provider, currency)): Path<(String, String)>,
+) -> Result<Json<ProviderIntent>> {
+    Ok(Json(service.set_active(&provider, &currency, true).await?))
+}
+
+async fn get_reputation(
+    State(service): State<Arc<IntentService>>,
+    Path(provider): Path<String>,
+) -> Result<Json<ProviderReputation>> {
+    Ok(Json(service.reputation(&provider).await?))
+}
@@ -1,5 +1,21 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{routing::get, Router};
+use shared_database::{AllocationRepository, KeyRing, OrderRepository, PayoutRepository, ProviderRepository};
+use shared_fx::{OracleConfig, ProviderRateSource};
 use tracing::info;
 
+mod error;
+mod health;
+mod intents;
+mod payouts;
+mod rates;
+
+use health::{store::HealthStore, HealthConfig, HealthMonitor};
+use intents::{IntentConfig, IntentService};
+use payouts::{AdapterRegistry, PayoutTracker, TrackerConfig};
+use rates::RateService;
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt::init();
@@ -7,5 +23,82 @@ async fn main() -> anyhow::Result<()> {
 
     info!("Provider Service starting...");
 
+    let pool = shared_database::initialize_database().await?;
+    let nats_url = std::env::var("NATS_URL").unwrap_or_else(|_| "nats://127.0.0.1:4222".to_string());
+    let nats = shared_messaging::connect_nats(&nats_url).await?;
+
+    let intent_service = Arc::new(IntentService::new(
+        ProviderRepository::new(pool.clone()),
+        nats.clone(),
+        IntentConfig::from_env(),
+    ));
+
+    let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+    let redis = redis::aio::ConnectionManager::new(redis::Client::open(redis_url)?).await?;
+    let rate_service = Arc::new(RateService::new(
+        intent_service.clone(),
+        ProviderRateSource::new(redis.clone(), OracleConfig::from_env().max_age),
+    ));
+    let monitor = Arc::new(HealthMonitor::new(
+        HealthStore::new(redis),
+        intent_service.clone(),
+        HealthConfig::from_env()?,
+    ));
+    monitor.clone().spawn_sweeper();
+    let consumer = monitor.clone();
+    let heartbeats = nats.clone();
+    tokio::spawn(async move {
+        if let Err(e) = consumer.run(heartbeats).await {
+            tracing::error!("Heartbeat consumer stopped: {}", e);
+        }
+    });
+
+    let mut tracker = PayoutTracker::new(
+        Arc::new(AdapterRegistry::from_env()),
+        PayoutRepository::new(pool.clone()),
+        OrderRepository::new(pool.clone()),
+        AllocationRepository::new(pool),
+        nats,
+        TrackerConfig::from_env(),
+    );
+    match KeyRing::from_env()? {
+        Some(keys) => {
+            info!("Sealing payout recipients with PII key {}", keys.active_key_id());
+            tracker = tracker.sealing(keys);
+        }
+        None => tracing::warn!("PII_ENCRYPTION_KEYS not set; payout recipients will not be stored"),
+    }
+    let tracker = Arc::new(tracker);
+    tracker.clone().spawn_poller();
+    let rewrapper = tracker.clone();
+    tokio::spawn(async move {
+        match rewrapper.rewrap_recipients().await {
+            Ok(0) => {}
+            Ok(count) => info!("Rewrapped {} payout recipients with the active PII key", count),
+            Err(e) => tracing::warn!("Rewrapping payout recipients failed: {}", e),
+        }
+    });
+
+    let app = Router::new()
+        .route("/health", get(health_check))
+        .merge(intents::routes::router(intent_service))
+        .merge(health::routes::router(monitor))
+        .merge(rates::routes::router(rate_service))
+        .merge(payouts::routes::router(tracker));
+
+    let port = std::env::var("PROVIDER_SERVICE_PORT")
+        .ok()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(8003);
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    info!("Provider Service listening on {}", addr);
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await?;
+
     Ok(())
 }
+
+async fn health_check() -> &'static str {
+    "OK"
+}
@@ -0,0 +1,238 @@
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use rust_decimal::prelude::ToPrimitive;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared_types::{PaymentRequest, PayoutResult, PayoutStatus, RecipientDetails};
+use shared_utils::signing::constant_time_eq;
+
+use super::{parse_amount, payout_reference, HttpAdapterConfig, PayoutAdapter, PayoutError, Result};
+
+/// Header carrying the secret hash configured on the Flutterwave dashboard
+const SIGNATURE_HEADER: &str = "verif-hash";
+
+const CURRENCIES: [&str; 4] = ["NGN", "GHS", "KES", "USD"];
+
+/// Flutterwave transfers to bank accounts, or to M-Pesa wallets in KES when
+/// the recipient has a phone number and no bank code
+pub struct FlutterwaveAdapter {
+    client: reqwest::Client,
+    config: HttpAdapterConfig,
+}
+
+/// Flutterwave's response envelope
+#[derive(Debug, Deserialize)]
+struct Envelope {
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    data: Value,
+}
+
+impl FlutterwaveAdapter {
+    pub const NAME: &'static str = "flutterwave";
+    pub const DEFAULT_BASE_URL: &'static str = "https://api.flutterwave.com/v3";
+
+    pub fn new(config: HttpAdapterConfig) -> Self {
+        Self { client: config.client(), config }
+    }
+
+    async fn call(&self, builder: reqwest::RequestBuilder) -> Result<Value> {
+        let response = builder
+            .bearer_auth(&self.config.secret_key)
+            .send()
+            .await
+            .map_err(|e| self.unavailable(e.to_string()))?;
+        let status = response.status();
+        let envelope: Envelope = response
+            .json()
+            .await
+            .map_err(|e| self.unavailable(format!("unreadable response ({}): {}", status, e)))?;
+
+        if status.is_server_error() {
+            return Err(self.unavailable(format!("{}: {}", status, envelope.message)));
+        }
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(PayoutError::NotFound(envelope.message));
+        }
+        if !status.is_success() || envelope.status != "success" {
+            return Err(PayoutError::Rejected { adapter: Self::NAME.to_string(), message: envelope.message });
+        }
+        Ok(envelope.data)
+    }
+
+    fn unavailable(&self, message: String) -> PayoutError {
+        PayoutError::Unavailable { adapter: Self::NAME.to_string(), message }
+    }
+
+    /// Transfers are looked up by Flutterwave's numeric id, which serves as
+    /// the payout reference
+    fn result(&self, data: &Value) -> Result<PayoutResult> {
+        let id = data["id"]
+            .as_u64()
+            .ok_or_else(|| self.unavailable("transfer without an id".to_string()))?;
+        let status = match data["status"].as_str().unwrap_or_default() {
+            "SUCCESSFUL" => PayoutStatus::Succeeded,
+            "FAILED" => PayoutStatus::Failed,
+            "NEW" => PayoutStatus::Pending,
+            _ => PayoutStatus::Processing,
+        };
+        Ok(PayoutResult {
+            adapter: Self::NAME.to_string(),
+            reference: id.to_string(),
+            status,
+            message: data["complete_message"]
+                .as_str()
+                .filter(|message| !message.is_empty())
+                .map(str::to_string),
+        })
+    }
+}
+
+#[async_trait]
+impl PayoutAdapter for FlutterwaveAdapter {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn supports(&self, currency: &str) -> bool {
+        CURRENCIES.contains(&currency.to_uppercase().as_str())
+    }
+
+    async fn initiate(&self, request: &PaymentRequest) -> Result<PayoutResult> {
+        if !self.supports(&request.currency) {
+            return Err(PayoutError::Unsupported(request.currency.clone()));
+        }
+        let recipient = &request.recipient_details;
+        let (account_bank, account_number) = match (&recipient.bank_code, &recipient.phone_number) {
+            (Some(bank_code), _) => (bank_code.as_str(), recipient.account_number.as_str()),
+            (None, Some(phone)) if request.currency.eq_ignore_ascii_case("KES") => ("MPS", phone.as_str()),
+            _ => return Err(PayoutError::InvalidRecipient("bank_code or an M-Pesa phone_number is required".to_string())),
+        };
+        // Flutterwave takes the amount as a JSON number in major units
+        let amount = parse_amount(&request.amount, 2)?
+            .to_f64()
+            .ok_or_else(|| PayoutError::InvalidAmount(request.amount.clone()))?;
+
+        let transfer = self
+            .call(self.client.post(format!("{}/transfers", self.config.base_url)).json(&json!({
+                "account_bank": account_bank,
+                "account_number": account_number,
+                "amount": amount,
+                "currency": request.currency.to_uppercase(),
+                "beneficiary_name": recipient.account_name,
+                "narration": format!("PayNode order {}", request.order_id),
+                "reference": payout_reference(request),
+            })))
+            .await?;
+        self.result(&transfer)
+    }
+
+    async fn status(&self, reference: &str) -> Result<PayoutResult> {
+        let transfer = self
+            .call(self.client.get(format!("{}/transfers/{}", self.config.base_url, reference)))
+            .await?;
+        self.result(&transfer)
+    }
+
+    /// Flutterwave resolves Nigerian bank accounts only
+    async fn resolve_account_name(&self, currency: &str, recipient: &RecipientDetails) -> Result<Option<String>> {
+        let Some(bank_code) = recipient.bank_code.as_deref() else {
+            return Ok(None);
+        };
+        if !currency.eq_ignore_ascii_case("NGN") {
+            return Ok(None);
+        }
+
+        let account = self
+            .call(self.client.post(format!("{}/accounts/resolve", self.config.base_url)).json(&json!({
+                "account_number": recipient.account_number,
+                "account_bank": bank_code,
+            })))
+            .await
+            .map_err(|e| match e {
+                PayoutError::Rejected { message, .. } | PayoutError::NotFound(message) => {
+                    PayoutError::InvalidRecipient(message)
+                }
+                other => other,
+            })?;
+        Ok(account["account_name"].as_str().map(str::to_string))
+    }
+
+    /// Flutterwave sends the secret hash set on its dashboard (configured
+    /// as `FLUTTERWAVE_WEBHOOK_SECRET`) with `transfer.completed` events
+    fn webhook(&self, headers: &HeaderMap, body: &[u8]) -> Result<Option<PayoutResult>> {
+        let secret = self
+            .config
+            .webhook_secret
+            .as_deref()
+            .ok_or_else(|| PayoutError::InvalidSignature("no webhook secret configured".to_string()))?;
+        let hash = headers
+            .get(SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| PayoutError::InvalidSignature(format!("missing {}", SIGNATURE_HEADER)))?;
+        if !constant_time_eq(hash.trim().as_bytes(), secret.as_bytes()) {
+            return Err(PayoutError::InvalidSignature(format!("{} mismatch", SIGNATURE_HEADER)));
+        }
+
+        let event: Value = serde_json::from_slice(body).map_err(|e| PayoutError::InvalidWebhook(e.to_string()))?;
+        if event["event"] != "transfer.completed" {
+            return Ok(None);
+        }
+        self.result(&event["data"]).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payouts::stub::{payment_request, StubPsp, StubRoute, STUB_WEBHOOK_SECRET};
+    use axum::http::Method;
+
+    #[tokio::test]
+    async fn test_flutterwave_mpesa_transfer_against_stub() {
+        let psp = StubPsp::start(vec![
+            StubRoute::new(
+                Method::POST,
+                "/transfers",
+                200,
+                json!({ "status": "success", "message": "Transfer Queued", "data": { "id": 4521, "status": "NEW" } }),
+            ),
+            StubRoute::new(
+                Method::GET,
+                "/transfers/4521",
+                200,
+                json!({ "status": "success", "data": { "id": 4521, "status": "FAILED", "complete_message": "Insufficient balance" } }),
+            ),
+        ])
+        .await;
+        let adapter = FlutterwaveAdapter::new(psp.config());
+
+        let mut request = payment_request("KES", "2500.50");
+        request.recipient_details.bank_code = None;
+        request.recipient_details.phone_number = Some("254712345678".to_string());
+        let payout = adapter.initiate(&request).await.unwrap();
+        assert_eq!((payout.reference.as_str(), payout.status), ("4521", PayoutStatus::Pending));
+
+        let failed = adapter.status("4521").await.unwrap();
+        assert_eq!(failed.status, PayoutStatus::Failed);
+        assert_eq!(failed.message.as_deref(), Some("Insufficient balance"));
+
+        let body = &psp.requests()[0].body;
+        assert_eq!(body["account_bank"], "MPS");
+        assert_eq!(body["account_number"], "254712345678");
+        assert_eq!(body["reference"], payout_reference(&request));
+
+        assert!(matches!(adapter.status("999").await, Err(PayoutError::NotFound(_))));
+
+        let webhook = json!({ "event": "transfer.completed", "data": { "id": 4521, "status": "SUCCESSFUL" } }).to_string();
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, STUB_WEBHOOK_SECRET.parse().unwrap());
+        let completed = adapter.webhook(&headers, webhook.as_bytes()).unwrap().unwrap();
+        assert_eq!((completed.reference.as_str(), completed.status), ("4521", PayoutStatus::Succeeded));
+        headers.insert(SIGNATURE_HEADER, "guess".parse().unwrap());
+        assert!(matches!(adapter.webhook(&headers, webhook.as_bytes()), Err(PayoutError::InvalidSignature(_))));
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use shared_types::{PaymentRequest, PayoutResult, PayoutStatus};
+
+use super::{parse_amount, payout_reference, PayoutAdapter, PayoutError, Result};
+
+/// How the mock settles payouts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockOutcome {
+    /// Paid out immediately
+    Succeed,
+    /// Left processing indefinitely
+    Hold,
+    /// Rejected immediately
+    Fail,
+}
+
+/// Local adapter that pays out without a PSP
+///
+/// Payouts settle as configured, except that recipients whose account
+/// number ends in `0000` always fail, so failure paths can be exercised
+/// end to end. Initiating the same request twice returns the same payout.
+pub struct MockAdapter {
+    currencies: HashSet<String>,
+    outcome: MockOutcome,
+    payouts: Mutex<HashMap<String, PayoutResult>>,
+}
+
+impl MockAdapter {
+    pub const NAME: &'static str = "mock";
+
+    pub fn new(currencies: impl IntoIterator<Item = String>, outcome: MockOutcome) -> Self {
+        Self {
+            currencies: currencies.into_iter().map(|c| c.to_uppercase()).collect(),
+            outcome,
+            payouts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Enabled for the currencies in `PAYOUT_MOCK_CURRENCIES`, settling per
+    /// `PAYOUT_MOCK_OUTCOME` (`succeed`, `hold` or `fail`)
+    pub fn from_env() -> Option<Self> {
+        let currencies: Vec<String> = std::env::var("PAYOUT_MOCK_CURRENCIES")
+            .unwrap_or_default()
+            .split(',')
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty())
+            .collect();
+        if currencies.is_empty() {
+            return None;
+        }
+        let outcome = match std::env::var("PAYOUT_MOCK_OUTCOME").unwrap_or_default().trim() {
+            "hold" => MockOutcome::Hold,
+            "fail" => MockOutcome::Fail,
+            _ => MockOutcome::Succeed,
+        };
+        Some(Self::new(currencies, outcome))
+    }
+}
+
+#[async_trait]
+impl PayoutAdapter for MockAdapter {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn supports(&self, currency: &str) -> bool {
+        self.currencies.contains(&currency.to_uppercase())
+    }
+
+    async fn initiate(&self, request: &PaymentRequest) -> Result<PayoutResult> {
+        if !self.supports(&request.currency) {
+            return Err(PayoutError::Unsupported(request.currency.clone()));
+        }
+        parse_amount(&request.amount, 2)?;
+        let account = &request.recipient_details.account_number;
+        if account.trim().is_empty() {
+            return Err(PayoutError::InvalidRecipient("account_number is required".to_string()));
+        }
+
+        let reference = payout_reference(request);
+        let mut payouts = self.payouts.lock().expect("mock payouts lock poisoned");
+        if let Some(existing) = payouts.get(&reference) {
+            return Ok(existing.clone());
+        }
+
+        let (status, message) = match self.outcome {
+            _ if account.ends_with("0000") => (PayoutStatus::Failed, Some("Recipient account rejected".to_string())),
+            MockOutcome::Succeed => (PayoutStatus::Succeeded, None),
+            MockOutcome::Hold => (PayoutStatus::Processing, None),
+            MockOutcome::Fail => (PayoutStatus::Failed, Some("Mock payout failure".to_string())),
+        };
+        let payout = PayoutResult {
+            adapter: Self::NAME.to_string(),
+            reference: reference.clone(),
+            status,
+            message,
+        };
+        payouts.insert(reference, payout.clone());
+        Ok(payout)
+    }
+
+    async fn status(&self, reference: &str) -> Result<PayoutResult> {
+        self.payouts
+            .lock()
+            .expect("mock payouts lock poisoned")
+            .get(reference)
+            .cloned()
+            .ok_or_else(|| PayoutError::NotFound(reference.to_string()))
+    }
+}
@@ -0,0 +1,214 @@
+//! Fiat payout execution
+//!
+//! Each PSP integration implements [`PayoutAdapter`]: it takes a
+//! [`PaymentRequest`] and starts the transfer to the recipient, and it can
+//! look the transfer up again by the PSP's reference. Adapters whose PSP
+//! sends webhooks also verify and read them. The
+//! [`AdapterRegistry`] holds the configured adapters and picks one per
+//! currency, after checking the recipient's details for the currency and,
+//! where the PSP offers name enquiry, that the account holder's name matches
+//! the one given. [`MockAdapter`] pays out locally without a PSP account, and
+//! adapters talking HTTP are tested against a stub PSP (`stub`).
+//!
+//! The [`PayoutTracker`] records every payout and follows it until the PSP
+//! confirms or rejects it, from webhooks or by polling, then publishes
+//! `order.fulfilled` or `order.failed`.
+
+pub mod flutterwave;
+pub mod mock;
+pub mod paystack;
+pub mod registry;
+pub mod routes;
+#[cfg(test)]
+pub mod stub;
+pub mod tracker;
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use rust_decimal::Decimal;
+use shared_types::{PaymentRequest, PayoutResult, RecipientDetails};
+use thiserror::Error;
+
+pub use flutterwave::FlutterwaveAdapter;
+pub use mock::MockAdapter;
+pub use paystack::PaystackAdapter;
+pub use registry::AdapterRegistry;
+pub use tracker::{PayoutTracker, TrackerConfig};
+
+#[derive(Error, Debug)]
+pub enum PayoutError {
+    #[error("No payout adapter for {0}")]
+    Unsupported(String),
+
+    #[error("Invalid recipient: {0}")]
+    InvalidRecipient(String),
+
+    /// Name enquiry returned a different account holder
+    #[error("Account name mismatch: {given} does not match {resolved}")]
+    NameMismatch { given: String, resolved: String },
+
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
+    #[error("Invalid webhook signature: {0}")]
+    InvalidSignature(String),
+
+    #[error("Invalid webhook: {0}")]
+    InvalidWebhook(String),
+
+    #[error("Payout not found: {0}")]
+    NotFound(String),
+
+    /// The PSP refused the payout; retrying will not help
+    #[error("Payout rejected by {adapter}: {message}")]
+    Rejected { adapter: String, message: String },
+
+    /// The PSP could not be reached or answered unexpectedly; the payout may
+    /// or may not have been created
+    #[error("{adapter} unavailable: {message}")]
+    Unavailable { adapter: String, message: String },
+}
+
+pub type Result<T> = std::result::Result<T, PayoutError>;
+
+/// A PSP integration able to send fiat to a recipient
+#[async_trait]
+pub trait PayoutAdapter: Send + Sync {
+    /// Name used to select the adapter and recorded with its payouts
+    fn name(&self) -> &str;
+
+    /// Whether the adapter can pay out in a currency
+    fn supports(&self, currency: &str) -> bool;
+
+    /// Start a payout
+    ///
+    /// Implementations pass [`payout_reference`] to the PSP as its
+    /// idempotency key, so a request retried after a timeout does not pay the
+    /// recipient twice.
+    async fn initiate(&self, request: &PaymentRequest) -> Result<PayoutResult>;
+
+    /// Current state of a payout started by [`Self::initiate`]
+    async fn status(&self, reference: &str) -> Result<PayoutResult>;
+
+    /// Name the PSP holds for the recipient's account (name enquiry)
+    ///
+    /// Returns None where the PSP cannot look the account up, such as for
+    /// mobile money wallets. Adapters without name enquiry never can.
+    async fn resolve_account_name(&self, currency: &str, recipient: &RecipientDetails) -> Result<Option<String>> {
+        let _ = (currency, recipient);
+        Ok(None)
+    }
+
+    /// Read a PSP webhook after checking it was signed by the PSP
+    ///
+    /// Returns None for events that do not concern a transfer. Adapters
+    /// without webhooks reject them all.
+    fn webhook(&self, headers: &HeaderMap, body: &[u8]) -> Result<Option<PayoutResult>> {
+        let _ = (headers, body);
+        Err(PayoutError::Unsupported(format!("webhooks from {}", self.name())))
+    }
+}
+
+/// Settings shared by the HTTP adapters
+#[derive(Debug, Clone)]
+pub struct HttpAdapterConfig {
+    pub base_url: String,
+    pub secret_key: String,
+    /// Secret webhooks are signed with, where the PSP uses a separate one
+    pub webhook_secret: Option<String>,
+    pub timeout: Duration,
+}
+
+impl HttpAdapterConfig {
+    /// Load `<PREFIX>_SECRET_KEY`, `<PREFIX>_WEBHOOK_SECRET` and
+    /// `<PREFIX>_BASE_URL`; None when no secret key is set, leaving the
+    /// adapter disabled
+    pub fn from_env(prefix: &str, default_base_url: &str, timeout: Duration) -> Option<Self> {
+        let secret_key = std::env::var(format!("{}_SECRET_KEY", prefix))
+            .ok()
+            .filter(|key| !key.trim().is_empty())?;
+        let base_url = std::env::var(format!("{}_BASE_URL", prefix))
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(|| default_base_url.to_string());
+        let webhook_secret = std::env::var(format!("{}_WEBHOOK_SECRET", prefix))
+            .ok()
+            .filter(|secret| !secret.trim().is_empty());
+        Some(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            secret_key,
+            webhook_secret,
+            timeout,
+        })
+    }
+
+    pub fn client(&self) -> reqwest::Client {
+        reqwest::Client::builder()
+            .timeout(self.timeout)
+            .build()
+            .unwrap_or_default()
+    }
+}
+
+/// Idempotency key sent to the PSP for a payment request
+///
+/// PSPs cap reference length, so the proposal id is shortened to its first
+/// 40 hex digits. Payouts of a split order's legs are suffixed with the leg.
+pub fn payout_reference(request: &PaymentRequest) -> String {
+    let id: String = request
+        .proposal_id
+        .trim_start_matches("0x")
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .take(40)
+        .collect();
+    match request.leg {
+        Some(leg) => format!("pn_{}_l{}", id.to_lowercase(), leg),
+        None => format!("pn_{}", id.to_lowercase()),
+    }
+}
+
+/// Parse a fiat amount in major units, such as `"1542.25"`
+pub fn parse_amount(amount: &str, decimals: u32) -> Result<Decimal> {
+    let value = Decimal::from_str(amount.trim()).map_err(|_| PayoutError::InvalidAmount(amount.to_string()))?;
+    if value <= Decimal::ZERO || value.scale() > decimals {
+        return Err(PayoutError::InvalidAmount(amount.to_string()));
+    }
+    Ok(value)
+}
+
+/// Convert a fiat amount in major units to minor units (kobo, pesewas, cents)
+pub fn minor_units(amount: &str, decimals: u32) -> Result<u64> {
+    let value = parse_amount(amount, decimals)? * Decimal::from(10u64.pow(decimals));
+    value
+        .trunc()
+        .to_string()
+        .parse()
+        .map_err(|_| PayoutError::InvalidAmount(amount.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amounts_and_references() {
+        assert_eq!(minor_units("1542.25", 2).unwrap(), 154_225);
+        assert_eq!(minor_units("500000", 2).unwrap(), 50_000_000);
+        assert!(minor_units("1.005", 2).is_err());
+        assert!(minor_units("0", 2).is_err());
+        assert!(minor_units("-5", 2).is_err());
+        assert!(minor_units("ten", 2).is_err());
+
+        let request = stub::payment_request("NGN", "1000");
+        let reference = payout_reference(&request);
+        assert!(reference.starts_with("pn_"));
+        assert_eq!(reference.len(), 43);
+
+        let leg = PaymentRequest { leg: Some(2), ..request };
+        assert_eq!(payout_reference(&leg), format!("{}_l2", reference));
+    }
+}
@@ -0,0 +1,292 @@
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared_types::{
+    banks::{self, BankKind},
+    PaymentRequest, PayoutResult, PayoutStatus, RecipientDetails,
+};
+use shared_utils::signing::{constant_time_eq, hmac_sha512_hex};
+
+use super::{minor_units, payout_reference, HttpAdapterConfig, PayoutAdapter, PayoutError, Result};
+
+/// Header carrying the HMAC-SHA512 of a webhook body, keyed by the secret key
+const SIGNATURE_HEADER: &str = "x-paystack-signature";
+
+/// Supported currencies and the recipient type Paystack uses for each
+const RECIPIENT_TYPES: [(&str, &str); 3] = [("NGN", "nuban"), ("GHS", "ghipss"), ("KES", "kepss")];
+
+/// Currencies whose bank accounts Paystack can resolve
+const RESOLVABLE_CURRENCIES: [&str; 2] = ["NGN", "GHS"];
+
+/// Paystack transfers: a transfer recipient is created for the account,
+/// then a transfer is made to it from the Paystack balance
+pub struct PaystackAdapter {
+    client: reqwest::Client,
+    config: HttpAdapterConfig,
+}
+
+/// Paystack's response envelope
+#[derive(Debug, Deserialize)]
+struct Envelope {
+    #[serde(default)]
+    status: bool,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    data: Value,
+}
+
+impl PaystackAdapter {
+    pub const NAME: &'static str = "paystack";
+    pub const DEFAULT_BASE_URL: &'static str = "https://api.paystack.co";
+
+    pub fn new(config: HttpAdapterConfig) -> Self {
+        Self { client: config.client(), config }
+    }
+
+    async fn call(&self, builder: reqwest::RequestBuilder) -> Result<Value> {
+        let response = builder
+            .bearer_auth(&self.config.secret_key)
+            .send()
+            .await
+            .map_err(|e| self.unavailable(e.to_string()))?;
+        let status = response.status();
+        let envelope: Envelope = response
+            .json()
+            .await
+            .map_err(|e| self.unavailable(format!("unreadable response ({}): {}", status, e)))?;
+
+        if status.is_server_error() {
+            return Err(self.unavailable(format!("{}: {}", status, envelope.message)));
+        }
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(PayoutError::NotFound(envelope.message));
+        }
+        if !status.is_success() || !envelope.status {
+            return Err(PayoutError::Rejected { adapter: Self::NAME.to_string(), message: envelope.message });
+        }
+        Ok(envelope.data)
+    }
+
+    fn unavailable(&self, message: String) -> PayoutError {
+        PayoutError::Unavailable { adapter: Self::NAME.to_string(), message }
+    }
+
+    fn result(&self, data: &Value) -> Result<PayoutResult> {
+        let reference = data["reference"]
+            .as_str()
+            .ok_or_else(|| self.unavailable("transfer without a reference".to_string()))?;
+        let status = match data["status"].as_str().unwrap_or_default() {
+            "success" => PayoutStatus::Succeeded,
+            "failed" | "reversed" | "abandoned" | "rejected" => PayoutStatus::Failed,
+            "pending" | "otp" => PayoutStatus::Pending,
+            _ => PayoutStatus::Processing,
+        };
+        Ok(PayoutResult {
+            adapter: Self::NAME.to_string(),
+            reference: reference.to_string(),
+            status,
+            message: data["gateway_response"].as_str().or(data["reason"].as_str()).map(str::to_string),
+        })
+    }
+}
+
+#[async_trait]
+impl PayoutAdapter for PaystackAdapter {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn supports(&self, currency: &str) -> bool {
+        recipient_type(currency).is_some()
+    }
+
+    async fn initiate(&self, request: &PaymentRequest) -> Result<PayoutResult> {
+        let mut recipient_type =
+            recipient_type(&request.currency).ok_or_else(|| PayoutError::Unsupported(request.currency.clone()))?;
+        let recipient = &request.recipient_details;
+        let bank_code = recipient
+            .bank_code
+            .as_deref()
+            .ok_or_else(|| PayoutError::InvalidRecipient("bank_code is required".to_string()))?;
+        if is_wallet(&request.currency, bank_code) {
+            recipient_type = "mobile_money";
+        }
+        let amount = minor_units(&request.amount, 2)?;
+
+        let recipient_data = self
+            .call(self.client.post(format!("{}/transferrecipient", self.config.base_url)).json(&json!({
+                "type": recipient_type,
+                "name": recipient.account_name,
+                "account_number": recipient.account_number,
+                "bank_code": bank_code,
+                "currency": request.currency.to_uppercase(),
+            })))
+            .await?;
+        let recipient_code = recipient_data["recipient_code"]
+            .as_str()
+            .ok_or_else(|| self.unavailable("recipient without a recipient_code".to_string()))?;
+
+        let transfer = self
+            .call(self.client.post(format!("{}/transfer", self.config.base_url)).json(&json!({
+                "source": "balance",
+                "amount": amount,
+                "recipient": recipient_code,
+                "reference": payout_reference(request),
+                "reason": format!("PayNode order {}", request.order_id),
+            })))
+            .await?;
+        self.result(&transfer)
+    }
+
+    async fn status(&self, reference: &str) -> Result<PayoutResult> {
+        let transfer = self
+            .call(self.client.get(format!("{}/transfer/verify/{}", self.config.base_url, reference)))
+            .await?;
+        self.result(&transfer)
+    }
+
+    /// Paystack resolves Nigerian and Ghanaian bank accounts; an account it
+    /// cannot find is an invalid recipient
+    async fn resolve_account_name(&self, currency: &str, recipient: &RecipientDetails) -> Result<Option<String>> {
+        let Some(bank_code) = recipient.bank_code.as_deref() else {
+            return Ok(None);
+        };
+        if !RESOLVABLE_CURRENCIES.iter().any(|c| c.eq_ignore_ascii_case(currency)) || is_wallet(currency, bank_code) {
+            return Ok(None);
+        }
+
+        let account = self
+            .call(
+                self.client
+                    .get(format!("{}/bank/resolve", self.config.base_url))
+                    .query(&[("account_number", recipient.account_number.as_str()), ("bank_code", bank_code)]),
+            )
+            .await
+            .map_err(|e| match e {
+                PayoutError::Rejected { message, .. } | PayoutError::NotFound(message) => {
+                    PayoutError::InvalidRecipient(message)
+                }
+                other => other,
+            })?;
+        Ok(account["account_name"].as_str().map(str::to_string))
+    }
+
+    /// Paystack signs webhooks with the account's secret key and sends
+    /// `transfer.success`, `transfer.failed` and `transfer.reversed` events
+    fn webhook(&self, headers: &HeaderMap, body: &[u8]) -> Result<Option<PayoutResult>> {
+        let signature = headers
+            .get(SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| PayoutError::InvalidSignature(format!("missing {}", SIGNATURE_HEADER)))?;
+        let expected = hmac_sha512_hex(self.config.secret_key.as_bytes(), body);
+        if !constant_time_eq(signature.trim().to_lowercase().as_bytes(), expected.as_bytes()) {
+            return Err(PayoutError::InvalidSignature(format!("{} mismatch", SIGNATURE_HEADER)));
+        }
+
+        let event: Value = serde_json::from_slice(body).map_err(|e| PayoutError::InvalidWebhook(e.to_string()))?;
+        if !event["event"].as_str().unwrap_or_default().starts_with("transfer.") {
+            return Ok(None);
+        }
+        self.result(&event["data"]).map(Some)
+    }
+}
+
+fn recipient_type(currency: &str) -> Option<&'static str> {
+    RECIPIENT_TYPES
+        .iter()
+        .find(|(supported, _)| supported.eq_ignore_ascii_case(currency))
+        .map(|(_, recipient_type)| *recipient_type)
+}
+
+/// Mobile money wallets are paid as `mobile_money` recipients
+fn is_wallet(currency: &str, bank_code: &str) -> bool {
+    banks::find_bank(currency, bank_code).is_some_and(|bank| bank.kind == BankKind::MobileMoney)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payouts::stub::{payment_request, StubPsp, StubRoute, STUB_SECRET_KEY};
+    use axum::http::Method;
+
+    #[tokio::test]
+    async fn test_paystack_transfer_against_stub() {
+        let request = payment_request("NGN", "1542.25");
+        let reference = payout_reference(&request);
+        let psp = StubPsp::start(vec![
+            StubRoute::new(
+                Method::POST,
+                "/transferrecipient",
+                201,
+                json!({ "status": true, "message": "Recipient created", "data": { "recipient_code": "RCP_1" } }),
+            ),
+            StubRoute::new(
+                Method::POST,
+                "/transfer",
+                200,
+                json!({ "status": true, "message": "Transfer queued", "data": { "reference": reference, "status": "pending" } }),
+            ),
+            StubRoute::new(
+                Method::GET,
+                &format!("/transfer/verify/{}", reference),
+                200,
+                json!({ "status": true, "message": "Transfer retrieved", "data": { "reference": reference, "status": "success" } }),
+            ),
+        ])
+        .await;
+        let adapter = PaystackAdapter::new(psp.config());
+
+        let payout = adapter.initiate(&request).await.unwrap();
+        assert_eq!(payout.status, PayoutStatus::Pending);
+        assert_eq!(adapter.status(&payout.reference).await.unwrap().status, PayoutStatus::Succeeded);
+
+        let requests = psp.requests();
+        let calls: Vec<(Method, &str)> = requests.iter().map(|r| (r.method.clone(), r.path.as_str())).collect();
+        assert_eq!(calls[..2], [(Method::POST, "/transferrecipient"), (Method::POST, "/transfer")]);
+        assert_eq!(requests[0].body["bank_code"], "058");
+        assert_eq!(requests[1].body["amount"], 154_225);
+        assert_eq!(requests[1].body["recipient"], "RCP_1");
+        assert_eq!(requests[1].authorization.as_deref(), Some(format!("Bearer {}", STUB_SECRET_KEY).as_str()));
+
+        // A refused recipient is final; an outage is not
+        let refused = StubPsp::start(vec![StubRoute::new(
+            Method::POST,
+            "/transferrecipient",
+            400,
+            json!({ "status": false, "message": "Account number is invalid" }),
+        )])
+        .await;
+        let err = PaystackAdapter::new(refused.config()).initiate(&request).await.unwrap_err();
+        assert!(matches!(err, PayoutError::Rejected { .. }));
+
+        let down = StubPsp::start(vec![StubRoute::new(Method::POST, "/transferrecipient", 503, json!({ "status": false }))]).await;
+        let err = PaystackAdapter::new(down.config()).initiate(&request).await.unwrap_err();
+        assert!(matches!(err, PayoutError::Unavailable { .. }));
+    }
+
+    #[test]
+    fn test_paystack_webhook_signature() {
+        let adapter = PaystackAdapter::new(HttpAdapterConfig {
+            base_url: PaystackAdapter::DEFAULT_BASE_URL.to_string(),
+            secret_key: STUB_SECRET_KEY.to_string(),
+            webhook_secret: None,
+            timeout: std::time::Duration::from_secs(1),
+        });
+        let body = json!({ "event": "transfer.reversed", "data": { "reference": "pn_abc", "status": "reversed" } }).to_string();
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, hmac_sha512_hex(STUB_SECRET_KEY.as_bytes(), body.as_bytes()).parse().unwrap());
+
+        let payout = adapter.webhook(&headers, body.as_bytes()).unwrap().unwrap();
+        assert_eq!((payout.reference.as_str(), payout.status), ("pn_abc", PayoutStatus::Failed));
+
+        let charge = json!({ "event": "charge.success", "data": {} }).to_string();
+        headers.insert(SIGNATURE_HEADER, hmac_sha512_hex(STUB_SECRET_KEY.as_bytes(), charge.as_bytes()).parse().unwrap());
+        assert!(adapter.webhook(&headers, charge.as_bytes()).unwrap().is_none());
+
+        let tampered = body.replace("reversed", "success");
+        assert!(matches!(adapter.webhook(&headers, tampered.as_bytes()), Err(PayoutError::InvalidSignature(_))));
+        assert!(matches!(adapter.webhook(&HeaderMap::new(), body.as_bytes()), Err(PayoutError::InvalidSignature(_))));
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use shared_types::{name_match_score, PaymentRequest, PayoutResult, RecipientDetails, RecipientVerification};
+use tracing::{info, warn};
+
+use super::{
+    FlutterwaveAdapter, HttpAdapterConfig, MockAdapter, PayoutAdapter, PayoutError, PaystackAdapter, Result,
+};
+
+/// Lowest name match score accepted unless `PAYOUT_NAME_MATCH_THRESHOLD`
+/// says otherwise
+const DEFAULT_NAME_MATCH_THRESHOLD: f64 = 0.8;
+
+/// Configured payout adapters and which one serves each currency
+#[derive(Default)]
+pub struct AdapterRegistry {
+    adapters: Vec<Arc<dyn PayoutAdapter>>,
+    /// Preferred adapter by currency; others fall back to the first adapter
+    /// registered that supports the currency
+    routes: HashMap<String, String>,
+    /// Lowest name match score a resolved account may have; None skips
+    /// name enquiry
+    name_match_threshold: Option<f64>,
+}
+
+impl AdapterRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an adapter; it replaces any adapter registered under its name
+    pub fn register(mut self, adapter: Arc<dyn PayoutAdapter>) -> Self {
+        self.adapters.retain(|existing| existing.name() != adapter.name());
+        self.adapters.push(adapter);
+        self
+    }
+
+    /// Prefer an adapter for a currency
+    pub fn route(mut self, currency: &str, adapter: &str) -> Self {
+        self.routes.insert(currency.to_uppercase(), adapter.to_string());
+        self
+    }
+
+    /// Check account names by name enquiry, refusing payouts to accounts
+    /// whose holder's name scores below `threshold`
+    pub fn name_enquiry(mut self, threshold: f64) -> Self {
+        self.name_match_threshold = Some(threshold);
+        self
+    }
+
+    /// Build from the environment
+    ///
+    /// HTTP adapters are enabled by their secret key (`PAYSTACK_SECRET_KEY`,
+    /// `FLUTTERWAVE_SECRET_KEY`; base URLs are overridable with
+    /// `*_BASE_URL`), the mock by `PAYOUT_MOCK_CURRENCIES`.
+    /// `PAYOUT_ROUTES` (`NGN=paystack,KES=flutterwave`) picks the adapter
+    /// per currency. `PAYOUT_NAME_MATCH_THRESHOLD` sets the name match score
+    /// required after name enquiry (default 0.8, 0 turns enquiry off).
+    pub fn from_env() -> Self {
+        let timeout = Duration::from_millis(
+            std::env::var("PAYOUT_HTTP_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10_000),
+        );
+
+        let mut registry = Self::new();
+        if let Some(config) = HttpAdapterConfig::from_env("PAYSTACK", PaystackAdapter::DEFAULT_BASE_URL, timeout) {
+            registry = registry.register(Arc::new(PaystackAdapter::new(config)));
+        }
+        if let Some(config) = HttpAdapterConfig::from_env("FLUTTERWAVE", FlutterwaveAdapter::DEFAULT_BASE_URL, timeout) {
+            registry = registry.register(Arc::new(FlutterwaveAdapter::new(config)));
+        }
+        if let Some(mock) = MockAdapter::from_env() {
+            registry = registry.register(Arc::new(mock));
+        }
+
+        for entry in std::env::var("PAYOUT_ROUTES").unwrap_or_default().split(',') {
+            if let Some((currency, adapter)) = entry.split_once('=') {
+                registry = registry.route(currency.trim(), adapter.trim());
+            }
+        }
+
+        let threshold = std::env::var("PAYOUT_NAME_MATCH_THRESHOLD")
+            .ok()
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .unwrap_or(DEFAULT_NAME_MATCH_THRESHOLD);
+        if threshold > 0.0 {
+            registry = registry.name_enquiry(threshold.min(1.0));
+        }
+
+        info!("Payout adapters: {:?}", registry.names());
+        registry
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.adapters.iter().map(|adapter| adapter.name()).collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn PayoutAdapter>> {
+        self.adapters.iter().find(|adapter| adapter.name() == name).cloned()
+    }
+
+    /// Adapter paying out in a currency: the preferred one if it is
+    /// registered and supports it, else the first that does
+    pub fn for_currency(&self, currency: &str) -> Result<Arc<dyn PayoutAdapter>> {
+        let preferred = self
+            .routes
+            .get(&currency.to_uppercase())
+            .and_then(|name| self.get(name))
+            .filter(|adapter| adapter.supports(currency));
+        preferred
+            .or_else(|| self.adapters.iter().find(|adapter| adapter.supports(currency)).cloned())
+            .ok_or_else(|| PayoutError::Unsupported(currency.to_string()))
+    }
+
+    /// Check a recipient's details for a currency, then ask the adapter
+    /// serving it for the account holder's name
+    pub async fn verify_recipient(&self, currency: &str, recipient: &RecipientDetails) -> Result<RecipientVerification> {
+        let recipient = recipient
+            .validated(currency)
+            .map_err(|e| PayoutError::InvalidRecipient(e.to_string()))?;
+        let mut verification = RecipientVerification {
+            recipient,
+            resolved_name: None,
+            name_match: None,
+            matches: true,
+        };
+        let Some(threshold) = self.name_match_threshold else {
+            return Ok(verification);
+        };
+
+        let adapter = self.for_currency(currency)?;
+        if let Some(resolved) = adapter.resolve_account_name(currency, &verification.recipient).await? {
+            let score = name_match_score(&verification.recipient.account_name, &resolved);
+            verification.matches = score >= threshold;
+            verification.resolved_name = Some(resolved);
+            verification.name_match = Some(score);
+        }
+        Ok(verification)
+    }
+
+    /// Start a payout with the adapter serving its currency, once its
+    /// recipient checks out
+    pub async fn initiate(&self, request: &PaymentRequest) -> Result<PayoutResult> {
+        let verification = self.verify_recipient(&request.currency, &request.recipient_details).await?;
+        if !verification.matches {
+            let given = verification.recipient.account_name;
+            let resolved = verification.resolved_name.unwrap_or_default();
+            warn!("Refusing payout for order {}: account holder {} is not {}", request.order_id, resolved, given);
+            return Err(PayoutError::NameMismatch { given, resolved });
+        }
+        let request = PaymentRequest {
+            recipient_details: verification.recipient,
+            ..request.clone()
+        };
+
+        let adapter = self.for_currency(&request.currency)?;
+        let payout = adapter.initiate(&request).await?;
+        info!(
+            "Payout {} for order {} via {}: {:?}",
+            payout.reference, request.order_id, payout.adapter, payout.status
+        );
+        Ok(payout)
+    }
+
+    /// Look up a payout with the adapter that made it
+    pub async fn status(&self, adapter: &str, reference: &str) -> Result<PayoutResult> {
+        self.get(adapter)
+            .ok_or_else(|| PayoutError::Unsupported(adapter.to_string()))?
+            .status(reference)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payouts::mock::MockOutcome;
+    use crate::payouts::stub::{payment_request, StubPsp, StubRoute};
+    use axum::http::Method;
+    use shared_types::PayoutStatus;
+
+    #[tokio::test]
+    async fn test_registry_routes_by_currency_to_mock() {
+        let registry = AdapterRegistry::new()
+            .register(Arc::new(MockAdapter::new(["NGN".to_string(), "KES".to_string()], MockOutcome::Succeed)))
+            .register(Arc::new(PaystackAdapter::new(HttpAdapterConfig {
+                base_url: "http://127.0.0.1:9".to_string(),
+                secret_key: "sk_unused".to_string(),
+                webhook_secret: None,
+                timeout: Duration::from_secs(1),
+            })))
+            .route("ngn", MockAdapter::NAME)
+            .route("GHS", "missing");
+
+        assert_eq!(registry.for_currency("NGN").unwrap().name(), MockAdapter::NAME);
+        assert_eq!(registry.for_currency("GHS").unwrap().name(), PaystackAdapter::NAME);
+        assert!(matches!(registry.for_currency("EUR"), Err(PayoutError::Unsupported(_))));
+
+        let request = payment_request("NGN", "1000");
+        let payout = registry.initiate(&request).await.unwrap();
+        assert_eq!(payout.status, PayoutStatus::Succeeded);
+        // Retried requests resolve to the same payout
+        assert_eq!(registry.initiate(&request).await.unwrap().reference, payout.reference);
+        assert_eq!(registry.status(MockAdapter::NAME, &payout.reference).await.unwrap().status, PayoutStatus::Succeeded);
+
+        let mut rejected = payment_request("KES", "1000");
+        rejected.proposal_id = format!("0x{}", "ef".repeat(32));
+        rejected.recipient_details.account_number = "0100000000".to_string();
+        rejected.recipient_details.bank_code = Some("68".to_string());
+        assert_eq!(registry.initiate(&rejected).await.unwrap().status, PayoutStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_name_enquiry_guards_payouts() {
+        let psp = StubPsp::start(vec![StubRoute::new(
+            Method::GET,
+            "/bank/resolve",
+            200,
+            serde_json::json!({ "status": true, "message": "Account number resolved", "data": { "account_name": "IBRAHIM MUSA" } }),
+        )])
+        .await;
+        let registry = AdapterRegistry::new()
+            .register(Arc::new(PaystackAdapter::new(psp.config())))
+            .name_enquiry(0.8);
+
+        let request = payment_request("NGN", "1000");
+        let err = registry.initiate(&request).await.unwrap_err();
+        assert!(matches!(err, PayoutError::NameMismatch { .. }));
+        // Refused before any transfer was made
+        assert_eq!(psp.requests().len(), 1);
+
+        let mut recipient = request.recipient_details.clone();
+        recipient.account_name = "Musa Ibrahim".to_string();
+        let verification = registry.verify_recipient("NGN", &recipient).await.unwrap();
+        assert!(verification.matches);
+        assert_eq!(verification.resolved_name.as_deref(), Some("IBRAHIM MUSA"));
+
+        recipient.account_number = "0123456789".to_string();
+        assert!(matches!(registry.verify_recipient("NGN", &recipient).await, Err(PayoutError::InvalidRecipient(_))));
+        assert_eq!(psp.requests().len(), 2);
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use shared_types::{
+    banks::{self, Bank},
+    PaymentRequest, Payout, RecipientDetails, RecipientVerification,
+};
+
+use super::PayoutTracker;
+use crate::error::Result;
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyRecipientRequest {
+    pub currency: String,
+    pub recipient_details: RecipientDetails,
+}
+
+/// Payout execution routes, for internal callers, plus the webhook endpoint
+/// PSPs call
+pub fn router(tracker: Arc<PayoutTracker>) -> Router {
+    Router::new()
+        .route("/banks/:currency", get(list_banks))
+        .route("/recipients/verify", post(verify_recipient))
+        .route("/payouts", post(initiate_payout))
+        .route("/payouts/:adapter/:reference", get(get_payout))
+        .route("/webhooks/payouts/:adapter", post(receive_webhook))
+        .with_state(tracker)
+}
+
+/// Banks and mobile money networks in the directory for a currency
+async fn list_banks(Path(currency): Path<String>) -> Json<Vec<&'static Bank>> {
+    Json(banks::banks(&currency).collect())
+}
+
+/// Validates the recipient and runs name enquiry without paying out
+async fn verify_recipient(
+    State(tracker): State<Arc<PayoutTracker>>,
+    Json(request): Json<VerifyRecipientRequest>,
+) -> Result<Json<RecipientVerification>> {
+    Ok(Json(tracker.verify_recipient(&request.currency, &request.recipient_details).await?))
+}
+
+async fn initiate_payout(
+    State(tracker): State<Arc<PayoutTracker>>,
+    Json(request): Json<PaymentRequest>,
+) -> Result<Json<Payout>> {
+    Ok(Json(tracker.initiate(&request).await?))
+}
+
+/// Looks the payout up at its PSP, recording any progress
+async fn get_payout(
+    State(tracker): State<Arc<PayoutTracker>>,
+    Path((adapter, reference)): Path<(String, String)>,
+) -> Result<Json<Payout>> {
+    Ok(Json(tracker.refresh(&adapter, &reference).await?))
+}
+
+/// Raw body, so the PSP's signature can be checked over the exact bytes
+async fn receive_webhook(
+    State(tracker): State<Arc<PayoutTracker>>,
+    Path(adapter): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode> {
+    tracker.webhook(&adapter, &headers, &body).await?;
+    Ok(StatusCode::OK)
+}
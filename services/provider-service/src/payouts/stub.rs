@@ -0,0 +1,156 @@
+//! Stub PSP for adapter tests
+//!
+//! [`StubPsp`] serves canned JSON responses on a local port and records the
+//! requests it receives, so HTTP adapters can be exercised end to end
+//! without a PSP account:
+//!
+//! ```ignore
+//! let psp = StubPsp::start(vec![StubRoute::new(Method::POST, "/transfer", 200, json!({...}))]).await;
+//! let adapter = PaystackAdapter::new(psp.config());
+//! adapter.initiate(&payment_request("NGN", "1000")).await?;
+//! assert_eq!(psp.requests()[0].body["amount"], 100000);
+//! ```
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, Method, StatusCode, Uri},
+    response::IntoResponse,
+    Json, Router,
+};
+use chrono::Utc;
+use serde_json::Value;
+use shared_types::{PaymentRequest, RecipientDetails};
+
+use super::HttpAdapterConfig;
+
+/// Secret key the stub expects adapters to authenticate with
+pub const STUB_SECRET_KEY: &str = "sk_test_stub";
+
+/// Webhook secret configured for adapters pointing at the stub
+pub const STUB_WEBHOOK_SECRET: &str = "whsec_stub";
+
+/// A canned response for one method and path
+#[derive(Debug, Clone)]
+pub struct StubRoute {
+    pub method: Method,
+    pub path: String,
+    pub status: StatusCode,
+    pub body: Value,
+}
+
+impl StubRoute {
+    pub fn new(method: Method, path: &str, status: u16, body: Value) -> Self {
+        Self {
+            method,
+            path: path.to_string(),
+            status: StatusCode::from_u16(status).expect("valid status code"),
+            body,
+        }
+    }
+}
+
+/// A request the stub received
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub path: String,
+    pub authorization: Option<String>,
+    pub body: Value,
+}
+
+#[derive(Default)]
+struct StubState {
+    routes: Vec<StubRoute>,
+    requests: Mutex<Vec<RecordedRequest>>,
+}
+
+/// A PSP stand-in listening on localhost
+pub struct StubPsp {
+    addr: SocketAddr,
+    state: Arc<StubState>,
+}
+
+impl StubPsp {
+    /// Serve `routes` until the stub is dropped with the test runtime;
+    /// unmatched requests get a 404
+    pub async fn start(routes: Vec<StubRoute>) -> Self {
+        let state = Arc::new(StubState { routes, ..Default::default() });
+        let app = Router::new().fallback(respond).with_state(state.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind stub PSP");
+        let addr = listener.local_addr().expect("stub PSP address");
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.ok();
+        });
+        Self { addr, state }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Adapter settings pointing at the stub
+    pub fn config(&self) -> HttpAdapterConfig {
+        HttpAdapterConfig {
+            base_url: self.url(),
+            secret_key: STUB_SECRET_KEY.to_string(),
+            webhook_secret: Some(STUB_WEBHOOK_SECRET.to_string()),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// Requests received so far, in order
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.requests.lock().expect("stub lock poisoned").clone()
+    }
+}
+
+async fn respond(
+    State(state): State<Arc<StubState>>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let path = uri.path().to_string();
+    state.requests.lock().expect("stub lock poisoned").push(RecordedRequest {
+        method: method.clone(),
+        path: path.clone(),
+        authorization: headers
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+    });
+
+    match state.routes.iter().find(|route| route.method == method && route.path == path) {
+        Some(route) => (route.status, Json(route.body.clone())),
+        None => (StatusCode::NOT_FOUND, Json(serde_json::json!({ "message": "no stub route" }))),
+    }
+}
+
+/// A bank payout request for tests
+pub fn payment_request(currency: &str, amount: &str) -> PaymentRequest {
+    PaymentRequest {
+        proposal_id: format!("0x{}", "ab".repeat(32)),
+        order_id: format!("0x{}", "cd".repeat(32)),
+        provider: "0x742d35cc6634c0532925a3b844bc9e7595f0beb0".to_string(),
+        amount: amount.to_string(),
+        currency: currency.to_string(),
+        recipient_details: RecipientDetails {
+            account_name: "Ada Obi".to_string(),
+            account_number: "0123456785".to_string(),
+            bank_name: Some("GTBank".to_string()),
+            bank_code: Some("058".to_string()),
+            phone_number: None,
+            additional_info: None,
+        },
+        deadline: Utc::now() + chrono::Duration::minutes(30),
+        leg: None,
+    }
+}
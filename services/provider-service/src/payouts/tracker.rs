@@ -0,0 +1,594 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::HeaderMap;
+use chrono::Utc;
+use shared_database::{
+    models::{OrderAllocationModel, PayoutModel},
+    AllocationRepository, KeyRing, OrderRepository, PayoutRepository,
+};
+use shared_messaging::subjects;
+use shared_types::{
+    helpers::bytes_to_hex, split_order_status, OrderAllocation, OrderFailedEvent, OrderStatus, OrderStatusChangedEvent,
+    PaymentRequest, Payout, PayoutResult, PayoutStatus, RecipientDetails, RecipientVerification,
+};
+use tracing::{debug, error, info, warn};
+
+use super::{payout_reference, AdapterRegistry, PayoutError};
+use crate::error::{ProviderServiceError, Result};
+
+/// Payouts looked up per polling pass
+const POLL_BATCH: i64 = 100;
+
+/// Payouts rewrapped per batch after a key rotation
+const REWRAP_BATCH: i64 = 100;
+
+/// How long a payout request may hold its claim without hearing back from
+/// the PSP before a retry takes it over
+const CLAIM_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Order statuses a settled payout fulfils an order from
+const FULFILLABLE: [OrderStatus; 2] = [OrderStatus::Pending, OrderStatus::Accepted];
+
+/// Polling settings, loaded from the environment
+#[derive(Debug, Clone)]
+pub struct TrackerConfig {
+    /// Delay before the first status lookup, doubled after each one
+    pub poll_interval: Duration,
+    /// Longest delay between lookups
+    pub max_poll_interval: Duration,
+    /// Lookups after which an unsettled payout is left for manual review
+    pub max_polls: u32,
+}
+
+impl Default for TrackerConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(30),
+            max_poll_interval: Duration::from_secs(900),
+            max_polls: 50,
+        }
+    }
+}
+
+impl TrackerConfig {
+    /// Load `PAYOUT_POLL_INTERVAL_SECS`, `PAYOUT_POLL_MAX_INTERVAL_SECS` and
+    /// `PAYOUT_POLL_MAX_ATTEMPTS`, falling back to defaults
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let env_u64 = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|value| *value > 0)
+        };
+
+        let poll_interval = env_u64("PAYOUT_POLL_INTERVAL_SECS")
+            .map(Duration::from_secs)
+            .unwrap_or(defaults.poll_interval);
+        Self {
+            poll_interval,
+            max_poll_interval: env_u64("PAYOUT_POLL_MAX_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.max_poll_interval)
+                .max(poll_interval),
+            max_polls: env_u64("PAYOUT_POLL_MAX_ATTEMPTS")
+                .map(|polls| polls.min(u32::MAX as u64) as u32)
+                .unwrap_or(defaults.max_polls),
+        }
+    }
+
+    /// Delay before the next lookup of a payout already looked up `polls` times
+    pub fn backoff(&self, polls: u32) -> Duration {
+        self.poll_interval
+            .saturating_mul(2u32.saturating_pow(polls.min(16)))
+            .min(self.max_poll_interval)
+    }
+}
+
+/// Follows payouts from initiation until the PSP settles them
+///
+/// Starting the first payout of a pending order marks it accepted and
+/// publishes `order.accepted`.
+///
+/// Updates arrive from PSP webhooks and from polling unsettled payouts, in
+/// any order and possibly more than once. Each is applied through the
+/// [`PayoutStatus`] state machine with a conditional write, so whichever
+/// update settles a payout first publishes `order.fulfilled` or
+/// `order.failed`, and later duplicates change nothing. A successful payout
+/// marks its order fulfilled before `order.fulfilled` is published. A
+/// payout for one leg of a split order fills what remained of the leg
+/// instead, moving the order on as its legs settle.
+///
+/// With a [`KeyRing`], each payout's recipient details are stored sealed;
+/// without one they are not stored at all.
+pub struct PayoutTracker {
+    registry: Arc<AdapterRegistry>,
+    payouts: PayoutRepository,
+    orders: OrderRepository,
+    allocations: AllocationRepository,
+    nats: async_nats::Client,
+    config: TrackerConfig,
+    keys: Option<KeyRing>,
+}
+
+impl PayoutTracker {
+    pub fn new(
+        registry: Arc<AdapterRegistry>,
+        payouts: PayoutRepository,
+        orders: OrderRepository,
+        allocations: AllocationRepository,
+        nats: async_nats::Client,
+        config: TrackerConfig,
+    ) -> Self {
+        Self { registry, payouts, orders, allocations, nats, config, keys: None }
+    }
+
+    /// Store recipient details sealed with `keys`
+    pub fn sealing(mut self, keys: KeyRing) -> Self {
+        self.keys = Some(keys);
+        self
+    }
+
+    /// Start a payout and track it
+    ///
+    /// The payout is claimed under its [`payout_reference`] before the PSP
+    /// is called, so a retried request returns the payout already recorded
+    /// for it and only a new one reaches the PSP. A request the PSP does not
+    /// accept gives up its claim and can be retried.
+    pub async fn initiate(&self, request: &PaymentRequest) -> Result<Payout> {
+        let key = payout_reference(request);
+        if let Some(existing) = self.payouts.get_by_payout_reference(&key).await? {
+            return recorded(existing);
+        }
+
+        let adapter = self.registry.for_currency(&request.currency)?;
+        let now = Utc::now();
+        let payout = Payout {
+            order_id: request.order_id.clone(),
+            proposal_id: request.proposal_id.clone(),
+            provider: request.provider.clone(),
+            adapter: adapter.name().to_string(),
+            reference: key.clone(),
+            leg: request.leg,
+            amount: request.amount.clone(),
+            currency: request.currency.to_uppercase(),
+            status: PayoutStatus::Pending,
+            message: None,
+            polls: 0,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        };
+        let mut model = PayoutModel::from_domain(&payout, None);
+        if let Some(keys) = &self.keys {
+            let envelope = keys.seal_recipient(&request.recipient_details, model.id.as_bytes())?;
+            model = model.with_recipient(envelope);
+        }
+        let stale_before = now - chrono::Duration::from_std(CLAIM_TIMEOUT).unwrap_or_default();
+        if !self.payouts.claim(&model, stale_before).await? {
+            // Lost a race with a concurrent request for the same payout
+            let existing = self.payouts.get_by_payout_reference(&key).await?;
+            return existing.map(recorded).unwrap_or_else(|| Err(in_progress(&key)));
+        }
+
+        let result = match self.registry.initiate(request).await {
+            Ok(result) => result,
+            Err(e) => {
+                // The PSP dedupes on the payout reference, so a retry is safe
+                // even if this request reached it
+                self.payouts.release_claim(model.id).await?;
+                return Err(e.into());
+            }
+        };
+        let next_poll_at = Utc::now() + chrono::Duration::from_std(self.config.poll_interval).unwrap_or_default();
+        let initiated = self
+            .payouts
+            .confirm(model.id, &result.reference, next_poll_at)
+            .await?
+            .ok_or_else(|| in_progress(&key))?;
+        self.accept_order(&initiated).await?;
+
+        self.apply(&result).await?;
+        self.get(&result.adapter, &result.reference).await
+    }
+
+    /// Check a recipient before a payout is requested, by the same rules
+    /// and name enquiry [`Self::initiate`] applies
+    pub async fn verify_recipient(&self, currency: &str, recipient: &RecipientDetails) -> Result<RecipientVerification> {
+        Ok(self.registry.verify_recipient(currency, recipient).await?)
+    }
+
+    /// A tracked payout, as last recorded
+    pub async fn get(&self, adapter: &str, reference: &str) -> Result<Payout> {
+        let payout = self
+            .payouts
+            .get(adapter, reference)
+            .await?
+            .ok_or_else(|| PayoutError::NotFound(format!("{}/{}", adapter, reference)))?;
+        Ok(payout.to_domain())
+    }
+
+    /// Look a payout up at its PSP now and record what it reports
+    pub async fn refresh(&self, adapter: &str, reference: &str) -> Result<Payout> {
+        let result = self.registry.status(adapter, reference).await?;
+        self.apply(&result).await?;
+        self.get(adapter, reference).await
+    }
+
+    /// Record a payout update carried by a webhook from `adapter`
+    ///
+    /// Webhooks for payouts this service did not make are ignored, so the
+    /// PSP does not keep retrying them.
+    pub async fn webhook(&self, adapter: &str, headers: &HeaderMap, body: &[u8]) -> Result<()> {
+        let adapter = self
+            .registry
+            .get(adapter)
+            .ok_or_else(|| PayoutError::Unsupported(adapter.to_string()))?;
+        let Some(result) = adapter.webhook(headers, body)? else {
+            return Ok(());
+        };
+        match self.apply(&result).await {
+            Err(ProviderServiceError::Payout(PayoutError::NotFound(_))) => {
+                warn!("Webhook from {} for unknown payout {}", result.adapter, result.reference);
+                Ok(())
+            }
+            other => other.map(|_| ()),
+        }
+    }
+
+    /// Move a payout to the status its PSP reports, if that is a step forward
+    ///
+    /// # Returns
+    /// * `Result<Option<Payout>>` - The updated payout, or None when the
+    ///   update was stale or a duplicate
+    pub async fn apply(&self, result: &PayoutResult) -> Result<Option<Payout>> {
+        // A payout moves at most twice, so losing a race more often than that
+        // means the update is no longer applicable
+        for _ in 0..3 {
+            let current = self
+                .payouts
+                .get(&result.adapter, &result.reference)
+                .await?
+                .ok_or_else(|| PayoutError::NotFound(format!("{}/{}", result.adapter, result.reference)))?;
+            let previous = current.status();
+            if !previous.can_transition_to(result.status) {
+                debug!(
+                    "Ignoring {:?} for payout {} already {:?}",
+                    result.status, result.reference, previous
+                );
+                return Ok(None);
+            }
+
+            // Fulfil the order before writing, so a failed update leaves the
+            // payout to be settled by the next one. The conditional write
+            // moves the order once, and only that update publishes it.
+            if result.status == PayoutStatus::Succeeded {
+                if let Some((subject, event)) = self.fulfil_order(&current).await? {
+                    self.publish(subject, &current.to_domain(), &event).await;
+                }
+            }
+
+            let Some(updated) = self
+                .payouts
+                .transition(
+                    &result.adapter,
+                    &result.reference,
+                    previous.as_str(),
+                    result.status.as_str(),
+                    result.message.as_deref(),
+                    result.status.is_final(),
+                )
+                .await?
+            else {
+                continue;
+            };
+
+            let payout = updated.to_domain();
+            info!(
+                "Payout {} for order {} moved {:?} -> {:?}",
+                payout.reference, payout.order_id, previous, payout.status
+            );
+            match payout.status {
+                PayoutStatus::Failed => {
+                    let event = OrderFailedEvent {
+                        order_id: payout.order_id.clone(),
+                        provider: payout.provider.clone(),
+                        reason: payout
+                            .message
+                            .clone()
+                            .unwrap_or_else(|| format!("Payout failed at {}", payout.adapter)),
+                        timestamp: Utc::now(),
+                    };
+                    self.publish(subjects::ORDER_FAILED, &payout, &event).await;
+                }
+                PayoutStatus::Pending | PayoutStatus::Processing | PayoutStatus::Succeeded => {}
+            }
+            return Ok(Some(payout));
+        }
+        Ok(None)
+    }
+
+    /// Mark a pending order accepted once a provider starts paying it out
+    async fn accept_order(&self, payout: &PayoutModel) -> Result<()> {
+        let accepted = self
+            .orders
+            .transition_status(&payout.order_id, &[OrderStatus::Pending.as_str()], OrderStatus::Accepted.as_str())
+            .await?;
+        if let Some((order, _)) = accepted {
+            let event = order.status_changed(Some(OrderStatus::Pending));
+            if let Err(e) = shared_messaging::publish_event(&self.nats, subjects::ORDER_ACCEPTED, &event).await {
+                error!("Failed to publish {} for order {}: {}", subjects::ORDER_ACCEPTED, event.order_id, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Mark a settled payout's order fulfilled, or fill its leg of a split
+    /// order, returning the subject and event to publish when this call
+    /// moved the order
+    async fn fulfil_order(&self, payout: &PayoutModel) -> Result<Option<(&'static str, OrderStatusChangedEvent)>> {
+        if let Some(leg) = payout.leg {
+            return self.fill_leg(payout, leg as u32).await;
+        }
+        // Legs of a split order are only filled by their own payouts
+        if !self.allocations.list_for_order(&payout.order_id).await?.is_empty() {
+            return Ok(None);
+        }
+        let from = FULFILLABLE.map(|status| status.as_str());
+        let fulfilled = self
+            .orders
+            .transition_status(&payout.order_id, &from, OrderStatus::Fulfilled.as_str())
+            .await?;
+        Ok(fulfilled.map(|(order, previous)| (subjects::ORDER_FULFILLED, order.status_changed(previous.parse().ok()))))
+    }
+
+    /// Record a settled leg payout as the fill of what remained of its leg,
+    /// with the order status that implies
+    async fn fill_leg(&self, payout: &PayoutModel, leg: u32) -> Result<Option<(&'static str, OrderStatusChangedEvent)>> {
+        let mut legs: Vec<OrderAllocation> = self
+            .allocations
+            .list_for_order(&payout.order_id)
+            .await?
+            .iter()
+            .map(OrderAllocationModel::to_domain)
+            .collect();
+        let previous_filled_amount = match legs.iter().find(|allocation| allocation.leg == leg) {
+            Some(allocation) => allocation.filled_amount.clone(),
+            None => {
+                warn!("Payout {} is for leg {} its order does not have", payout.payout_reference, leg);
+                return Ok(None);
+            }
+        };
+        let Some(index) = settle_leg(&mut legs, leg, &payout.payout_reference) else {
+            // Already settled by a duplicate update, or closed as failed
+            return Ok(None);
+        };
+
+        let order = self.orders.get_by_order_id(&payout.order_id).await?;
+        let previous_status = order.status.parse::<OrderStatus>().ok();
+        let status = split_order_status(&legs).filter(|status| Some(*status) != previous_status);
+        let model = OrderAllocationModel::from_domain(&legs[index]);
+        if !self
+            .allocations
+            .record_fill(&model, &previous_filled_amount, status.map(|s| s.as_str()))
+            .await?
+        {
+            return Err(ProviderServiceError::Conflict(format!(
+                "Leg {} of order {} changed concurrently",
+                leg,
+                bytes_to_hex(&payout.order_id)
+            )));
+        }
+        info!("Payout {} filled leg {} of order {}", payout.payout_reference, leg, bytes_to_hex(&payout.order_id));
+
+        Ok(status.map(|status| {
+            let subject = match status {
+                OrderStatus::Fulfilled => subjects::ORDER_FULFILLED,
+                _ => subjects::ORDER_PARTIALLY_FULFILLED,
+            };
+            let event = OrderStatusChangedEvent {
+                order_id: bytes_to_hex(&order.order_id),
+                user_address: bytes_to_hex(&order.user_address),
+                integrator_address: bytes_to_hex(&order.integrator_address),
+                previous_status,
+                status,
+                timestamp: Utc::now(),
+            };
+            (subject, event)
+        }))
+    }
+
+    async fn publish<T: serde::Serialize>(&self, subject: &str, payout: &Payout, event: &T) {
+        if let Err(e) = shared_messaging::publish_event(&self.nats, subject, event).await {
+            error!("Failed to publish {} for payout {}: {}", subject, payout.reference, e);
+        }
+    }
+
+    /// Look up every unsettled payout whose next lookup is due
+    pub async fn poll(&self) -> Result<()> {
+        let now = Utc::now();
+        for payout in self.payouts.list_due(now, POLL_BATCH).await? {
+            let settled = match self.registry.status(&payout.adapter, &payout.reference).await {
+                Ok(result) => match self.apply(&result).await {
+                    Ok(_) => result.status.is_final(),
+                    Err(e) => {
+                        warn!("Failed to record status of payout {}: {}", payout.reference, e);
+                        false
+                    }
+                },
+                Err(e) => {
+                    warn!("Status lookup for payout {} failed: {}", payout.reference, e);
+                    false
+                }
+            };
+            if settled {
+                continue;
+            }
+
+            let polls = payout.polls.max(0) as u32 + 1;
+            let next_poll_at = if polls >= self.config.max_polls {
+                error!(
+                    "Payout {} for order {} unsettled after {} lookups; reconcile it manually",
+                    payout.reference,
+                    bytes_to_hex(&payout.order_id),
+                    polls
+                );
+                None
+            } else {
+                Some(now + chrono::Duration::from_std(self.config.backoff(polls)).unwrap_or_default())
+            };
+            self.payouts.schedule_poll(payout.id, next_poll_at).await?;
+        }
+        Ok(())
+    }
+
+    /// Rewrap the data keys of recipients sealed under keys other than the
+    /// active one, after a rotation
+    ///
+    /// # Returns
+    /// * `Result<usize>` - Payouts rewrapped
+    pub async fn rewrap_recipients(&self) -> Result<usize> {
+        let Some(keys) = &self.keys else {
+            return Ok(0);
+        };
+        let mut rewrapped = 0;
+        loop {
+            let batch = self
+                .payouts
+                .list_recipients_to_rewrap(keys.active_key_id(), REWRAP_BATCH)
+                .await?;
+            if batch.is_empty() {
+                return Ok(rewrapped);
+            }
+            let mut progressed = false;
+            for payout in batch {
+                let Some(envelope) = payout.recipient() else {
+                    continue;
+                };
+                match keys.rewrap(&envelope) {
+                    Ok(Some(updated)) => {
+                        if self
+                            .payouts
+                            .rewrap_recipient(payout.id, &envelope.key_id, &updated.key_id, &updated.wrapped_key)
+                            .await?
+                        {
+                            rewrapped += 1;
+                        }
+                        progressed = true;
+                    }
+                    Ok(None) => {}
+                    Err(e) => warn!("Cannot rewrap recipient of payout {}: {}", payout.reference, e),
+                }
+            }
+            // Rows whose key is missing from the ring stay listed; stop
+            // rather than fetching them again
+            if !progressed {
+                return Ok(rewrapped);
+            }
+        }
+    }
+
+    /// Poll unsettled payouts in the background
+    pub fn spawn_poller(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.config.poll_interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.poll().await {
+                    warn!("Payout polling failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+/// A payout found under its payout reference, once the PSP has accepted it
+fn recorded(payout: PayoutModel) -> Result<Payout> {
+    if !payout.is_initiated() {
+        return Err(in_progress(&payout.payout_reference));
+    }
+    Ok(payout.to_domain())
+}
+
+/// Fill leg `leg` with what remained of it, as its settled payout
+/// `reference` paid out
+///
+/// # Returns
+/// * `Option<usize>` - Index of the filled leg, or None when the order has
+///   no such leg or it is closed
+fn settle_leg(legs: &mut [OrderAllocation], leg: u32, reference: &str) -> Option<usize> {
+    let index = legs.iter().position(|allocation| allocation.leg == leg)?;
+    let remaining = legs[index].remaining().to_string();
+    legs[index].record_fill(&remaining, Some(reference.to_string())).ok()?;
+    Some(index)
+}
+
+fn in_progress(payout_reference: &str) -> ProviderServiceError {
+    ProviderServiceError::Conflict(format!("Payout {} is already being initiated", payout_reference))
+}
+
+#[cfg(test)]
+mod tests {
+    use shared_types::AllocationStatus;
+
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_cap() {
+        let config = TrackerConfig {
+            poll_interval: Duration::from_secs(30),
+            max_poll_interval: Duration::from_secs(300),
+            max_polls: 10,
+        };
+        assert_eq!(config.backoff(0), Duration::from_secs(30));
+        assert_eq!(config.backoff(1), Duration::from_secs(60));
+        assert_eq!(config.backoff(3), Duration::from_secs(240));
+        assert_eq!(config.backoff(4), Duration::from_secs(300));
+        assert_eq!(config.backoff(40), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_settled_payouts_fulfil_only_open_orders() {
+        let from = FULFILLABLE.map(|status| status.as_str());
+        assert_eq!(from, ["PENDING", "ACCEPTED"]);
+
+        // Refunded, expired or already fulfilled orders stay as they are, so
+        // a duplicate success publishes nothing
+        for status in [OrderStatus::Fulfilled, OrderStatus::Refunded, OrderStatus::Expired] {
+            assert!(!from.contains(&status.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_leg_payouts_fill_their_leg() {
+        let allocation = |leg: u32, amount: &str, filled_amount: &str| OrderAllocation {
+            id: uuid::Uuid::new_v4(),
+            order_id: format!("0x{}", "cd".repeat(32)),
+            leg,
+            provider: format!("0x{}", "0".repeat(40)),
+            amount: amount.to_string(),
+            filled_amount: filled_amount.to_string(),
+            status: if filled_amount == "0" { AllocationStatus::Pending } else { AllocationStatus::PartiallyFilled },
+            tx_hash: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            settled_at: None,
+        };
+        let mut legs = vec![allocation(1, "1000", "0"), allocation(2, "500", "200")];
+        assert_eq!(settle_leg(&mut legs, 3, "pn_ab_l3"), None);
+
+        assert_eq!(settle_leg(&mut legs, 1, "pn_ab_l1"), Some(0));
+        assert_eq!(legs[0].status, AllocationStatus::Settled);
+        assert_eq!(legs[0].filled_amount, "1000");
+        assert_eq!(legs[0].tx_hash.as_deref(), Some("pn_ab_l1"));
+        assert_eq!(split_order_status(&legs), Some(OrderStatus::PartiallyFulfilled));
+
+        // A duplicate success fills nothing more
+        assert_eq!(settle_leg(&mut legs, 1, "pn_ab_l1"), None);
+
+        assert_eq!(settle_leg(&mut legs, 2, "pn_ab_l2"), Some(1));
+        assert_eq!(legs[1].filled_amount, "500");
+        assert_eq!(split_order_status(&legs), Some(OrderStatus::Fulfilled));
+    }
+}
@@ -0,0 +1,76 @@
+//! Provider-submitted FX rates
+//!
+//! A provider quotes the USD rate it pays out at in each currency it has a
+//! live intent for. The latest submission per provider and pair is kept in
+//! Redis, where the FX oracle reads it alongside its other sources.
+
+pub mod routes;
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use rust_decimal::Decimal;
+use shared_fx::{FxPair, ProviderRateSource, USD};
+use shared_types::{Currency, SubmitRateRequest};
+use tracing::info;
+
+use crate::error::{ProviderServiceError, Result};
+use crate::intents::IntentService;
+
+/// Records the rates providers submit
+pub struct RateService {
+    intents: Arc<IntentService>,
+    source: ProviderRateSource,
+}
+
+impl RateService {
+    pub fn new(intents: Arc<IntentService>, source: ProviderRateSource) -> Self {
+        Self { intents, source }
+    }
+
+    /// Record a provider's rate for a currency it has a live intent in
+    pub async fn submit(&self, provider: &str, request: &SubmitRateRequest) -> Result<()> {
+        let rate = parse_submission(request)?;
+        let intents = self.intents.list_active(provider).await?;
+        let currency = Currency::from(request.currency.as_str());
+        if !intents.iter().any(|intent| intent.currency == currency) {
+            return Err(ProviderServiceError::InvalidRequest(format!(
+                "No live {} intent to submit a rate for",
+                request.currency
+            )));
+        }
+
+        let pair = FxPair::new(USD, &request.currency);
+        self.source.submit(provider, &pair, rate).await?;
+        info!("Provider {} submitted {} for {}", provider, rate, pair);
+        Ok(())
+    }
+}
+
+/// Check the currency and read the rate of a submission
+fn parse_submission(request: &SubmitRateRequest) -> Result<Decimal> {
+    if !shared_utils::validate_currency(&request.currency) {
+        return Err(ProviderServiceError::InvalidRequest(format!("Unsupported currency: {}", request.currency)));
+    }
+    Decimal::from_str(request.rate.trim())
+        .ok()
+        .filter(|rate| rate.is_sign_positive() && !rate.is_zero())
+        .ok_or_else(|| ProviderServiceError::InvalidRequest("rate must be a positive decimal".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submissions_need_a_positive_rate_and_known_currency() {
+        let submission = |currency: &str, rate: &str| SubmitRateRequest {
+            currency: currency.to_string(),
+            rate: rate.to_string(),
+        };
+        assert_eq!(parse_submission(&submission("NGN", "1550.25")).unwrap(), Decimal::new(155025, 2));
+        for (currency, rate) in [("NGN", "0"), ("NGN", "-3"), ("NGN", "abc"), ("XYZ", "1550")] {
+            assert!(parse_submission(&submission(currency, rate)).is_err());
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::post,
+    Json, Router,
+};
+use shared_types::SubmitRateRequest;
+
+use super::RateService;
+use crate::error::Result;
+
+/// Rate submission routes; the gateway authenticates the provider and passes
+/// its address in the path
+pub fn router(service: Arc<RateService>) -> Router {
+    Router::new()
+        .route("/providers/:provider/rates", post(submit_rate))
+        .with_state(service)
+}
+
+async fn submit_rate(
+    State(service): State<Arc<RateService>>,
+    Path(provider): Path<String>,
+    Json(request): Json<SubmitRateRequest>,
+) -> Result<StatusCode> {
+    service.submit(&provider, &request).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,13 @@
+//! Connect with `DATABASE_URL` and run the migrations
+//!
+//! ```text
+//! cargo run -p shared-database --example test_connection
+//! ```
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let pool = shared_database::initialize_database().await?;
+    let orders: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM orders").fetch_one(&pool).await?;
+    println!("Connected; {} orders stored", orders);
+    Ok(())
+}
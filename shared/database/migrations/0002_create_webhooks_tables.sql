@@ -0,0 +1,69 @@
+-- ------------------------------------------------------------
+-- Outbound webhooks: integrator endpoints, events and deliveries
+-- ------------------------------------------------------------
+
+CREATE TYPE webhook_delivery_status AS ENUM ('PENDING', 'SUCCEEDED', 'EXHAUSTED');
+
+CREATE TABLE IF NOT EXISTS webhook_endpoints (
+    id                    UUID        PRIMARY KEY,
+    integrator_address    BYTEA       NOT NULL,
+    url                   TEXT        NOT NULL,
+    secret                TEXT        NOT NULL,
+    event_types           TEXT[]      NOT NULL,
+    is_active             BOOLEAN     NOT NULL DEFAULT true,
+    consecutive_failures  INTEGER     NOT NULL DEFAULT 0,
+    disabled_at           TIMESTAMPTZ,
+    created_at            TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at            TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One row per order status transition; payload is the exact JSON body sent
+CREATE TABLE IF NOT EXISTS webhook_events (
+    id                  UUID        PRIMARY KEY,
+    integrator_address  BYTEA       NOT NULL,
+    order_id            BYTEA       NOT NULL,
+    event_type          VARCHAR(32) NOT NULL,
+    payload             JSONB       NOT NULL,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Delivery of one event to one endpoint, retried until it succeeds or is exhausted
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id               BIGSERIAL               PRIMARY KEY,
+    endpoint_id      UUID                    NOT NULL REFERENCES webhook_endpoints(id) ON DELETE CASCADE,
+    event_id         UUID                    NOT NULL REFERENCES webhook_events(id) ON DELETE CASCADE,
+    status           webhook_delivery_status NOT NULL DEFAULT 'PENDING',
+    attempts         INTEGER                 NOT NULL DEFAULT 0,
+    next_attempt_at  TIMESTAMPTZ             NOT NULL DEFAULT NOW(),
+    last_status_code INTEGER,
+    last_error       TEXT,
+    created_at       TIMESTAMPTZ             NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ             NOT NULL DEFAULT NOW()
+);
+
+-- Audit trail of every HTTP attempt
+CREATE TABLE IF NOT EXISTS webhook_delivery_attempts (
+    id            BIGSERIAL   PRIMARY KEY,
+    delivery_id   BIGINT      NOT NULL REFERENCES webhook_deliveries(id) ON DELETE CASCADE,
+    attempt       INTEGER     NOT NULL,
+    status_code   INTEGER,
+    error         TEXT,
+    succeeded     BOOLEAN     NOT NULL,
+    duration_ms   BIGINT      NOT NULL,
+    attempted_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_endpoints_integrator ON webhook_endpoints(integrator_address);
+CREATE INDEX IF NOT EXISTS idx_webhook_events_order_id      ON webhook_events(order_id);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due       ON webhook_deliveries(status, next_attempt_at);
+CREATE INDEX IF NOT EXISTS idx_webhook_attempts_delivery    ON webhook_delivery_attempts(delivery_id);
+
+CREATE TRIGGER trg_webhook_endpoints_updated_at
+    BEFORE UPDATE ON webhook_endpoints
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
+
+CREATE TRIGGER trg_webhook_deliveries_updated_at
+    BEFORE UPDATE ON webhook_deliveries
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,23 @@
+-- ------------------------------------------------------------
+-- Signed, time-limited payout quotes referenced at order creation
+-- ------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS quotes (
+    quote_id            UUID        PRIMARY KEY,
+    token               BYTEA       NOT NULL,
+    amount              TEXT        NOT NULL,
+    currency            VARCHAR(10) NOT NULL,
+    integrator_address  BYTEA,
+    -- Provider whose intent the quote was priced against
+    provider            BYTEA       NOT NULL,
+    rate                TEXT        NOT NULL,
+    provider_fee_bps    INTEGER     NOT NULL,
+    integrator_fee_bps  INTEGER     NOT NULL,
+    protocol_fee_bps    INTEGER     NOT NULL,
+    fiat_amount         TEXT        NOT NULL,
+    signature           TEXT        NOT NULL,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at          TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_quotes_expires_at ON quotes(expires_at);
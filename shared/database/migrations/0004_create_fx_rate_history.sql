@@ -0,0 +1,19 @@
+-- ------------------------------------------------------------
+-- FX rates used to price quotes and orders, kept for audits
+-- ------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS fx_rate_history (
+    id              BIGSERIAL   PRIMARY KEY,
+    quote_id        UUID        REFERENCES quotes(quote_id) ON DELETE SET NULL,
+    order_id        BYTEA,
+    pair            VARCHAR(21) NOT NULL,
+    rate            TEXT        NOT NULL,
+    sources         TEXT[]      NOT NULL,
+    rejected_count  INTEGER     NOT NULL DEFAULT 0,
+    stale           BOOLEAN     NOT NULL DEFAULT false,
+    observed_at     TIMESTAMPTZ NOT NULL,
+    recorded_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_fx_rate_history_quote_id ON fx_rate_history(quote_id);
+CREATE INDEX IF NOT EXISTS idx_fx_rate_history_order_id ON fx_rate_history(order_id);
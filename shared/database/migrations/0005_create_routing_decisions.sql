@@ -0,0 +1,21 @@
+-- ------------------------------------------------------------
+-- AI router decisions, kept to explain provider selection
+-- ------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS routing_decisions (
+    id               UUID        PRIMARY KEY,
+    order_id         BYTEA       NOT NULL,
+    currency         VARCHAR(10) NOT NULL,
+    tier             order_tier  NOT NULL,
+    scorer           VARCHAR(64) NOT NULL,
+    scorer_version   VARCHAR(128) NOT NULL,
+    chosen_provider  BYTEA,
+    -- Ranked CandidateEvaluation list
+    candidates       JSONB       NOT NULL,
+    decided_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_routing_decisions_order_id ON routing_decisions(order_id);
+CREATE INDEX IF NOT EXISTS idx_routing_decisions_decided_at ON routing_decisions(decided_at);
+-- Lets a provider's lost decisions be found via the candidate list
+CREATE INDEX IF NOT EXISTS idx_routing_decisions_candidates ON routing_decisions USING GIN (candidates jsonb_path_ops);
@@ -0,0 +1,32 @@
+-- ------------------------------------------------------------
+-- Split-order routing: orders too large for any one provider are
+-- divided into legs, each settled separately
+-- ------------------------------------------------------------
+
+ALTER TYPE order_status ADD VALUE IF NOT EXISTS 'PARTIALLY_FULFILLED' AFTER 'ACCEPTED';
+
+CREATE TABLE IF NOT EXISTS order_allocations (
+    id             UUID        PRIMARY KEY,
+    order_id       BYTEA       NOT NULL REFERENCES orders(order_id) ON DELETE CASCADE,
+    leg            INTEGER     NOT NULL CHECK (leg > 0),
+    provider       BYTEA       NOT NULL,
+    amount         TEXT        NOT NULL,
+    filled_amount  TEXT        NOT NULL DEFAULT '0',
+    status         VARCHAR(20) NOT NULL DEFAULT 'PENDING'
+                   CHECK (status IN ('PENDING', 'PARTIALLY_FILLED', 'SETTLED', 'FAILED')),
+    tx_hash        BYTEA,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    settled_at     TIMESTAMPTZ,
+    UNIQUE (order_id, leg)
+);
+
+CREATE INDEX IF NOT EXISTS idx_order_allocations_provider ON order_allocations(provider);
+
+CREATE TRIGGER trg_order_allocations_updated_at
+    BEFORE UPDATE ON order_allocations
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
+
+-- Planned legs, recorded with the decision that produced them
+ALTER TABLE routing_decisions ADD COLUMN IF NOT EXISTS allocations JSONB NOT NULL DEFAULT '[]';
@@ -0,0 +1,24 @@
+-- ------------------------------------------------------------
+-- Tier limits per token, in whole tokens. Services reload them
+-- periodically, so edits apply without a restart
+-- ------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS tier_limits (
+    token       BYTEA         NOT NULL,
+    -- Empty for limits that apply to every currency without its own row
+    currency    VARCHAR(10)   NOT NULL DEFAULT '',
+    decimals    SMALLINT      NOT NULL CHECK (decimals BETWEEN 0 AND 24),
+    alpha_max   NUMERIC(40, 18) NOT NULL,
+    beta_max    NUMERIC(40, 18) NOT NULL,
+    delta_max   NUMERIC(40, 18) NOT NULL,
+    omega_max   NUMERIC(40, 18) NOT NULL,
+    created_at  TIMESTAMPTZ   NOT NULL DEFAULT NOW(),
+    updated_at  TIMESTAMPTZ   NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (token, currency),
+    CHECK (alpha_max > 0 AND alpha_max < beta_max AND beta_max < delta_max AND delta_max < omega_max)
+);
+
+CREATE TRIGGER trg_tier_limits_updated_at
+    BEFORE UPDATE ON tier_limits
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,34 @@
+-- ------------------------------------------------------------
+-- Fiat payouts made through PSP adapters, tracked until the PSP
+-- confirms or rejects them (by webhook or by polling)
+-- ------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS payouts (
+    id            UUID        PRIMARY KEY,
+    order_id      BYTEA       NOT NULL,
+    proposal_id   BYTEA       NOT NULL,
+    provider      BYTEA       NOT NULL,
+    adapter       VARCHAR(32) NOT NULL,
+    -- PSP's reference, unique per adapter
+    reference     TEXT        NOT NULL,
+    amount        TEXT        NOT NULL,
+    currency      VARCHAR(10) NOT NULL,
+    status        VARCHAR(20) NOT NULL
+                  CHECK (status IN ('pending', 'processing', 'succeeded', 'failed')),
+    message       TEXT,
+    polls         INTEGER     NOT NULL DEFAULT 0,
+    -- Next status lookup; NULL once final or when polling gave up
+    next_poll_at  TIMESTAMPTZ,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at  TIMESTAMPTZ,
+    UNIQUE (adapter, reference)
+);
+
+CREATE INDEX IF NOT EXISTS idx_payouts_order_id ON payouts(order_id);
+CREATE INDEX IF NOT EXISTS idx_payouts_next_poll_at ON payouts(next_poll_at) WHERE next_poll_at IS NOT NULL;
+
+CREATE TRIGGER trg_payouts_updated_at
+    BEFORE UPDATE ON payouts
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,38 @@
+-- ------------------------------------------------------------
+-- Disputes: users contest payouts they did not receive. Settlement
+-- of the order is held until an admin releases or refunds it
+-- ------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS disputes (
+    id                     UUID        PRIMARY KEY,
+    order_id               BYTEA       NOT NULL REFERENCES orders(order_id) ON DELETE CASCADE,
+    user_address           BYTEA       NOT NULL,
+    provider               BYTEA       NOT NULL,
+    reason                 TEXT        NOT NULL,
+    status                 VARCHAR(20) NOT NULL DEFAULT 'open'
+                           CHECK (status IN ('open', 'evidence_submitted', 'resolved')),
+    -- Provider's PaymentProof, evidence included in its metadata
+    proof                  JSONB,
+    outcome                VARCHAR(10) CHECK (outcome IN ('release', 'refund')),
+    resolution_note        TEXT,
+    resolved_by            BYTEA,
+    opened_at              TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    evidence_submitted_at  TIMESTAMPTZ,
+    resolved_at            TIMESTAMPTZ,
+    updated_at             TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((status = 'resolved') = (outcome IS NOT NULL))
+);
+
+-- At most one dispute under review per order
+CREATE UNIQUE INDEX IF NOT EXISTS idx_disputes_active_order
+    ON disputes(order_id) WHERE status <> 'resolved';
+CREATE INDEX IF NOT EXISTS idx_disputes_provider ON disputes(provider);
+CREATE INDEX IF NOT EXISTS idx_disputes_status ON disputes(status, opened_at);
+
+CREATE TRIGGER trg_disputes_updated_at
+    BEFORE UPDATE ON disputes
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
+
+ALTER TABLE provider_reputation ADD COLUMN IF NOT EXISTS disputes BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE provider_reputation ADD COLUMN IF NOT EXISTS disputes_lost BIGINT NOT NULL DEFAULT 0;
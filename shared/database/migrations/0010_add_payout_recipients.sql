@@ -0,0 +1,13 @@
+-- ------------------------------------------------------------
+-- Recipient details of payouts, envelope-encrypted: the record is
+-- sealed with its own data key, which is stored wrapped by the
+-- key-encryption key recipient_key_id. Rows are rewrapped when the
+-- active key is rotated.
+-- ------------------------------------------------------------
+
+ALTER TABLE payouts
+    ADD COLUMN IF NOT EXISTS recipient_key_id      VARCHAR(64),
+    ADD COLUMN IF NOT EXISTS recipient_wrapped_key BYTEA,
+    ADD COLUMN IF NOT EXISTS recipient_ciphertext  BYTEA;
+
+CREATE INDEX IF NOT EXISTS idx_payouts_recipient_key_id ON payouts(recipient_key_id) WHERE recipient_key_id IS NOT NULL;
@@ -0,0 +1,64 @@
+-- ------------------------------------------------------------
+-- Provider collateral: stake per token from on-chain deposits and
+-- withdrawals, locked against assigned orders and slashed on
+-- proven failures
+-- ------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS provider_stakes (
+    provider    BYTEA          NOT NULL,
+    token       BYTEA          NOT NULL,
+    staked      NUMERIC(78, 0) NOT NULL DEFAULT 0 CHECK (staked >= 0),
+    locked      NUMERIC(78, 0) NOT NULL DEFAULT 0 CHECK (locked >= 0),
+    slashed     NUMERIC(78, 0) NOT NULL DEFAULT 0 CHECK (slashed >= 0),
+    created_at  TIMESTAMPTZ    NOT NULL DEFAULT NOW(),
+    updated_at  TIMESTAMPTZ    NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (provider, token)
+);
+
+CREATE INDEX IF NOT EXISTS idx_provider_stakes_token ON provider_stakes(token);
+
+CREATE TRIGGER trg_provider_stakes_updated_at
+    BEFORE UPDATE ON provider_stakes
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
+
+-- Deposits and withdrawals seen by the indexer; the log position makes a
+-- replayed transfer count once
+CREATE TABLE IF NOT EXISTS stake_transfers (
+    tx_hash       BYTEA          NOT NULL,
+    log_index     BIGINT         NOT NULL,
+    provider      BYTEA          NOT NULL,
+    token         BYTEA          NOT NULL,
+    kind          VARCHAR(10)    NOT NULL CHECK (kind IN ('deposit', 'withdrawal')),
+    amount        NUMERIC(78, 0) NOT NULL CHECK (amount >= 0),
+    block_number  BIGINT         NOT NULL,
+    created_at    TIMESTAMPTZ    NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_stake_transfers_provider ON stake_transfers(provider, token);
+
+-- Collateral held for one provider's assignment on an order
+CREATE TABLE IF NOT EXISTS collateral_locks (
+    order_id        BYTEA          NOT NULL,
+    provider        BYTEA          NOT NULL,
+    token           BYTEA          NOT NULL,
+    -- Token amount assigned to the provider, slashes are a share of it
+    order_amount    NUMERIC(78, 0) NOT NULL,
+    amount          NUMERIC(78, 0) NOT NULL,
+    state           VARCHAR(10)    NOT NULL DEFAULT 'locked'
+                    CHECK (state IN ('locked', 'released', 'slashed')),
+    slashed_amount  NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    slash_reason    VARCHAR(10)    CHECK (slash_reason IN ('failure', 'no_show', 'dispute')),
+    created_at      TIMESTAMPTZ    NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ    NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (order_id, provider),
+    CHECK ((state = 'slashed') = (slash_reason IS NOT NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_collateral_locks_provider ON collateral_locks(provider, state);
+
+CREATE TRIGGER trg_collateral_locks_updated_at
+    BEFORE UPDATE ON collateral_locks
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,7 @@
+-- ------------------------------------------------------------
+-- Webhook payloads as text: JSONB normalizes key order and
+-- whitespace, so the stored value was not the body that was
+-- signed and sent. Every attempt now sends the stored bytes.
+-- ------------------------------------------------------------
+
+ALTER TABLE webhook_events ALTER COLUMN payload TYPE TEXT USING payload::TEXT;
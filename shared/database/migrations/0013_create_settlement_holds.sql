@@ -0,0 +1,20 @@
+-- ------------------------------------------------------------
+-- Settlement holds: opening a dispute holds the order's
+-- settlement. A fulfilment that arrives while the dispute is
+-- under review is recorded here instead of settled, and settles
+-- once an admin releases the dispute.
+-- ------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS settlement_holds (
+    order_id      BYTEA       PRIMARY KEY REFERENCES orders(order_id) ON DELETE CASCADE,
+    dispute_id    UUID        NOT NULL REFERENCES disputes(id) ON DELETE CASCADE,
+    held_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Fulfilment kept from settling by the hold
+    fulfilled_at  TIMESTAMPTZ,
+    -- Dispute resolved; the hold no longer applies
+    released_at   TIMESTAMPTZ,
+    -- Held fulfilment settled after a release
+    settled_at    TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_settlement_holds_dispute ON settlement_holds(dispute_id);
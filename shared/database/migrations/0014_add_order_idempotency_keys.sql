@@ -0,0 +1,10 @@
+-- ------------------------------------------------------------
+-- Idempotency keys of created orders: a create retried with the
+-- same key by the same user returns the order it first created
+-- instead of creating another.
+-- ------------------------------------------------------------
+
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS idempotency_key VARCHAR(255);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_orders_idempotency_key
+    ON orders(user_address, idempotency_key) WHERE idempotency_key IS NOT NULL;
@@ -0,0 +1,8 @@
+-- ------------------------------------------------------------
+-- Quotes backing orders: an order created from a quote keeps its
+-- ID, and the quote is marked used so it backs only one order.
+-- ------------------------------------------------------------
+
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS quote_id UUID REFERENCES quotes(quote_id);
+
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS used_at TIMESTAMPTZ;
@@ -0,0 +1,16 @@
+-- ------------------------------------------------------------
+-- Routing decisions held for admin approval by their tier
+-- policy. Kept until approved or rejected so a router restart
+-- does not drop held orders.
+-- ------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS routing_approvals (
+    order_id   BYTEA       PRIMARY KEY,
+    -- Serialized Order as it was routed
+    order_data JSONB       NOT NULL,
+    -- Serialized RoutingDecision awaiting approval
+    decision   JSONB       NOT NULL,
+    held_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_routing_approvals_held_at ON routing_approvals(held_at);
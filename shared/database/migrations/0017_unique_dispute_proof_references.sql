@@ -0,0 +1,9 @@
+-- ------------------------------------------------------------
+-- Dispute proofs: a payment's transaction reference answers at
+-- most one dispute, enforced here rather than per service
+-- replica so a proof cannot be replayed against another order.
+-- ------------------------------------------------------------
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_disputes_proof_reference
+    ON disputes ((btrim(proof->>'transaction_reference')))
+    WHERE proof IS NOT NULL;
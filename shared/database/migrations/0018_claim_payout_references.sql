@@ -0,0 +1,20 @@
+-- ------------------------------------------------------------
+-- Payout claims: a payout row is written under the idempotency
+-- key sent to the PSP (payout_reference) before the PSP is
+-- called, so a retried request finds the row instead of paying
+-- out again. initiated_at stays NULL until the PSP accepts the
+-- payout and its own reference is recorded.
+-- ------------------------------------------------------------
+
+ALTER TABLE payouts
+    ADD COLUMN IF NOT EXISTS payout_reference TEXT,
+    ADD COLUMN IF NOT EXISTS initiated_at     TIMESTAMPTZ;
+
+UPDATE payouts
+SET payout_reference = 'pn_' || left(encode(proposal_id, 'hex'), 40),
+    initiated_at = created_at
+WHERE payout_reference IS NULL;
+
+ALTER TABLE payouts ALTER COLUMN payout_reference SET NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_payouts_payout_reference ON payouts(payout_reference);
@@ -0,0 +1,7 @@
+-- ------------------------------------------------------------
+-- Payout legs: a payout for one leg of a split order records
+-- the leg, so the leg can be filled once the payout settles.
+-- NULL for payouts of orders that were not split.
+-- ------------------------------------------------------------
+
+ALTER TABLE payouts ADD COLUMN IF NOT EXISTS leg INTEGER;
@@ -0,0 +1,12 @@
+-- ------------------------------------------------------------
+-- Provider collateral is only recorded for now: stake is not
+-- locked against assignments or slashed until the indexer
+-- publishes stake transfers, so the lock table and the locked
+-- and slashed totals are dropped.
+-- ------------------------------------------------------------
+
+DROP TABLE IF EXISTS collateral_locks;
+
+ALTER TABLE provider_stakes
+    DROP COLUMN IF EXISTS locked,
+    DROP COLUMN IF EXISTS slashed;
@@ -23,6 +23,9 @@ pub enum DatabaseError {
     
     #[error("Transaction error: {0}")]
     TransactionError(String),
+
+    #[error("Encryption error: {0}")]
+    EncryptionError(String),
 }
 
 pub type Result<T> = std::result::Result<T, DatabaseError>;
\ No newline at end of file
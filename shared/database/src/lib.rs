@@ -1,12 +1,16 @@
 pub mod error;
+pub mod limits;
 pub mod pool;
+pub mod pii;
 pub mod models;
 pub mod repositories;
 
 // Re-export commonly used items
 pub use error::{DatabaseError, Result};
+pub use pii::{Envelope, KeyRing};
+pub use limits::{TierLimitsStore, TierLimitsTable};
 pub use pool::{create_pool, create_default_pool, create_pool_from_env, run_migrations, check_connection,load_database_config,  DatabaseConfig};
-pub use repositories::{OrderRepository, ProviderRepository, ProposalRepository};
+pub use repositories::{AllocationRepository, DisputeRepository, FxRateRepository, OrderRepository, PayoutRepository, ProviderRepository, ProposalRepository, QuoteRepository, RoutingApprovalRepository, RoutingDecisionRepository, StakeRepository, TierLimitsRepository, WebhookRepository};
 
 // Helper function to initialize database for a service
 pub async fn initialize_database() -> Result<sqlx::PgPool> {
@@ -0,0 +1,164 @@
+//! Hot-reloaded tier limits
+//!
+//! [`TierLimitsStore`] keeps the `tier_limits` table in memory and reloads it
+//! on an interval, so services classify orders against the current limits
+//! without a restart. Rows that fail validation are skipped with a warning
+//! rather than replacing good limits with unusable ones.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use shared_types::{Currency, OrderTier, TierLimits, TokenAmount};
+
+use crate::error::{DatabaseError, Result};
+use crate::repositories::TierLimitsRepository;
+
+/// Validated limits keyed by token and currency
+#[derive(Debug, Clone, Default)]
+pub struct TierLimitsTable {
+    limits: HashMap<(String, String), TierLimits>,
+}
+
+impl TierLimitsTable {
+    /// Build a table from limits, dropping any that fail validation
+    pub fn new(limits: Vec<TierLimits>) -> Self {
+        let limits = limits
+            .into_iter()
+            .filter_map(|limits| match limits.validate() {
+                Ok(()) => Some((key(&limits.token, limits.currency.as_ref()), limits)),
+                Err(e) => {
+                    tracing::warn!("Ignoring tier limits for token {}: {}", limits.token, e);
+                    None
+                }
+            })
+            .collect();
+        Self { limits }
+    }
+
+    /// Limits of a token for a currency, falling back to the token's
+    /// currency-wide limits
+    pub fn get(&self, token: &str, currency: &Currency) -> Option<&TierLimits> {
+        self.limits
+            .get(&key(token, Some(currency)))
+            .or_else(|| self.limits.get(&key(token, None)))
+    }
+
+    /// Classify an order amount against its token's limits
+    ///
+    /// # Returns
+    /// * `Result<OrderTier>` - InvalidData for an amount that is not a
+    ///   positive integer, NotFound when the token has no limits
+    pub fn classify(&self, token: &str, currency: &Currency, amount: &str) -> Result<OrderTier> {
+        let amount = TokenAmount::parse(amount).map_err(|e| DatabaseError::InvalidData(e.to_string()))?;
+        let limits = self.get(token, currency).ok_or_else(|| {
+            DatabaseError::NotFound(format!("Tier limits for token {} in {}", token, currency.as_str()))
+        })?;
+        Ok(OrderTier::from_amount(amount, limits))
+    }
+
+    pub fn len(&self) -> usize {
+        self.limits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.limits.is_empty()
+    }
+
+    /// Every entry, ordered by token then currency
+    pub fn list(&self) -> Vec<TierLimits> {
+        let mut limits: Vec<TierLimits> = self.limits.values().cloned().collect();
+        limits.sort_by_key(|limits| key(&limits.token, limits.currency.as_ref()));
+        limits
+    }
+}
+
+/// Tier limits loaded from the database and refreshed in the background
+pub struct TierLimitsStore {
+    repo: TierLimitsRepository,
+    table: RwLock<Arc<TierLimitsTable>>,
+}
+
+impl TierLimitsStore {
+    pub fn new(repo: TierLimitsRepository) -> Self {
+        Self {
+            repo,
+            table: RwLock::new(Arc::new(TierLimitsTable::default())),
+        }
+    }
+
+    /// Replace the in-memory limits with what is stored
+    ///
+    /// # Returns
+    /// * `Result<usize>` - Number of valid entries loaded
+    pub async fn reload(&self) -> Result<usize> {
+        let rows = self.repo.list().await?;
+        let limits = rows
+            .iter()
+            .filter_map(|row| match row.to_domain() {
+                Ok(limits) => Some(limits),
+                Err(e) => {
+                    tracing::warn!("Ignoring stored tier limits: {}", e);
+                    None
+                }
+            })
+            .collect();
+        let table = TierLimitsTable::new(limits);
+        let loaded = table.len();
+        *self.table.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::new(table);
+        Ok(loaded)
+    }
+
+    /// Current limits; cheap to call per request
+    pub fn current(&self) -> Arc<TierLimitsTable> {
+        self.table.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+
+    pub fn repository(&self) -> &TierLimitsRepository {
+        &self.repo
+    }
+
+    /// Reload on `interval` until the process exits, keeping the last good
+    /// limits when a reload fails
+    pub fn spawn_refresh(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.reload().await {
+                    tracing::warn!("Tier limits reload failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+fn key(token: &str, currency: Option<&Currency>) -> (String, String) {
+    (token.to_lowercase(), currency.map(Currency::as_str).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_table_lookup_and_classification() {
+        let usdc = TierLimits::standard("0xUSDC", 6);
+        let usdc_kes = TierLimits {
+            currency: Some(Currency::KES),
+            alpha: Decimal::from(500),
+            ..TierLimits::standard("0xusdc", 6)
+        };
+        let invalid = TierLimits { beta: Decimal::ZERO, ..TierLimits::standard("0xbad", 18) };
+        let table = TierLimitsTable::new(vec![usdc, usdc_kes, invalid]);
+        assert_eq!(table.len(), 2);
+
+        // 1,000 USDC: Alpha by default, Beta where KES lowered the Alpha limit
+        assert_eq!(table.classify("0xusdc", &Currency::NGN, "1000000000").unwrap(), OrderTier::Alpha);
+        assert_eq!(table.classify("0xusdc", &Currency::KES, "1000000000").unwrap(), OrderTier::Beta);
+
+        assert!(matches!(table.classify("0xusdc", &Currency::NGN, "ten"), Err(DatabaseError::InvalidData(_))));
+        assert!(matches!(table.classify("0xbad", &Currency::NGN, "1000"), Err(DatabaseError::NotFound(_))));
+    }
+}
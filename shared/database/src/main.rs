@@ -1,8 +1,9 @@
-use shared_database::*;
+use shared_database::{check_connection, create_pool_from_env};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let pool = connect_db().await?;
+    let pool = create_pool_from_env().await?;
+    check_connection(&pool).await?;
     println!("✅ Successfully connected to Neon DB!");
     Ok(())
 }
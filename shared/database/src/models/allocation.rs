@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use shared_types::{AllocationStatus, OrderAllocation};
+use uuid::Uuid;
+
+use super::hex_to_bytes;
+
+/// Database representation of one leg of a split order
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct OrderAllocationModel {
+    pub id: Uuid,
+    pub order_id: Vec<u8>,
+    pub leg: i32,
+    pub provider: Vec<u8>,
+    pub amount: String,
+    pub filled_amount: String,
+    pub status: String,
+    pub tx_hash: Option<Vec<u8>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub settled_at: Option<DateTime<Utc>>,
+}
+
+impl OrderAllocationModel {
+    /// Converts database model to domain type; unknown statuses read as pending
+    pub fn to_domain(&self) -> OrderAllocation {
+        OrderAllocation {
+            id: self.id,
+            order_id: format!("0x{}", hex::encode(&self.order_id)),
+            leg: self.leg.max(0) as u32,
+            provider: format!("0x{}", hex::encode(&self.provider)),
+            amount: self.amount.clone(),
+            filled_amount: self.filled_amount.clone(),
+            status: self.status.parse().unwrap_or(AllocationStatus::Pending),
+            tx_hash: self.tx_hash.as_ref().map(|hash| format!("0x{}", hex::encode(hash))),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            settled_at: self.settled_at,
+        }
+    }
+
+    /// Converts a domain leg back into its database form
+    pub fn from_domain(allocation: &OrderAllocation) -> Self {
+        Self {
+            id: allocation.id,
+            order_id: hex_to_bytes(&allocation.order_id),
+            leg: allocation.leg as i32,
+            provider: hex_to_bytes(&allocation.provider),
+            amount: allocation.amount.clone(),
+            filled_amount: allocation.filled_amount.clone(),
+            status: allocation.status.as_str().to_string(),
+            tx_hash: allocation.tx_hash.as_deref().map(hex_to_bytes),
+            created_at: allocation.created_at,
+            updated_at: allocation.updated_at,
+            settled_at: allocation.settled_at,
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use shared_types::{Dispute, DisputeOutcome, DisputeStatus, SettlementHold};
+use uuid::Uuid;
+
+use super::hex_to_bytes;
+
+/// Database representation of a dispute
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DisputeModel {
+    pub id: Uuid,
+    pub order_id: Vec<u8>,
+    pub user_address: Vec<u8>,
+    pub provider: Vec<u8>,
+    pub reason: String,
+    pub status: String,
+    /// Serialized `PaymentProof`
+    pub proof: Option<serde_json::Value>,
+    pub outcome: Option<String>,
+    pub resolution_note: Option<String>,
+    pub resolved_by: Option<Vec<u8>>,
+    pub opened_at: DateTime<Utc>,
+    pub evidence_submitted_at: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl DisputeModel {
+    /// Converts database model to domain type; unknown statuses read as open
+    /// so the dispute keeps holding settlement
+    pub fn to_domain(&self) -> Dispute {
+        Dispute {
+            id: self.id,
+            order_id: format!("0x{}", hex::encode(&self.order_id)),
+            user_address: format!("0x{}", hex::encode(&self.user_address)),
+            provider: format!("0x{}", hex::encode(&self.provider)),
+            reason: self.reason.clone(),
+            status: self.status.parse().unwrap_or(DisputeStatus::Open),
+            proof: self.proof.clone().and_then(|proof| serde_json::from_value(proof).ok()),
+            outcome: self.outcome.as_deref().and_then(|outcome| outcome.parse::<DisputeOutcome>().ok()),
+            resolution_note: self.resolution_note.clone(),
+            resolved_by: self.resolved_by.as_ref().map(|admin| format!("0x{}", hex::encode(admin))),
+            opened_at: self.opened_at,
+            evidence_submitted_at: self.evidence_submitted_at,
+            resolved_at: self.resolved_at,
+        }
+    }
+
+    /// Converts domain type to database model
+    pub fn from_domain(dispute: &Dispute) -> Self {
+        Self {
+            id: dispute.id,
+            order_id: hex_to_bytes(&dispute.order_id),
+            user_address: hex_to_bytes(&dispute.user_address),
+            provider: hex_to_bytes(&dispute.provider),
+            reason: dispute.reason.clone(),
+            status: dispute.status.as_str().to_string(),
+            proof: dispute.proof.as_ref().and_then(|proof| serde_json::to_value(proof).ok()),
+            outcome: dispute.outcome.map(|outcome| outcome.as_str().to_string()),
+            resolution_note: dispute.resolution_note.clone(),
+            resolved_by: dispute.resolved_by.as_deref().map(hex_to_bytes),
+            opened_at: dispute.opened_at,
+            evidence_submitted_at: dispute.evidence_submitted_at,
+            resolved_at: dispute.resolved_at,
+        }
+    }
+}
+
+/// Database representation of an order's settlement hold
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SettlementHoldModel {
+    pub order_id: Vec<u8>,
+    pub dispute_id: Uuid,
+    pub held_at: DateTime<Utc>,
+    pub fulfilled_at: Option<DateTime<Utc>>,
+    pub released_at: Option<DateTime<Utc>>,
+    pub settled_at: Option<DateTime<Utc>>,
+}
+
+impl SettlementHoldModel {
+    pub fn to_domain(&self) -> SettlementHold {
+        SettlementHold {
+            order_id: format!("0x{}", hex::encode(&self.order_id)),
+            dispute_id: self.dispute_id,
+            held_at: self.held_at,
+            fulfilled_at: self.fulfilled_at,
+            released_at: self.released_at,
+            settled_at: self.settled_at,
+        }
+    }
+
+    pub fn from_domain(hold: &SettlementHold) -> Self {
+        Self {
+            order_id: hex_to_bytes(&hold.order_id),
+            dispute_id: hold.dispute_id,
+            held_at: hold.held_at,
+            fulfilled_at: hold.fulfilled_at,
+            released_at: hold.released_at,
+            settled_at: hold.settled_at,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use shared_types::FxRateSnapshot;
+use uuid::Uuid;
+
+/// Database representation of a recorded FX rate
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct FxRateRecordModel {
+    pub id: i64,
+    pub quote_id: Option<Uuid>,
+    pub order_id: Option<Vec<u8>>,
+    pub pair: String,
+    pub rate: String,
+    pub sources: Vec<String>,
+    pub rejected_count: i32,
+    pub stale: bool,
+    pub observed_at: DateTime<Utc>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl FxRateRecordModel {
+    /// Converts database model to domain type
+    pub fn to_domain(&self) -> FxRateSnapshot {
+        FxRateSnapshot {
+            quote_id: self.quote_id,
+            order_id: self.order_id.as_ref().map(|id| format!("0x{}", hex::encode(id))),
+            pair: self.pair.clone(),
+            rate: self.rate.clone(),
+            sources: self.sources.clone(),
+            rejected_count: self.rejected_count.max(0) as u32,
+            stale: self.stale,
+            observed_at: self.observed_at,
+            recorded_at: self.recorded_at,
+        }
+    }
+}
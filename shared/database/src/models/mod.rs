@@ -1,7 +1,25 @@
+pub mod allocation;
+pub mod dispute;
+pub mod fx;
 pub mod order;
+pub mod payout;
 pub mod provider;
 pub mod proposal;
+pub mod quote;
+pub mod routing;
+pub mod stake;
+pub mod tier_limits;
+pub mod webhook;
 
+pub use allocation::*;
+pub use dispute::*;
+pub use fx::*;
 pub use order::*;
+pub use payout::*;
 pub use provider::*;
-pub use proposal::*;
\ No newline at end of file
+pub use proposal::*;
+pub use quote::*;
+pub use routing::*;
+pub use stake::*;
+pub use tier_limits::*;
+pub use webhook::*;
\ No newline at end of file
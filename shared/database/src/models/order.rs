@@ -86,7 +86,7 @@ impl OrderModel {
             amount: self.amount.clone(),
             
             // Parse database strings to strongly-typed enums with safe fallbacks
-            currency: Currency::from_str(self.currency.as_deref().unwrap_or_default()),
+            currency: Currency::from(self.currency.as_deref().unwrap_or_default()),
             tier: self.parse_tier(),
             status: self.parse_status(),
             
@@ -106,6 +106,19 @@ impl OrderModel {
         })
     }
     
+    /// Event announcing that the order moved from `previous_status` to its
+    /// current status
+    pub fn status_changed(&self, previous_status: Option<OrderStatus>) -> shared_types::OrderStatusChangedEvent {
+        shared_types::OrderStatusChangedEvent {
+            order_id: format!("0x{}", hex::encode(&self.order_id)),
+            user_address: format!("0x{}", hex::encode(&self.user_address)),
+            integrator_address: format!("0x{}", hex::encode(&self.integrator_address)),
+            previous_status,
+            status: self.parse_status(),
+            timestamp: self.updated_at,
+        }
+    }
+
     /// Fetches integrator-specific fee from database
     /// Integrators configure their own fees via the PayNode dashboard
     /// This enables flexible pricing strategies per integrator
@@ -118,16 +131,14 @@ impl OrderModel {
     async fn get_integrator_fee_bps(&self, db_pool: &sqlx::PgPool) -> Result<u64, sqlx::Error> {
         // Query integrator_fees table using integrator_address as primary key
         // This query is optimized by the PRIMARY KEY index on integrator_address
-        let result = sqlx::query!(
-            "SELECT fee_bps FROM integrator_fees WHERE integrator_address = $1",
-            &self.integrator_address
-        )
-        .fetch_optional(db_pool)
-        .await?;
+        let fee_bps = sqlx::query_scalar::<_, i32>("SELECT fee_bps FROM integrator_fees WHERE integrator_address = $1")
+            .bind(&self.integrator_address)
+            .fetch_optional(db_pool)
+            .await?;
         
         // Return configured fee or default (50 bps = 0.50%) if not configured
         // This ensures orders can proceed even if integrator hasn't set a custom fee
-        Ok(result.map(|r| r.fee_bps as u64).unwrap_or(50))
+        Ok(fee_bps.map(|bps| bps as u64).unwrap_or(50))
     }
     
     /// Parses database status string into OrderStatus enum
@@ -140,6 +151,7 @@ impl OrderModel {
         match self.status.as_str() {
             "PENDING" => OrderStatus::Pending,
             "ACCEPTED" => OrderStatus::Accepted,
+            "PARTIALLY_FULFILLED" => OrderStatus::PartiallyFulfilled,
             "FULFILLED" => OrderStatus::Fulfilled,
             "REFUNDED" => OrderStatus::Refunded,
             "EXPIRED" => OrderStatus::Expired,
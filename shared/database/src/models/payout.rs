@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use shared_types::{Payout, PayoutStatus};
+use uuid::Uuid;
+
+use super::hex_to_bytes;
+use crate::pii::Envelope;
+
+/// Database representation of a fiat payout
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct PayoutModel {
+    pub id: Uuid,
+    pub order_id: Vec<u8>,
+    pub proposal_id: Vec<u8>,
+    pub provider: Vec<u8>,
+    pub adapter: String,
+    /// PSP's reference; the payout reference until the PSP accepts the payout
+    pub reference: String,
+    /// Idempotency key the payout is sent to the PSP with
+    pub payout_reference: String,
+    /// Leg of a split order the payout pays out
+    pub leg: Option<i32>,
+    pub amount: String,
+    pub currency: String,
+    pub status: String,
+    pub message: Option<String>,
+    pub polls: i32,
+    pub next_poll_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When the PSP accepted the payout; None while the request is only
+    /// claimed
+    pub initiated_at: Option<DateTime<Utc>>,
+    /// Sealed recipient details; see [`crate::pii`]
+    pub recipient_key_id: Option<String>,
+    pub recipient_wrapped_key: Option<Vec<u8>>,
+    pub recipient_ciphertext: Option<Vec<u8>>,
+}
+
+impl PayoutModel {
+    /// Stored status; unknown values read as processing so the payout keeps
+    /// being reconciled rather than treated as settled
+    pub fn status(&self) -> PayoutStatus {
+        self.status.parse().unwrap_or(PayoutStatus::Processing)
+    }
+
+    /// Whether the PSP accepted the payout, rather than it only being
+    /// claimed
+    pub fn is_initiated(&self) -> bool {
+        self.initiated_at.is_some()
+    }
+
+    /// Converts database model to domain type
+    pub fn to_domain(&self) -> Payout {
+        Payout {
+            order_id: format!("0x{}", hex::encode(&self.order_id)),
+            proposal_id: format!("0x{}", hex::encode(&self.proposal_id)),
+            provider: format!("0x{}", hex::encode(&self.provider)),
+            adapter: self.adapter.clone(),
+            reference: self.reference.clone(),
+            leg: self.leg.map(|leg| leg as u32),
+            amount: self.amount.clone(),
+            currency: self.currency.clone(),
+            status: self.status(),
+            message: self.message.clone(),
+            polls: self.polls.max(0) as u32,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            completed_at: self.completed_at,
+        }
+    }
+
+    /// Converts a domain payout back into its database form, claimed under
+    /// its reference and not yet initiated
+    pub fn from_domain(payout: &Payout, next_poll_at: Option<DateTime<Utc>>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            order_id: hex_to_bytes(&payout.order_id),
+            proposal_id: hex_to_bytes(&payout.proposal_id),
+            provider: hex_to_bytes(&payout.provider),
+            adapter: payout.adapter.clone(),
+            reference: payout.reference.clone(),
+            payout_reference: payout.reference.clone(),
+            leg: payout.leg.map(|leg| leg as i32),
+            amount: payout.amount.clone(),
+            currency: payout.currency.clone(),
+            status: payout.status.as_str().to_string(),
+            message: payout.message.clone(),
+            polls: payout.polls as i32,
+            next_poll_at,
+            created_at: payout.created_at,
+            updated_at: payout.updated_at,
+            completed_at: payout.completed_at,
+            initiated_at: None,
+            recipient_key_id: None,
+            recipient_wrapped_key: None,
+            recipient_ciphertext: None,
+        }
+    }
+
+    /// Attach sealed recipient details
+    pub fn with_recipient(mut self, envelope: Envelope) -> Self {
+        self.recipient_key_id = Some(envelope.key_id);
+        self.recipient_wrapped_key = Some(envelope.wrapped_key);
+        self.recipient_ciphertext = Some(envelope.ciphertext);
+        self
+    }
+
+    /// Sealed recipient details, if stored
+    pub fn recipient(&self) -> Option<Envelope> {
+        match (&self.recipient_key_id, &self.recipient_wrapped_key, &self.recipient_ciphertext) {
+            (Some(key_id), Some(wrapped_key), Some(ciphertext)) => Some(Envelope {
+                key_id: key_id.clone(),
+                wrapped_key: wrapped_key.clone(),
+                ciphertext: ciphertext.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
@@ -3,6 +3,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use shared_types::{Proposal, ProposalStatus};
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct ProposalModel {
@@ -17,4 +18,30 @@ pub struct ProposalModel {
     pub accepted_at: Option<DateTime<Utc>>,
     pub executed_at: Option<DateTime<Utc>>,
     pub tx_hash: Option<Vec<u8>>,
-}
\ No newline at end of file
+}
+
+impl ProposalModel {
+    /// Converts database model to domain type; unknown statuses read as pending
+    pub fn to_domain(&self) -> Proposal {
+        let status = match self.status.as_str() {
+            "ACCEPTED" => ProposalStatus::Accepted,
+            "REJECTED" => ProposalStatus::Rejected,
+            "TIMED_OUT" => ProposalStatus::TimedOut,
+            "EXECUTED" => ProposalStatus::Executed,
+            _ => ProposalStatus::Pending,
+        };
+
+        Proposal {
+            proposal_id: format!("0x{}", hex::encode(&self.proposal_id)),
+            order_id: format!("0x{}", hex::encode(&self.order_id)),
+            provider: format!("0x{}", hex::encode(&self.provider)),
+            proposed_fee_bps: self.proposed_fee_bps.max(0) as u64,
+            status,
+            created_at: self.created_at,
+            deadline: self.deadline,
+            accepted_at: self.accepted_at,
+            executed_at: self.executed_at,
+            tx_hash: self.tx_hash.as_ref().map(|hash| format!("0x{}", hex::encode(hash))),
+        }
+    }
+}
@@ -2,6 +2,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use shared_types::{Currency, ProviderIntent, ProviderReputation};
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct ProviderIntentModel {
@@ -25,7 +26,43 @@ pub struct ProviderReputationModel {
     pub successful_orders: i64,
     pub failed_orders: i64,
     pub no_shows: i64,
+    pub disputes: i64,
+    pub disputes_lost: i64,
     pub avg_settlement_time_seconds: i64,
     pub total_volume: String,
     pub last_updated: DateTime<Utc>,
-}
\ No newline at end of file
+}
+impl ProviderIntentModel {
+    /// Converts database model to domain type
+    pub fn to_domain(&self) -> ProviderIntent {
+        ProviderIntent {
+            provider: format!("0x{}", hex::encode(&self.provider)),
+            currency: Currency::from(self.currency.as_str()),
+            available_amount: self.available_amount.clone(),
+            min_fee_bps: self.min_fee_bps.max(0) as u64,
+            max_fee_bps: self.max_fee_bps.max(0) as u64,
+            commitment_window_seconds: self.commitment_window.max(0) as u64,
+            is_active: self.is_active,
+            registered_at: self.created_at,
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+impl ProviderReputationModel {
+    /// Converts database model to domain type
+    pub fn to_domain(&self) -> ProviderReputation {
+        ProviderReputation {
+            provider: format!("0x{}", hex::encode(&self.provider)),
+            total_orders: self.total_orders.max(0) as u64,
+            successful_orders: self.successful_orders.max(0) as u64,
+            failed_orders: self.failed_orders.max(0) as u64,
+            no_shows: self.no_shows.max(0) as u64,
+            disputes: self.disputes.max(0) as u64,
+            disputes_lost: self.disputes_lost.max(0) as u64,
+            avg_settlement_time_seconds: self.avg_settlement_time_seconds.max(0) as u64,
+            total_volume: self.total_volume.clone(),
+            last_updated: self.last_updated,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use shared_types::{Currency, Quote};
+use uuid::Uuid;
+
+/// Database representation of an issued quote
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct QuoteModel {
+    pub quote_id: Uuid,
+    pub token: Vec<u8>,
+    pub amount: String,
+    pub currency: String,
+    pub integrator_address: Option<Vec<u8>>,
+    pub provider: Vec<u8>,
+    pub rate: String,
+    pub provider_fee_bps: i32,
+    pub integrator_fee_bps: i32,
+    pub protocol_fee_bps: i32,
+    pub fiat_amount: String,
+    pub signature: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl QuoteModel {
+    /// Converts database model to domain type
+    pub fn to_domain(&self) -> Quote {
+        Quote {
+            quote_id: self.quote_id,
+            token: format!("0x{}", hex::encode(&self.token)),
+            amount: self.amount.clone(),
+            currency: Currency::from(self.currency.as_str()),
+            integrator_address: self
+                .integrator_address
+                .as_ref()
+                .map(|address| format!("0x{}", hex::encode(address))),
+            rate: self.rate.clone(),
+            provider_fee_bps: self.provider_fee_bps.max(0) as u64,
+            integrator_fee_bps: self.integrator_fee_bps.max(0) as u64,
+            protocol_fee_bps: self.protocol_fee_bps.max(0) as u64,
+            fiat_amount: self.fiat_amount.clone(),
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+            signature: self.signature.clone(),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use shared_types::{Currency, Order, OrderTier, RoutingDecision};
+use uuid::Uuid;
+
+use super::hex_to_bytes;
+use crate::error::{DatabaseError, Result};
+
+/// Database representation of an AI router decision
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RoutingDecisionModel {
+    pub id: Uuid,
+    pub order_id: Vec<u8>,
+    pub currency: String,
+    pub tier: OrderTier,
+    pub scorer: String,
+    pub scorer_version: String,
+    pub chosen_provider: Option<Vec<u8>>,
+    /// Serialized `Vec<CandidateEvaluation>`, best first
+    pub candidates: serde_json::Value,
+    /// Serialized `Vec<LegAllocation>`; empty unless the order was split
+    pub allocations: serde_json::Value,
+    pub decided_at: DateTime<Utc>,
+}
+
+impl RoutingDecisionModel {
+    /// Converts database model to domain type
+    pub fn to_domain(&self) -> RoutingDecision {
+        RoutingDecision {
+            id: self.id,
+            order_id: format!("0x{}", hex::encode(&self.order_id)),
+            currency: Currency::from(self.currency.as_str()),
+            tier: self.tier,
+            scorer: self.scorer.clone(),
+            scorer_version: self.scorer_version.clone(),
+            chosen_provider: self
+                .chosen_provider
+                .as_ref()
+                .map(|provider| format!("0x{}", hex::encode(provider))),
+            candidates: serde_json::from_value(self.candidates.clone()).unwrap_or_default(),
+            allocations: serde_json::from_value(self.allocations.clone()).unwrap_or_default(),
+            decided_at: self.decided_at,
+        }
+    }
+}
+
+/// Database representation of a routing decision held for approval
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RoutingApprovalModel {
+    pub order_id: Vec<u8>,
+    /// Serialized `Order`
+    pub order_data: serde_json::Value,
+    /// Serialized `RoutingDecision`
+    pub decision: serde_json::Value,
+    pub held_at: DateTime<Utc>,
+}
+
+impl RoutingApprovalModel {
+    pub fn from_domain(order: &Order, decision: &RoutingDecision) -> Result<Self> {
+        let invalid = |e: serde_json::Error| DatabaseError::InvalidData(format!("Held decision for order {}: {}", order.order_id, e));
+        Ok(Self {
+            order_id: hex_to_bytes(&order.order_id),
+            order_data: serde_json::to_value(order).map_err(invalid)?,
+            decision: serde_json::to_value(decision).map_err(invalid)?,
+            held_at: Utc::now(),
+        })
+    }
+
+    /// Converts database model to the held order and decision
+    pub fn to_domain(&self) -> Result<(Order, RoutingDecision)> {
+        let invalid = |e: serde_json::Error| {
+            DatabaseError::InvalidData(format!("Held decision for order 0x{}: {}", hex::encode(&self.order_id), e))
+        };
+        Ok((
+            serde_json::from_value(self.order_data.clone()).map_err(invalid)?,
+            serde_json::from_value(self.decision.clone()).map_err(invalid)?,
+        ))
+    }
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use shared_types::{ProviderStake, StakeTransferEvent};
+
+use super::hex_to_bytes;
+
+/// Database representation of a provider's stake in one token; amounts are
+/// selected as text
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ProviderStakeModel {
+    pub provider: Vec<u8>,
+    pub token: Vec<u8>,
+    pub staked: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ProviderStakeModel {
+    /// Converts database model to domain type
+    pub fn to_domain(&self) -> ProviderStake {
+        ProviderStake {
+            provider: format!("0x{}", hex::encode(&self.provider)),
+            token: format!("0x{}", hex::encode(&self.token)),
+            staked: self.staked.clone(),
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+/// Database representation of an on-chain stake deposit or withdrawal
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct StakeTransferModel {
+    pub tx_hash: Vec<u8>,
+    pub log_index: i64,
+    pub provider: Vec<u8>,
+    pub token: Vec<u8>,
+    /// `deposit` or `withdrawal`
+    pub kind: String,
+    pub amount: String,
+    pub block_number: i64,
+}
+
+impl StakeTransferModel {
+    pub fn from_event(event: &StakeTransferEvent, kind: &str) -> Self {
+        Self {
+            tx_hash: hex_to_bytes(&event.tx_hash),
+            log_index: event.log_index,
+            provider: hex_to_bytes(&event.provider),
+            token: hex_to_bytes(&event.token),
+            kind: kind.to_string(),
+            amount: event.amount.clone(),
+            block_number: event.block_number,
+        }
+    }
+}
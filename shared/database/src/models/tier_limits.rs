@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use shared_types::{Currency, TierLimits};
+
+use super::hex_to_bytes;
+use crate::error::{DatabaseError, Result};
+
+/// Database representation of one token's tier limits
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TierLimitsModel {
+    pub token: Vec<u8>,
+    /// Empty when the limits apply to every currency
+    pub currency: String,
+    pub decimals: i16,
+    /// Limits in whole tokens, as NUMERIC text
+    pub alpha_max: String,
+    pub beta_max: String,
+    pub delta_max: String,
+    pub omega_max: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TierLimitsModel {
+    /// Converts database model to domain type
+    pub fn to_domain(&self) -> Result<TierLimits> {
+        let parse = |value: &str| {
+            value
+                .parse::<Decimal>()
+                .map_err(|_| DatabaseError::InvalidData(format!("tier limit {} is not a decimal", value)))
+        };
+        Ok(TierLimits {
+            token: format!("0x{}", hex::encode(&self.token)),
+            currency: (!self.currency.is_empty()).then(|| Currency::from(self.currency.as_str())),
+            decimals: self.decimals.max(0) as u32,
+            alpha: parse(&self.alpha_max)?,
+            beta: parse(&self.beta_max)?,
+            delta: parse(&self.delta_max)?,
+            omega: parse(&self.omega_max)?,
+        })
+    }
+
+    /// Converts domain type to database model; timestamps are set by the
+    /// database
+    pub fn from_domain(limits: &TierLimits) -> Self {
+        Self {
+            token: hex_to_bytes(&limits.token),
+            currency: limits.currency.as_ref().map(Currency::as_str).unwrap_or_default(),
+            decimals: limits.decimals as i16,
+            alpha_max: limits.alpha.to_string(),
+            beta_max: limits.beta.to_string(),
+            delta_max: limits.delta.to_string(),
+            omega_max: limits.omega.to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+}
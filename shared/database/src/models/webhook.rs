@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use shared_types::{WebhookDeliveryAttempt, WebhookEndpoint, WebhookEventType};
+use uuid::Uuid;
+
+/// Database representation of an integrator webhook endpoint
+/// The signing secret never leaves this model except at registration time
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct WebhookEndpointModel {
+    pub id: Uuid,
+    pub integrator_address: Vec<u8>,
+    pub url: String,
+    pub secret: String,
+    /// Subscribed event names (e.g. "order.fulfilled")
+    pub event_types: Vec<String>,
+    pub is_active: bool,
+    pub consecutive_failures: i32,
+    pub disabled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookEndpointModel {
+    /// Converts database model to domain type, dropping unknown event names
+    pub fn to_domain(&self) -> WebhookEndpoint {
+        WebhookEndpoint {
+            id: self.id,
+            integrator_address: format!("0x{}", hex::encode(&self.integrator_address)),
+            url: self.url.clone(),
+            event_types: self
+                .event_types
+                .iter()
+                .filter_map(|name| name.parse::<WebhookEventType>().ok())
+                .collect(),
+            is_active: self.is_active,
+            consecutive_failures: self.consecutive_failures.max(0) as u32,
+            created_at: self.created_at,
+            disabled_at: self.disabled_at,
+        }
+    }
+}
+
+/// Order event captured for webhook fan-out
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct WebhookEventModel {
+    pub id: Uuid,
+    pub integrator_address: Vec<u8>,
+    pub order_id: Vec<u8>,
+    pub event_type: String,
+    /// Serialized `WebhookPayload`, stored as text so every attempt sends
+    /// and signs the same bytes
+    pub payload: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Pending or finished delivery of one event to one endpoint
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct WebhookDeliveryModel {
+    pub id: i64,
+    pub endpoint_id: Uuid,
+    pub event_id: Uuid,
+    pub status: String, // PENDING, SUCCEEDED, EXHAUSTED
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Due delivery joined with everything the worker needs to send it
+#[derive(Debug, Clone, FromRow)]
+pub struct DueWebhookDelivery {
+    pub delivery_id: i64,
+    pub attempts: i32,
+    pub endpoint_id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub payload: String,
+}
+
+/// Single HTTP attempt recorded for auditing
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct WebhookAttemptModel {
+    pub id: i64,
+    pub delivery_id: i64,
+    pub endpoint_id: Uuid,
+    pub event_id: Uuid,
+    pub attempt: i32,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub succeeded: bool,
+    pub duration_ms: i64,
+    pub attempted_at: DateTime<Utc>,
+}
+
+impl WebhookAttemptModel {
+    pub fn to_domain(&self) -> WebhookDeliveryAttempt {
+        WebhookDeliveryAttempt {
+            delivery_id: self.delivery_id,
+            endpoint_id: self.endpoint_id,
+            event_id: self.event_id,
+            attempt: self.attempt.max(0) as u32,
+            status_code: self.status_code.map(|code| code as u16),
+            error: self.error.clone(),
+            succeeded: self.succeeded,
+            duration_ms: self.duration_ms.max(0) as u64,
+            attempted_at: self.attempted_at,
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Envelope encryption of recipient PII at rest
+//!
+//! Each record is sealed with its own random data key under AES-256-GCM.
+//! The data key is wrapped by a key-encryption key from the [`KeyRing`] and
+//! stored next to the ciphertext with that key's ID. Keys are rotated by
+//! adding a new key, making it active and [rewrapping](KeyRing::rewrap) the
+//! data keys of older records; their ciphertext is left as it is.
+//!
+//! Any service with the key ring can seal. Opening and rewrapping need the
+//! `pii-decrypt` feature, which only the provider-service enables, so
+//! recipient details are only ever decrypted where payouts are made.
+
+use std::collections::HashMap;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key};
+use shared_types::RecipientDetails;
+
+use crate::error::{DatabaseError, Result};
+
+/// Bytes of the nonce prefixed to wrapped keys and ciphertexts
+#[cfg(feature = "pii-decrypt")]
+const NONCE_LEN: usize = 12;
+
+/// A sealed record: ciphertext plus its data key, wrapped by the key-encryption
+/// key `key_id`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope {
+    pub key_id: String,
+    /// Nonce followed by the encrypted data key
+    pub wrapped_key: Vec<u8>,
+    /// Nonce followed by the encrypted record
+    pub ciphertext: Vec<u8>,
+}
+
+/// Key-encryption keys by ID, and the one new records are sealed with
+pub struct KeyRing {
+    active: String,
+    keys: HashMap<String, Key<Aes256Gcm>>,
+}
+
+impl std::fmt::Debug for KeyRing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut ids: Vec<&String> = self.keys.keys().collect();
+        ids.sort();
+        f.debug_struct("KeyRing").field("active", &self.active).field("keys", &ids).finish()
+    }
+}
+
+impl KeyRing {
+    /// Build a key ring from `(id, 32-byte key)` pairs
+    pub fn new(active: &str, keys: impl IntoIterator<Item = (String, [u8; 32])>) -> Result<Self> {
+        let keys: HashMap<String, Key<Aes256Gcm>> = keys
+            .into_iter()
+            .map(|(id, key)| (id, Key::<Aes256Gcm>::from(key)))
+            .collect();
+        if !keys.contains_key(active) {
+            return Err(DatabaseError::ConfigError(format!("Active PII key {} is not in the key ring", active)));
+        }
+        Ok(Self { active: active.to_string(), keys })
+    }
+
+    /// Load `PII_ENCRYPTION_KEYS` (`id:hex,...` with 32-byte keys) and
+    /// `PII_ACTIVE_KEY_ID`, which defaults to the last key listed
+    ///
+    /// An all-zero key is refused, so a placeholder can't end up sealing
+    /// real records.
+    ///
+    /// # Returns
+    /// * `Result<Option<KeyRing>>` - None when no keys are configured
+    pub fn from_env() -> Result<Option<Self>> {
+        let Ok(raw) = std::env::var("PII_ENCRYPTION_KEYS") else {
+            return Ok(None);
+        };
+        let mut keys = Vec::new();
+        for entry in raw.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (id, key) = entry
+                .split_once(':')
+                .ok_or_else(|| DatabaseError::ConfigError(format!("PII key entry without an ID: {}", mask(entry))))?;
+            let key: [u8; 32] = hex::decode(key.trim())
+                .ok()
+                .and_then(|key| key.try_into().ok())
+                .ok_or_else(|| DatabaseError::ConfigError(format!("PII key {} must be 32 hex-encoded bytes", id)))?;
+            if key == [0u8; 32] {
+                return Err(DatabaseError::ConfigError(format!("PII key {} is all zeros", id)));
+            }
+            keys.push((id.trim().to_string(), key));
+        }
+        let Some((last, _)) = keys.last() else {
+            return Ok(None);
+        };
+        let active = std::env::var("PII_ACTIVE_KEY_ID")
+            .ok()
+            .filter(|id| !id.trim().is_empty())
+            .unwrap_or_else(|| last.clone());
+        Self::new(active.trim(), keys).map(Some)
+    }
+
+    /// ID of the key new records are sealed with
+    pub fn active_key_id(&self) -> &str {
+        &self.active
+    }
+
+    /// Encrypt `plaintext` under a fresh data key
+    ///
+    /// `context` is authenticated but not stored, e.g. the ID of the row the
+    /// record belongs to, so a ciphertext copied to another row will not open.
+    pub fn seal(&self, plaintext: &[u8], context: &[u8]) -> Result<Envelope> {
+        let data_key = Aes256Gcm::generate_key(OsRng);
+        let ciphertext = encrypt(&data_key, plaintext, context)?;
+        let wrapped_key = encrypt(self.key(&self.active)?, &data_key, self.active.as_bytes())?;
+        Ok(Envelope { key_id: self.active.clone(), wrapped_key, ciphertext })
+    }
+
+    /// Seal recipient details for storage
+    pub fn seal_recipient(&self, recipient: &RecipientDetails, context: &[u8]) -> Result<Envelope> {
+        let plaintext = serde_json::to_vec(&recipient.expose())
+            .map_err(|e| DatabaseError::EncryptionError(format!("Failed to encode recipient: {}", e)))?;
+        self.seal(&plaintext, context)
+    }
+
+    /// Decrypt a record sealed with the same `context`
+    #[cfg(feature = "pii-decrypt")]
+    pub fn open(&self, envelope: &Envelope, context: &[u8]) -> Result<Vec<u8>> {
+        let data_key = self.unwrap_key(envelope)?;
+        decrypt(&data_key, &envelope.ciphertext, context)
+    }
+
+    #[cfg(feature = "pii-decrypt")]
+    pub fn open_recipient(&self, envelope: &Envelope, context: &[u8]) -> Result<RecipientDetails> {
+        serde_json::from_slice(&self.open(envelope, context)?)
+            .map_err(|e| DatabaseError::EncryptionError(format!("Failed to decode recipient: {}", e)))
+    }
+
+    /// Wrap a record's data key with the active key, leaving its ciphertext
+    /// untouched
+    ///
+    /// # Returns
+    /// * `Result<Option<Envelope>>` - None when the record already uses the
+    ///   active key
+    #[cfg(feature = "pii-decrypt")]
+    pub fn rewrap(&self, envelope: &Envelope) -> Result<Option<Envelope>> {
+        if envelope.key_id == self.active {
+            return Ok(None);
+        }
+        let data_key = self.unwrap_key(envelope)?;
+        Ok(Some(Envelope {
+            key_id: self.active.clone(),
+            wrapped_key: encrypt(self.key(&self.active)?, &data_key, self.active.as_bytes())?,
+            ciphertext: envelope.ciphertext.clone(),
+        }))
+    }
+
+    #[cfg(feature = "pii-decrypt")]
+    fn unwrap_key(&self, envelope: &Envelope) -> Result<Key<Aes256Gcm>> {
+        let key = self.key(&envelope.key_id)?;
+        let data_key = decrypt(key, &envelope.wrapped_key, envelope.key_id.as_bytes())?;
+        let data_key = <[u8; 32]>::try_from(data_key.as_slice())
+            .map_err(|_| DatabaseError::EncryptionError("Wrapped data key has the wrong length".to_string()))?;
+        Ok(Key::<Aes256Gcm>::from(data_key))
+    }
+
+    fn key(&self, id: &str) -> Result<&Key<Aes256Gcm>> {
+        self.keys
+            .get(id)
+            .ok_or_else(|| DatabaseError::EncryptionError(format!("Unknown PII key {}", id)))
+    }
+}
+
+fn encrypt(key: &Key<Aes256Gcm>, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = Aes256Gcm::new(key)
+        .encrypt(&nonce, Payload { msg: plaintext, aad })
+        .map_err(|_| DatabaseError::EncryptionError("Encryption failed".to_string()))?;
+    let mut sealed = nonce.to_vec();
+    sealed.extend(ciphertext);
+    Ok(sealed)
+}
+
+#[cfg(feature = "pii-decrypt")]
+fn decrypt(key: &Key<Aes256Gcm>, sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    if sealed.len() < NONCE_LEN {
+        return Err(DatabaseError::EncryptionError("Sealed value is truncated".to_string()));
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let nonce = <[u8; NONCE_LEN]>::try_from(nonce)
+        .map_err(|_| DatabaseError::EncryptionError("Sealed value is truncated".to_string()))?;
+    Aes256Gcm::new(key)
+        .decrypt(&aes_gcm::Nonce::from(nonce), Payload { msg: ciphertext, aad })
+        .map_err(|_| DatabaseError::EncryptionError("Decryption failed: wrong key or tampered value".to_string()))
+}
+
+/// Keeps key material out of configuration errors
+fn mask(entry: &str) -> String {
+    format!("{}...", entry.chars().take(4).collect::<String>())
+}
+
+#[cfg(all(test, feature = "pii-decrypt"))]
+mod tests {
+    use super::*;
+
+    fn recipient() -> RecipientDetails {
+        RecipientDetails {
+            account_name: "Adaeze Okafor".to_string(),
+            account_number: "0123456785".to_string(),
+            bank_name: Some("GTBank".to_string()),
+            bank_code: Some("058".to_string()),
+            phone_number: None,
+            additional_info: None,
+        }
+    }
+
+    #[test]
+    fn test_seal_open_and_rotate() {
+        let old = KeyRing::new("2025-01", [("2025-01".to_string(), [1u8; 32])]).unwrap();
+        let envelope = old.seal_recipient(&recipient(), b"order-1").unwrap();
+        assert_eq!(envelope.key_id, "2025-01");
+        assert!(!envelope.ciphertext.windows(10).any(|w| w == b"0123456785"));
+
+        let opened = old.open_recipient(&envelope, b"order-1").unwrap();
+        assert_eq!(opened.account_number, "0123456785");
+        assert!(matches!(old.open(&envelope, b"order-2"), Err(DatabaseError::EncryptionError(_))));
+
+        // Rotation rewraps the data key; the ciphertext is unchanged
+        let ring = KeyRing::new(
+            "2026-01",
+            [("2025-01".to_string(), [1u8; 32]), ("2026-01".to_string(), [2u8; 32])],
+        )
+        .unwrap();
+        let rewrapped = ring.rewrap(&envelope).unwrap().unwrap();
+        assert_eq!(rewrapped.key_id, "2026-01");
+        assert_eq!(rewrapped.ciphertext, envelope.ciphertext);
+        assert!(ring.rewrap(&rewrapped).unwrap().is_none());
+
+        let current = KeyRing::new("2026-01", [("2026-01".to_string(), [2u8; 32])]).unwrap();
+        assert_eq!(current.open_recipient(&rewrapped, b"order-1").unwrap().account_name, "Adaeze Okafor");
+        assert!(current.open(&envelope, b"order-1").is_err());
+        assert!(KeyRing::new("missing", [("2026-01".to_string(), [2u8; 32])]).is_err());
+    }
+
+    #[test]
+    fn test_from_env_refuses_zero_key() {
+        std::env::set_var("PII_ENCRYPTION_KEYS", format!("dev-1:{}", "00".repeat(32)));
+        std::env::remove_var("PII_ACTIVE_KEY_ID");
+        assert!(matches!(KeyRing::from_env(), Err(DatabaseError::ConfigError(_))));
+
+        std::env::set_var("PII_ENCRYPTION_KEYS", format!("dev-1:{}", "01".repeat(32)));
+        assert_eq!(KeyRing::from_env().unwrap().unwrap().active_key_id(), "dev-1");
+        std::env::remove_var("PII_ENCRYPTION_KEYS");
+    }
+}
@@ -0,0 +1,148 @@
+use sqlx::PgPool;
+use crate::{error::Result, models::OrderAllocationModel};
+
+pub struct AllocationRepository {
+    pool: PgPool,
+}
+
+const SELECT_ALLOCATION: &str = r#"
+    SELECT
+        id, order_id, leg, provider, amount, filled_amount, status, tx_hash,
+        created_at, updated_at, settled_at
+    FROM order_allocations
+"#;
+
+impl AllocationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Insert every leg of a split order atomically
+    pub async fn create_all(&self, allocations: &[OrderAllocationModel]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for allocation in allocations {
+            sqlx::query(
+                r#"
+                INSERT INTO order_allocations (
+                    id, order_id, leg, provider, amount, filled_amount, status
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+            )
+            .bind(allocation.id)
+            .bind(&allocation.order_id)
+            .bind(allocation.leg)
+            .bind(&allocation.provider)
+            .bind(&allocation.amount)
+            .bind(&allocation.filled_amount)
+            .bind(&allocation.status)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Legs of an order, in leg order
+    pub async fn list_for_order(&self, order_id: &[u8]) -> Result<Vec<OrderAllocationModel>> {
+        let allocations = sqlx::query_as::<_, OrderAllocationModel>(&format!(
+            "{} WHERE order_id = $1 ORDER BY leg ASC",
+            SELECT_ALLOCATION
+        ))
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(allocations)
+    }
+
+    /// Store a fill on a leg and, when given, the order status it implies
+    ///
+    /// The leg is only updated if its filled amount is still
+    /// `previous_filled_amount`, so concurrent fills cannot overwrite each
+    /// other.
+    ///
+    /// # Returns
+    /// * `Result<bool>` - False when another fill landed first and nothing
+    ///   was written
+    pub async fn record_fill(
+        &self,
+        allocation: &OrderAllocationModel,
+        previous_filled_amount: &str,
+        order_status: Option<&str>,
+    ) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let updated = sqlx::query(
+            r#"
+            UPDATE order_allocations
+            SET filled_amount = $1, status = $2, tx_hash = $3, settled_at = $4
+            WHERE id = $5 AND filled_amount = $6
+            "#,
+        )
+        .bind(&allocation.filled_amount)
+        .bind(&allocation.status)
+        .bind(&allocation.tx_hash)
+        .bind(allocation.settled_at)
+        .bind(allocation.id)
+        .bind(previous_filled_amount)
+        .execute(&mut *tx)
+        .await?;
+        if updated.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        if let Some(status) = order_status {
+            sqlx::query("UPDATE orders SET status = $1::order_status WHERE order_id = $2")
+                .bind(status)
+                .bind(&allocation.order_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Close a leg the provider could not pay out
+    ///
+    /// # Returns
+    /// * `Result<bool>` - False when the leg was already settled or failed
+    pub async fn mark_failed(&self, order_id: &[u8], leg: i32) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE order_allocations
+            SET status = 'FAILED'
+            WHERE order_id = $1 AND leg = $2 AND status IN ('PENDING', 'PARTIALLY_FILLED')
+            "#,
+        )
+        .bind(order_id)
+        .bind(leg)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Hand a leg nothing has been paid out on to another provider
+    ///
+    /// # Returns
+    /// * `Result<bool>` - False when the leg has fills or is closed
+    pub async fn reassign(&self, order_id: &[u8], leg: i32, provider: &[u8]) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE order_allocations
+            SET provider = $3
+            WHERE order_id = $1 AND leg = $2 AND status = 'PENDING'
+            "#,
+        )
+        .bind(order_id)
+        .bind(leg)
+        .bind(provider)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
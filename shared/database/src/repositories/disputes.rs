@@ -0,0 +1,297 @@
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::{
+    error::{DatabaseError, Result},
+    models::{DisputeModel, SettlementHoldModel},
+};
+
+pub struct DisputeRepository {
+    pool: PgPool,
+}
+
+const DISPUTE_COLUMNS: &str = r#"
+    id, order_id, user_address, provider, reason, status, proof, outcome,
+    resolution_note, resolved_by, opened_at, evidence_submitted_at, resolved_at
+"#;
+
+impl DisputeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Open a dispute, hold the order's settlement and count the dispute
+    /// against the provider
+    ///
+    /// # Returns
+    /// * `Result<bool>` - False when the order already has a dispute under
+    ///   review and nothing was written
+    pub async fn create(&self, dispute: &DisputeModel) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO disputes (id, order_id, user_address, provider, reason, status, opened_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (order_id) WHERE status <> 'resolved' DO NOTHING
+            "#,
+        )
+        .bind(dispute.id)
+        .bind(&dispute.order_id)
+        .bind(&dispute.user_address)
+        .bind(&dispute.provider)
+        .bind(&dispute.reason)
+        .bind(&dispute.status)
+        .bind(dispute.opened_at)
+        .execute(&mut *tx)
+        .await?;
+        if inserted.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        // A later dispute on the same order starts a fresh hold
+        sqlx::query(
+            r#"
+            INSERT INTO settlement_holds (order_id, dispute_id, held_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (order_id) DO UPDATE
+            SET dispute_id = EXCLUDED.dispute_id, held_at = EXCLUDED.held_at,
+                fulfilled_at = NULL, released_at = NULL, settled_at = NULL
+            "#,
+        )
+        .bind(&dispute.order_id)
+        .bind(dispute.id)
+        .bind(dispute.opened_at)
+        .execute(&mut *tx)
+        .await?;
+
+        bump_reputation(&mut tx, &dispute.provider, "disputes").await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<Option<DisputeModel>> {
+        let dispute = sqlx::query_as::<_, DisputeModel>(&format!(
+            "SELECT {} FROM disputes WHERE id = $1",
+            DISPUTE_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(dispute)
+    }
+
+    /// The dispute holding an order's settlement, if any
+    pub async fn active_for_order(&self, order_id: &[u8]) -> Result<Option<DisputeModel>> {
+        let dispute = sqlx::query_as::<_, DisputeModel>(&format!(
+            "SELECT {} FROM disputes WHERE order_id = $1 AND status <> 'resolved'",
+            DISPUTE_COLUMNS
+        ))
+        .bind(order_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(dispute)
+    }
+
+    /// Whether a dispute other than `id` was answered with a proof of the
+    /// same payment
+    pub async fn proof_reference_used(&self, id: Uuid, transaction_reference: &str) -> Result<bool> {
+        let used = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM disputes
+                WHERE id <> $1 AND proof IS NOT NULL AND btrim(proof->>'transaction_reference') = $2
+            )
+            "#,
+        )
+        .bind(id)
+        .bind(transaction_reference)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(used)
+    }
+
+    /// Disputes on an order, newest first
+    pub async fn list_for_order(&self, order_id: &[u8]) -> Result<Vec<DisputeModel>> {
+        let disputes = sqlx::query_as::<_, DisputeModel>(&format!(
+            "SELECT {} FROM disputes WHERE order_id = $1 ORDER BY opened_at DESC",
+            DISPUTE_COLUMNS
+        ))
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(disputes)
+    }
+
+    /// Disputes, oldest first, optionally only those in a status or against
+    /// a provider
+    pub async fn list(&self, status: Option<&str>, provider: Option<&[u8]>, limit: i64) -> Result<Vec<DisputeModel>> {
+        let disputes = sqlx::query_as::<_, DisputeModel>(&format!(
+            r#"
+            SELECT {} FROM disputes
+            WHERE ($1::TEXT IS NULL OR status = $1) AND ($2::BYTEA IS NULL OR provider = $2)
+            ORDER BY opened_at ASC
+            LIMIT $3
+            "#,
+            DISPUTE_COLUMNS
+        ))
+        .bind(status)
+        .bind(provider)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(disputes)
+    }
+
+    /// Store the provider's proof and evidence; providers may replace it
+    /// until the dispute is resolved
+    ///
+    /// # Returns
+    /// * `Result<Option<DisputeModel>>` - None when the dispute is not
+    ///   against `provider` or is already resolved
+    ///
+    /// # Errors
+    /// * `DatabaseError::DuplicateEntry` - Another dispute was answered with
+    ///   the proof's transaction reference
+    pub async fn submit_evidence(
+        &self,
+        id: Uuid,
+        provider: &[u8],
+        proof: &serde_json::Value,
+    ) -> Result<Option<DisputeModel>> {
+        let dispute = sqlx::query_as::<_, DisputeModel>(&format!(
+            r#"
+            UPDATE disputes
+            SET proof = $3, status = 'evidence_submitted', evidence_submitted_at = NOW()
+            WHERE id = $1 AND provider = $2 AND status <> 'resolved'
+            RETURNING {}
+            "#,
+            DISPUTE_COLUMNS
+        ))
+        .bind(id)
+        .bind(provider)
+        .bind(proof)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.constraint() == Some("idx_disputes_proof_reference") => {
+                let reference = proof.get("transaction_reference").and_then(|r| r.as_str()).unwrap_or_default();
+                DatabaseError::DuplicateEntry(format!("payment {} already answered another dispute", reference.trim()))
+            }
+            e => e.into(),
+        })?;
+
+        Ok(dispute)
+    }
+
+    /// Record an admin's decision and lift the order's settlement hold;
+    /// refunds count against the provider
+    ///
+    /// # Returns
+    /// * `Result<Option<DisputeModel>>` - None when the dispute was already
+    ///   resolved and nothing was written
+    pub async fn resolve(
+        &self,
+        id: Uuid,
+        outcome: &str,
+        note: Option<&str>,
+        resolved_by: &[u8],
+        counts_against_provider: bool,
+    ) -> Result<Option<DisputeModel>> {
+        let mut tx = self.pool.begin().await?;
+
+        let dispute = sqlx::query_as::<_, DisputeModel>(&format!(
+            r#"
+            UPDATE disputes
+            SET status = 'resolved', outcome = $2, resolution_note = $3, resolved_by = $4, resolved_at = NOW()
+            WHERE id = $1 AND status <> 'resolved'
+            RETURNING {}
+            "#,
+            DISPUTE_COLUMNS
+        ))
+        .bind(id)
+        .bind(outcome)
+        .bind(note)
+        .bind(resolved_by)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(dispute) = dispute else {
+            return Ok(None);
+        };
+
+        sqlx::query("UPDATE settlement_holds SET released_at = NOW() WHERE dispute_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        if counts_against_provider {
+            bump_reputation(&mut tx, &dispute.provider, "disputes_lost").await?;
+        }
+        tx.commit().await?;
+        Ok(Some(dispute))
+    }
+
+    /// The order's settlement hold, if it was ever disputed
+    pub async fn settlement_hold(&self, order_id: &[u8]) -> Result<Option<SettlementHoldModel>> {
+        let hold = sqlx::query_as::<_, SettlementHoldModel>(
+            r#"
+            SELECT order_id, dispute_id, held_at, fulfilled_at, released_at, settled_at
+            FROM settlement_holds
+            WHERE order_id = $1
+            "#,
+        )
+        .bind(order_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(hold)
+    }
+
+    /// Store the fulfilment a hold recorded or settled; the release is only
+    /// ever written by [`resolve`](Self::resolve)
+    ///
+    /// # Returns
+    /// * `Result<bool>` - False when the hold was released or reopened since
+    ///   it was read
+    pub async fn save_settlement_hold(&self, hold: &SettlementHoldModel) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE settlement_holds
+            SET fulfilled_at = $3, settled_at = $4
+            WHERE order_id = $1 AND dispute_id = $2
+              AND released_at IS NOT DISTINCT FROM $5
+            "#,
+        )
+        .bind(&hold.order_id)
+        .bind(hold.dispute_id)
+        .bind(hold.fulfilled_at)
+        .bind(hold.settled_at)
+        .bind(hold.released_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Increment one of the provider's dispute counters, creating its
+/// reputation row if needed
+async fn bump_reputation(tx: &mut Transaction<'_, Postgres>, provider: &[u8], counter: &'static str) -> Result<()> {
+    sqlx::query(&format!(
+        r#"
+        INSERT INTO provider_reputation (provider, {counter}, last_updated)
+        VALUES ($1, 1, NOW())
+        ON CONFLICT (provider) DO UPDATE
+        SET {counter} = provider_reputation.{counter} + 1, last_updated = NOW()
+        "#,
+        counter = counter
+    ))
+    .bind(provider)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
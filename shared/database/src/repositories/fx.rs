@@ -0,0 +1,86 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::{error::Result, models::FxRateRecordModel};
+
+pub struct FxRateRepository {
+    pool: PgPool,
+}
+
+impl FxRateRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record the rate a quote or order was priced with
+    pub async fn record(&self, record: &FxRateRecordModel) -> Result<i64> {
+        let (id,): (i64,) = sqlx::query_as(
+            r#"
+            INSERT INTO fx_rate_history (
+                quote_id, order_id, pair, rate, sources, rejected_count, stale, observed_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id
+            "#,
+        )
+        .bind(record.quote_id)
+        .bind(&record.order_id)
+        .bind(&record.pair)
+        .bind(&record.rate)
+        .bind(&record.sources)
+        .bind(record.rejected_count)
+        .bind(record.stale)
+        .bind(record.observed_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Attach the order created from a quote to the rates recorded for it
+    pub async fn link_order(&self, quote_id: Uuid, order_id: &[u8]) -> Result<u64> {
+        let result = sqlx::query("UPDATE fx_rate_history SET order_id = $2 WHERE quote_id = $1")
+            .bind(quote_id)
+            .bind(order_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Rates recorded for a quote, oldest first
+    pub async fn list_for_quote(&self, quote_id: Uuid) -> Result<Vec<FxRateRecordModel>> {
+        let records = sqlx::query_as::<_, FxRateRecordModel>(
+            r#"
+            SELECT
+                id, quote_id, order_id, pair, rate, sources, rejected_count, stale,
+                observed_at, recorded_at
+            FROM fx_rate_history
+            WHERE quote_id = $1
+            ORDER BY recorded_at ASC
+            "#,
+        )
+        .bind(quote_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Rates recorded for an order, oldest first
+    pub async fn list_for_order(&self, order_id: &[u8]) -> Result<Vec<FxRateRecordModel>> {
+        let records = sqlx::query_as::<_, FxRateRecordModel>(
+            r#"
+            SELECT
+                id, quote_id, order_id, pair, rate, sources, rejected_count, stale,
+                observed_at, recorded_at
+            FROM fx_rate_history
+            WHERE order_id = $1
+            ORDER BY recorded_at ASC
+            "#,
+        )
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+}
@@ -1,7 +1,25 @@
+pub mod allocations;
+pub mod disputes;
+pub mod fx;
 pub mod orders;
+pub mod payouts;
 pub mod providers;
 pub mod proposals;
+pub mod quotes;
+pub mod routing;
+pub mod stakes;
+pub mod tier_limits;
+pub mod webhooks;
 
+pub use allocations::AllocationRepository;
+pub use disputes::DisputeRepository;
+pub use fx::FxRateRepository;
 pub use orders::OrderRepository;
+pub use payouts::PayoutRepository;
 pub use providers::ProviderRepository;
-pub use proposals::ProposalRepository;
\ No newline at end of file
+pub use proposals::ProposalRepository;
+pub use quotes::QuoteRepository;
+pub use routing::{RoutingApprovalRepository, RoutingDecisionRepository};
+pub use stakes::StakeRepository;
+pub use tier_limits::TierLimitsRepository;
+pub use webhooks::WebhookRepository;
\ No newline at end of file
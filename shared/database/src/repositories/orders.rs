@@ -1,5 +1,10 @@
-use sqlx::PgPool;
-use crate::{error::Result, models::OrderModel};
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool, Row};
+use uuid::Uuid;
+use crate::{
+    error::{DatabaseError, Result},
+    models::OrderModel,
+};
 
 pub struct OrderRepository {
     pool: PgPool,
@@ -12,119 +17,348 @@ impl OrderRepository {
     
     /// Insert a new order
     pub async fn create(&self, order: &OrderModel) -> Result<i32> {
-        let record = sqlx::query!(
+        let id = sqlx::query_scalar::<_, i32>(
             r#"
             INSERT INTO orders (
-                order_id, user_address, token, amount, 
-                refund_address, integrator_address, integrator_fees, status, tier, 
+                order_id, user_address, token, amount,
+                refund_address, integrator_address, integrator_fees, status, tier,
                 currency, block_number, tx_hash, created_at, expires_at
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8::order_status, $9::order_tier, $10, $11, $12, $13, $14)
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6,
+                COALESCE((SELECT fee_bps FROM integrator_fees WHERE integrator_address = $6), 50),
+                $7::order_status, $8::order_tier, $9, $10, $11, $12, $13
+            )
             RETURNING id
             "#,
-            order.order_id,
-            order.user_address,
-            order.token,
-            order.amount,
-            order.refund_address,
-            order.integrator_address,
-            order.integrator_fees,
-            order.status.as_str(),      // I Convert enum to string
-            order.tier.as_str(),         // I Convert enum to string
-            order.currency,
-            order.block_number,
-            order.tx_hash,
-            order.created_at,
-            order.expires_at
         )
+        .bind(&order.order_id)
+        .bind(&order.user_address)
+        .bind(&order.token)
+        .bind(&order.amount)
+        .bind(&order.refund_address)
+        .bind(&order.integrator_address)
+        .bind(&order.status)
+        .bind(&order.tier)
+        .bind(&order.currency)
+        .bind(order.block_number)
+        .bind(&order.tx_hash)
+        .bind(order.created_at)
+        .bind(order.expires_at)
         .fetch_one(&self.pool)
         .await?;
-        
-        Ok(record.id)
+
+        Ok(id)
     }
-    
+
     /// Get order by blockchain order_id (bytes32)
     pub async fn get_by_order_id(&self, order_id: &[u8]) -> Result<OrderModel> {
-        let order = sqlx::query_as!(
-            OrderModel,
-            r#"
-            SELECT 
-                id, order_id, user_address, token, amount,
-                refund_address, integrator_address, integrator_fees,
-                status as "status: OrderStatus",
-                tier as "tier: OrderTier",
-                currency,
-                block_number, tx_hash, created_at, expires_at, updated_at
-            FROM orders
-            WHERE order_id = $1
-            "#,
-            order_id
-        )
-        .fetch_one(&self.pool)
-        .await?;
-        
-        Ok(order)
+        self.find(order_id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Order 0x{}", hex::encode(order_id))))
     }
-    
+
     /// Get all pending orders
     pub async fn get_pending_orders(&self) -> Result<Vec<OrderModel>> {
-        let orders = sqlx::query_as!(
-            OrderModel,
+        let orders = sqlx::query_as::<_, OrderModel>(
             r#"
-            SELECT 
+            SELECT
                 id, order_id, user_address, token, amount,
-                refund_address, integrator_address, integrator_fees,
-                status as "status: OrderStatus",
-                tier as "tier: OrderTier",
-                currency,
+                refund_address, integrator_address,
+                int4send(integrator_fees) AS integrator_fee,
+                status::TEXT AS status, tier::TEXT AS tier, currency,
                 block_number, tx_hash, created_at, expires_at, updated_at
             FROM orders
             WHERE status = 'PENDING'
             ORDER BY created_at DESC
-            "#
+            "#,
         )
         .fetch_all(&self.pool)
         .await?;
-        
+
         Ok(orders)
     }
-    
+
     /// Update order status
     pub async fn update_status(&self, order_id: &[u8], new_status: &str) -> Result<()> {
-        sqlx::query!(
+        sqlx::query(
             r#"
             UPDATE orders
             SET status = $1::order_status, updated_at = NOW()
             WHERE order_id = $2
             "#,
-            .bind(new_status),
-            .bind(dorder_id)
         )
+        .bind(new_status)
+        .bind(order_id)
         .execute(&self.pool)
         .await?;
-        
+
         Ok(())
     }
-    
+
     /// Get expired orders
     pub async fn get_expired_orders(&self) -> Result<Vec<OrderModel>> {
-        let orders = sqlx::query_as!(
-            OrderModel,
+        let orders = sqlx::query_as::<_, OrderModel>(
             r#"
-            SELECT 
+            SELECT
                 id, order_id, user_address, token, amount,
-                refund_address, integrator_address, integrator_fees,
-                status as "status: OrderStatus",
-                tier as "tier: OrderTier",
-                currency,
+                refund_address, integrator_address,
+                int4send(integrator_fees) AS integrator_fee,
+                status::TEXT AS status, tier::TEXT AS tier, currency,
                 block_number, tx_hash, created_at, expires_at, updated_at
             FROM orders
             WHERE status = 'PENDING'
             AND expires_at < NOW()
-            "#
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(orders)
+    }
+
+    /// Orders created between two instants, oldest first
+    pub async fn list_created_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<OrderModel>> {
+        let orders = sqlx::query_as::<_, OrderModel>(
+            r#"
+            SELECT
+                id, order_id, user_address, token, amount,
+                refund_address, integrator_address,
+                int4send(integrator_fees) AS integrator_fee,
+                status::TEXT AS status, tier::TEXT AS tier, currency,
+                block_number, tx_hash, created_at, expires_at, updated_at
+            FROM orders
+            WHERE created_at >= $1 AND created_at < $2
+            ORDER BY created_at ASC
+            "#,
         )
+        .bind(from)
+        .bind(to)
         .fetch_all(&self.pool)
         .await?;
-        
+
         Ok(orders)
     }
-}
\ No newline at end of file
+
+    /// Insert an order, charging the integrator's configured fee (50 bps
+    /// when none is set), and return it as stored. An order created from a
+    /// quote keeps its ID and marks the quote used.
+    ///
+    /// # Returns
+    /// * `Result<Option<OrderModel>>` - None when the user already created an
+    ///   order with `idempotency_key`
+    ///
+    /// # Errors
+    /// * `DatabaseError::DuplicateEntry` - The quote already backs an order
+    pub async fn insert(
+        &self,
+        order: &OrderModel,
+        idempotency_key: Option<&str>,
+        quote_id: Option<Uuid>,
+    ) -> Result<Option<OrderModel>> {
+        let mut tx = self.pool.begin().await?;
+
+        if let Some(quote_id) = quote_id {
+            // Locks the quote, so a concurrent order from it waits and then
+            // finds it used
+            let claimed = sqlx::query("UPDATE quotes SET used_at = NOW() WHERE quote_id = $1 AND used_at IS NULL")
+                .bind(quote_id)
+                .execute(&mut *tx)
+                .await?;
+            if claimed.rows_affected() == 0 {
+                return Err(DatabaseError::DuplicateEntry(format!("quote {} was already used", quote_id)));
+            }
+        }
+
+        let stored = sqlx::query_as::<_, OrderModel>(
+            r#"
+            INSERT INTO orders (
+                order_id, user_address, token, amount, refund_address, integrator_address,
+                integrator_fees, status, tier, currency, block_number, tx_hash, created_at, expires_at,
+                idempotency_key, quote_id
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6,
+                COALESCE((SELECT fee_bps FROM integrator_fees WHERE integrator_address = $6), 50),
+                $7::order_status, $8::order_tier, $9, $10, $11, $12, $13, $14, $15
+            )
+            ON CONFLICT (user_address, idempotency_key) WHERE idempotency_key IS NOT NULL DO NOTHING
+            RETURNING
+                id, order_id, user_address, token, amount,
+                refund_address, integrator_address,
+                int4send(integrator_fees) AS integrator_fee,
+                status::TEXT AS status, tier::TEXT AS tier, currency,
+                block_number, tx_hash, created_at, expires_at, updated_at
+            "#,
+        )
+        .bind(&order.order_id)
+        .bind(&order.user_address)
+        .bind(&order.token)
+        .bind(&order.amount)
+        .bind(&order.refund_address)
+        .bind(&order.integrator_address)
+        .bind(&order.status)
+        .bind(&order.tier)
+        .bind(&order.currency)
+        .bind(order.block_number)
+        .bind(&order.tx_hash)
+        .bind(order.created_at)
+        .bind(order.expires_at)
+        .bind(idempotency_key)
+        .bind(quote_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        // A repeated create leaves the quote to the order it first created
+        if stored.is_some() {
+            tx.commit().await?;
+        }
+        Ok(stored)
+    }
+
+    /// The order a user created with an idempotency key, if any
+    pub async fn find_by_idempotency_key(&self, user_address: &[u8], idempotency_key: &str) -> Result<Option<OrderModel>> {
+        let order = sqlx::query_as::<_, OrderModel>(
+            r#"
+            SELECT
+                id, order_id, user_address, token, amount,
+                refund_address, integrator_address,
+                int4send(integrator_fees) AS integrator_fee,
+                status::TEXT AS status, tier::TEXT AS tier, currency,
+                block_number, tx_hash, created_at, expires_at, updated_at
+            FROM orders
+            WHERE user_address = $1 AND idempotency_key = $2
+            "#,
+        )
+        .bind(user_address)
+        .bind(idempotency_key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(order)
+    }
+
+    /// Order by blockchain order_id, if it exists
+    pub async fn find(&self, order_id: &[u8]) -> Result<Option<OrderModel>> {
+        let order = sqlx::query_as::<_, OrderModel>(
+            r#"
+            SELECT
+                id, order_id, user_address, token, amount,
+                refund_address, integrator_address,
+                int4send(integrator_fees) AS integrator_fee,
+                status::TEXT AS status, tier::TEXT AS tier, currency,
+                block_number, tx_hash, created_at, expires_at, updated_at
+            FROM orders
+            WHERE order_id = $1
+            "#,
+        )
+        .bind(order_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(order)
+    }
+
+    /// One page of the orders of a user and/or integrator, newest first,
+    /// starting after the `(created_at, id)` of the previous page's last order
+    pub async fn list_page(
+        &self,
+        user_address: Option<&[u8]>,
+        integrator_address: Option<&[u8]>,
+        after: Option<(DateTime<Utc>, i32)>,
+        limit: i64,
+    ) -> Result<Vec<OrderModel>> {
+        let orders = sqlx::query_as::<_, OrderModel>(
+            r#"
+            SELECT
+                id, order_id, user_address, token, amount,
+                refund_address, integrator_address,
+                int4send(integrator_fees) AS integrator_fee,
+                status::TEXT AS status, tier::TEXT AS tier, currency,
+                block_number, tx_hash, created_at, expires_at, updated_at
+            FROM orders
+            WHERE ($1::BYTEA IS NULL OR user_address = $1)
+            AND ($2::BYTEA IS NULL OR integrator_address = $2)
+            AND ($3::TIMESTAMPTZ IS NULL OR (created_at, id) < ($3, $4::INTEGER))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $5
+            "#,
+        )
+        .bind(user_address)
+        .bind(integrator_address)
+        .bind(after.map(|(created_at, _)| created_at))
+        .bind(after.map(|(_, id)| id))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(orders)
+    }
+
+    /// Move an order to `status` if it is still in one of `from`
+    ///
+    /// # Returns
+    /// * `Result<Option<(OrderModel, String)>>` - The order as updated and
+    ///   the status it moved from, or None when it was in none of `from`
+    pub async fn transition_status(
+        &self,
+        order_id: &[u8],
+        from: &[&str],
+        status: &str,
+    ) -> Result<Option<(OrderModel, String)>> {
+        let row = sqlx::query(
+            r#"
+            UPDATE orders o
+            SET status = $3::order_status
+            FROM orders previous
+            WHERE o.id = previous.id AND o.order_id = $1 AND o.status::TEXT = ANY($2)
+            RETURNING
+                o.id, o.order_id, o.user_address, o.token, o.amount,
+                o.refund_address, o.integrator_address,
+                int4send(o.integrator_fees) AS integrator_fee,
+                o.status::TEXT AS status, o.tier::TEXT AS tier, o.currency,
+                o.block_number, o.tx_hash, o.created_at, o.expires_at, o.updated_at,
+                previous.status::TEXT AS previous_status
+            "#,
+        )
+        .bind(order_id)
+        .bind(from)
+        .bind(status)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some((OrderModel::from_row(&row)?, row.try_get("previous_status")?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Expire up to `limit` pending orders past their deadline
+    ///
+    /// # Returns
+    /// * `Result<Vec<OrderModel>>` - The orders expired, as updated
+    pub async fn expire_overdue(&self, limit: i64) -> Result<Vec<OrderModel>> {
+        let orders = sqlx::query_as::<_, OrderModel>(
+            r#"
+            UPDATE orders
+            SET status = 'EXPIRED'
+            WHERE id IN (
+                SELECT id FROM orders
+                WHERE status = 'PENDING' AND expires_at < NOW()
+                ORDER BY expires_at ASC
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING
+                id, order_id, user_address, token, amount,
+                refund_address, integrator_address,
+                int4send(integrator_fees) AS integrator_fee,
+                status::TEXT AS status, tier::TEXT AS tier, currency,
+                block_number, tx_hash, created_at, expires_at, updated_at
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(orders)
+    }
+}
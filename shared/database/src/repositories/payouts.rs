@@ -0,0 +1,254 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::{error::Result, models::PayoutModel};
+
+pub struct PayoutRepository {
+    pool: PgPool,
+}
+
+const SELECT_PAYOUT: &str = r#"
+    SELECT
+        id, order_id, proposal_id, provider, adapter, reference, payout_reference, leg, amount,
+        currency, status, message, polls, next_poll_at, created_at, updated_at, completed_at,
+        initiated_at, recipient_key_id, recipient_wrapped_key, recipient_ciphertext
+    FROM payouts
+"#;
+
+impl PayoutRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Claim a payout request under its payout reference, before the PSP
+    /// is called
+    ///
+    /// A claim left unconfirmed since `stale_before`, by a request that
+    /// never heard back from the PSP, is replaced.
+    ///
+    /// # Returns
+    /// * `Result<bool>` - False when the payout reference is already
+    ///   claimed, as happens when an initiation is retried
+    pub async fn claim(&self, payout: &PayoutModel, stale_before: DateTime<Utc>) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM payouts WHERE payout_reference = $1 AND initiated_at IS NULL AND created_at < $2")
+            .bind(&payout.payout_reference)
+            .bind(stale_before)
+            .execute(&mut *tx)
+            .await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO payouts (
+                id, order_id, proposal_id, provider, adapter, reference, payout_reference,
+                leg, amount, currency, status, message, next_poll_at, completed_at,
+                recipient_key_id, recipient_wrapped_key, recipient_ciphertext
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(payout.id)
+        .bind(&payout.order_id)
+        .bind(&payout.proposal_id)
+        .bind(&payout.provider)
+        .bind(&payout.adapter)
+        .bind(&payout.reference)
+        .bind(&payout.payout_reference)
+        .bind(payout.leg)
+        .bind(&payout.amount)
+        .bind(&payout.currency)
+        .bind(&payout.status)
+        .bind(&payout.message)
+        .bind(payout.next_poll_at)
+        .bind(payout.completed_at)
+        .bind(&payout.recipient_key_id)
+        .bind(&payout.recipient_wrapped_key)
+        .bind(&payout.recipient_ciphertext)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record that the PSP accepted a claimed payout under its own
+    /// `reference`, and schedule the first status lookup
+    ///
+    /// # Returns
+    /// * `Result<Option<PayoutModel>>` - The initiated payout, or None when
+    ///   the claim had gone stale and was replaced
+    pub async fn confirm(&self, id: Uuid, reference: &str, next_poll_at: DateTime<Utc>) -> Result<Option<PayoutModel>> {
+        let payout = sqlx::query_as::<_, PayoutModel>(
+            r#"
+            UPDATE payouts
+            SET reference = $2, next_poll_at = $3, initiated_at = NOW()
+            WHERE id = $1 AND initiated_at IS NULL
+            RETURNING
+                id, order_id, proposal_id, provider, adapter, reference, payout_reference, leg, amount,
+                currency, status, message, polls, next_poll_at, created_at, updated_at, completed_at,
+                initiated_at, recipient_key_id, recipient_wrapped_key, recipient_ciphertext
+            "#,
+        )
+        .bind(id)
+        .bind(reference)
+        .bind(next_poll_at)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(payout)
+    }
+
+    /// Drop a claim the PSP did not accept, so the request can be retried
+    pub async fn release_claim(&self, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM payouts WHERE id = $1 AND initiated_at IS NULL")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// The payout claimed under a payout reference, initiated or not
+    pub async fn get_by_payout_reference(&self, payout_reference: &str) -> Result<Option<PayoutModel>> {
+        let payout = sqlx::query_as::<_, PayoutModel>(&format!("{} WHERE payout_reference = $1", SELECT_PAYOUT))
+            .bind(payout_reference)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(payout)
+    }
+
+    pub async fn get(&self, adapter: &str, reference: &str) -> Result<Option<PayoutModel>> {
+        let payout = sqlx::query_as::<_, PayoutModel>(&format!(
+            "{} WHERE adapter = $1 AND reference = $2",
+            SELECT_PAYOUT
+        ))
+        .bind(adapter)
+        .bind(reference)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(payout)
+    }
+
+    /// Payouts for an order, oldest first
+    pub async fn list_for_order(&self, order_id: &[u8]) -> Result<Vec<PayoutModel>> {
+        let payouts = sqlx::query_as::<_, PayoutModel>(&format!(
+            "{} WHERE order_id = $1 ORDER BY created_at ASC",
+            SELECT_PAYOUT
+        ))
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(payouts)
+    }
+
+    /// Move a payout to a new status
+    ///
+    /// The payout is only updated if it is still in `previous_status`, so
+    /// concurrent webhooks and polls apply each transition once. Final
+    /// statuses stop polling and stamp `completed_at`.
+    ///
+    /// # Returns
+    /// * `Result<Option<PayoutModel>>` - The updated payout, or None when
+    ///   another update landed first and nothing was written
+    pub async fn transition(
+        &self,
+        adapter: &str,
+        reference: &str,
+        previous_status: &str,
+        status: &str,
+        message: Option<&str>,
+        is_final: bool,
+    ) -> Result<Option<PayoutModel>> {
+        let payout = sqlx::query_as::<_, PayoutModel>(
+            r#"
+            UPDATE payouts
+            SET status = $3,
+                message = COALESCE($4, message),
+                next_poll_at = CASE WHEN $5 THEN NULL ELSE next_poll_at END,
+                completed_at = CASE WHEN $5 THEN NOW() ELSE completed_at END
+            WHERE adapter = $1 AND reference = $2 AND status = $6
+            RETURNING
+                id, order_id, proposal_id, provider, adapter, reference, payout_reference, leg, amount,
+                currency, status, message, polls, next_poll_at, created_at, updated_at, completed_at,
+                initiated_at, recipient_key_id, recipient_wrapped_key, recipient_ciphertext
+            "#,
+        )
+        .bind(adapter)
+        .bind(reference)
+        .bind(status)
+        .bind(message)
+        .bind(is_final)
+        .bind(previous_status)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(payout)
+    }
+
+    /// Unsettled payouts whose next status lookup is due, most overdue first
+    pub async fn list_due(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<PayoutModel>> {
+        let payouts = sqlx::query_as::<_, PayoutModel>(&format!(
+            "{} WHERE next_poll_at <= $1 AND status IN ('pending', 'processing') ORDER BY next_poll_at ASC LIMIT $2",
+            SELECT_PAYOUT
+        ))
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(payouts)
+    }
+
+    /// Count a status lookup and schedule the next one; None stops polling
+    pub async fn schedule_poll(&self, id: Uuid, next_poll_at: Option<DateTime<Utc>>) -> Result<()> {
+        sqlx::query("UPDATE payouts SET polls = polls + 1, next_poll_at = $2 WHERE id = $1")
+            .bind(id)
+            .bind(next_poll_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Payouts whose recipient is sealed under a key other than the active
+    /// one, to be rewrapped after a rotation
+    pub async fn list_recipients_to_rewrap(&self, active_key_id: &str, limit: i64) -> Result<Vec<PayoutModel>> {
+        let payouts = sqlx::query_as::<_, PayoutModel>(&format!(
+            "{} WHERE recipient_key_id IS NOT NULL AND recipient_key_id <> $1 ORDER BY created_at ASC LIMIT $2",
+            SELECT_PAYOUT
+        ))
+        .bind(active_key_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(payouts)
+    }
+
+    /// Replace a payout's wrapped data key, if it is still wrapped by
+    /// `previous_key_id`
+    ///
+    /// # Returns
+    /// * `Result<bool>` - False when another rewrap landed first
+    pub async fn rewrap_recipient(&self, id: Uuid, previous_key_id: &str, key_id: &str, wrapped_key: &[u8]) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE payouts
+            SET recipient_key_id = $3, recipient_wrapped_key = $4
+            WHERE id = $1 AND recipient_key_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(previous_key_id)
+        .bind(key_id)
+        .bind(wrapped_key)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
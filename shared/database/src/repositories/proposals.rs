@@ -1,4 +1,5 @@
 
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use crate::{error::Result, models::ProposalModel};
 
@@ -13,7 +14,7 @@ impl ProposalRepository {
     
     /// Create a new proposal
     pub async fn create(&self, proposal: &ProposalModel) -> Result<i32> {
-        let record = sqlx::query!(
+        let id = sqlx::query_scalar::<_, i32>(
             r#"
             INSERT INTO proposals (
                 proposal_id, order_id, provider, proposed_fee_bps,
@@ -21,36 +22,93 @@ impl ProposalRepository {
             ) VALUES ($1, $2, $3, $4, $5::proposal_status, $6, $7)
             RETURNING id
             "#,
-            proposal.proposal_id,
-            proposal.order_id,
-            proposal.provider,
-            proposal.proposed_fee_bps,
-            proposal.status.as_str(),
-            proposal.created_at,
-            proposal.deadline
         )
+        .bind(&proposal.proposal_id)
+        .bind(&proposal.order_id)
+        .bind(&proposal.provider)
+        .bind(proposal.proposed_fee_bps)
+        .bind(&proposal.status)
+        .bind(proposal.created_at)
+        .bind(proposal.deadline)
         .fetch_one(&self.pool)
         .await?;
-        
-        Ok(record.id)
+
+        Ok(id)
     }
-    
+
     /// Update proposal status
-   pub async fn update_status(&self, proposal_id: &[u8], new_status: &str) -> Result<()> {
-    sqlx::query!(
-        r#"
-        UPDATE proposals
-        SET status = $1::proposal_status
-        WHERE proposal_id = $2
-        "#,
-        .bind(new_status),
+    pub async fn update_status(&self, proposal_id: &[u8], new_status: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE proposals
+            SET status = $1::proposal_status
+            WHERE proposal_id = $2
+            "#,
+        )
+        .bind(new_status)
         .bind(proposal_id)
-    )
-    .execute(&self.pool)
-    .await?;
-    
-    Ok(())
-}
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
 
+    /// Proposals made between two instants, oldest first
+    pub async fn list_created_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<ProposalModel>> {
+        let proposals = sqlx::query_as::<_, ProposalModel>(
+            r#"
+            SELECT
+                id, proposal_id, order_id, provider, proposed_fee_bps,
+                status::TEXT AS status, created_at, deadline, accepted_at, executed_at, tx_hash
+            FROM proposals
+            WHERE created_at >= $1 AND created_at < $2
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
 
-}
\ No newline at end of file
+        Ok(proposals)
+    }
+
+    /// The proposal an order was settled through, most recently accepted first
+    pub async fn get_accepted_for_order(&self, order_id: &[u8]) -> Result<Option<ProposalModel>> {
+        let proposal = sqlx::query_as::<_, ProposalModel>(
+            r#"
+            SELECT
+                id, proposal_id, order_id, provider, proposed_fee_bps,
+                status::TEXT AS status, created_at, deadline, accepted_at, executed_at, tx_hash
+            FROM proposals
+            WHERE order_id = $1 AND status::TEXT IN ('ACCEPTED', 'EXECUTED')
+            ORDER BY accepted_at DESC NULLS LAST
+            LIMIT 1
+            "#,
+        )
+        .bind(order_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(proposal)
+    }
+
+    /// Proposals made for an order, newest first
+    pub async fn list_for_order(&self, order_id: &[u8]) -> Result<Vec<ProposalModel>> {
+        let proposals = sqlx::query_as::<_, ProposalModel>(
+            r#"
+            SELECT
+                id, proposal_id, order_id, provider, proposed_fee_bps,
+                status::TEXT AS status, created_at, deadline, accepted_at, executed_at, tx_hash
+            FROM proposals
+            WHERE order_id = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(proposals)
+    }
+}
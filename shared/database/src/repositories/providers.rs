@@ -16,13 +16,13 @@ impl ProviderRepository {
     
     /// Upsert provider intent
     pub async fn upsert_intent(&self, intent: &ProviderIntentModel) -> Result<()> {
-        sqlx::query!(
+        sqlx::query(
             r#"
             INSERT INTO provider_intents (
-                provider, currency, available_amount, min_fee_bps, 
+                provider, currency, available_amount, min_fee_bps,
                 max_fee_bps, commitment_window, is_active, expires_at
             ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-            ON CONFLICT (provider, currency) 
+            ON CONFLICT (provider, currency)
             DO UPDATE SET
                 available_amount = $3,
                 min_fee_bps = $4,
@@ -32,31 +32,30 @@ impl ProviderRepository {
                 expires_at = $8,
                 updated_at = NOW()
             "#,
-            intent.provider,
-            intent.currency,
-            intent.available_amount,
-            intent.min_fee_bps,
-            intent.max_fee_bps,
-            intent.commitment_window,
-            intent.is_active,
-            intent.expires_at
         )
+        .bind(&intent.provider)
+        .bind(&intent.currency)
+        .bind(&intent.available_amount)
+        .bind(intent.min_fee_bps)
+        .bind(intent.max_fee_bps)
+        .bind(intent.commitment_window)
+        .bind(intent.is_active)
+        .bind(intent.expires_at)
         .execute(&self.pool)
         .await?;
-        
+
         Ok(())
     }
-    
+
     /// Get eligible providers for a currency and amount
     pub async fn get_eligible_providers(
         &self,
         currency: &str,
         min_amount: &str,
     ) -> Result<Vec<ProviderIntentModel>> {
-        let providers = sqlx::query_as!(
-            ProviderIntentModel,
+        let providers = sqlx::query_as::<_, ProviderIntentModel>(
             r#"
-            SELECT 
+            SELECT
                 id, provider, currency, available_amount,
                 min_fee_bps, max_fee_bps, commitment_window,
                 is_active, expires_at, created_at, updated_at
@@ -67,31 +66,180 @@ impl ProviderRepository {
             AND expires_at > NOW()
             ORDER BY min_fee_bps ASC
             "#,
-            currency,
-            min_amount
         )
+        .bind(currency)
+        .bind(min_amount)
         .fetch_all(&self.pool)
         .await?;
-        
+
         Ok(providers)
     }
-    
+
     /// Get provider reputation
     pub async fn get_reputation(&self, provider: &[u8]) -> Result<Option<ProviderReputationModel>> {
-        let reputation = sqlx::query_as!(
-            ProviderReputationModel,
+        let reputation = sqlx::query_as::<_, ProviderReputationModel>(
             r#"
-            SELECT 
+            SELECT
                 provider, total_orders, successful_orders, failed_orders,
-                no_shows, avg_settlement_time_seconds, total_volume, last_updated
+                no_shows, disputes, disputes_lost, avg_settlement_time_seconds, total_volume, last_updated
             FROM provider_reputation
             WHERE provider = $1
             "#,
-            provider
         )
+        .bind(provider)
         .fetch_optional(&self.pool)
         .await?;
-        
+
         Ok(reputation)
     }
-}
\ No newline at end of file
+
+    /// Every intent ever registered, active or not
+    pub async fn list_intents(&self) -> Result<Vec<ProviderIntentModel>> {
+        let intents = sqlx::query_as::<_, ProviderIntentModel>(
+            r#"
+            SELECT
+                id, provider, currency, available_amount,
+                min_fee_bps, max_fee_bps, commitment_window,
+                is_active, expires_at, created_at, updated_at
+            FROM provider_intents
+            ORDER BY created_at ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(intents)
+    }
+
+    /// Current reputation of every provider
+    pub async fn list_reputations(&self) -> Result<Vec<ProviderReputationModel>> {
+        let reputations = sqlx::query_as::<_, ProviderReputationModel>(
+            r#"
+            SELECT
+                provider, total_orders, successful_orders, failed_orders,
+                no_shows, disputes, disputes_lost, avg_settlement_time_seconds, total_volume, last_updated
+            FROM provider_reputation
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reputations)
+    }
+
+    /// Live intents in a currency, regardless of size
+    pub async fn list_active_intents(&self, currency: &str) -> Result<Vec<ProviderIntentModel>> {
+        let intents = sqlx::query_as::<_, ProviderIntentModel>(
+            r#"
+            SELECT
+                id, provider, currency, available_amount,
+                min_fee_bps, max_fee_bps, commitment_window,
+                is_active, expires_at, created_at, updated_at
+            FROM provider_intents
+            WHERE currency = $1 AND is_active = true AND expires_at > NOW()
+            "#,
+        )
+        .bind(currency)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(intents)
+    }
+
+    /// A provider's intent in one currency
+    pub async fn get_intent(&self, provider: &[u8], currency: &str) -> Result<Option<ProviderIntentModel>> {
+        let intent = sqlx::query_as::<_, ProviderIntentModel>(
+            r#"
+            SELECT
+                id, provider, currency, available_amount,
+                min_fee_bps, max_fee_bps, commitment_window,
+                is_active, expires_at, created_at, updated_at
+            FROM provider_intents
+            WHERE provider = $1 AND currency = $2
+            "#,
+        )
+        .bind(provider)
+        .bind(currency)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(intent)
+    }
+
+    /// A provider's intents by currency; only live ones when `active_only`
+    pub async fn list_provider_intents(&self, provider: &[u8], active_only: bool) -> Result<Vec<ProviderIntentModel>> {
+        let intents = sqlx::query_as::<_, ProviderIntentModel>(
+            r#"
+            SELECT
+                id, provider, currency, available_amount,
+                min_fee_bps, max_fee_bps, commitment_window,
+                is_active, expires_at, created_at, updated_at
+            FROM provider_intents
+            WHERE provider = $1
+            AND (NOT $2 OR (is_active = true AND expires_at > NOW()))
+            ORDER BY currency ASC
+            "#,
+        )
+        .bind(provider)
+        .bind(active_only)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(intents)
+    }
+
+    /// Pause or resume a provider's intent in one currency
+    ///
+    /// # Returns
+    /// * `Result<Option<ProviderIntentModel>>` - The updated intent; None
+    ///   when the provider has no intent in the currency
+    pub async fn set_intent_active(
+        &self,
+        provider: &[u8],
+        currency: &str,
+        is_active: bool,
+    ) -> Result<Option<ProviderIntentModel>> {
+        let intent = sqlx::query_as::<_, ProviderIntentModel>(
+            r#"
+            UPDATE provider_intents
+            SET is_active = $3, updated_at = NOW()
+            WHERE provider = $1 AND currency = $2
+            RETURNING
+                id, provider, currency, available_amount,
+                min_fee_bps, max_fee_bps, commitment_window,
+                is_active, expires_at, created_at, updated_at
+            "#,
+        )
+        .bind(provider)
+        .bind(currency)
+        .bind(is_active)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(intent)
+    }
+
+    /// Take paid-out liquidity off a provider's intent, never below zero
+    ///
+    /// # Returns
+    /// * `Result<Option<String>>` - The intent's new available amount; None
+    ///   when the provider has no intent in the currency
+    pub async fn deduct_available(&self, provider: &[u8], currency: &str, amount: &str) -> Result<Option<String>> {
+        let available = sqlx::query_scalar::<_, String>(
+            r#"
+            UPDATE provider_intents
+            SET available_amount = GREATEST(available_amount::NUMERIC - $3::NUMERIC, 0)::TEXT,
+                updated_at = NOW()
+            WHERE provider = $1 AND currency = $2
+            RETURNING available_amount
+            "#,
+        )
+        .bind(provider)
+        .bind(currency)
+        .bind(amount)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(available)
+    }
+}
@@ -0,0 +1,77 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::{
+    error::{DatabaseError, Result},
+    models::QuoteModel,
+};
+
+pub struct QuoteRepository {
+    pool: PgPool,
+}
+
+impl QuoteRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Insert an issued quote
+    pub async fn create(&self, quote: &QuoteModel) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO quotes (
+                quote_id, token, amount, currency, integrator_address, provider, rate,
+                provider_fee_bps, integrator_fee_bps, protocol_fee_bps, fiat_amount,
+                signature, created_at, expires_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            "#,
+        )
+        .bind(quote.quote_id)
+        .bind(&quote.token)
+        .bind(&quote.amount)
+        .bind(&quote.currency)
+        .bind(&quote.integrator_address)
+        .bind(&quote.provider)
+        .bind(&quote.rate)
+        .bind(quote.provider_fee_bps)
+        .bind(quote.integrator_fee_bps)
+        .bind(quote.protocol_fee_bps)
+        .bind(&quote.fiat_amount)
+        .bind(&quote.signature)
+        .bind(quote.created_at)
+        .bind(quote.expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get quote by ID
+    pub async fn get(&self, quote_id: Uuid) -> Result<QuoteModel> {
+        sqlx::query_as::<_, QuoteModel>(
+            r#"
+            SELECT
+                quote_id, token, amount, currency, integrator_address, provider, rate,
+                provider_fee_bps, integrator_fee_bps, protocol_fee_bps, fiat_amount,
+                signature, created_at, expires_at
+            FROM quotes
+            WHERE quote_id = $1
+            "#,
+        )
+        .bind(quote_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DatabaseError::NotFound(format!("quote {}", quote_id)))
+    }
+
+    /// Fee configured by an integrator, None if they have not set one
+    pub async fn get_integrator_fee_bps(&self, integrator_address: &[u8]) -> Result<Option<i32>> {
+        let fee: Option<(i32,)> = sqlx::query_as(
+            "SELECT fee_bps FROM integrator_fees WHERE integrator_address = $1",
+        )
+        .bind(integrator_address)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(fee.map(|(fee_bps,)| fee_bps))
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::{
+    error::{DatabaseError, Result},
+    models::{RoutingApprovalModel, RoutingDecisionModel},
+};
+
+pub struct RoutingDecisionRepository {
+    pool: PgPool,
+}
+
+const SELECT_DECISION: &str = r#"
+    SELECT
+        id, order_id, currency, tier, scorer, scorer_version, chosen_provider,
+        candidates, allocations, decided_at
+    FROM routing_decisions
+"#;
+
+impl RoutingDecisionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, decision: &RoutingDecisionModel) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO routing_decisions (
+                id, order_id, currency, tier, scorer, scorer_version, chosen_provider,
+                candidates, allocations, decided_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(decision.id)
+        .bind(&decision.order_id)
+        .bind(&decision.currency)
+        .bind(decision.tier)
+        .bind(&decision.scorer)
+        .bind(&decision.scorer_version)
+        .bind(&decision.chosen_provider)
+        .bind(&decision.candidates)
+        .bind(&decision.allocations)
+        .bind(decision.decided_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<RoutingDecisionModel> {
+        sqlx::query_as::<_, RoutingDecisionModel>(&format!("{} WHERE id = $1", SELECT_DECISION))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Routing decision {}", id)))
+    }
+
+    /// Decisions for an order, newest first
+    pub async fn list_for_order(&self, order_id: &[u8]) -> Result<Vec<RoutingDecisionModel>> {
+        let decisions = sqlx::query_as::<_, RoutingDecisionModel>(&format!(
+            "{} WHERE order_id = $1 ORDER BY decided_at DESC",
+            SELECT_DECISION
+        ))
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(decisions)
+    }
+
+    /// Most recent decisions a provider was a candidate in, newest first
+    pub async fn list_for_provider(&self, provider: &str, limit: i64) -> Result<Vec<RoutingDecisionModel>> {
+        let filter = serde_json::json!([{ "provider": provider.to_lowercase() }]);
+        let decisions = sqlx::query_as::<_, RoutingDecisionModel>(&format!(
+            "{} WHERE candidates @> $1 ORDER BY decided_at DESC LIMIT $2",
+            SELECT_DECISION
+        ))
+        .bind(filter)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(decisions)
+    }
+
+    /// Most recent decisions, newest first
+    pub async fn list_recent(&self, limit: i64) -> Result<Vec<RoutingDecisionModel>> {
+        let decisions = sqlx::query_as::<_, RoutingDecisionModel>(&format!(
+            "{} ORDER BY decided_at DESC LIMIT $1",
+            SELECT_DECISION
+        ))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(decisions)
+    }
+
+    /// Delete decisions made before the cutoff, returning how many were removed
+    pub async fn delete_before(&self, cutoff: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM routing_decisions WHERE decided_at < $1")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+/// Routing decisions held for approval, kept across router restarts
+pub struct RoutingApprovalRepository {
+    pool: PgPool,
+}
+
+impl RoutingApprovalRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Hold a decision, replacing any already held for the order
+    pub async fn hold(&self, approval: &RoutingApprovalModel) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO routing_approvals (order_id, order_data, decision, held_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (order_id) DO UPDATE
+            SET order_data = EXCLUDED.order_data, decision = EXCLUDED.decision, held_at = EXCLUDED.held_at
+            "#,
+        )
+        .bind(&approval.order_id)
+        .bind(&approval.order_data)
+        .bind(&approval.decision)
+        .bind(approval.held_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove the decision held for an order, returning it if there was one
+    pub async fn take(&self, order_id: &[u8]) -> Result<Option<RoutingApprovalModel>> {
+        let approval = sqlx::query_as::<_, RoutingApprovalModel>(
+            "DELETE FROM routing_approvals WHERE order_id = $1 RETURNING order_id, order_data, decision, held_at",
+        )
+        .bind(order_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(approval)
+    }
+
+    /// Held decisions, oldest first
+    pub async fn list(&self) -> Result<Vec<RoutingApprovalModel>> {
+        let approvals = sqlx::query_as::<_, RoutingApprovalModel>(
+            "SELECT order_id, order_data, decision, held_at FROM routing_approvals ORDER BY held_at",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(approvals)
+    }
+}
@@ -0,0 +1,108 @@
+use sqlx::PgPool;
+use crate::{
+    error::Result,
+    models::{ProviderStakeModel, StakeTransferModel},
+};
+
+/// Provider stakes, from the deposits and withdrawals recorded on-chain
+pub struct StakeRepository {
+    pool: PgPool,
+}
+
+const STAKE_COLUMNS: &str = "provider, token, staked::TEXT AS staked, updated_at";
+
+impl StakeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Apply a deposit or withdrawal to the provider's stake
+    ///
+    /// Withdrawals floor the stake at zero; the contract only lets providers
+    /// withdraw what they have.
+    ///
+    /// # Returns
+    /// * `Result<bool>` - False when the transfer was already recorded and
+    ///   nothing changed
+    pub async fn record_transfer(&self, transfer: &StakeTransferModel) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO stake_transfers (tx_hash, log_index, provider, token, kind, amount, block_number)
+            VALUES ($1, $2, $3, $4, $5, $6::NUMERIC, $7)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(&transfer.tx_hash)
+        .bind(transfer.log_index)
+        .bind(&transfer.provider)
+        .bind(&transfer.token)
+        .bind(&transfer.kind)
+        .bind(&transfer.amount)
+        .bind(transfer.block_number)
+        .execute(&mut *tx)
+        .await?;
+        if inserted.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        let delta = if transfer.kind == "withdrawal" { "-" } else { "+" };
+        sqlx::query(&format!(
+            r#"
+            INSERT INTO provider_stakes (provider, token, staked)
+            VALUES ($1, $2, GREATEST(0 {delta} $3::NUMERIC, 0))
+            ON CONFLICT (provider, token) DO UPDATE
+            SET staked = GREATEST(provider_stakes.staked {delta} $3::NUMERIC, 0)
+            "#,
+            delta = delta
+        ))
+        .bind(&transfer.provider)
+        .bind(&transfer.token)
+        .bind(&transfer.amount)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    pub async fn get(&self, provider: &[u8], token: &[u8]) -> Result<Option<ProviderStakeModel>> {
+        let stake = sqlx::query_as::<_, ProviderStakeModel>(&format!(
+            "SELECT {} FROM provider_stakes WHERE provider = $1 AND token = $2",
+            STAKE_COLUMNS
+        ))
+        .bind(provider)
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(stake)
+    }
+
+    /// A provider's stake in every token it has deposited
+    pub async fn list_for_provider(&self, provider: &[u8]) -> Result<Vec<ProviderStakeModel>> {
+        let stakes = sqlx::query_as::<_, ProviderStakeModel>(&format!(
+            "SELECT {} FROM provider_stakes WHERE provider = $1 ORDER BY token",
+            STAKE_COLUMNS
+        ))
+        .bind(provider)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(stakes)
+    }
+
+    /// Every provider's stake in a token
+    pub async fn list_for_token(&self, token: &[u8]) -> Result<Vec<ProviderStakeModel>> {
+        let stakes = sqlx::query_as::<_, ProviderStakeModel>(&format!(
+            "SELECT {} FROM provider_stakes WHERE token = $1",
+            STAKE_COLUMNS
+        ))
+        .bind(token)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(stakes)
+    }
+}
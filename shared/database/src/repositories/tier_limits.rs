@@ -0,0 +1,74 @@
+use sqlx::PgPool;
+use crate::{error::Result, models::TierLimitsModel};
+
+pub struct TierLimitsRepository {
+    pool: PgPool,
+}
+
+impl TierLimitsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Every configured token's limits
+    pub async fn list(&self) -> Result<Vec<TierLimitsModel>> {
+        let limits = sqlx::query_as::<_, TierLimitsModel>(
+            r#"
+            SELECT
+                token, currency, decimals, alpha_max::TEXT, beta_max::TEXT, delta_max::TEXT,
+                omega_max::TEXT, created_at, updated_at
+            FROM tier_limits
+            ORDER BY token, currency
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(limits)
+    }
+
+    /// Insert or replace the limits of a token and currency
+    pub async fn upsert(&self, limits: &TierLimitsModel) -> Result<TierLimitsModel> {
+        let stored = sqlx::query_as::<_, TierLimitsModel>(
+            r#"
+            INSERT INTO tier_limits (
+                token, currency, decimals, alpha_max, beta_max, delta_max, omega_max
+            ) VALUES ($1, $2, $3, $4::NUMERIC, $5::NUMERIC, $6::NUMERIC, $7::NUMERIC)
+            ON CONFLICT (token, currency) DO UPDATE SET
+                decimals = EXCLUDED.decimals,
+                alpha_max = EXCLUDED.alpha_max,
+                beta_max = EXCLUDED.beta_max,
+                delta_max = EXCLUDED.delta_max,
+                omega_max = EXCLUDED.omega_max
+            RETURNING
+                token, currency, decimals, alpha_max::TEXT, beta_max::TEXT, delta_max::TEXT,
+                omega_max::TEXT, created_at, updated_at
+            "#,
+        )
+        .bind(&limits.token)
+        .bind(&limits.currency)
+        .bind(limits.decimals)
+        .bind(&limits.alpha_max)
+        .bind(&limits.beta_max)
+        .bind(&limits.delta_max)
+        .bind(&limits.omega_max)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(stored)
+    }
+
+    /// Remove the limits of a token and currency
+    ///
+    /// # Returns
+    /// * `Result<bool>` - Whether a row was removed
+    pub async fn delete(&self, token: &[u8], currency: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM tier_limits WHERE token = $1 AND currency = $2")
+            .bind(token)
+            .bind(currency)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
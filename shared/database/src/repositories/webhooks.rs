@@ -0,0 +1,353 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::{
+    error::{DatabaseError, Result},
+    models::{DueWebhookDelivery, WebhookAttemptModel, WebhookEndpointModel, WebhookEventModel},
+};
+
+pub struct WebhookRepository {
+    pool: PgPool,
+}
+
+impl WebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Insert a new webhook endpoint
+    pub async fn create_endpoint(&self, endpoint: &WebhookEndpointModel) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_endpoints (
+                id, integrator_address, url, secret, event_types, is_active
+            ) VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(endpoint.id)
+        .bind(&endpoint.integrator_address)
+        .bind(&endpoint.url)
+        .bind(&endpoint.secret)
+        .bind(&endpoint.event_types)
+        .bind(endpoint.is_active)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get endpoint by ID
+    pub async fn get_endpoint(&self, id: Uuid) -> Result<WebhookEndpointModel> {
+        sqlx::query_as::<_, WebhookEndpointModel>(
+            r#"
+            SELECT
+                id, integrator_address, url, secret, event_types, is_active,
+                consecutive_failures, disabled_at, created_at, updated_at
+            FROM webhook_endpoints
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DatabaseError::NotFound(format!("webhook endpoint {}", id)))
+    }
+
+    /// List all endpoints registered by an integrator
+    pub async fn list_endpoints(&self, integrator_address: &[u8]) -> Result<Vec<WebhookEndpointModel>> {
+        let endpoints = sqlx::query_as::<_, WebhookEndpointModel>(
+            r#"
+            SELECT
+                id, integrator_address, url, secret, event_types, is_active,
+                consecutive_failures, disabled_at, created_at, updated_at
+            FROM webhook_endpoints
+            WHERE integrator_address = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(integrator_address)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(endpoints)
+    }
+
+    /// Active endpoints of an integrator subscribed to the given event
+    pub async fn get_subscribed_endpoints(
+        &self,
+        integrator_address: &[u8],
+        event_type: &str,
+    ) -> Result<Vec<WebhookEndpointModel>> {
+        let endpoints = sqlx::query_as::<_, WebhookEndpointModel>(
+            r#"
+            SELECT
+                id, integrator_address, url, secret, event_types, is_active,
+                consecutive_failures, disabled_at, created_at, updated_at
+            FROM webhook_endpoints
+            WHERE integrator_address = $1
+            AND is_active = true
+            AND $2 = ANY(event_types)
+            "#,
+        )
+        .bind(integrator_address)
+        .bind(event_type)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(endpoints)
+    }
+
+    /// Re-enable or disable an endpoint, clearing its failure streak
+    pub async fn set_endpoint_active(&self, id: Uuid, is_active: bool) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE webhook_endpoints
+            SET is_active = $2,
+                consecutive_failures = 0,
+                disabled_at = CASE WHEN $2 THEN NULL ELSE NOW() END
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(is_active)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DatabaseError::NotFound(format!("webhook endpoint {}", id)));
+        }
+        Ok(())
+    }
+
+    /// Store an event and queue one delivery per endpoint in a single transaction
+    pub async fn create_event_with_deliveries(
+        &self,
+        event: &WebhookEventModel,
+        endpoint_ids: &[Uuid],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_events (
+                id, integrator_address, order_id, event_type, payload, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(event.id)
+        .bind(&event.integrator_address)
+        .bind(&event.order_id)
+        .bind(&event.event_type)
+        .bind(&event.payload)
+        .bind(event.created_at)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_deliveries (endpoint_id, event_id)
+            SELECT endpoint_id, $2 FROM UNNEST($1::uuid[]) AS endpoint_id
+            "#,
+        )
+        .bind(endpoint_ids)
+        .bind(event.id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Claim deliveries that are due, leasing them so concurrent workers skip them
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of deliveries to claim
+    /// * `lease_seconds` - How long the claim holds before another worker may retry it
+    pub async fn claim_due_deliveries(&self, limit: i64, lease_seconds: i64) -> Result<Vec<DueWebhookDelivery>> {
+        let deliveries = sqlx::query_as::<_, DueWebhookDelivery>(
+            r#"
+            WITH due AS (
+                SELECT d.id
+                FROM webhook_deliveries d
+                JOIN webhook_endpoints e ON e.id = d.endpoint_id
+                WHERE d.status = 'PENDING'
+                AND d.next_attempt_at <= NOW()
+                AND e.is_active = true
+                ORDER BY d.next_attempt_at
+                LIMIT $1
+                FOR UPDATE OF d SKIP LOCKED
+            ), claimed AS (
+                UPDATE webhook_deliveries d
+                SET next_attempt_at = NOW() + make_interval(secs => $2)
+                FROM due
+                WHERE d.id = due.id
+                RETURNING d.id, d.attempts, d.endpoint_id, d.event_id
+            )
+            SELECT
+                c.id AS delivery_id, c.attempts, c.endpoint_id, e.url, e.secret,
+                c.event_id, ev.event_type, ev.payload
+            FROM claimed c
+            JOIN webhook_endpoints e ON e.id = c.endpoint_id
+            JOIN webhook_events ev ON ev.id = c.event_id
+            "#,
+        )
+        .bind(limit)
+        .bind(lease_seconds as f64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    /// Append an attempt to the audit trail
+    pub async fn record_attempt(
+        &self,
+        delivery_id: i64,
+        attempt: i32,
+        status_code: Option<i32>,
+        error: Option<&str>,
+        succeeded: bool,
+        duration_ms: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_delivery_attempts (
+                delivery_id, attempt, status_code, error, succeeded, duration_ms
+            ) VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(delivery_id)
+        .bind(attempt)
+        .bind(status_code)
+        .bind(error)
+        .bind(succeeded)
+        .bind(duration_ms)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Update a delivery after an attempt
+    ///
+    /// # Arguments
+    /// * `status` - New delivery status (PENDING, SUCCEEDED or EXHAUSTED)
+    /// * `next_attempt_at` - When to retry; ignored unless status is PENDING
+    pub async fn update_delivery(
+        &self,
+        delivery_id: i64,
+        status: &str,
+        attempts: i32,
+        next_attempt_at: DateTime<Utc>,
+        status_code: Option<i32>,
+        error: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2::webhook_delivery_status,
+                attempts = $3,
+                next_attempt_at = $4,
+                last_status_code = $5,
+                last_error = $6
+            WHERE id = $1
+            "#,
+        )
+        .bind(delivery_id)
+        .bind(status)
+        .bind(attempts)
+        .bind(next_attempt_at)
+        .bind(status_code)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Increment an endpoint's failure streak, disabling it once the threshold is hit
+    ///
+    /// # Returns
+    /// * `bool` - true if this failure disabled the endpoint
+    pub async fn record_endpoint_failure(&self, endpoint_id: Uuid, disable_threshold: i32) -> Result<bool> {
+        let disabled: Option<bool> = sqlx::query_scalar(
+            r#"
+            UPDATE webhook_endpoints
+            SET consecutive_failures = consecutive_failures + 1,
+                is_active = consecutive_failures + 1 < $2,
+                disabled_at = CASE
+                    WHEN consecutive_failures + 1 >= $2 THEN NOW()
+                    ELSE disabled_at
+                END
+            WHERE id = $1 AND is_active = true
+            RETURNING NOT is_active
+            "#,
+        )
+        .bind(endpoint_id)
+        .bind(disable_threshold)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(disabled.unwrap_or(false))
+    }
+
+    /// Reset an endpoint's failure streak after a successful delivery
+    pub async fn reset_endpoint_failures(&self, endpoint_id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE webhook_endpoints
+            SET consecutive_failures = 0
+            WHERE id = $1 AND consecutive_failures <> 0
+            "#,
+        )
+        .bind(endpoint_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Queue a fresh delivery of an already-sent event to the same endpoint
+    ///
+    /// # Returns
+    /// * `i64` - ID of the new delivery
+    pub async fn redeliver(&self, endpoint_id: Uuid, delivery_id: i64) -> Result<i64> {
+        let id: Option<i64> = sqlx::query_scalar(
+            r#"
+            INSERT INTO webhook_deliveries (endpoint_id, event_id)
+            SELECT endpoint_id, event_id
+            FROM webhook_deliveries
+            WHERE id = $1 AND endpoint_id = $2
+            RETURNING id
+            "#,
+        )
+        .bind(delivery_id)
+        .bind(endpoint_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        id.ok_or_else(|| DatabaseError::NotFound(format!("webhook delivery {}", delivery_id)))
+    }
+
+    /// Most recent attempts made against an endpoint
+    pub async fn list_attempts(&self, endpoint_id: Uuid, limit: i64) -> Result<Vec<WebhookAttemptModel>> {
+        let attempts = sqlx::query_as::<_, WebhookAttemptModel>(
+            r#"
+            SELECT
+                a.id, a.delivery_id, d.endpoint_id, d.event_id, a.attempt,
+                a.status_code, a.error, a.succeeded, a.duration_ms, a.attempted_at
+            FROM webhook_delivery_attempts a
+            JOIN webhook_deliveries d ON d.id = a.delivery_id
+            WHERE d.endpoint_id = $1
+            ORDER BY a.attempted_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(endpoint_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(attempts)
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{FxError, Result};
+use crate::{FxPair, FxRate};
+
+/// Aggregated rate together with when it was cached
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedRate {
+    pub rate: FxRate,
+    pub cached_at: DateTime<Utc>,
+}
+
+/// Last known aggregated rate per pair
+///
+/// Entries are never expired by the cache itself: the oracle serves fresh
+/// entries directly and falls back to older ones, flagged stale, when every
+/// source is down.
+#[async_trait]
+pub trait RateCache: Send + Sync {
+    async fn get(&self, pair: &FxPair) -> Result<Option<CachedRate>>;
+
+    async fn put(&self, rate: &FxRate) -> Result<()>;
+}
+
+/// Redis-backed cache shared by every service instance
+pub struct RedisRateCache {
+    conn: ConnectionManager,
+}
+
+impl RedisRateCache {
+    pub fn new(conn: ConnectionManager) -> Self {
+        Self { conn }
+    }
+
+    fn key(pair: &FxPair) -> String {
+        format!("fx:rate:{}:{}", pair.base, pair.quote)
+    }
+}
+
+#[async_trait]
+impl RateCache for RedisRateCache {
+    async fn get(&self, pair: &FxPair) -> Result<Option<CachedRate>> {
+        let mut conn = self.conn.clone();
+        let value: Option<String> = conn.get(Self::key(pair)).await?;
+        value
+            .map(|v| serde_json::from_str(&v).map_err(|e| FxError::Parse(e.to_string())))
+            .transpose()
+    }
+
+    async fn put(&self, rate: &FxRate) -> Result<()> {
+        let entry = CachedRate { rate: rate.clone(), cached_at: Utc::now() };
+        let value = serde_json::to_string(&entry).map_err(|e| FxError::Parse(e.to_string()))?;
+
+        let mut conn = self.conn.clone();
+        conn.set::<_, _, ()>(Self::key(&rate.pair), value).await?;
+        Ok(())
+    }
+}
+
+/// In-process cache for tests and single-instance tools
+#[derive(Default)]
+pub struct MemoryRateCache {
+    entries: Mutex<HashMap<FxPair, CachedRate>>,
+}
+
+#[async_trait]
+impl RateCache for MemoryRateCache {
+    async fn get(&self, pair: &FxPair) -> Result<Option<CachedRate>> {
+        Ok(self.entries.lock().expect("rate cache lock poisoned").get(pair).cloned())
+    }
+
+    async fn put(&self, rate: &FxRate) -> Result<()> {
+        let entry = CachedRate { rate: rate.clone(), cached_at: Utc::now() };
+        self.entries
+            .lock()
+            .expect("rate cache lock poisoned")
+            .insert(rate.pair.clone(), entry);
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+//! Token→fiat exchange rate oracle
+//!
+//! Rates are gathered from pluggable [`RateSource`]s (HTTP price feeds,
+//! provider-submitted rates, static files), aggregated into a median with
+//! outlier rejection, cached in Redis and flagged when stale.
+
+pub mod cache;
+pub mod error;
+pub mod oracle;
+pub mod sources;
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+pub use cache::{MemoryRateCache, RateCache, RedisRateCache};
+pub use error::{FxError, Result};
+pub use oracle::{aggregate, FxOracle, OracleConfig};
+pub use sources::{HttpSource, ProviderRateSource, RateSource, StaticSource};
+
+/// Base asset every supported token is pegged to
+pub const USD: &str = "USD";
+
+/// Currency pair, e.g. USD/NGN (fiat received per unit of base)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FxPair {
+    pub base: String,
+    pub quote: String,
+}
+
+impl FxPair {
+    pub fn new(base: &str, quote: &str) -> Self {
+        Self {
+            base: base.trim().to_uppercase(),
+            quote: quote.trim().to_uppercase(),
+        }
+    }
+
+    /// Parse a `BASE/QUOTE` string
+    pub fn parse(s: &str) -> Option<Self> {
+        let (base, quote) = s.split_once('/')?;
+        (!base.trim().is_empty() && !quote.trim().is_empty()).then(|| Self::new(base, quote))
+    }
+}
+
+impl fmt::Display for FxPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.base, self.quote)
+    }
+}
+
+/// Single rate observation from one source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateSample {
+    /// Source name, e.g. "static", "http:coingecko" or "provider:0xabc..."
+    pub source: String,
+    pub rate: Decimal,
+    pub observed_at: DateTime<Utc>,
+}
+
+/// Aggregated rate for a pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FxRate {
+    pub pair: FxPair,
+    /// Median of the samples kept after outlier rejection
+    pub rate: Decimal,
+    /// Sources of the kept samples
+    pub sources: Vec<String>,
+    /// Samples discarded as outliers
+    pub rejected: usize,
+    /// Observation time of the oldest kept sample
+    pub observed_at: DateTime<Utc>,
+    /// True when the rate is older than the oracle's maximum age
+    pub stale: bool,
+}
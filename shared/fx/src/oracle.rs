@@ -0,0 +1,334 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use redis::aio::ConnectionManager;
+use rust_decimal::Decimal;
+use tracing::warn;
+
+use crate::cache::{RateCache, RedisRateCache};
+use crate::error::{FxError, Result};
+use crate::sources::{HttpSource, ProviderRateSource, RateSource, StaticSource};
+use crate::{FxPair, FxRate, RateSample};
+
+/// Oracle tuning, loaded from the environment
+#[derive(Debug, Clone)]
+pub struct OracleConfig {
+    /// Samples older than this are only used when nothing fresher exists, and
+    /// rates built from them are flagged stale
+    pub max_age: Duration,
+    /// Samples further than this from the median are rejected as outliers
+    pub max_deviation_bps: u32,
+    /// Samples that must survive outlier rejection for a rate to be produced
+    pub min_samples: usize,
+    /// Per-source fetch timeout
+    pub source_timeout: Duration,
+    /// How long a cached rate is served without querying the sources
+    pub cache_ttl: Duration,
+}
+
+impl Default for OracleConfig {
+    fn default() -> Self {
+        Self {
+            max_age: Duration::from_secs(300),
+            max_deviation_bps: 200,
+            min_samples: 1,
+            source_timeout: Duration::from_secs(2),
+            cache_ttl: Duration::from_secs(30),
+        }
+    }
+}
+
+impl OracleConfig {
+    /// Load oracle configuration, falling back to defaults for unset variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let env_u64 = |key: &str| std::env::var(key).ok().and_then(|v| v.parse::<u64>().ok());
+
+        Self {
+            max_age: env_u64("FX_MAX_AGE_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.max_age),
+            max_deviation_bps: env_u64("FX_MAX_DEVIATION_BPS")
+                .map(|v| v as u32)
+                .unwrap_or(defaults.max_deviation_bps),
+            min_samples: env_u64("FX_MIN_SOURCES")
+                .map(|v| (v as usize).max(1))
+                .unwrap_or(defaults.min_samples),
+            source_timeout: env_u64("FX_SOURCE_TIMEOUT_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.source_timeout),
+            cache_ttl: env_u64("FX_CACHE_TTL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.cache_ttl),
+        }
+    }
+}
+
+fn median(sorted: &[Decimal]) -> Decimal {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / Decimal::TWO
+    } else {
+        sorted[mid]
+    }
+}
+
+fn sorted_rates(samples: &[RateSample]) -> Vec<Decimal> {
+    let mut rates: Vec<Decimal> = samples.iter().map(|s| s.rate).collect();
+    rates.sort();
+    rates
+}
+
+fn is_older_than(observed_at: DateTime<Utc>, now: DateTime<Utc>, max_age: Duration) -> bool {
+    now - observed_at > chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX)
+}
+
+/// Combine samples into a single rate
+///
+/// Takes the median of the fresh samples (or of all samples when none are
+/// fresh), rejects those deviating from it by more than `max_deviation_bps`,
+/// and returns the median of what remains.
+///
+/// # Returns
+/// * `Option<FxRate>` - None when there are no samples or fewer than
+///   `min_samples` survive outlier rejection
+pub fn aggregate(pair: &FxPair, samples: Vec<RateSample>, now: DateTime<Utc>, config: &OracleConfig) -> Option<FxRate> {
+    let (fresh, old): (Vec<_>, Vec<_>) = samples
+        .into_iter()
+        .partition(|s| !is_older_than(s.observed_at, now, config.max_age));
+    let candidates = if fresh.is_empty() { old } else { fresh };
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let center = median(&sorted_rates(&candidates));
+    let tolerance = center * Decimal::from(config.max_deviation_bps) / Decimal::from(10_000);
+    let total = candidates.len();
+    let kept: Vec<RateSample> = candidates
+        .into_iter()
+        .filter(|s| (s.rate - center).abs() <= tolerance)
+        .collect();
+    if kept.is_empty() || kept.len() < config.min_samples {
+        return None;
+    }
+
+    let observed_at = kept.iter().map(|s| s.observed_at).min()?;
+    Some(FxRate {
+        pair: pair.clone(),
+        rate: median(&sorted_rates(&kept)),
+        sources: kept.iter().map(|s| s.source.clone()).collect(),
+        rejected: total - kept.len(),
+        observed_at,
+        stale: is_older_than(observed_at, now, config.max_age),
+    })
+}
+
+/// Aggregates rates across sources, with caching and stale fallback
+pub struct FxOracle {
+    sources: Vec<Box<dyn RateSource>>,
+    cache: Option<Box<dyn RateCache>>,
+    config: OracleConfig,
+}
+
+impl FxOracle {
+    pub fn new(config: OracleConfig) -> Self {
+        Self {
+            sources: Vec::new(),
+            cache: None,
+            config,
+        }
+    }
+
+    pub fn with_source(mut self, source: impl RateSource + 'static) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    pub fn with_cache(mut self, cache: impl RateCache + 'static) -> Self {
+        self.cache = Some(Box::new(cache));
+        self
+    }
+
+    /// Build an oracle from the environment
+    ///
+    /// * `FX_RATES` - static USD rates, e.g. `NGN=1550.25,KES=129.10`
+    /// * `FX_RATES_FILE` - JSON file of static rates, e.g. `{"USD/NGN": "1550.25"}`
+    /// * `FX_HTTP_SOURCES` - `name|url|pointer` feeds separated by `;`
+    ///
+    /// With a Redis connection, provider-submitted rates are used as a source
+    /// and aggregated rates are cached in Redis.
+    pub fn from_env(redis: Option<ConnectionManager>) -> Result<Self> {
+        let config = OracleConfig::from_env();
+        let mut oracle = Self::new(config.clone());
+
+        if let Ok(spec) = std::env::var("FX_RATES") {
+            if !spec.trim().is_empty() {
+                oracle = oracle.with_source(StaticSource::parse(&spec));
+            }
+        }
+        if let Ok(path) = std::env::var("FX_RATES_FILE") {
+            if !path.trim().is_empty() {
+                oracle = oracle.with_source(StaticSource::from_file(path.trim())?);
+            }
+        }
+        for feed in std::env::var("FX_HTTP_SOURCES").unwrap_or_default().split(';') {
+            if feed.trim().is_empty() {
+                continue;
+            }
+            let parts: Vec<&str> = feed.split('|').map(str::trim).collect();
+            let [name, url, pointer] = parts[..] else {
+                return Err(FxError::Parse(format!("Invalid FX_HTTP_SOURCES entry: {}", feed)));
+            };
+            oracle = oracle.with_source(HttpSource::new(name, url, pointer, config.source_timeout)?);
+        }
+        if let Some(conn) = redis {
+            oracle = oracle
+                .with_source(ProviderRateSource::new(conn.clone(), config.max_age))
+                .with_cache(RedisRateCache::new(conn));
+        }
+
+        Ok(oracle)
+    }
+
+    /// Current rate for a pair
+    ///
+    /// Served from cache when recently aggregated. When every source fails
+    /// the last cached rate is returned with `stale` set.
+    pub async fn rate(&self, pair: &FxPair) -> Result<FxRate> {
+        let now = Utc::now();
+        let cached = match &self.cache {
+            Some(cache) => cache.get(pair).await.unwrap_or_else(|e| {
+                warn!("FX cache read failed for {}: {}", pair, e);
+                None
+            }),
+            None => None,
+        };
+
+        if let Some(entry) = &cached {
+            if !is_older_than(entry.cached_at, now, self.config.cache_ttl) {
+                let mut rate = entry.rate.clone();
+                rate.stale = is_older_than(rate.observed_at, now, self.config.max_age);
+                return Ok(rate);
+            }
+        }
+
+        if let Some(rate) = aggregate(pair, self.collect(pair).await, now, &self.config) {
+            if let Some(cache) = &self.cache {
+                if let Err(e) = cache.put(&rate).await {
+                    warn!("FX cache write failed for {}: {}", pair, e);
+                }
+            }
+            return Ok(rate);
+        }
+
+        match cached {
+            Some(entry) => {
+                warn!("No live FX rate for {}, serving last known rate", pair);
+                Ok(FxRate { stale: true, ..entry.rate })
+            }
+            None => Err(FxError::NoRate(pair.to_string())),
+        }
+    }
+
+    async fn collect(&self, pair: &FxPair) -> Vec<RateSample> {
+        let fetches = self
+            .sources
+            .iter()
+            .map(|source| async move { (source.name(), tokio::time::timeout(self.config.source_timeout, source.fetch(pair)).await) });
+
+        let mut samples = Vec::new();
+        for (name, result) in join_all(fetches).await {
+            match result {
+                Ok(Ok(found)) => samples.extend(found),
+                Ok(Err(e)) => warn!("FX source {} failed for {}: {}", name, pair, e),
+                Err(_) => warn!("FX source {} timed out for {}", name, pair),
+            }
+        }
+        samples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::MemoryRateCache;
+    use async_trait::async_trait;
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    fn sample(source: &str, rate: &str, age_secs: i64) -> RateSample {
+        RateSample {
+            source: source.to_string(),
+            rate: Decimal::from_str(rate).unwrap(),
+            observed_at: Utc::now() - chrono::Duration::seconds(age_secs),
+        }
+    }
+
+    #[test]
+    fn test_aggregate_rejects_outliers_and_flags_stale() {
+        let pair = FxPair::new("USD", "NGN");
+        let config = OracleConfig::default();
+
+        let samples = vec![
+            sample("a", "1550", 10),
+            sample("b", "1552", 10),
+            sample("c", "1548", 10),
+            sample("d", "1900", 10),
+            // Old sample ignored while fresh ones exist
+            sample("e", "1200", 3600),
+        ];
+        let rate = aggregate(&pair, samples, Utc::now(), &config).unwrap();
+        assert_eq!(rate.rate, Decimal::from(1550));
+        assert_eq!(rate.rejected, 1);
+        assert_eq!(rate.sources, vec!["a", "b", "c"]);
+        assert!(!rate.stale);
+
+        let rate = aggregate(&pair, vec![sample("e", "1500", 3600)], Utc::now(), &config).unwrap();
+        assert!(rate.stale);
+
+        // Two sources that disagree wildly cannot be trusted
+        let config = OracleConfig { min_samples: 2, ..config };
+        assert!(aggregate(&pair, vec![sample("a", "1000", 0), sample("b", "2000", 0)], Utc::now(), &config).is_none());
+    }
+
+    struct Toggle {
+        up: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl RateSource for Toggle {
+        fn name(&self) -> &str {
+            "toggle"
+        }
+
+        async fn fetch(&self, _pair: &FxPair) -> Result<Vec<RateSample>> {
+            if self.up.load(Ordering::SeqCst) {
+                Ok(vec![sample("toggle", "1550", 0)])
+            } else {
+                Err(FxError::NoRate("down".to_string()))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oracle_falls_back_to_stale_cached_rate() {
+        let up = Arc::new(AtomicBool::new(true));
+        let config = OracleConfig { cache_ttl: Duration::ZERO, ..OracleConfig::default() };
+        let oracle = FxOracle::new(config)
+            .with_source(Toggle { up: up.clone() })
+            .with_cache(MemoryRateCache::default());
+        let pair = FxPair::new("USD", "NGN");
+
+        let live = oracle.rate(&pair).await.unwrap();
+        assert!(!live.stale);
+
+        up.store(false, Ordering::SeqCst);
+        let fallback = oracle.rate(&pair).await.unwrap();
+        assert_eq!(fallback.rate, live.rate);
+        assert!(fallback.stale);
+
+        assert!(matches!(oracle.rate(&FxPair::new("USD", "KES")).await, Err(FxError::NoRate(_))));
+    }
+}
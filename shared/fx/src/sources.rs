@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{FxError, Result};
+use crate::{FxPair, RateSample, USD};
+
+/// Somewhere rates can be read from
+#[async_trait]
+pub trait RateSource: Send + Sync {
+    /// Name used in logs and recorded alongside aggregated rates
+    fn name(&self) -> &str;
+
+    /// Current samples for a pair; empty if the source does not quote it
+    async fn fetch(&self, pair: &FxPair) -> Result<Vec<RateSample>>;
+}
+
+fn parse_rate(value: &str) -> Option<Decimal> {
+    Decimal::from_str(value.trim())
+        .ok()
+        .filter(|rate| rate.is_sign_positive() && !rate.is_zero())
+}
+
+// ─── Static ─────────────────────────────────────────────────────────
+
+/// Fixed rates from configuration or a file, always reported as fresh
+pub struct StaticSource {
+    name: String,
+    rates: HashMap<FxPair, Decimal>,
+}
+
+impl StaticSource {
+    pub fn new(name: &str, rates: HashMap<FxPair, Decimal>) -> Self {
+        Self { name: name.to_string(), rates }
+    }
+
+    /// Parse a `NGN=1550.25,KES=129.10` list of USD rates, skipping malformed entries
+    pub fn parse(spec: &str) -> Self {
+        let rates = spec
+            .split(',')
+            .filter_map(|entry| {
+                let (currency, rate) = entry.trim().split_once('=')?;
+                Some((FxPair::new(USD, currency), parse_rate(rate)?))
+            })
+            .collect();
+        Self::new("static", rates)
+    }
+
+    /// Load a JSON file mapping pairs to rates, e.g. `{"USD/NGN": "1550.25"}`
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let contents = std::fs::read_to_string(path.as_ref())?;
+        let entries: HashMap<String, String> =
+            serde_json::from_str(&contents).map_err(|e| FxError::Parse(e.to_string()))?;
+
+        let rates = entries
+            .iter()
+            .map(|(pair, rate)| {
+                let parsed_pair = FxPair::parse(pair).ok_or_else(|| FxError::Parse(format!("Invalid pair: {}", pair)))?;
+                let parsed_rate = parse_rate(rate).ok_or_else(|| FxError::Parse(format!("Invalid rate for {}: {}", pair, rate)))?;
+                Ok((parsed_pair, parsed_rate))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self::new("file", rates))
+    }
+}
+
+#[async_trait]
+impl RateSource for StaticSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn fetch(&self, pair: &FxPair) -> Result<Vec<RateSample>> {
+        Ok(self
+            .rates
+            .get(pair)
+            .map(|rate| RateSample {
+                source: self.name.clone(),
+                rate: *rate,
+                observed_at: Utc::now(),
+            })
+            .into_iter()
+            .collect())
+    }
+}
+
+// ─── HTTP price feed ────────────────────────────────────────────────
+
+/// JSON price feed
+///
+/// The URL may contain `{base}` and `{quote}` placeholders (lowercased when
+/// substituted); the rate is read from the response with a JSON pointer, e.g.
+/// `/rates/NGN`, and may be a number or a numeric string.
+pub struct HttpSource {
+    name: String,
+    client: reqwest::Client,
+    url_template: String,
+    pointer: String,
+}
+
+impl HttpSource {
+    pub fn new(name: &str, url_template: &str, pointer: &str, timeout: Duration) -> Result<Self> {
+        Ok(Self {
+            name: format!("http:{}", name),
+            client: reqwest::Client::builder().timeout(timeout).build()?,
+            url_template: url_template.to_string(),
+            pointer: pointer.to_string(),
+        })
+    }
+
+    fn url_for(&self, pair: &FxPair) -> String {
+        self.url_template
+            .replace("{base}", &pair.base.to_lowercase())
+            .replace("{quote}", &pair.quote.to_lowercase())
+    }
+
+    fn pointer_for(&self, pair: &FxPair) -> String {
+        self.pointer
+            .replace("{base}", &pair.base)
+            .replace("{quote}", &pair.quote)
+    }
+}
+
+#[async_trait]
+impl RateSource for HttpSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn fetch(&self, pair: &FxPair) -> Result<Vec<RateSample>> {
+        let body: serde_json::Value = self
+            .client
+            .get(self.url_for(pair))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let rate = match body.pointer(&self.pointer_for(pair)) {
+            Some(serde_json::Value::String(s)) => parse_rate(s),
+            Some(serde_json::Value::Number(n)) => parse_rate(&n.to_string()),
+            _ => None,
+        };
+
+        Ok(rate
+            .map(|rate| RateSample {
+                source: self.name.clone(),
+                rate,
+                observed_at: Utc::now(),
+            })
+            .into_iter()
+            .collect())
+    }
+}
+
+// ─── Provider-submitted rates ───────────────────────────────────────
+
+/// Rate a provider committed to, as stored in Redis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ProviderSubmission {
+    rate: Decimal,
+    submitted_at: DateTime<Utc>,
+}
+
+/// Rates providers submit for the corridors they serve
+///
+/// Each provider's latest submission is kept in a Redis hash per pair, so any
+/// service can read what the provider-service received. Submissions older
+/// than `max_age` are ignored.
+pub struct ProviderRateSource {
+    conn: ConnectionManager,
+    max_age: Duration,
+}
+
+impl ProviderRateSource {
+    pub fn new(conn: ConnectionManager, max_age: Duration) -> Self {
+        Self { conn, max_age }
+    }
+
+    fn key(pair: &FxPair) -> String {
+        format!("fx:provider:{}:{}", pair.base, pair.quote)
+    }
+
+    /// Record a provider's current rate for a pair
+    pub async fn submit(&self, provider: &str, pair: &FxPair, rate: Decimal) -> Result<()> {
+        if parse_rate(&rate.to_string()).is_none() {
+            return Err(FxError::Parse(format!("Invalid rate: {}", rate)));
+        }
+        let submission = ProviderSubmission { rate, submitted_at: Utc::now() };
+        let value = serde_json::to_string(&submission).map_err(|e| FxError::Parse(e.to_string()))?;
+
+        let mut conn = self.conn.clone();
+        conn.hset::<_, _, _, ()>(Self::key(pair), provider.to_lowercase(), value).await?;
+        Ok(())
+    }
+}
+
+/// Turn stored submissions into samples, dropping expired or unreadable ones
+fn fresh_submissions(entries: HashMap<String, String>, now: DateTime<Utc>, max_age: Duration) -> Vec<RateSample> {
+    let max_age = chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX);
+    let mut samples: Vec<RateSample> = entries
+        .into_iter()
+        .filter_map(|(provider, value)| {
+            let submission: ProviderSubmission = serde_json::from_str(&value).ok()?;
+            (now - submission.submitted_at <= max_age).then(|| RateSample {
+                source: format!("provider:{}", provider),
+                rate: submission.rate,
+                observed_at: submission.submitted_at,
+            })
+        })
+        .collect();
+    samples.sort_by(|a, b| a.source.cmp(&b.source));
+    samples
+}
+
+#[async_trait]
+impl RateSource for ProviderRateSource {
+    fn name(&self) -> &str {
+        "provider"
+    }
+
+    async fn fetch(&self, pair: &FxPair) -> Result<Vec<RateSample>> {
+        let mut conn = self.conn.clone();
+        let entries: HashMap<String, String> = conn.hgetall(Self::key(pair)).await?;
+        Ok(fresh_submissions(entries, Utc::now(), self.max_age))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_static_sources_parse_env_and_file() {
+        let source = StaticSource::parse("NGN=1550.25, kes=129.1,GHS=abc,ZAR=-3,EUR");
+        let ngn = source.fetch(&FxPair::new("USD", "NGN")).await.unwrap();
+        assert_eq!(ngn[0].rate, Decimal::from_str("1550.25").unwrap());
+        assert_eq!(source.fetch(&FxPair::new("USD", "KES")).await.unwrap().len(), 1);
+        assert!(source.fetch(&FxPair::new("USD", "GHS")).await.unwrap().is_empty());
+        assert!(source.fetch(&FxPair::new("USD", "ZAR")).await.unwrap().is_empty());
+
+        let path = std::env::temp_dir().join(format!("fx-rates-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"USD/NGN": "1549.90", "usd/kes": "129"}"#).unwrap();
+        let source = StaticSource::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(source.fetch(&FxPair::new("USD", "KES")).await.unwrap()[0].rate, Decimal::from(129));
+    }
+
+    #[test]
+    fn test_expired_provider_submissions_are_dropped() {
+        let now = Utc::now();
+        let submission = |rate: &str, age_secs: i64| {
+            serde_json::to_string(&ProviderSubmission {
+                rate: Decimal::from_str(rate).unwrap(),
+                submitted_at: now - chrono::Duration::seconds(age_secs),
+            })
+            .unwrap()
+        };
+        let entries = HashMap::from([
+            ("0xfresh".to_string(), submission("1551", 30)),
+            ("0xold".to_string(), submission("1400", 900)),
+            ("0xbroken".to_string(), "not json".to_string()),
+        ]);
+
+        let samples = fresh_submissions(entries, now, Duration::from_secs(300));
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].source, "provider:0xfresh");
+    }
+}
@@ -1,7 +1,17 @@
 use async_nats::Client;
 use anyhow::Result;
+use serde::Serialize;
+
+pub mod subjects;
 
 pub async fn connect_nats(url: &str) -> Result<Client> {
     let client = async_nats::connect(url).await?;
     Ok(client)
 }
+
+/// Serializes an event as JSON and publishes it on the given subject
+pub async fn publish_event<T: Serialize>(client: &Client, subject: &str, event: &T) -> Result<()> {
+    let payload = serde_json::to_vec(event)?;
+    client.publish(subject.to_string(), payload.into()).await?;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+//! NATS subject names used across services
+//!
+//! Order lifecycle events are published on `order.<stage>`; consumers that care
+//! about every stage subscribe to [`ORDER_ALL`].
+
+/// Order escrowed on-chain (Indexer → Order Service)
+pub const ORDER_CREATED: &str = "order.created";
+/// Order ready for routing (Order Service → AI Router)
+pub const ORDER_PENDING: &str = "order.pending";
+/// Provider selected for the order (AI Router → Provider Service)
+pub const ORDER_ASSIGNED: &str = "order.assigned";
+/// Provider started paying the order out (Provider Service)
+pub const ORDER_ACCEPTED: &str = "order.accepted";
+/// Some legs of a split order paid out (Order Service)
+pub const ORDER_PARTIALLY_FULFILLED: &str = "order.partially_fulfilled";
+/// Provider paid out the fiat leg (Provider Service → Settlement Service)
+pub const ORDER_FULFILLED: &str = "order.fulfilled";
+/// Provider failed to pay out; triggers fallback routing
+pub const ORDER_FAILED: &str = "order.failed";
+/// Router exhausted its failover budget (AI Router → Settlement Service)
+pub const ORDER_REFUND_REQUESTED: &str = "order.refund_requested";
+/// Escrow released on-chain (Settlement Service → Analytics)
+pub const ORDER_SETTLED: &str = "order.settled";
+/// Escrowed funds returned to the refund address (Settlement Service), or an
+/// order never escrowed closed on a refund request (Order Service)
+pub const ORDER_REFUNDED: &str = "order.refunded";
+/// Order expired before any provider took it (Order Service)
+pub const ORDER_EXPIRED: &str = "order.expired";
+/// User contested the payout; settlement is held (Order Service → Settlement Service)
+pub const ORDER_DISPUTED: &str = "order.disputed";
+/// Admin released or refunded a disputed order (Order Service → Settlement Service)
+pub const ORDER_DISPUTE_RESOLVED: &str = "order.dispute_resolved";
+
+/// Provider proposed to settle an order (Provider Service → AI Router)
+pub const PROPOSAL_CREATED: &str = "proposal.created";
+
+/// Provider published, changed or paused an intent (Provider Service → AI Router)
+pub const PROVIDER_INTENT_UPDATED: &str = "provider.intent.updated";
+/// Signed health ping from a provider (Provider → Provider Service)
+pub const PROVIDER_HEARTBEAT: &str = "provider.heartbeat";
+/// Provider staked collateral on-chain (Indexer → Balance Service)
+pub const PROVIDER_STAKE_DEPOSITED: &str = "provider.stake.deposited";
+/// Provider withdrew collateral on-chain (Indexer → Balance Service)
+pub const PROVIDER_STAKE_WITHDRAWN: &str = "provider.stake.withdrawn";
+
+/// Wildcard matching every order lifecycle subject
+pub const ORDER_ALL: &str = "order.*";
+
+/// Wildcard matching every provider stake subject
+pub const PROVIDER_STAKE_ALL: &str = "provider.stake.*";
@@ -0,0 +1,251 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::OrderStatus;
+use crate::error::{Result, TypesError};
+
+/// Settlement state of one leg of a split order
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum AllocationStatus {
+    /// Assigned to the provider, nothing paid out yet
+    Pending,
+    /// Provider has paid out part of the leg
+    PartiallyFilled,
+    /// Leg paid out in full
+    Settled,
+    /// Provider failed to pay out the rest of the leg
+    Failed,
+}
+
+impl AllocationStatus {
+    /// Returns the string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AllocationStatus::Pending => "PENDING",
+            AllocationStatus::PartiallyFilled => "PARTIALLY_FILLED",
+            AllocationStatus::Settled => "SETTLED",
+            AllocationStatus::Failed => "FAILED",
+        }
+    }
+
+    /// Whether the leg can still receive fills
+    pub fn is_open(&self) -> bool {
+        matches!(self, AllocationStatus::Pending | AllocationStatus::PartiallyFilled)
+    }
+}
+
+impl FromStr for AllocationStatus {
+    type Err = TypesError;
+
+    /// Parses a stored status
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "PENDING" => Ok(AllocationStatus::Pending),
+            "PARTIALLY_FILLED" => Ok(AllocationStatus::PartiallyFilled),
+            "SETTLED" => Ok(AllocationStatus::Settled),
+            "FAILED" => Ok(AllocationStatus::Failed),
+            _ => Err(TypesError::InvalidStatus(s.to_string())),
+        }
+    }
+}
+
+/// Share of a split order the router assigned to one provider
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LegAllocation {
+    /// Position of the leg within the order, starting at 1
+    pub leg: u32,
+
+    pub provider: String,
+
+    /// Token amount assigned to the provider (in wei)
+    pub amount: String,
+}
+
+/// One leg of a split order, tracked through settlement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OrderAllocation {
+    pub id: Uuid,
+
+    /// Parent order ID (bytes32 as hex string)
+    pub order_id: String,
+
+    /// Position of the leg within the order, starting at 1
+    pub leg: u32,
+
+    pub provider: String,
+
+    /// Token amount assigned to the leg (in wei)
+    pub amount: String,
+
+    /// Token amount paid out so far (in wei)
+    pub filled_amount: String,
+
+    pub status: AllocationStatus,
+
+    /// Transaction of the most recent fill
+    pub tx_hash: Option<String>,
+
+    pub created_at: DateTime<Utc>,
+
+    pub updated_at: DateTime<Utc>,
+
+    /// When the leg was paid out in full
+    pub settled_at: Option<DateTime<Utc>>,
+}
+
+/// Payout reported against one leg of a split order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RecordFillRequest {
+    /// Token amount paid out (in wei)
+    pub amount: String,
+
+    /// Transaction the payout settled in
+    pub tx_hash: Option<String>,
+}
+
+impl OrderAllocation {
+    /// Amount still to be paid out on this leg
+    pub fn remaining(&self) -> u128 {
+        let amount: u128 = self.amount.parse().unwrap_or(0);
+        let filled: u128 = self.filled_amount.parse().unwrap_or(0);
+        amount.saturating_sub(filled)
+    }
+
+    /// Apply a payout to the leg, settling it once the full amount is filled
+    ///
+    /// # Returns
+    /// * `Result<()>` - An error when the leg is closed or the fill exceeds
+    ///   what remains
+    pub fn record_fill(&mut self, amount: &str, tx_hash: Option<String>) -> Result<()> {
+        if !self.status.is_open() {
+            return Err(TypesError::InvalidStatus(format!(
+                "leg {} of order {} is {}",
+                self.leg,
+                self.order_id,
+                self.status.as_str()
+            )));
+        }
+        let fill: u128 = amount
+            .parse()
+            .map_err(|_| TypesError::InvalidAmount(amount.to_string()))?;
+        let remaining = self.remaining();
+        if fill == 0 || fill > remaining {
+            return Err(TypesError::InvalidAmount(format!(
+                "fill of {} on leg {} with {} remaining",
+                amount, self.leg, remaining
+            )));
+        }
+
+        let filled: u128 = self.filled_amount.parse().unwrap_or(0);
+        let now = Utc::now();
+        self.filled_amount = (filled + fill).to_string();
+        self.tx_hash = tx_hash.or(self.tx_hash.take());
+        self.updated_at = now;
+        if fill == remaining {
+            self.status = AllocationStatus::Settled;
+            self.settled_at = Some(now);
+        } else {
+            self.status = AllocationStatus::PartiallyFilled;
+        }
+        Ok(())
+    }
+}
+
+/// Order status implied by the legs of a split order
+///
+/// A failed leg is closed like a settled one: what it did not pay out is
+/// refunded on its own, so it no longer holds the order open.
+///
+/// # Returns
+/// * `Option<OrderStatus>` - Once every leg is closed, fulfilled if anything
+///   was paid out and refunded if nothing was; while legs are open,
+///   partially fulfilled once anything was paid out, None until then
+pub fn split_order_status(allocations: &[OrderAllocation]) -> Option<OrderStatus> {
+    if allocations.is_empty() {
+        return None;
+    }
+    let paid = allocations
+        .iter()
+        .any(|a| a.filled_amount.parse::<u128>().unwrap_or(0) > 0);
+    if allocations.iter().all(|a| !a.status.is_open()) {
+        return Some(if paid { OrderStatus::Fulfilled } else { OrderStatus::Refunded });
+    }
+    paid.then_some(OrderStatus::PartiallyFulfilled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allocation(leg: u32, amount: &str) -> OrderAllocation {
+        OrderAllocation {
+            id: Uuid::new_v4(),
+            order_id: "0xorder".to_string(),
+            leg,
+            provider: format!("0xprovider{}", leg),
+            amount: amount.to_string(),
+            filled_amount: "0".to_string(),
+            status: AllocationStatus::Pending,
+            tx_hash: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            settled_at: None,
+        }
+    }
+
+    #[test]
+    fn test_leg_fills() {
+        let mut leg = allocation(1, "1000");
+        leg.record_fill("400", Some("0xtx1".to_string())).unwrap();
+        assert_eq!(leg.status, AllocationStatus::PartiallyFilled);
+        assert_eq!(leg.remaining(), 600);
+
+        assert!(leg.record_fill("700", None).is_err()); // Overfill
+        leg.record_fill("600", None).unwrap();
+        assert_eq!(leg.status, AllocationStatus::Settled);
+        assert_eq!(leg.tx_hash.as_deref(), Some("0xtx1"));
+        assert!(leg.settled_at.is_some());
+        assert!(leg.record_fill("1", None).is_err()); // Closed
+    }
+
+    #[test]
+    fn test_split_order_status() {
+        let mut legs = vec![allocation(1, "1000"), allocation(2, "500")];
+        assert_eq!(split_order_status(&legs), None);
+
+        legs[0].record_fill("1000", None).unwrap();
+        assert_eq!(split_order_status(&legs), Some(OrderStatus::PartiallyFulfilled));
+
+        legs[1].record_fill("500", None).unwrap();
+        assert_eq!(split_order_status(&legs), Some(OrderStatus::Fulfilled));
+    }
+
+    #[test]
+    fn test_split_order_status_with_failed_legs() {
+        let mut legs = vec![allocation(1, "1000"), allocation(2, "500")];
+        legs[0].record_fill("1000", None).unwrap();
+        legs[1].status = AllocationStatus::Failed;
+        assert_eq!(split_order_status(&legs), Some(OrderStatus::Fulfilled));
+
+        // A leg that failed part way still counts as paid out
+        let mut legs = vec![allocation(1, "1000"), allocation(2, "500")];
+        legs[0].record_fill("300", None).unwrap();
+        legs[0].status = AllocationStatus::Failed;
+        assert_eq!(split_order_status(&legs), Some(OrderStatus::PartiallyFulfilled));
+        legs[1].status = AllocationStatus::Failed;
+        assert_eq!(split_order_status(&legs), Some(OrderStatus::Fulfilled));
+
+        let mut legs = vec![allocation(1, "1000"), allocation(2, "500")];
+        legs[0].status = AllocationStatus::Failed;
+        assert_eq!(split_order_status(&legs), None);
+        legs[1].status = AllocationStatus::Failed;
+        assert_eq!(split_order_status(&legs), Some(OrderStatus::Refunded));
+    }
+}
@@ -0,0 +1,118 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::TypesError;
+
+/// Where a liquidity reservation stands
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ReservationState {
+    /// Held for an assigned order
+    Reserved,
+    /// Paid out; the amount comes off the provider's intent
+    Committed,
+    /// Given back after the provider failed or the order ended unpaid
+    Released,
+    /// Given back because nothing settled it before its TTL
+    Expired,
+}
+
+impl ReservationState {
+    /// Returns the string representation for storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReservationState::Reserved => "reserved",
+            ReservationState::Committed => "committed",
+            ReservationState::Released => "released",
+            ReservationState::Expired => "expired",
+        }
+    }
+}
+
+impl FromStr for ReservationState {
+    type Err = TypesError;
+
+    /// Parses a stored state
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reserved" => Ok(ReservationState::Reserved),
+            "committed" => Ok(ReservationState::Committed),
+            "released" => Ok(ReservationState::Released),
+            "expired" => Ok(ReservationState::Expired),
+            _ => Err(TypesError::InvalidStatus(s.to_string())),
+        }
+    }
+}
+
+/// Liquidity held for one provider's assignment on an order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Reservation {
+    pub order_id: String,
+    pub provider: String,
+    pub currency: String,
+    /// Token amount in base units, as on the assignment
+    pub amount: String,
+    pub state: ReservationState,
+    pub created_at: DateTime<Utc>,
+    /// When the reservation is released if nothing settles it
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Reservation {
+    /// Reservations are keyed by order and provider, so an assignment
+    /// delivered twice reserves once
+    pub fn id(order_id: &str, provider: &str) -> String {
+        format!("{}:{}", order_id.to_lowercase(), provider.to_lowercase())
+    }
+}
+
+/// A provider's liquidity in one currency, as the balance service sees it
+///
+/// All amounts are token base units. `free` is what new assignments can
+/// reserve: `available - reserved - committed`, where `committed` is paid
+/// out but not yet deducted from the provider's intent.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProviderLiquidity {
+    pub provider: String,
+    pub currency: String,
+    /// Intent's `available_amount` at the last reconciliation
+    pub available: String,
+    pub reserved: String,
+    pub committed: String,
+    pub free: String,
+}
+
+/// A provider's collateral in one token
+///
+/// `staked` is what the provider has deposited less withdrawals, in token
+/// base units. Stake is only recorded, not yet locked against assignments
+/// or slashed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProviderStake {
+    pub provider: String,
+    pub token: String,
+    pub staked: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A stake deposit or withdrawal seen on-chain, published by the indexer on
+/// `provider.stake.deposited` or `provider.stake.withdrawn`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakeTransferEvent {
+    pub provider: String,
+    pub token: String,
+    /// Token amount in base units
+    pub amount: String,
+    pub tx_hash: String,
+    /// Position of the log in its transaction's block; with `tx_hash` it
+    /// makes a transfer replayed by the indexer count once
+    pub log_index: i64,
+    pub block_number: i64,
+    pub timestamp: DateTime<Utc>,
+}
@@ -0,0 +1,135 @@
+//! Directory of banks and mobile money networks payouts can be sent to
+//!
+//! Codes are the ones PSPs take as `bank_code`: CBN codes in Nigeria,
+//! Kenya Bankers Association codes in Kenya, GhIPSS sort codes and network
+//! codes in Ghana, and universal branch codes in South Africa. Currencies
+//! without an entry here (EUR, USD) identify the bank by the account number
+//! itself or accept any code.
+
+use serde::Serialize;
+
+/// What a directory entry pays out to
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BankKind {
+    Bank,
+    /// Wallets addressed by phone number
+    MobileMoney,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct Bank {
+    pub currency: &'static str,
+    pub code: &'static str,
+    pub name: &'static str,
+    /// Other names users know the bank by
+    pub aliases: &'static [&'static str],
+    pub kind: BankKind,
+    /// Whether account numbers end in a NUBAN check digit over this code;
+    /// false for banks that issue phone-number based accounts
+    pub nuban: bool,
+}
+
+const fn bank(currency: &'static str, code: &'static str, name: &'static str, aliases: &'static [&'static str]) -> Bank {
+    Bank { currency, code, name, aliases, kind: BankKind::Bank, nuban: false }
+}
+
+const fn nuban(code: &'static str, name: &'static str, aliases: &'static [&'static str]) -> Bank {
+    Bank { currency: "NGN", code, name, aliases, kind: BankKind::Bank, nuban: true }
+}
+
+const fn wallet(currency: &'static str, code: &'static str, name: &'static str, aliases: &'static [&'static str]) -> Bank {
+    Bank { currency, code, name, aliases, kind: BankKind::MobileMoney, nuban: false }
+}
+
+pub const BANKS: &[Bank] = &[
+    nuban("044", "Access Bank", &["Access"]),
+    nuban("023", "Citibank Nigeria", &["Citibank"]),
+    nuban("050", "Ecobank Nigeria", &["Ecobank"]),
+    nuban("070", "Fidelity Bank", &["Fidelity"]),
+    nuban("011", "First Bank of Nigeria", &["First Bank", "FBN"]),
+    nuban("214", "First City Monument Bank", &["FCMB"]),
+    nuban("058", "Guaranty Trust Bank", &["GTBank", "GTB", "GTCO"]),
+    nuban("030", "Heritage Bank", &["Heritage"]),
+    nuban("301", "Jaiz Bank", &["Jaiz"]),
+    nuban("082", "Keystone Bank", &["Keystone"]),
+    nuban("076", "Polaris Bank", &["Polaris"]),
+    nuban("101", "Providus Bank", &["Providus"]),
+    nuban("221", "Stanbic IBTC Bank", &["Stanbic IBTC", "Stanbic"]),
+    nuban("068", "Standard Chartered Bank Nigeria", &["Standard Chartered"]),
+    nuban("232", "Sterling Bank", &["Sterling"]),
+    nuban("032", "Union Bank of Nigeria", &["Union Bank"]),
+    nuban("033", "United Bank for Africa", &["UBA"]),
+    nuban("215", "Unity Bank", &["Unity"]),
+    nuban("035", "Wema Bank", &["Wema", "ALAT"]),
+    nuban("057", "Zenith Bank", &["Zenith"]),
+    bank("NGN", "50211", "Kuda Bank", &["Kuda"]),
+    bank("NGN", "50515", "Moniepoint MFB", &["Moniepoint"]),
+    bank("NGN", "999992", "OPay", &["Paycom"]),
+    bank("NGN", "999991", "PalmPay", &[]),
+    bank("KES", "01", "Kenya Commercial Bank", &["KCB"]),
+    bank("KES", "02", "Standard Chartered Bank Kenya", &["Standard Chartered"]),
+    bank("KES", "03", "Absa Bank Kenya", &["Absa", "Barclays"]),
+    bank("KES", "07", "NCBA Bank", &["NCBA"]),
+    bank("KES", "11", "Co-operative Bank of Kenya", &["Co-op Bank", "Coop"]),
+    bank("KES", "31", "Stanbic Bank Kenya", &["Stanbic"]),
+    bank("KES", "57", "I&M Bank", &["I&M"]),
+    bank("KES", "63", "Diamond Trust Bank", &["DTB"]),
+    bank("KES", "68", "Equity Bank", &["Equity"]),
+    bank("KES", "70", "Family Bank", &[]),
+    bank("GHS", "030100", "Absa Bank Ghana", &["Absa", "Barclays"]),
+    bank("GHS", "280100", "Access Bank Ghana", &["Access"]),
+    bank("GHS", "080100", "Agricultural Development Bank", &["ADB"]),
+    bank("GHS", "140100", "CalBank", &["Cal Bank"]),
+    bank("GHS", "130100", "Ecobank Ghana", &["Ecobank"]),
+    bank("GHS", "240100", "Fidelity Bank Ghana", &["Fidelity"]),
+    bank("GHS", "040100", "GCB Bank", &["GCB", "Ghana Commercial Bank"]),
+    bank("GHS", "190100", "Stanbic Bank Ghana", &["Stanbic"]),
+    bank("GHS", "020100", "Standard Chartered Bank Ghana", &["Standard Chartered"]),
+    bank("GHS", "060100", "United Bank for Africa Ghana", &["UBA"]),
+    bank("GHS", "120100", "Zenith Bank Ghana", &["Zenith"]),
+    wallet("GHS", "MTN", "MTN Mobile Money", &["MTN MoMo", "MoMo"]),
+    wallet("GHS", "VOD", "Telecel Cash", &["Vodafone Cash", "Telecel"]),
+    wallet("GHS", "ATL", "AirtelTigo Money", &["AT Money", "AirtelTigo"]),
+    bank("ZAR", "632005", "Absa Bank", &["Absa"]),
+    bank("ZAR", "430000", "African Bank", &[]),
+    bank("ZAR", "470010", "Capitec Bank", &["Capitec"]),
+    bank("ZAR", "679000", "Discovery Bank", &["Discovery"]),
+    bank("ZAR", "250655", "First National Bank", &["FNB"]),
+    bank("ZAR", "580105", "Investec Bank", &["Investec"]),
+    bank("ZAR", "198765", "Nedbank", &[]),
+    bank("ZAR", "051001", "Standard Bank", &[]),
+    bank("ZAR", "678910", "TymeBank", &["Tyme"]),
+];
+
+/// Banks and networks paying out in a currency
+pub fn banks(currency: &str) -> impl Iterator<Item = &'static Bank> + '_ {
+    BANKS.iter().filter(move |bank| bank.currency.eq_ignore_ascii_case(currency))
+}
+
+/// Whether the directory lists banks for a currency, so unknown codes in it
+/// can be rejected
+pub fn has_directory(currency: &str) -> bool {
+    banks(currency).next().is_some()
+}
+
+pub fn find_bank(currency: &str, code: &str) -> Option<&'static Bank> {
+    let code = code.trim();
+    banks(currency).find(|bank| bank.code.eq_ignore_ascii_case(code))
+}
+
+/// Look a bank up by its name or an alias, ignoring case and punctuation
+pub fn find_bank_by_name(currency: &str, name: &str) -> Option<&'static Bank> {
+    let key = name_key(name);
+    if key.is_empty() {
+        return None;
+    }
+    banks(currency).find(|bank| name_key(bank.name) == key || bank.aliases.iter().any(|alias| name_key(alias) == key))
+}
+
+fn name_key(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
@@ -0,0 +1,282 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::enums::OrderStatus;
+use crate::error::TypesError;
+use crate::payment::PaymentProof;
+
+/// Progress of a dispute
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DisputeStatus {
+    /// Opened by the user, waiting for the provider's evidence
+    Open,
+    /// Provider has submitted evidence, waiting for an admin
+    EvidenceSubmitted,
+    /// Decided by an admin
+    Resolved,
+}
+
+impl DisputeStatus {
+    /// Returns the string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisputeStatus::Open => "open",
+            DisputeStatus::EvidenceSubmitted => "evidence_submitted",
+            DisputeStatus::Resolved => "resolved",
+        }
+    }
+
+    /// Whether settlement of the order is still held
+    pub fn is_active(&self) -> bool {
+        !matches!(self, DisputeStatus::Resolved)
+    }
+}
+
+impl FromStr for DisputeStatus {
+    type Err = TypesError;
+
+    /// Parses a stored status
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(DisputeStatus::Open),
+            "evidence_submitted" => Ok(DisputeStatus::EvidenceSubmitted),
+            "resolved" => Ok(DisputeStatus::Resolved),
+            _ => Err(TypesError::InvalidStatus(s.to_string())),
+        }
+    }
+}
+
+/// How an admin settled a dispute
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DisputeOutcome {
+    /// Provider showed the payout was made; escrow is released to them
+    Release,
+    /// User was not paid; escrow is refunded and counts against the provider
+    Refund,
+}
+
+impl DisputeOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisputeOutcome::Release => "release",
+            DisputeOutcome::Refund => "refund",
+        }
+    }
+}
+
+impl FromStr for DisputeOutcome {
+    type Err = TypesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "release" => Ok(DisputeOutcome::Release),
+            "refund" => Ok(DisputeOutcome::Refund),
+            _ => Err(TypesError::ParseError(format!("Unknown dispute outcome: {}", s))),
+        }
+    }
+}
+
+/// A user's claim that they were not paid for an order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Dispute {
+    pub id: Uuid,
+    pub order_id: String,
+    /// User who opened the dispute
+    pub user_address: String,
+    /// Provider whose payout is contested
+    pub provider: String,
+    pub reason: String,
+    pub status: DisputeStatus,
+    /// Provider's payment proof, with their evidence under `metadata.evidence`
+    pub proof: Option<PaymentProof>,
+    pub outcome: Option<DisputeOutcome>,
+    pub resolution_note: Option<String>,
+    /// Admin who resolved the dispute
+    pub resolved_by: Option<String>,
+    pub opened_at: DateTime<Utc>,
+    pub evidence_submitted_at: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl Dispute {
+    /// Whether a dispute may be opened on an order in `status` that last
+    /// changed at `updated_at`
+    pub fn can_open(status: OrderStatus, updated_at: DateTime<Utc>, window: chrono::Duration, now: DateTime<Utc>) -> bool {
+        matches!(
+            status,
+            OrderStatus::Accepted | OrderStatus::PartiallyFulfilled | OrderStatus::Fulfilled
+        ) && now - updated_at <= window
+    }
+}
+
+/// Settlement of an order held by its dispute
+///
+/// A fulfilment that arrives while the dispute is under review is recorded
+/// rather than settled. Once the dispute is released it settles, once; a
+/// refund leaves it unsettled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettlementHold {
+    pub order_id: String,
+    pub dispute_id: Uuid,
+    pub held_at: DateTime<Utc>,
+    pub fulfilled_at: Option<DateTime<Utc>>,
+    pub released_at: Option<DateTime<Utc>>,
+    pub settled_at: Option<DateTime<Utc>>,
+}
+
+impl SettlementHold {
+    /// Whether the dispute is still under review
+    pub fn is_active(&self) -> bool {
+        self.released_at.is_none()
+    }
+
+    /// Record a fulfilment; true when the hold keeps it from settling
+    pub fn hold_fulfilment(&mut self, now: DateTime<Utc>) -> bool {
+        if !self.is_active() {
+            return false;
+        }
+        self.fulfilled_at.get_or_insert(now);
+        true
+    }
+
+    /// Take the fulfilment held until a release; true only the first time
+    pub fn take_held_fulfilment(&mut self, now: DateTime<Utc>) -> bool {
+        if self.is_active() || self.fulfilled_at.is_none() || self.settled_at.is_some() {
+            return false;
+        }
+        self.settled_at = Some(now);
+        true
+    }
+}
+
+/// Dispute opened by a user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OpenDisputeRequest {
+    /// What went wrong, e.g. "No credit received on my account"
+    pub reason: String,
+}
+
+/// Provider's answer to a dispute
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SubmitEvidenceRequest {
+    /// Payment proof for the order's payout
+    pub proof: PaymentProof,
+    /// Supporting material (bank statement references, session IDs,
+    /// receipts), stored in the proof's metadata
+    pub evidence: Value,
+}
+
+/// Admin decision on a dispute
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ResolveDisputeRequest {
+    pub outcome: DisputeOutcome,
+    pub note: Option<String>,
+}
+
+/// Settlement of an order is held while its dispute is reviewed
+/// (published on `order.disputed`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderDisputedEvent {
+    pub order_id: String,
+    pub dispute_id: Uuid,
+    pub provider: String,
+    pub reason: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A dispute was decided and the held settlement can proceed
+/// (published on `order.dispute_resolved`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisputeResolvedEvent {
+    pub order_id: String,
+    pub dispute_id: Uuid,
+    pub provider: String,
+    pub outcome: DisputeOutcome,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl PaymentProof {
+    /// Attach dispute evidence under `metadata.evidence`, keeping the rest
+    /// of the metadata; metadata is not signed, so the proof stays valid
+    pub fn with_evidence(mut self, evidence: Value) -> Self {
+        match &mut self.metadata {
+            Value::Object(metadata) => {
+                metadata.insert("evidence".to_string(), evidence);
+            }
+            Value::Null => self.metadata = serde_json::json!({ "evidence": evidence }),
+            other => {
+                let details = other.take();
+                self.metadata = serde_json::json!({ "details": details, "evidence": evidence });
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_fulfilment_during_an_open_dispute_is_held() {
+        let now = Utc::now();
+        let mut hold = SettlementHold {
+            order_id: "0xorder".to_string(),
+            dispute_id: Uuid::new_v4(),
+            held_at: now,
+            fulfilled_at: None,
+            released_at: None,
+            settled_at: None,
+        };
+        // Nothing to settle while the dispute is open
+        assert!(!hold.take_held_fulfilment(now));
+        assert!(hold.hold_fulfilment(now));
+        assert!(hold.hold_fulfilment(now + Duration::minutes(1)));
+        assert_eq!(hold.fulfilled_at, Some(now));
+        assert!(!hold.take_held_fulfilment(now));
+
+        hold.released_at = Some(now + Duration::hours(1));
+        assert!(hold.take_held_fulfilment(now + Duration::hours(1)));
+        assert!(!hold.take_held_fulfilment(now + Duration::hours(2)));
+        // Fulfilments after the release settle as usual
+        assert!(!hold.hold_fulfilment(now + Duration::hours(2)));
+    }
+
+    #[test]
+    fn test_dispute_window_and_evidence() {
+        let now = Utc::now();
+        let window = Duration::hours(72);
+        assert!(Dispute::can_open(OrderStatus::Fulfilled, now - Duration::hours(71), window, now));
+        assert!(Dispute::can_open(OrderStatus::Accepted, now, window, now));
+        assert!(!Dispute::can_open(OrderStatus::Fulfilled, now - Duration::hours(73), window, now));
+        assert!(!Dispute::can_open(OrderStatus::Pending, now, window, now));
+        assert!(!Dispute::can_open(OrderStatus::Refunded, now, window, now));
+
+        let proof = PaymentProof {
+            proposal_id: "0xproposal".to_string(),
+            provider: "0xprovider".to_string(),
+            transaction_reference: "TXN123".to_string(),
+            timestamp: now,
+            amount: "5000".to_string(),
+            currency: "NGN".to_string(),
+            signature: "0xsig".to_string(),
+            metadata: serde_json::json!({ "bank": "GTBank" }),
+        };
+        let proof = proof.with_evidence(serde_json::json!({ "session_id": "000013230101" }));
+        assert_eq!(proof.metadata["bank"], "GTBank");
+        assert_eq!(proof.metadata["evidence"]["session_id"], "000013230101");
+    }
+}
@@ -1,8 +1,15 @@
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::TypesError;
+use crate::tier::{TierLimits, TokenAmount};
+
 /// Order classification tiers based on token amount ranges
 /// These tiers determine order priority and matching strategies
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+/// Ranges below are the standard limits; each token can configure its own
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::Type)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[sqlx(type_name = "order_tier", rename_all = "UPPERCASE")]
 pub enum OrderTier {
     /// Smallest orders: < 3,000 tokens
@@ -22,24 +29,22 @@ impl OrderTier {
     /// Used during order creation to classify orders for optimal provider matching
     /// 
     /// # Arguments
-    /// * `amount` - Token amount as string to avoid precision loss with large numbers
-    /// * `limits` - Tier limit configuration defining amount boundaries for each tier
+    /// * `amount` - Validated token amount in base units
+    /// * `limits` - Tier limits of the order's token
     /// 
     /// # Returns
     /// * `OrderTier` - The classified tier for the given amount
-    pub fn from_amount(amount: &str, limits: &TierLimits) -> Self {
-        // Parse amount to u128, default to 0 if parsing fails to handle invalid input
-        let amount: u128 = amount.parse().unwrap_or(0);
-        
-        // Determine tier based on amount ranges using configured limits
-        // Orders are classified into tiers for optimized matching and risk management
-        if amount <= limits.alpha {
+    pub fn from_amount(amount: TokenAmount, limits: &TierLimits) -> Self {
+        let [alpha, beta, delta, omega] = limits.base_unit_limits();
+        let amount = amount.value();
+
+        if amount <= alpha {
             OrderTier::Alpha
-        } else if amount <= limits.beta {
+        } else if amount <= beta {
             OrderTier::Beta
-        } else if amount <= limits.delta {
+        } else if amount <= delta {
             OrderTier::Delta
-        } else if amount <= limits.omega {
+        } else if amount <= omega {
             OrderTier::Omega
         } else {
             OrderTier::Titan
@@ -62,23 +67,6 @@ impl OrderTier {
     }
 }
 
-/// Configuration for tier amount boundaries
-/// Defines the token amount thresholds that separate order tiers
-/// These limits enable the system to classify orders for optimized matching
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TierLimits {
-    /// Maximum token amount for Alpha tier (smallest orders)
-    pub alpha: u128,
-    /// Maximum token amount for Beta tier (small to medium orders)
-    pub beta: u128,
-    /// Maximum token amount for Delta tier (medium orders)
-    pub delta: u128,
-    /// Maximum token amount for Omega tier (large orders)
-    pub omega: u128,
-    /// Minimum token amount for Titan tier (largest orders, no upper limit)
-    pub titan: u128,
-}
-
 /// Order lifecycle status tracking order progression through the settlement pipeline
 /// Each status represents a specific stage in the order fulfillment process
 #[derive(
@@ -91,6 +79,7 @@ pub struct TierLimits {
     Eq,
     sqlx::Type
 )]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[sqlx(type_name = "order_status", rename_all = "UPPERCASE")]
 pub enum OrderStatus {
     /// Order created but not yet accepted by any provider
@@ -99,6 +88,10 @@ pub enum OrderStatus {
     /// Order accepted by a provider, awaiting settlement execution
     /// Transition to this state occurs when user accepts a provider's proposal
     Accepted,
+    /// Some legs of a split order have settled, others are outstanding
+    /// Only split orders pass through this state
+    #[sqlx(rename = "PARTIALLY_FULFILLED")]
+    PartiallyFulfilled,
     /// Order successfully completed and funds settled
     /// Final state indicating successful transaction completion
     Fulfilled,
@@ -120,12 +113,17 @@ impl OrderStatus {
         match self {
             OrderStatus::Pending => "PENDING",
             OrderStatus::Accepted => "ACCEPTED", 
+            OrderStatus::PartiallyFulfilled => "PARTIALLY_FULFILLED",
             OrderStatus::Fulfilled => "FULFILLED",
             OrderStatus::Refunded => "REFUNDED",
             OrderStatus::Expired => "EXPIRED",
         }
     }
-    
+}
+
+impl FromStr for OrderStatus {
+    type Err = TypesError;
+
     /// Parses string from database into OrderStatus enum
     /// Used when converting database records to domain models
     /// 
@@ -133,15 +131,16 @@ impl OrderStatus {
     /// * `s` - String representation from database
     /// 
     /// # Returns
-    /// * `Option<Self>` - Some(OrderStatus) if valid, None if unrecognized
-    pub fn from_str(s: &str) -> Option<Self> {
+    /// * `Result<Self, TypesError>` - OrderStatus if valid, InvalidStatus if unrecognized
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "PENDING" => Some(OrderStatus::Pending),
-            "ACCEPTED" => Some(OrderStatus::Accepted),
-            "FULFILLED" => Some(OrderStatus::Fulfilled),
-            "REFUNDED" => Some(OrderStatus::Refunded),
-            "EXPIRED" => Some(OrderStatus::Expired),
-            _ => None,
+            "PENDING" => Ok(OrderStatus::Pending),
+            "ACCEPTED" => Ok(OrderStatus::Accepted),
+            "PARTIALLY_FULFILLED" => Ok(OrderStatus::PartiallyFulfilled),
+            "FULFILLED" => Ok(OrderStatus::Fulfilled),
+            "REFUNDED" => Ok(OrderStatus::Refunded),
+            "EXPIRED" => Ok(OrderStatus::Expired),
+            _ => Err(TypesError::InvalidStatus(s.to_string())),
         }
     }
 }
@@ -149,6 +148,7 @@ impl OrderStatus {
 /// Proposal lifecycle status tracking provider responses to orders
 /// Represents the state of settlement proposals between providers and users
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[sqlx(type_name = "proposal_status", rename_all = "UPPERCASE")]
 pub enum ProposalStatus {
     /// Proposal created but not yet accepted/rejected by user
@@ -187,6 +187,7 @@ impl ProposalStatus {
 /// Supported fiat currencies for off-ramping operations
 /// Defines the target currencies users can receive for their crypto assets
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum Currency {
     /// Nigerian Naira - Primary currency for Nigerian users
     NGN,
@@ -221,7 +222,9 @@ impl Currency {
             Currency::Custom(s) => s.clone(),
         }
     }
-    
+}
+
+impl From<&str> for Currency {
     /// Parses currency code string into Currency enum
     /// Used when converting database strings to domain types
     /// Unknown currencies are stored as Custom variant for flexibility
@@ -231,7 +234,7 @@ impl Currency {
     /// 
     /// # Returns
     /// * `Currency` - Corresponding enum variant, Custom if unrecognized
-    pub fn from_str(s: &str) -> Self {
+    fn from(s: &str) -> Self {
         match s {
             "NGN" => Currency::NGN,
             "KES" => Currency::KES,
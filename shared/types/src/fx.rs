@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// FX rate used to price a quote or order, kept for audits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FxRateSnapshot {
+    pub quote_id: Option<Uuid>,
+
+    /// Blockchain order ID, once an order references the quote
+    pub order_id: Option<String>,
+
+    /// Currency pair, e.g. "USD/NGN"
+    pub pair: String,
+
+    /// Aggregated rate as a decimal string
+    pub rate: String,
+
+    /// Sources whose samples made up the rate
+    pub sources: Vec<String>,
+
+    /// Samples rejected as outliers
+    pub rejected_count: u32,
+
+    /// Whether the rate was stale when used
+    pub stale: bool,
+
+    /// When the underlying samples were observed
+    pub observed_at: DateTime<Utc>,
+
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Rate a provider commits to for a currency it pays out in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SubmitRateRequest {
+    /// Off-ramp currency code; the rate is for USD/<currency>
+    pub currency: String,
+
+    /// Fiat paid out per USD, as a decimal string
+    pub rate: String,
+}
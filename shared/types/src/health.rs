@@ -0,0 +1,212 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Provider-reported availability
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum HeartbeatStatus {
+    /// Accepting and paying out orders
+    Online,
+    /// Accepting orders with reduced capacity or slower payouts
+    Degraded,
+    /// Not accepting orders
+    Offline,
+}
+
+impl HeartbeatStatus {
+    /// Whether the provider can take orders in this state
+    pub fn is_up(&self) -> bool {
+        !matches!(self, HeartbeatStatus::Offline)
+    }
+}
+
+/// Periodic health ping sent by a provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProviderHeartbeat {
+    /// Provider's wallet address
+    #[serde(alias = "providerId")]
+    pub provider: String,
+
+    pub status: HeartbeatStatus,
+
+    /// Fiat balance available for payouts, as reported by the provider
+    #[serde(default)]
+    pub balance: Option<String>,
+
+    /// Latency of the provider's payout rail, as measured by the provider
+    pub latency_ms: u64,
+}
+
+/// One heartbeat as kept in the rolling window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeartbeatSample {
+    pub received_at: DateTime<Utc>,
+    pub status: HeartbeatStatus,
+    pub latency_ms: u64,
+}
+
+/// Rolling health of a provider, computed from its recent heartbeats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProviderHealth {
+    /// Provider address
+    pub provider: String,
+
+    /// Latest reported status; Offline once heartbeats stop
+    pub status: HeartbeatStatus,
+
+    /// Share of heartbeat intervals in the window with the provider up, 0 to 1
+    pub uptime: f64,
+
+    /// Latency percentiles over heartbeats received while up
+    pub latency_p50_ms: Option<u64>,
+    pub latency_p95_ms: Option<u64>,
+    pub latency_p99_ms: Option<u64>,
+
+    /// Heartbeats received in the window
+    pub heartbeats: u64,
+
+    /// Last heartbeat received, whatever its status
+    pub last_heartbeat_at: Option<DateTime<Utc>>,
+
+    /// Length of the window the figures cover (seconds)
+    pub window_seconds: u64,
+
+    pub computed_at: DateTime<Utc>,
+}
+
+impl ProviderHealth {
+    /// Redis key holding a provider's latest health, written by the provider
+    /// service and read by the router
+    pub fn key(provider: &str) -> String {
+        format!("provider:health:{}", provider.to_lowercase())
+    }
+
+    /// Summarize the heartbeats received in the `window` before `now`
+    ///
+    /// The window is divided into `interval`-long slots, starting at the
+    /// first heartbeat so new providers are not penalised for the time before
+    /// they joined. A slot counts as up when it holds a heartbeat with an up
+    /// status. The status turns Offline once no heartbeat has arrived for
+    /// `timeout`.
+    pub fn summarize(
+        provider: &str,
+        samples: &[HeartbeatSample],
+        now: DateTime<Utc>,
+        window: chrono::Duration,
+        interval: chrono::Duration,
+        timeout: chrono::Duration,
+    ) -> Self {
+        let start = now - window;
+        let mut samples: Vec<&HeartbeatSample> = samples
+            .iter()
+            .filter(|s| s.received_at > start && s.received_at <= now)
+            .collect();
+        samples.sort_by_key(|s| s.received_at);
+
+        let interval_ms = interval.num_milliseconds().max(1);
+        let uptime = match samples.first() {
+            Some(first) => {
+                let observed_ms = (now - first.received_at).num_milliseconds().max(0);
+                let slots = observed_ms / interval_ms + 1;
+                let mut up: Vec<i64> = samples
+                    .iter()
+                    .filter(|s| s.status.is_up())
+                    .map(|s| (s.received_at - first.received_at).num_milliseconds() / interval_ms)
+                    .collect();
+                up.dedup();
+                up.len() as f64 / slots as f64
+            }
+            None => 0.0,
+        };
+
+        let mut latencies: Vec<u64> = samples
+            .iter()
+            .filter(|s| s.status.is_up())
+            .map(|s| s.latency_ms)
+            .collect();
+        latencies.sort_unstable();
+
+        let last = samples.last();
+        let status = match last {
+            Some(last) if now - last.received_at <= timeout => last.status,
+            _ => HeartbeatStatus::Offline,
+        };
+
+        Self {
+            provider: provider.to_lowercase(),
+            status,
+            uptime,
+            latency_p50_ms: percentile(&latencies, 50),
+            latency_p95_ms: percentile(&latencies, 95),
+            latency_p99_ms: percentile(&latencies, 99),
+            heartbeats: samples.len() as u64,
+            last_heartbeat_at: last.map(|s| s.received_at),
+            window_seconds: window.num_seconds().max(0) as u64,
+            computed_at: now,
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[u64], pct: usize) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    Some(sorted[rank - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_summarize_uptime_and_percentiles() {
+        let now = Utc::now();
+        let sample = |secs_ago: i64, status: HeartbeatStatus, latency_ms: u64| HeartbeatSample {
+            received_at: now - Duration::seconds(secs_ago),
+            status,
+            latency_ms,
+        };
+        // Ten 30s slots: eight up, one offline, one missed
+        let mut samples: Vec<HeartbeatSample> = (0..8)
+            .map(|i| sample(270 - i * 30, HeartbeatStatus::Online, 100 + i as u64 * 10))
+            .collect();
+        samples.push(sample(30, HeartbeatStatus::Offline, 0));
+        samples.push(sample(7200, HeartbeatStatus::Online, 5)); // outside the window
+
+        let health = ProviderHealth::summarize(
+            "0xABC",
+            &samples,
+            now,
+            Duration::hours(1),
+            Duration::seconds(30),
+            Duration::seconds(120),
+        );
+        assert_eq!(health.provider, "0xabc");
+        assert_eq!(health.heartbeats, 9);
+        assert!((health.uptime - 0.8).abs() < 1e-9);
+        assert_eq!(health.latency_p50_ms, Some(130));
+        assert_eq!(health.latency_p95_ms, Some(170));
+        assert_eq!(health.status, HeartbeatStatus::Offline);
+
+        // Silence past the timeout reads as offline
+        let stale = ProviderHealth::summarize(
+            "0xabc",
+            &samples[..8],
+            now,
+            Duration::hours(1),
+            Duration::seconds(30),
+            Duration::seconds(30),
+        );
+        assert_eq!(stale.status, HeartbeatStatus::Offline);
+
+        let unseen = ProviderHealth::summarize("0xabc", &[], now, Duration::hours(1), Duration::seconds(30), Duration::seconds(30));
+        assert_eq!(unseen.uptime, 0.0);
+        assert_eq!(unseen.latency_p99_ms, None);
+    }
+}
@@ -2,22 +2,49 @@
 //! 
 //! This crate contains all common data structures used across services.
 
+pub mod allocation;
+pub mod balance;
+pub mod banks;
+pub mod dispute;
 pub mod enums;
 pub mod error;
+pub mod fx;
+pub mod health;
 pub mod order;
+pub mod pagination;
 pub mod provider;
 pub mod proposal;
 pub mod reputation;
 pub mod payment;
+#[cfg(feature = "blockchain")]
+pub mod proof;
+pub mod quote;
+pub mod recipient;
+pub mod routing;
+pub mod tier;
+pub mod webhook;
 
 // Re-export commonly used types
+pub use allocation::*;
+pub use balance::*;
+pub use dispute::*;
 pub use enums::*;
 pub use error::*;
+pub use fx::*;
+pub use health::*;
 pub use order::*;
+pub use pagination::*;
 pub use provider::*;
 pub use proposal::*;
 pub use reputation::*;
 pub use payment::*;
+#[cfg(feature = "blockchain")]
+pub use proof::*;
+pub use quote::*;
+pub use recipient::*;
+pub use routing::*;
+pub use tier::*;
+pub use webhook::*;
 
 // Helper functions
 pub mod helpers {
@@ -36,6 +63,12 @@ pub mod helpers {
     pub fn is_valid_address(addr: &str) -> bool {
         addr.starts_with("0x") && addr.len() == 42
     }
+
+    /// Validate a bytes32 identifier such as an order ID: 0x and 64 hex digits
+    pub fn is_valid_bytes32(id: &str) -> bool {
+        id.strip_prefix("0x")
+            .is_some_and(|hex| hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+    }
 }
 
 #[cfg(test)]
@@ -52,6 +85,14 @@ mod tests {
         assert_eq!(decoded, bytes);
     }
     
+    #[test]
+    fn test_bytes32_validation() {
+        assert!(helpers::is_valid_bytes32(&format!("0x{}", "ab".repeat(32))));
+        assert!(!helpers::is_valid_bytes32(&"ab".repeat(32)));
+        assert!(!helpers::is_valid_bytes32(&format!("0x{}", "ab".repeat(31))));
+        assert!(!helpers::is_valid_bytes32(&format!("0x..%2F{}", "a".repeat(59))));
+    }
+    
     #[test]
     fn test_address_validation() {
         assert!(helpers::is_valid_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb"));
@@ -7,6 +7,7 @@ use crate::enums::{OrderStatus, OrderTier, Currency};
 
 /// Core order structure (domain model)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Order {
     /// Internal UUID for tracking
     pub id: Uuid,
@@ -59,6 +60,7 @@ pub struct Order {
 
 impl Order {
     /// Create a new order
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         order_id: String,
         user_address: String,
@@ -116,12 +118,16 @@ impl Order {
 
 /// Request to create an order
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CreateOrderRequest {
     pub token: String,
     pub amount: String,
     pub currency: String,
     pub refund_address: String,
     pub integrator_address: String,
+    /// Quote the order was priced with, if any
+    #[serde(default)]
+    pub quote_id: Option<Uuid>,
 }
 
 /// Order created event (from blockchain)
@@ -138,6 +144,91 @@ pub struct OrderCreatedEvent {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Order status transition event (published on `order.<stage>` subjects)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OrderStatusChangedEvent {
+    pub order_id: String,
+    pub user_address: String,
+    pub integrator_address: String,
+    pub previous_status: Option<OrderStatus>,
+    pub status: OrderStatus,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl OrderStatusChangedEvent {
+    /// A status change from `previous_status` to the order's current status
+    pub fn of(order: &Order, previous_status: Option<OrderStatus>) -> Self {
+        Self {
+            order_id: order.order_id.clone(),
+            user_address: order.user_address.clone(),
+            integrator_address: order.integrator_address.clone(),
+            previous_status,
+            status: order.status,
+            timestamp: order.updated_at,
+        }
+    }
+
+    /// Parse the status change an `order.*` message carries, if any
+    ///
+    /// `order.pending` carries the new [`Order`] itself, which the router
+    /// needs in full; it is read as the order's creation.
+    pub fn from_message(payload: &[u8]) -> Option<Self> {
+        if let Ok(event) = serde_json::from_slice::<Self>(payload) {
+            return Some(event);
+        }
+        serde_json::from_slice::<Order>(payload)
+            .ok()
+            .map(|order| Self::of(&order, None))
+    }
+}
+
+/// Order handed to a provider by the router (published on `order.assigned`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderAssignedEvent {
+    pub order_id: String,
+    pub provider: String,
+    /// Token amount the provider should pay out; less than the order for a
+    /// leg of a split order
+    pub amount: String,
+    /// Leg of a split order, None when the provider takes the whole order
+    pub leg: Option<u32>,
+    /// 1 for the first assignment, incremented on each failover
+    pub attempt: u32,
+    /// Routing decision the provider was ranked in
+    pub decision_id: Uuid,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Assigned provider could not pay out (published on `order.failed`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderFailedEvent {
+    pub order_id: String,
+    pub provider: String,
+    pub reason: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Router gave up on an order and its escrow should be returned
+/// (published on `order.refund_requested`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderRefundRequestedEvent {
+    pub order_id: String,
+    /// Assignments made before giving up
+    pub attempts: u32,
+    /// Providers that failed or timed out, in assignment order
+    pub failed_providers: Vec<String>,
+    pub reason: String,
+    /// Leg of a split order given up while the other legs carry on; None
+    /// when the whole order is refunded
+    #[serde(default)]
+    pub leg: Option<u32>,
+    /// Token amount of the leg to refund; None for the whole order
+    #[serde(default)]
+    pub amount: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,6 +254,37 @@ mod tests {
         assert_eq!(order.tier, OrderTier::Alpha);
     }
     
+    #[test]
+    fn test_status_change_read_from_a_new_order() {
+        let order = Order::new(
+            "0xorder".to_string(),
+            "0xuser".to_string(),
+            "0xusdc".to_string(),
+            "1000000".to_string(),
+            "0xrefund".to_string(),
+            "0xintegrator".to_string(),
+            50,
+            Currency::NGN,
+            OrderTier::Alpha,
+            Utc::now(),
+            0,
+            String::new(),
+        );
+        let event = OrderStatusChangedEvent::from_message(&serde_json::to_vec(&order).unwrap()).unwrap();
+        assert_eq!(event.order_id, "0xorder");
+        assert_eq!(event.integrator_address, "0xintegrator");
+        assert_eq!(event.previous_status, None);
+        assert_eq!(event.status, OrderStatus::Pending);
+
+        let mut accepted = OrderStatusChangedEvent::of(&order, Some(OrderStatus::Pending));
+        accepted.status = OrderStatus::Accepted;
+        let parsed = OrderStatusChangedEvent::from_message(&serde_json::to_vec(&accepted).unwrap()).unwrap();
+        assert_eq!(parsed.previous_status, Some(OrderStatus::Pending));
+        assert_eq!(parsed.status, OrderStatus::Accepted);
+
+        assert!(OrderStatusChangedEvent::from_message(br#"{"order_id":"0xorder"}"#).is_none());
+    }
+
     #[test]
     fn test_order_expiry() {
         let order = Order::new(
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// One page of a cursor-paginated list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Page<T> {
+    pub data: Vec<T>,
+
+    /// Cursor for the next page, None on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Check if more pages follow this one
+    pub fn has_more(&self) -> bool {
+        self.next_cursor.is_some()
+    }
+}
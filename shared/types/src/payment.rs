@@ -1,10 +1,15 @@
 
-use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::error::TypesError;
+
 /// Payment proof submitted by provider
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PaymentProof {
     /// Proposal ID this payment is for
     pub proposal_id: String,
@@ -37,11 +42,32 @@ impl PaymentProof {
         self.proposal_id == proposal_id
     }
     
-    /// Check if proof is recent (within last hour)
+    /// Check if proof is recent, within the default [`ProofFreshness`]
     pub fn is_recent(&self) -> bool {
-        let now = Utc::now();
-        let diff = now - self.timestamp;
-        diff.num_hours() < 1
+        self.is_fresh(&ProofFreshness::default(), Utc::now())
+    }
+
+    /// Check the proof was made within `freshness` of `now`
+    pub fn is_fresh(&self, freshness: &ProofFreshness, now: DateTime<Utc>) -> bool {
+        let age = now - self.timestamp;
+        age <= freshness.max_age && -age <= freshness.max_clock_skew
+    }
+}
+
+/// How old a payment proof may be when verified, and how far its timestamp
+/// may run ahead of the verifier's clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofFreshness {
+    pub max_age: Duration,
+    pub max_clock_skew: Duration,
+}
+
+impl Default for ProofFreshness {
+    fn default() -> Self {
+        Self {
+            max_age: Duration::hours(1),
+            max_clock_skew: Duration::minutes(5),
+        }
     }
 }
 
@@ -55,10 +81,19 @@ pub struct PaymentRequest {
     pub currency: String,
     pub recipient_details: RecipientDetails,
     pub deadline: DateTime<Utc>,
+    /// Leg of a split order the payment pays out; None when the order was
+    /// not split
+    #[serde(default)]
+    pub leg: Option<u32>,
 }
 
 /// Recipient details for off-chain payment
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// `Debug` and `Serialize` mask the account name, account number, phone
+/// number and additional info, so details that end up in logs, events or
+/// responses do not carry the recipient's PII. [`Self::expose`] gives the
+/// full details for encryption and PSP requests.
+#[derive(Clone, Deserialize)]
 pub struct RecipientDetails {
     pub account_name: String,
     pub account_number: String,
@@ -68,6 +103,185 @@ pub struct RecipientDetails {
     pub additional_info: Option<Value>,
 }
 
+/// Serialized form of [`RecipientDetails`], borrowed either as is or masked
+#[derive(Serialize)]
+pub struct ExposedRecipient<'a> {
+    pub account_name: &'a str,
+    pub account_number: &'a str,
+    pub bank_name: Option<&'a str>,
+    pub bank_code: Option<&'a str>,
+    pub phone_number: Option<&'a str>,
+    pub additional_info: Option<&'a Value>,
+}
+
+/// Stands in for masked `additional_info`
+const REDACTED: &str = "[redacted]";
+
+impl RecipientDetails {
+    /// Unmasked details; only for sealing them at rest and for PSP calls,
+    /// never for logging
+    pub fn expose(&self) -> ExposedRecipient<'_> {
+        ExposedRecipient {
+            account_name: &self.account_name,
+            account_number: &self.account_number,
+            bank_name: self.bank_name.as_deref(),
+            bank_code: self.bank_code.as_deref(),
+            phone_number: self.phone_number.as_deref(),
+            additional_info: self.additional_info.as_ref(),
+        }
+    }
+
+    /// Account name reduced to initials, e.g. `A*** O***`
+    pub fn masked_account_name(&self) -> String {
+        self.account_name
+            .split_whitespace()
+            .map(|word| format!("{}***", word.chars().next().unwrap_or('*')))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn masked(&self) -> (String, String, Option<String>) {
+        (
+            self.masked_account_name(),
+            mask_number(&self.account_number),
+            self.phone_number.as_deref().map(mask_number),
+        )
+    }
+}
+
+/// All but the last four characters replaced, e.g. `******6785`
+pub fn mask_number(value: &str) -> String {
+    let chars: Vec<char> = value.trim().chars().collect();
+    let shown = if chars.len() > 4 { 4 } else { 0 };
+    let mut masked = "*".repeat(chars.len() - shown);
+    masked.extend(&chars[chars.len() - shown..]);
+    masked
+}
+
+impl std::fmt::Debug for RecipientDetails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (account_name, account_number, phone_number) = self.masked();
+        f.debug_struct("RecipientDetails")
+            .field("account_name", &account_name)
+            .field("account_number", &account_number)
+            .field("bank_name", &self.bank_name)
+            .field("bank_code", &self.bank_code)
+            .field("phone_number", &phone_number)
+            .field("additional_info", &self.additional_info.as_ref().map(|_| REDACTED))
+            .finish()
+    }
+}
+
+impl Serialize for RecipientDetails {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (account_name, account_number, phone_number) = self.masked();
+        let redacted = Value::String(REDACTED.to_string());
+        ExposedRecipient {
+            account_name: &account_name,
+            account_number: &account_number,
+            bank_name: self.bank_name.as_deref(),
+            bank_code: self.bank_code.as_deref(),
+            phone_number: phone_number.as_deref(),
+            additional_info: self.additional_info.as_ref().map(|_| &redacted),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// State of a fiat payout at the PSP
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutStatus {
+    /// Accepted by the PSP but not yet sent
+    Pending,
+    /// Sent and awaiting confirmation from the recipient's bank or network
+    Processing,
+    /// Funds delivered to the recipient
+    Succeeded,
+    /// Rejected or reversed; the recipient was not paid
+    Failed,
+}
+
+impl PayoutStatus {
+    /// Returns the string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PayoutStatus::Pending => "pending",
+            PayoutStatus::Processing => "processing",
+            PayoutStatus::Succeeded => "succeeded",
+            PayoutStatus::Failed => "failed",
+        }
+    }
+
+    /// Whether the payout can no longer change state
+    pub fn is_final(&self) -> bool {
+        matches!(self, PayoutStatus::Succeeded | PayoutStatus::Failed)
+    }
+
+    /// Whether a payout in this state may move to `next`
+    ///
+    /// Payouts only move forward (pending, processing, then succeeded or
+    /// failed), so a late or repeated PSP callback cannot undo a newer state.
+    pub fn can_transition_to(&self, next: PayoutStatus) -> bool {
+        match self {
+            PayoutStatus::Pending => next != PayoutStatus::Pending,
+            PayoutStatus::Processing => next.is_final(),
+            PayoutStatus::Succeeded | PayoutStatus::Failed => false,
+        }
+    }
+}
+
+impl FromStr for PayoutStatus {
+    type Err = TypesError;
+
+    /// Parses a stored status
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(PayoutStatus::Pending),
+            "processing" => Ok(PayoutStatus::Processing),
+            "succeeded" => Ok(PayoutStatus::Succeeded),
+            "failed" => Ok(PayoutStatus::Failed),
+            _ => Err(TypesError::InvalidStatus(s.to_string())),
+        }
+    }
+}
+
+/// Outcome of initiating or looking up a payout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutResult {
+    /// Adapter that executed the payout
+    pub adapter: String,
+
+    /// PSP's reference for the payout, used to look it up
+    pub reference: String,
+
+    pub status: PayoutStatus,
+
+    /// PSP's explanation, typically for failures
+    pub message: Option<String>,
+}
+
+/// A payout tracked from initiation until the PSP confirms or rejects it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Payout {
+    pub order_id: String,
+    pub proposal_id: String,
+    pub provider: String,
+    pub adapter: String,
+    pub reference: String,
+    /// Leg of a split order the payout pays out
+    pub leg: Option<u32>,
+    pub amount: String,
+    pub currency: String,
+    pub status: PayoutStatus,
+    pub message: Option<String>,
+    /// Status lookups made while waiting for the PSP
+    pub polls: u32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,5 +301,53 @@ mod tests {
         
         assert!(proof.is_for_proposal("0xproposal..."));
         assert!(proof.is_recent());
+
+        let strict = ProofFreshness { max_age: Duration::minutes(10), max_clock_skew: Duration::seconds(30) };
+        assert!(!proof.is_fresh(&strict, proof.timestamp + Duration::minutes(11)));
+        assert!(!proof.is_fresh(&strict, proof.timestamp - Duration::minutes(1)));
+        assert!(proof.is_fresh(&strict, proof.timestamp - Duration::seconds(20)));
+    }
+
+    #[test]
+    fn test_recipient_details_are_masked() {
+        let recipient = RecipientDetails {
+            account_name: "Adaeze Okafor".to_string(),
+            account_number: "0123456785".to_string(),
+            bank_name: Some("GTBank".to_string()),
+            bank_code: Some("058".to_string()),
+            phone_number: Some("+2348031234567".to_string()),
+            additional_info: Some(serde_json::json!({ "bvn": "22212345678" })),
+        };
+
+        let logged = format!("{:?}", recipient);
+        let json = serde_json::to_string(&recipient).unwrap();
+        for shown in [logged.as_str(), json.as_str()] {
+            assert!(shown.contains("A*** O***") && shown.contains("******6785") && shown.contains("058"));
+            assert!(!shown.contains("Adaeze") && !shown.contains("0123456785") && !shown.contains("22212345678"));
+            assert!(!shown.contains("8031234567"));
+        }
+
+        let exposed = serde_json::to_value(recipient.expose()).unwrap();
+        assert_eq!(exposed["account_number"], "0123456785");
+        assert_eq!(exposed["additional_info"]["bvn"], "22212345678");
+        let parsed: RecipientDetails = serde_json::from_value(exposed).unwrap();
+        assert_eq!(parsed.account_name, "Adaeze Okafor");
+    }
+
+    #[test]
+    fn test_payout_status_only_moves_forward() {
+        use PayoutStatus::*;
+
+        assert!(Pending.can_transition_to(Processing));
+        assert!(Pending.can_transition_to(Succeeded));
+        assert!(Processing.can_transition_to(Failed));
+        assert!(!Processing.can_transition_to(Pending));
+        assert!(!Processing.can_transition_to(Processing));
+        assert!(!Succeeded.can_transition_to(Failed));
+        assert!(!Failed.can_transition_to(Failed));
+
+        for status in [Pending, Processing, Succeeded, Failed] {
+            assert_eq!(status.as_str().parse::<PayoutStatus>().ok(), Some(status));
+        }
     }
 }
\ No newline at end of file
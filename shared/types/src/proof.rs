@@ -0,0 +1,287 @@
+//! EIP-712 signatures over payment proofs
+//!
+//! Providers sign a [`PaymentProof`] as typed data, so the same signature
+//! can be checked here and by the escrow contract:
+//!
+//! ```text
+//! PaymentProof(bytes32 proposalId,address provider,string transactionReference,uint256 timestamp,string amount,string currency)
+//! ```
+//!
+//! in the `PayNode` version `1` domain of the escrow contract on its chain.
+//! `timestamp` is in unix seconds; `metadata` is not signed.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use ethers::abi::{encode, Token};
+use ethers::types::transaction::eip712::{EIP712Domain, Eip712};
+use ethers::types::{Address, Signature, H256, U256};
+use ethers::utils::keccak256;
+use thiserror::Error;
+
+use crate::payment::{PaymentProof, ProofFreshness};
+use crate::proposal::Proposal;
+
+/// EIP-712 type of a payment proof
+pub const PAYMENT_PROOF_TYPE: &str = "PaymentProof(bytes32 proposalId,address provider,string transactionReference,uint256 timestamp,string amount,string currency)";
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ProofError {
+    #[error("Invalid {field}: {value}")]
+    InvalidField { field: &'static str, value: String },
+
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+
+    #[error("Proof is for proposal {proof}, not {expected}")]
+    ProposalMismatch { proof: String, expected: String },
+
+    #[error("Proof names provider {proof}, not {expected}")]
+    ProviderMismatch { proof: String, expected: String },
+
+    #[error("Proof signed by {recovered:?}, not provider {expected:?}")]
+    SignerMismatch { expected: Address, recovered: Address },
+
+    #[error("Proof timestamp outside freshness window")]
+    Stale,
+
+    #[error("Transaction reference already used: {0}")]
+    Replayed(String),
+}
+
+/// Escrow contract payment proofs are signed for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofDomain {
+    pub chain_id: u64,
+    pub verifying_contract: Address,
+}
+
+impl ProofDomain {
+    pub const NAME: &'static str = "PayNode";
+    pub const VERSION: &'static str = "1";
+
+    pub fn new(chain_id: u64, verifying_contract: Address) -> Self {
+        Self { chain_id, verifying_contract }
+    }
+
+    /// Domain of the escrow contract at a hex `verifying_contract` address
+    pub fn parse(chain_id: u64, verifying_contract: &str) -> Result<Self, ProofError> {
+        Ok(Self::new(chain_id, parse_address("verifying_contract", verifying_contract)?))
+    }
+
+    pub fn eip712(&self) -> EIP712Domain {
+        EIP712Domain {
+            name: Some(Self::NAME.to_string()),
+            version: Some(Self::VERSION.to_string()),
+            chain_id: Some(U256::from(self.chain_id)),
+            verifying_contract: Some(self.verifying_contract),
+            salt: None,
+        }
+    }
+}
+
+/// A payment proof bound to its signing domain, as signed by providers
+///
+/// Implements [`Eip712`], so provider tooling can sign it with
+/// `Signer::sign_typed_data`.
+#[derive(Debug, Clone, Copy)]
+pub struct TypedPaymentProof<'a> {
+    pub proof: &'a PaymentProof,
+    pub domain: &'a ProofDomain,
+}
+
+impl Eip712 for TypedPaymentProof<'_> {
+    type Error = ProofError;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(self.domain.eip712())
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(keccak256(PAYMENT_PROOF_TYPE))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        let proof = self.proof;
+        let timestamp = u64::try_from(proof.timestamp.timestamp()).map_err(|_| ProofError::InvalidField {
+            field: "timestamp",
+            value: proof.timestamp.to_rfc3339(),
+        })?;
+
+        Ok(keccak256(encode(&[
+            Token::FixedBytes(Self::type_hash()?.to_vec()),
+            Token::FixedBytes(proposal_id_bytes(&proof.proposal_id)?.to_vec()),
+            Token::Address(parse_address("provider", &proof.provider)?),
+            Token::FixedBytes(keccak256(&proof.transaction_reference).to_vec()),
+            Token::Uint(U256::from(timestamp)),
+            Token::FixedBytes(keccak256(&proof.amount).to_vec()),
+            Token::FixedBytes(keccak256(&proof.currency).to_vec()),
+        ])))
+    }
+}
+
+impl PaymentProof {
+    pub fn typed<'a>(&'a self, domain: &'a ProofDomain) -> TypedPaymentProof<'a> {
+        TypedPaymentProof { proof: self, domain }
+    }
+
+    /// EIP-712 digest the provider signs
+    pub fn signing_hash(&self, domain: &ProofDomain) -> Result<H256, ProofError> {
+        Ok(H256::from(self.typed(domain).encode_eip712()?))
+    }
+
+    /// Address that produced `signature`
+    pub fn recover_signer(&self, domain: &ProofDomain) -> Result<Address, ProofError> {
+        let signature = Signature::from_str(self.signature.trim_start_matches("0x"))
+            .map_err(|e| ProofError::InvalidSignature(e.to_string()))?;
+        signature
+            .recover(self.signing_hash(domain)?)
+            .map_err(|e| ProofError::InvalidSignature(e.to_string()))
+    }
+}
+
+/// Checks payment proofs against the proposals they settle
+///
+/// Transaction references are remembered until proofs carrying them would
+/// be stale anyway, so a proof cannot be submitted twice. The record is per
+/// verifier; services running several replicas must also keep references
+/// unique in storage.
+pub struct ProofVerifier {
+    domain: ProofDomain,
+    freshness: ProofFreshness,
+    /// Transaction reference -> when its proof goes stale
+    seen: Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+impl ProofVerifier {
+    pub fn new(domain: ProofDomain, freshness: ProofFreshness) -> Self {
+        Self {
+            domain,
+            freshness,
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Verify a proof for `proposal` and claim its transaction reference
+    ///
+    /// The proof must name the proposal and its provider, be fresh at `now`
+    /// and be signed by the provider. The reference is only claimed once
+    /// every other check passes, so a rejected proof can be corrected and
+    /// resubmitted.
+    ///
+    /// # Returns
+    /// * `Result<Address, ProofError>` - The provider that signed the proof
+    pub fn verify(&self, proof: &PaymentProof, proposal: &Proposal, now: DateTime<Utc>) -> Result<Address, ProofError> {
+        if !proof.proposal_id.eq_ignore_ascii_case(&proposal.proposal_id) {
+            return Err(ProofError::ProposalMismatch {
+                proof: proof.proposal_id.clone(),
+                expected: proposal.proposal_id.clone(),
+            });
+        }
+        let expected = parse_address("proposal provider", &proposal.provider)?;
+        let claimed = parse_address("provider", &proof.provider)?;
+        if claimed != expected {
+            return Err(ProofError::ProviderMismatch {
+                proof: proof.provider.clone(),
+                expected: proposal.provider.clone(),
+            });
+        }
+        if !proof.is_fresh(&self.freshness, now) {
+            return Err(ProofError::Stale);
+        }
+
+        let recovered = proof.recover_signer(&self.domain)?;
+        if recovered != expected {
+            return Err(ProofError::SignerMismatch { expected, recovered });
+        }
+
+        let reference = proof.transaction_reference.trim().to_string();
+        if reference.is_empty() {
+            return Err(ProofError::InvalidField {
+                field: "transaction_reference",
+                value: proof.transaction_reference.clone(),
+            });
+        }
+        let mut seen = self.seen.lock().expect("proof replay lock poisoned");
+        seen.retain(|_, stale_at| *stale_at > now);
+        if seen.contains_key(&reference) {
+            return Err(ProofError::Replayed(reference));
+        }
+        seen.insert(reference, proof.timestamp + self.freshness.max_age);
+        Ok(recovered)
+    }
+}
+
+fn proposal_id_bytes(proposal_id: &str) -> Result<[u8; 32], ProofError> {
+    let invalid = || ProofError::InvalidField { field: "proposal_id", value: proposal_id.to_string() };
+    let bytes = hex::decode(proposal_id.trim_start_matches("0x")).map_err(|_| invalid())?;
+    bytes.try_into().map_err(|_| invalid())
+}
+
+fn parse_address(field: &'static str, address: &str) -> Result<Address, ProofError> {
+    Address::from_str(address).map_err(|_| ProofError::InvalidField { field, value: address.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::ProposalStatus;
+    use chrono::Duration;
+    use ethers::signers::{LocalWallet, Signer};
+
+    fn signed_proof(wallet: &LocalWallet, domain: &ProofDomain, reference: &str) -> (PaymentProof, Proposal) {
+        let now = Utc::now();
+        let mut proof = PaymentProof {
+            proposal_id: format!("0x{}", "ab".repeat(32)),
+            provider: format!("{:?}", wallet.address()),
+            transaction_reference: reference.to_string(),
+            timestamp: now,
+            amount: "150000.00".to_string(),
+            currency: "NGN".to_string(),
+            signature: String::new(),
+            metadata: serde_json::json!({ "bank": "GTBank" }),
+        };
+        let hash = proof.signing_hash(domain).unwrap();
+        proof.signature = format!("0x{}", wallet.sign_hash(hash).unwrap());
+
+        let proposal = Proposal {
+            proposal_id: proof.proposal_id.clone(),
+            order_id: format!("0x{}", "cd".repeat(32)),
+            provider: proof.provider.clone(),
+            proposed_fee_bps: 50,
+            status: ProposalStatus::Accepted,
+            created_at: now,
+            deadline: now + Duration::minutes(10),
+            accepted_at: Some(now),
+            executed_at: None,
+            tx_hash: None,
+        };
+        (proof, proposal)
+    }
+
+    #[test]
+    fn test_verify_recovers_provider_and_rejects_replays() {
+        let wallet = LocalWallet::from_str("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318").unwrap();
+        let domain = ProofDomain::new(8453, Address::repeat_byte(0x11));
+        let verifier = ProofVerifier::new(domain.clone(), ProofFreshness::default());
+
+        let (proof, proposal) = signed_proof(&wallet, &domain, "TXN-001");
+        let now = proof.timestamp + Duration::seconds(30);
+        assert_eq!(verifier.verify(&proof, &proposal, now), Ok(wallet.address()));
+        assert_eq!(verifier.verify(&proof, &proposal, now), Err(ProofError::Replayed("TXN-001".to_string())));
+
+        // Any signed field changed, or another domain, recovers someone else
+        let (mut tampered, proposal) = signed_proof(&wallet, &domain, "TXN-002");
+        tampered.amount = "1500000.00".to_string();
+        assert!(matches!(verifier.verify(&tampered, &proposal, now), Err(ProofError::SignerMismatch { .. })));
+        let (proof, proposal) = signed_proof(&wallet, &domain, "TXN-003");
+        let other_chain = ProofVerifier::new(ProofDomain::new(1, Address::repeat_byte(0x11)), ProofFreshness::default());
+        assert!(matches!(other_chain.verify(&proof, &proposal, now), Err(ProofError::SignerMismatch { .. })));
+
+        assert_eq!(verifier.verify(&proof, &proposal, now + Duration::hours(2)), Err(ProofError::Stale));
+        // Rejected proofs do not use up their reference
+        assert_eq!(verifier.verify(&proof, &proposal, now), Ok(wallet.address()));
+    }
+}
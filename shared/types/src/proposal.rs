@@ -6,6 +6,7 @@ use crate::enums::ProposalStatus;
 
 /// Settlement proposal
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Proposal {
     /// Proposal ID (bytes32 as hex)
     pub proposal_id: String,
@@ -6,6 +6,7 @@ use crate::enums::Currency;
 
 /// Provider intent to offer liquidity
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ProviderIntent {
     /// Provider's wallet address
     pub provider: String,
@@ -56,6 +57,7 @@ impl ProviderIntent {
 
 /// Provider registration request
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RegisterProviderRequest {
     pub currency: String,
     pub available_amount: String,
@@ -64,6 +66,17 @@ pub struct RegisterProviderRequest {
     pub commitment_window_seconds: u64,
 }
 
+/// Changes to a provider's intent in one currency; omitted fields keep
+/// their current value
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateIntentRequest {
+    pub available_amount: Option<String>,
+    pub min_fee_bps: Option<u64>,
+    pub max_fee_bps: Option<u64>,
+    pub commitment_window_seconds: Option<u64>,
+}
+
 /// Provider intent updated event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderIntentEvent {
@@ -73,9 +86,34 @@ pub struct ProviderIntentEvent {
     pub min_fee_bps: u64,
     pub max_fee_bps: u64,
     pub commitment_window: u64,
+    /// False once the intent is paused
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+    pub expires_at: Option<DateTime<Utc>>,
     pub timestamp: DateTime<Utc>,
 }
 
+fn default_true() -> bool {
+    true
+}
+
+impl ProviderIntentEvent {
+    /// Event describing the current state of an intent
+    pub fn from_intent(intent: &ProviderIntent) -> Self {
+        Self {
+            provider: intent.provider.clone(),
+            currency: intent.currency.as_str(),
+            available_amount: intent.available_amount.clone(),
+            min_fee_bps: intent.min_fee_bps,
+            max_fee_bps: intent.max_fee_bps,
+            commitment_window: intent.commitment_window_seconds,
+            is_active: intent.is_active,
+            expires_at: Some(intent.expires_at),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
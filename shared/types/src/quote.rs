@@ -0,0 +1,151 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::Currency;
+use crate::order::CreateOrderRequest;
+
+/// Request for the expected fiat payout of an order before it is created
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct QuoteRequest {
+    /// Token contract address
+    pub token: String,
+
+    /// Amount in smallest unit
+    pub amount: String,
+
+    /// Off-ramp currency code (NGN, KES, etc.)
+    pub currency: String,
+
+    /// Integrator the order will be placed through, if any
+    pub integrator_address: Option<String>,
+}
+
+/// Signed, time-limited quote that can be referenced when creating an order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Quote {
+    pub quote_id: Uuid,
+
+    pub token: String,
+
+    /// Amount in smallest unit
+    pub amount: String,
+
+    pub currency: Currency,
+
+    pub integrator_address: Option<String>,
+
+    /// Fiat received per whole token, as a decimal string
+    pub rate: String,
+
+    /// Fee of the provider the quote was priced against, in basis points
+    pub provider_fee_bps: u64,
+
+    /// Integrator fee in basis points
+    pub integrator_fee_bps: u64,
+
+    /// Protocol fee in basis points
+    pub protocol_fee_bps: u64,
+
+    /// Expected fiat payout after all fees, as a decimal string
+    pub fiat_amount: String,
+
+    pub created_at: DateTime<Utc>,
+
+    /// Orders referencing the quote after this time are rejected
+    pub expires_at: DateTime<Utc>,
+
+    /// Server signature over the quote terms
+    pub signature: String,
+}
+
+impl Quote {
+    /// Check if quote can no longer be referenced
+    pub fn is_expired(&self) -> bool {
+        Utc::now() > self.expires_at
+    }
+
+    /// Check if an order request has the terms this quote was issued for.
+    /// The integrator's fee is part of the price, so a quote issued without
+    /// an integrator cannot back an order.
+    pub fn matches_order(&self, request: &CreateOrderRequest) -> bool {
+        let integrator_matches = self
+            .integrator_address
+            .as_ref()
+            .is_some_and(|address| address.eq_ignore_ascii_case(&request.integrator_address));
+        self.token.eq_ignore_ascii_case(&request.token)
+            && self.amount == request.amount
+            && self.currency.as_str() == request.currency
+            && integrator_matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_order_terms() {
+        let quote = Quote {
+            quote_id: Uuid::new_v4(),
+            token: "0xtoken".to_string(),
+            amount: "1000000".to_string(),
+            currency: Currency::NGN,
+            integrator_address: Some("0xintegrator".to_string()),
+            rate: "1550".to_string(),
+            provider_fee_bps: 20,
+            integrator_fee_bps: 0,
+            protocol_fee_bps: 30,
+            fiat_amount: "1542.25".to_string(),
+            created_at: Utc::now(),
+            expires_at: Utc::now(),
+            signature: String::new(),
+        };
+        let mut request = CreateOrderRequest {
+            token: "0xTOKEN".to_string(),
+            amount: "1000000".to_string(),
+            currency: "NGN".to_string(),
+            refund_address: "0xrefund".to_string(),
+            integrator_address: "0xintegrator".to_string(),
+            quote_id: Some(quote.quote_id),
+        };
+        assert!(quote.matches_order(&request));
+
+        request.integrator_address = "0xother".to_string();
+        assert!(!quote.matches_order(&request));
+
+        request.integrator_address = "0xintegrator".to_string();
+        request.amount = "2000000".to_string();
+        assert!(!quote.matches_order(&request));
+    }
+
+    #[test]
+    fn test_quotes_without_an_integrator_back_no_order() {
+        let quote = Quote {
+            quote_id: Uuid::new_v4(),
+            token: "0xtoken".to_string(),
+            amount: "1000000".to_string(),
+            currency: Currency::NGN,
+            integrator_address: None,
+            rate: "1550".to_string(),
+            provider_fee_bps: 20,
+            integrator_fee_bps: 50,
+            protocol_fee_bps: 30,
+            fiat_amount: "1534.5".to_string(),
+            created_at: Utc::now(),
+            expires_at: Utc::now(),
+            signature: String::new(),
+        };
+        let request = CreateOrderRequest {
+            token: "0xtoken".to_string(),
+            amount: "1000000".to_string(),
+            currency: "NGN".to_string(),
+            refund_address: "0xrefund".to_string(),
+            integrator_address: "0xintegrator".to_string(),
+            quote_id: Some(quote.quote_id),
+        };
+        assert!(!quote.matches_order(&request));
+    }
+}
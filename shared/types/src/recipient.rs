@@ -0,0 +1,441 @@
+//! Checks on payout recipients before a payout is routed
+//!
+//! [`RecipientDetails::validated`] applies the rules of the payout currency
+//! and returns the details in the form PSPs take:
+//!
+//! - NGN: 10-digit NUBAN, with its check digit for banks that use one
+//! - KES: Safaricom M-Pesa numbers when no bank is given, else bank accounts
+//! - GHS: GhIPSS bank accounts, or MTN, Telecel and AirtelTigo wallets
+//! - ZAR: bank accounts with the bank's universal branch code
+//! - EUR: IBAN with its mod-97 checksum, and an optional BIC
+//!
+//! Other currencies only need an account number. Bank codes are checked
+//! against the [`banks`](crate::banks) directory, and a known bank name is
+//! enough when the code is missing.
+//!
+//! [`name_match_score`] compares the account name a user gave with the one
+//! the recipient's bank returns on name enquiry.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::banks::{self, Bank, BankKind};
+use crate::payment::RecipientDetails;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum RecipientError {
+    #[error("{0} is required")]
+    Missing(&'static str),
+
+    #[error("Invalid {field}: {reason}")]
+    Invalid { field: &'static str, reason: String },
+
+    #[error("Unknown {currency} bank: {bank}")]
+    UnknownBank { currency: String, bank: String },
+}
+
+/// Outcome of checking a recipient, including name enquiry where the PSP
+/// supports it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecipientVerification {
+    /// Details in canonical form
+    pub recipient: RecipientDetails,
+    /// Name the recipient's bank holds for the account
+    pub resolved_name: Option<String>,
+    /// [`name_match_score`] of the given name against the resolved one
+    pub name_match: Option<f64>,
+    /// False when the resolved name is too far from the given one
+    pub matches: bool,
+}
+
+/// Titles dropped before names are compared
+const TITLES: [&str; 13] = [
+    "MR", "MRS", "MS", "MISS", "DR", "PROF", "CHIEF", "ALHAJI", "ALHAJA", "ENGR", "SIR", "PASTOR", "BARR",
+];
+
+/// Account number lengths per IBAN country
+const IBAN_LENGTHS: [(&str, usize); 35] = [
+    ("AD", 24), ("AT", 20), ("BE", 16), ("BG", 22), ("CH", 21), ("CY", 28), ("CZ", 24),
+    ("DE", 22), ("DK", 18), ("EE", 20), ("ES", 24), ("FI", 18), ("FR", 27), ("GB", 22),
+    ("GR", 27), ("HR", 21), ("HU", 28), ("IE", 22), ("IS", 26), ("IT", 27), ("LI", 21),
+    ("LT", 20), ("LU", 20), ("LV", 21), ("MC", 27), ("MT", 31), ("NL", 18), ("NO", 15),
+    ("PL", 28), ("PT", 25), ("RO", 24), ("SE", 24), ("SI", 19), ("SK", 24), ("SM", 27),
+];
+
+impl RecipientDetails {
+    /// Check the details for a payout in `currency` and return them in
+    /// canonical form: digits only, phone numbers in international format
+    /// without `+`, IBANs without spaces, and the bank's code and name filled
+    /// in from the directory
+    pub fn validated(&self, currency: &str) -> Result<RecipientDetails, RecipientError> {
+        let currency = currency.trim().to_uppercase();
+        let mut details = self.clone();
+        details.account_name = self.account_name.split_whitespace().collect::<Vec<_>>().join(" ");
+        if details.account_name.is_empty() {
+            return Err(RecipientError::Missing("account_name"));
+        }
+
+        let bank = self.bank(&currency)?;
+        if let Some(bank) = bank {
+            details.bank_code = Some(bank.code.to_string());
+            details.bank_name = Some(bank.name.to_string());
+        }
+
+        match currency.as_str() {
+            "NGN" => {
+                let bank = bank.ok_or(RecipientError::Missing("bank_code"))?;
+                let account = digits("account_number", &self.account_number, 10..=10)?;
+                if bank.nuban && !nuban_is_valid(bank.code, &account) {
+                    return Err(RecipientError::Invalid {
+                        field: "account_number",
+                        reason: format!("{} is not a valid {} NUBAN", account, bank.name),
+                    });
+                }
+                details.account_number = account;
+            }
+            "KES" => match bank {
+                Some(_) => details.account_number = digits("account_number", &self.account_number, 6..=16)?,
+                None => {
+                    let phone = self.phone_number.as_deref().unwrap_or(&self.account_number);
+                    let phone = mpesa_number(phone)?;
+                    details.account_number = phone.clone();
+                    details.phone_number = Some(phone);
+                }
+            },
+            "GHS" => {
+                let bank = bank.ok_or(RecipientError::Missing("bank_code"))?;
+                match bank.kind {
+                    BankKind::Bank => details.account_number = digits("account_number", &self.account_number, 10..=16)?,
+                    BankKind::MobileMoney => {
+                        let phone = self.phone_number.as_deref().unwrap_or(&self.account_number);
+                        let phone = ghana_wallet_number(bank, phone)?;
+                        details.account_number = format!("0{}", &phone[3..]);
+                        details.phone_number = Some(phone);
+                    }
+                }
+            }
+            "ZAR" => {
+                bank.ok_or(RecipientError::Missing("bank_code"))?;
+                details.account_number = digits("account_number", &self.account_number, 7..=11)?;
+            }
+            "EUR" => {
+                details.account_number = iban(&self.account_number)?;
+                if let Some(bic) = self.bank_code.as_deref().map(str::trim).filter(|bic| !bic.is_empty()) {
+                    details.bank_code = Some(bic_code(bic)?);
+                }
+            }
+            _ => {
+                details.account_number = self.account_number.trim().to_string();
+                if details.account_number.is_empty() {
+                    return Err(RecipientError::Missing("account_number"));
+                }
+            }
+        }
+        Ok(details)
+    }
+
+    /// The directory entry for the bank, by code or else by name
+    ///
+    /// Unknown codes are rejected in currencies the directory covers.
+    fn bank(&self, currency: &str) -> Result<Option<&'static Bank>, RecipientError> {
+        let code = self.bank_code.as_deref().map(str::trim).filter(|code| !code.is_empty());
+        let name = self.bank_name.as_deref().map(str::trim).filter(|name| !name.is_empty());
+        if !banks::has_directory(currency) {
+            return Ok(None);
+        }
+        match (code, name) {
+            (Some(code), _) => banks::find_bank(currency, code).map(Some).ok_or_else(|| RecipientError::UnknownBank {
+                currency: currency.to_string(),
+                bank: code.to_string(),
+            }),
+            (None, Some(name)) => banks::find_bank_by_name(currency, name)
+                .map(Some)
+                .ok_or_else(|| RecipientError::UnknownBank { currency: currency.to_string(), bank: name.to_string() }),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+/// Whether the last digit of a 10-digit account number is the NUBAN check
+/// digit over the bank's CBN code and the first nine digits
+pub fn nuban_is_valid(bank_code: &str, account_number: &str) -> bool {
+    const WEIGHTS: [u32; 15] = [3, 7, 3, 3, 7, 3, 3, 7, 3, 3, 7, 3, 3, 7, 3];
+
+    // Codes are widened to six digits: "000" before a bank's 3-digit code,
+    // "9" before a 5-digit institution code
+    let code = match bank_code.len() {
+        3 => format!("000{}", bank_code),
+        5 => format!("9{}", bank_code),
+        6 => bank_code.to_string(),
+        _ => return false,
+    };
+    if account_number.len() != 10 || !account_number.bytes().all(|b| b.is_ascii_digit()) || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+
+    let payload = format!("{}{}", code, &account_number[..9]);
+    let sum: u32 = payload
+        .bytes()
+        .zip(WEIGHTS)
+        .map(|(digit, weight)| u32::from(digit - b'0') * weight)
+        .sum();
+    let check = (10 - sum % 10) % 10;
+    u32::from(account_number.as_bytes()[9] - b'0') == check
+}
+
+/// Whether an IBAN has its country's length and a valid mod-97 checksum
+pub fn iban_is_valid(iban: &str) -> bool {
+    let iban: String = iban.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase();
+    let Some(length) = IBAN_LENGTHS
+        .iter()
+        .find(|(country, _)| iban.starts_with(country))
+        .map(|(_, length)| *length)
+    else {
+        return false;
+    };
+    if iban.len() != length || !iban.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return false;
+    }
+
+    let rearranged = iban[4..].chars().chain(iban[..4].chars());
+    let mut remainder = 0u32;
+    for c in rearranged {
+        let value = c.to_digit(36).expect("alphanumeric");
+        remainder = if value < 10 {
+            (remainder * 10 + value) % 97
+        } else {
+            (remainder * 100 + value) % 97
+        };
+    }
+    remainder == 1
+}
+
+/// How closely a resolved account name matches the name given, from 0 to 1
+///
+/// Names are compared word by word in any order, ignoring case, punctuation
+/// and titles, so "OBI, ADA C." matches "Ada Obi". Each word of the shorter
+/// name is paired with the closest unused word of the other by edit
+/// distance, an initial matching any word it starts. The score averages
+/// those pairs over at least two words, so a lone first name is not enough.
+pub fn name_match_score(given: &str, resolved: &str) -> f64 {
+    let given = name_words(given);
+    let resolved = name_words(resolved);
+    let (shorter, longer) = if given.len() <= resolved.len() { (given, resolved) } else { (resolved, given) };
+    if shorter.is_empty() {
+        return 0.0;
+    }
+
+    let mut used = vec![false; longer.len()];
+    let mut total = 0.0;
+    for word in &shorter {
+        let best = longer
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !used[*i])
+            .map(|(i, other)| (i, word_similarity(word, other)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((i, similarity)) = best {
+            used[i] = true;
+            total += similarity;
+        }
+    }
+    total / shorter.len().max(longer.len().min(2)) as f64
+}
+
+fn name_words(name: &str) -> Vec<String> {
+    name.split(|c: char| c.is_whitespace() || c == ',' || c == '.' || c == '-')
+        .map(|word| word.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_uppercase())
+        .filter(|word| !word.is_empty() && !TITLES.contains(&word.as_str()))
+        .collect()
+}
+
+fn word_similarity(a: &str, b: &str) -> f64 {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    if a.len() == 1 || b.len() == 1 {
+        return if a[0] == b[0] { 1.0 } else { 0.0 };
+    }
+    1.0 - levenshtein(&a, &b) as f64 / a.len().max(b.len()) as f64
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Digits of an account number, allowing spaces and dashes between them
+fn digits(field: &'static str, value: &str, lengths: std::ops::RangeInclusive<usize>) -> Result<String, RecipientError> {
+    let cleaned: String = value.chars().filter(|c| !c.is_whitespace() && *c != '-').collect();
+    if cleaned.is_empty() {
+        return Err(RecipientError::Missing(field));
+    }
+    if !cleaned.bytes().all(|b| b.is_ascii_digit()) || !lengths.contains(&cleaned.len()) {
+        let reason = if lengths.start() == lengths.end() {
+            format!("expected {} digits", lengths.start())
+        } else {
+            format!("expected {} to {} digits", lengths.start(), lengths.end())
+        };
+        return Err(RecipientError::Invalid { field, reason });
+    }
+    Ok(cleaned)
+}
+
+/// National significant number of a phone given locally (`0712...`), without
+/// its leading zero (`712...`) or internationally (`+254712...`)
+fn national_number(phone: &str, country_code: &str, length: usize) -> Option<String> {
+    let cleaned: String = phone
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '-' | '(' | ')'))
+        .collect();
+    let cleaned = cleaned.trim_start_matches('+');
+    if !cleaned.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let national = match cleaned.len() {
+        n if n == length => cleaned,
+        n if n == length + 1 && cleaned.starts_with('0') => &cleaned[1..],
+        n if n == length + country_code.len() && cleaned.starts_with(country_code) => &cleaned[country_code.len()..],
+        _ => return None,
+    };
+    Some(national.to_string())
+}
+
+/// Safaricom number in the `2547XXXXXXXX` form M-Pesa takes
+fn mpesa_number(phone: &str) -> Result<String, RecipientError> {
+    if phone.trim().is_empty() {
+        return Err(RecipientError::Missing("phone_number"));
+    }
+    let invalid = || RecipientError::Invalid {
+        field: "phone_number",
+        reason: format!("{} is not a Safaricom M-Pesa number", phone.trim()),
+    };
+    let national = national_number(phone, "254", 9).ok_or_else(invalid)?;
+    let prefix: u32 = national[..3].parse().map_err(|_| invalid())?;
+    let safaricom = matches!(prefix, 700..=729 | 740..=746 | 748 | 757..=759 | 768..=769 | 790..=799 | 110..=115);
+    if !safaricom {
+        return Err(invalid());
+    }
+    Ok(format!("254{}", national))
+}
+
+/// Ghanaian number in `233XXXXXXXXX` form, on the wallet's network
+fn ghana_wallet_number(wallet: &Bank, phone: &str) -> Result<String, RecipientError> {
+    if phone.trim().is_empty() {
+        return Err(RecipientError::Missing("phone_number"));
+    }
+    let invalid = || RecipientError::Invalid {
+        field: "phone_number",
+        reason: format!("{} is not a {} number", phone.trim(), wallet.name),
+    };
+    let national = national_number(phone, "233", 9).ok_or_else(invalid)?;
+    let prefixes: &[&str] = match wallet.code {
+        "MTN" => &["24", "25", "53", "54", "55", "59"],
+        "VOD" => &["20", "50"],
+        "ATL" => &["26", "27", "56", "57"],
+        _ => &[],
+    };
+    if !prefixes.iter().any(|prefix| national.starts_with(prefix)) {
+        return Err(invalid());
+    }
+    Ok(format!("233{}", national))
+}
+
+fn iban(value: &str) -> Result<String, RecipientError> {
+    let iban: String = value.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase();
+    if iban.is_empty() {
+        return Err(RecipientError::Missing("account_number"));
+    }
+    if !iban_is_valid(&iban) {
+        return Err(RecipientError::Invalid { field: "account_number", reason: format!("{} is not a valid IBAN", iban) });
+    }
+    Ok(iban)
+}
+
+/// BIC: bank, country and location codes, optionally a branch code
+fn bic_code(value: &str) -> Result<String, RecipientError> {
+    let bic = value.to_uppercase();
+    // Checked as ASCII first, so the slices below fall on char boundaries
+    let valid = bic.is_ascii()
+        && matches!(bic.len(), 8 | 11)
+        && bic[..6].chars().all(|c| c.is_ascii_alphabetic())
+        && bic[6..].chars().all(|c| c.is_ascii_alphanumeric());
+    if !valid {
+        return Err(RecipientError::Invalid { field: "bank_code", reason: format!("{} is not a valid BIC", value) });
+    }
+    Ok(bic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipient(account_number: &str, bank_code: Option<&str>, phone_number: Option<&str>) -> RecipientDetails {
+        RecipientDetails {
+            account_name: "Ada  Obi".to_string(),
+            account_number: account_number.to_string(),
+            bank_name: None,
+            bank_code: bank_code.map(str::to_string),
+            phone_number: phone_number.map(str::to_string),
+            additional_info: None,
+        }
+    }
+
+    #[test]
+    fn test_validators_per_currency() {
+        // CBN's worked example: bank 011, serial 000001457, check digit 9
+        assert!(nuban_is_valid("011", "0000014579"));
+        assert!(!nuban_is_valid("011", "0000014578"));
+        let ngn = recipient("012-345-6785", Some("058"), None).validated("ngn").unwrap();
+        assert_eq!((ngn.account_number.as_str(), ngn.bank_name.as_deref()), ("0123456785", Some("Guaranty Trust Bank")));
+        assert_eq!(ngn.account_name, "Ada Obi");
+        assert!(matches!(recipient("0123456789", Some("058"), None).validated("NGN"), Err(RecipientError::Invalid { .. })));
+        assert!(matches!(recipient("0123456785", Some("999"), None).validated("NGN"), Err(RecipientError::UnknownBank { .. })));
+        let mut by_name = recipient("8031234567", None, None);
+        by_name.bank_name = Some("opay".to_string());
+        assert_eq!(by_name.validated("NGN").unwrap().bank_code.as_deref(), Some("999992"));
+
+        let mpesa = recipient("", None, Some("+254 712 345 678")).validated("KES").unwrap();
+        assert_eq!(mpesa.phone_number.as_deref(), Some("254712345678"));
+        assert_eq!(recipient("0110123456", None, None).validated("KES").unwrap().account_number, "254110123456");
+        assert!(recipient("", None, Some("0733123456")).validated("KES").is_err());
+
+        let momo = recipient("", Some("MTN"), Some("0551234987")).validated("GHS").unwrap();
+        assert_eq!((momo.account_number.as_str(), momo.phone_number.as_deref()), ("0551234987", Some("233551234987")));
+        assert!(recipient("", Some("VOD"), Some("0551234987")).validated("GHS").is_err());
+        assert!(recipient("1234567890123", Some("040100"), None).validated("GHS").is_ok());
+
+        assert!(recipient("1234567890", Some("470010"), None).validated("ZAR").is_ok());
+        assert_eq!(recipient("1234567890", None, None).validated("ZAR").unwrap_err(), RecipientError::Missing("bank_code"));
+
+        let eur = recipient("de89 3704 0044 0532 0130 00", Some("cobadeffxxx"), None).validated("EUR").unwrap();
+        assert_eq!((eur.account_number.as_str(), eur.bank_code.as_deref()), ("DE89370400440532013000", Some("COBADEFFXXX")));
+        assert!(iban_is_valid("GB82 WEST 1234 5698 7654 32"));
+        assert!(!iban_is_valid("GB82 WEST 1234 5698 7654 33"));
+    }
+
+    #[test]
+    fn test_bic_must_be_ascii() {
+        let eur = |bic| recipient("DE89370400440532013000", Some(bic), None).validated("EUR");
+        // Eight bytes, but the last character spans three of them
+        assert!(matches!(eur("AAAAA€"), Err(RecipientError::Invalid { field: "bank_code", .. })));
+        assert!(matches!(eur("COBADEFé"), Err(RecipientError::Invalid { field: "bank_code", .. })));
+        assert!(eur("COBADEFF").is_ok());
+    }
+
+    #[test]
+    fn test_name_match_score() {
+        assert_eq!(name_match_score("Ada Obi", "OBI, ADA CHIOMA"), 1.0);
+        assert_eq!(name_match_score("Mrs. Ada C. Obi", "ADA CHIOMA OBI"), 1.0);
+        assert!(name_match_score("Jon Smith", "JOHN SMITH") > 0.8);
+        assert!(name_match_score("Ada", "ADA OBI") <= 0.5);
+        assert!(name_match_score("Ada Obi", "MUSA IBRAHIM") < 0.3);
+        assert_eq!(name_match_score("", "ADA OBI"), 0.0);
+    }
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// Provider reputation metrics
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ProviderReputation {
     /// Provider address
     pub provider: String,
@@ -19,6 +20,14 @@ pub struct ProviderReputation {
     
     /// Times provider didn't respond to proposal
     pub no_shows: u64,
+
+    /// Disputes opened against the provider's payouts
+    #[serde(default)]
+    pub disputes: u64,
+
+    /// Disputes resolved against the provider (escrow refunded)
+    #[serde(default)]
+    pub disputes_lost: u64,
     
     /// Average settlement time in seconds
     pub avg_settlement_time_seconds: u64,
@@ -39,6 +48,8 @@ impl ProviderReputation {
             successful_orders: 0,
             failed_orders: 0,
             no_shows: 0,
+            disputes: 0,
+            disputes_lost: 0,
             avg_settlement_time_seconds: 0,
             total_volume: "0".to_string(),
             last_updated: Utc::now(),
@@ -54,12 +65,15 @@ impl ProviderReputation {
     }
     
     /// Calculate reliability score (0.0 to 1.0)
+    ///
+    /// Orders the provider did not show up for, or lost a dispute over,
+    /// count against it.
     pub fn reliability_score(&self) -> f64 {
         if self.total_orders == 0 {
             return 0.5;
         }
-        let show_rate = 1.0 - (self.no_shows as f64 / self.total_orders as f64);
-        show_rate
+        let unreliable = self.no_shows + self.disputes_lost;
+        (1.0 - unreliable as f64 / self.total_orders as f64).max(0.0)
     }
     
     /// Update after successful settlement
@@ -92,6 +106,18 @@ impl ProviderReputation {
         self.no_shows += 1;
         self.last_updated = Utc::now();
     }
+
+    /// Update after a dispute is opened against the provider
+    pub fn record_dispute(&mut self) {
+        self.disputes += 1;
+        self.last_updated = Utc::now();
+    }
+
+    /// Update after a dispute is resolved against the provider
+    pub fn record_dispute_lost(&mut self) {
+        self.disputes_lost += 1;
+        self.last_updated = Utc::now();
+    }
 }
 
 #[cfg(test)]
@@ -112,5 +138,11 @@ mod tests {
         assert_eq!(reputation.successful_orders, 2);
         assert_eq!(reputation.success_rate(), 0.5);
         assert_eq!(reputation.avg_settlement_time_seconds, 105); // (120 + 90) / 2
+        assert_eq!(reputation.reliability_score(), 0.75);
+
+        reputation.record_dispute();
+        reputation.record_dispute_lost();
+        assert_eq!((reputation.disputes, reputation.disputes_lost), (1, 1));
+        assert_eq!(reputation.reliability_score(), 0.5);
     }
 }
\ No newline at end of file
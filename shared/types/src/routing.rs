@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::allocation::LegAllocation;
+use crate::enums::{Currency, OrderTier};
+
+/// How one provider fared in a routing decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CandidateEvaluation {
+    pub provider: String,
+
+    /// Position in the ranking, starting at 1
+    pub rank: u32,
+
+    pub score: f64,
+
+    /// Normalized feature values the scorer saw
+    pub features: BTreeMap<String, f64>,
+
+    /// Amount each input added to the score
+    pub contributions: BTreeMap<String, f64>,
+}
+
+/// Record of a routing decision, kept to explain why a provider won or lost
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RoutingDecision {
+    pub id: Uuid,
+
+    /// Blockchain order ID (bytes32 as hex string)
+    pub order_id: String,
+
+    pub currency: Currency,
+
+    pub tier: OrderTier,
+
+    /// Scorer that ranked the candidates
+    pub scorer: String,
+
+    /// Scorer name and build, so decisions can be tied to the logic in force
+    pub scorer_version: String,
+
+    /// Top-ranked provider; None when no candidate was eligible or the order
+    /// was split
+    pub chosen_provider: Option<String>,
+
+    /// Legs the order was split into when no single provider could fill it
+    #[serde(default)]
+    pub allocations: Vec<LegAllocation>,
+
+    /// Eligible candidates, best first
+    pub candidates: Vec<CandidateEvaluation>,
+
+    pub decided_at: DateTime<Utc>,
+}
+
+impl RoutingDecision {
+    /// Evaluation of a provider in this decision, if it was a candidate
+    pub fn candidate(&self, provider: &str) -> Option<&CandidateEvaluation> {
+        self.candidates
+            .iter()
+            .find(|c| c.provider.eq_ignore_ascii_case(provider))
+    }
+}
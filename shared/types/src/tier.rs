@@ -0,0 +1,155 @@
+use std::fmt;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::Currency;
+use crate::error::{Result, TypesError};
+
+/// Most decimals a token may have for its tier limits to be converted to
+/// base units
+pub const MAX_TOKEN_DECIMALS: u32 = 24;
+
+/// A positive token amount in base units (wei for 18 decimals), parsed from
+/// the decimal string amounts are carried as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TokenAmount(u128);
+
+impl TokenAmount {
+    /// Parse a base-unit amount
+    ///
+    /// # Returns
+    /// * `Result<TokenAmount>` - An error for anything but a positive integer
+    pub fn parse(amount: &str) -> Result<Self> {
+        let trimmed = amount.trim();
+        if trimmed.is_empty() || !trimmed.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(TypesError::InvalidAmount(amount.to_string()));
+        }
+        match trimmed.parse::<u128>() {
+            Ok(value) if value > 0 => Ok(Self(value)),
+            _ => Err(TypesError::InvalidAmount(amount.to_string())),
+        }
+    }
+
+    pub fn value(&self) -> u128 {
+        self.0
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Tier boundaries for one token, in whole tokens rather than base units so
+/// the same thresholds read the same for 6- and 18-decimal tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TierLimits {
+    /// Token address, lowercase
+    pub token: String,
+
+    /// Currency the limits apply to; None applies them to every currency
+    /// without limits of its own
+    pub currency: Option<Currency>,
+
+    /// Token decimals used to convert the limits to base units
+    pub decimals: u32,
+
+    /// Largest Alpha-tier order, in whole tokens
+    pub alpha: Decimal,
+    /// Largest Beta-tier order, in whole tokens
+    pub beta: Decimal,
+    /// Largest Delta-tier order, in whole tokens
+    pub delta: Decimal,
+    /// Largest Omega-tier order, in whole tokens; anything above is Titan
+    pub omega: Decimal,
+}
+
+impl TierLimits {
+    /// Default boundaries of 3,000 / 10,000 / 50,000 / 100,000 tokens
+    pub fn standard(token: &str, decimals: u32) -> Self {
+        Self {
+            token: token.to_lowercase(),
+            currency: None,
+            decimals,
+            alpha: Decimal::from(3_000),
+            beta: Decimal::from(10_000),
+            delta: Decimal::from(50_000),
+            omega: Decimal::from(100_000),
+        }
+    }
+
+    /// Check the limits are positive, strictly increasing and convertible to
+    /// base units
+    pub fn validate(&self) -> Result<()> {
+        if self.decimals > MAX_TOKEN_DECIMALS {
+            return Err(TypesError::InvalidTier(format!(
+                "token {} has {} decimals, at most {} are supported",
+                self.token, self.decimals, MAX_TOKEN_DECIMALS
+            )));
+        }
+        let limits = [self.alpha, self.beta, self.delta, self.omega];
+        if limits[0] <= Decimal::ZERO || limits.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(TypesError::InvalidTier(format!(
+                "limits for token {} must be positive and increasing, got {} / {} / {} / {}",
+                self.token, self.alpha, self.beta, self.delta, self.omega
+            )));
+        }
+        for limit in limits {
+            self.to_base_units(limit)?;
+        }
+        Ok(())
+    }
+
+    /// Upper bounds of Alpha through Omega in base units
+    ///
+    /// Limits that do not convert leave the tier unbounded, which
+    /// [`validate`](Self::validate) rules out for stored limits.
+    pub fn base_unit_limits(&self) -> [u128; 4] {
+        [self.alpha, self.beta, self.delta, self.omega].map(|limit| self.to_base_units(limit).unwrap_or(u128::MAX))
+    }
+
+    fn to_base_units(&self, limit: Decimal) -> Result<u128> {
+        let scale = Decimal::from_i128_with_scale(10i128.pow(self.decimals.min(MAX_TOKEN_DECIMALS)), 0);
+        limit
+            .checked_mul(scale)
+            .and_then(|units| units.trunc().to_u128())
+            .ok_or_else(|| TypesError::InvalidTier(format!("limit {} of token {} is out of range", limit, self.token)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::OrderTier;
+
+    #[test]
+    fn test_token_amount_parsing() {
+        assert_eq!(TokenAmount::parse("1000").unwrap().value(), 1000);
+        for invalid in ["", "0", "-5", "1.5", "abc", "1e18", "340282366920938463463374607431768211456"] {
+            assert!(TokenAmount::parse(invalid).is_err(), "{} should be rejected", invalid);
+        }
+    }
+
+    #[test]
+    fn test_limits_in_human_units() {
+        let usdc = TierLimits::standard("0xUSDC", 6);
+        let dai = TierLimits::standard("0xdai", 18);
+        usdc.validate().unwrap();
+        dai.validate().unwrap();
+
+        // 5,000 tokens is Beta whatever the decimals
+        let five_thousand_usdc = TokenAmount::parse("5000000000").unwrap();
+        let five_thousand_dai = TokenAmount::parse("5000000000000000000000").unwrap();
+        assert_eq!(OrderTier::from_amount(five_thousand_usdc, &usdc), OrderTier::Beta);
+        assert_eq!(OrderTier::from_amount(five_thousand_dai, &dai), OrderTier::Beta);
+        assert_eq!(OrderTier::from_amount(five_thousand_dai, &usdc), OrderTier::Titan);
+
+        let inverted = TierLimits { beta: Decimal::from(1_000), ..usdc.clone() };
+        assert!(inverted.validate().is_err());
+        let fractional = TierLimits { alpha: Decimal::new(25, 1), ..usdc };
+        assert!(fractional.validate().is_ok());
+    }
+}
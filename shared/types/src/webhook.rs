@@ -0,0 +1,202 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::OrderStatus;
+use crate::error::{Result, TypesError};
+use crate::order::OrderStatusChangedEvent;
+
+/// Order lifecycle events integrators can subscribe to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum WebhookEventType {
+    #[serde(rename = "order.created")]
+    OrderCreated,
+    #[serde(rename = "order.accepted")]
+    OrderAccepted,
+    #[serde(rename = "order.partially_fulfilled")]
+    OrderPartiallyFulfilled,
+    #[serde(rename = "order.fulfilled")]
+    OrderFulfilled,
+    #[serde(rename = "order.refunded")]
+    OrderRefunded,
+    #[serde(rename = "order.expired")]
+    OrderExpired,
+}
+
+impl WebhookEventType {
+    /// Returns the event name sent to integrators and stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventType::OrderCreated => "order.created",
+            WebhookEventType::OrderAccepted => "order.accepted",
+            WebhookEventType::OrderPartiallyFulfilled => "order.partially_fulfilled",
+            WebhookEventType::OrderFulfilled => "order.fulfilled",
+            WebhookEventType::OrderRefunded => "order.refunded",
+            WebhookEventType::OrderExpired => "order.expired",
+        }
+    }
+
+    /// Maps the status an order just transitioned into to its webhook event
+    pub fn from_status(status: OrderStatus) -> Self {
+        match status {
+            OrderStatus::Pending => WebhookEventType::OrderCreated,
+            OrderStatus::Accepted => WebhookEventType::OrderAccepted,
+            OrderStatus::PartiallyFulfilled => WebhookEventType::OrderPartiallyFulfilled,
+            OrderStatus::Fulfilled => WebhookEventType::OrderFulfilled,
+            OrderStatus::Refunded => WebhookEventType::OrderRefunded,
+            OrderStatus::Expired => WebhookEventType::OrderExpired,
+        }
+    }
+}
+
+impl FromStr for WebhookEventType {
+    type Err = TypesError;
+
+    /// Parses an event name
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "order.created" => Ok(WebhookEventType::OrderCreated),
+            "order.accepted" => Ok(WebhookEventType::OrderAccepted),
+            "order.partially_fulfilled" => Ok(WebhookEventType::OrderPartiallyFulfilled),
+            "order.fulfilled" => Ok(WebhookEventType::OrderFulfilled),
+            "order.refunded" => Ok(WebhookEventType::OrderRefunded),
+            "order.expired" => Ok(WebhookEventType::OrderExpired),
+            _ => Err(TypesError::ParseError(format!("Unknown webhook event: {}", s))),
+        }
+    }
+}
+
+/// Integrator-registered endpoint receiving order notifications
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WebhookEndpoint {
+    pub id: Uuid,
+
+    /// Integrator that owns the endpoint
+    pub integrator_address: String,
+
+    /// HTTPS URL notifications are POSTed to
+    pub url: String,
+
+    /// Events the integrator subscribed to
+    pub event_types: Vec<WebhookEventType>,
+
+    /// Disabled endpoints receive no deliveries until re-enabled
+    pub is_active: bool,
+
+    /// Failed deliveries since the last success
+    pub consecutive_failures: u32,
+
+    pub created_at: DateTime<Utc>,
+
+    /// When the endpoint was automatically disabled
+    pub disabled_at: Option<DateTime<Utc>>,
+}
+
+impl WebhookEndpoint {
+    /// Check if endpoint should receive the given event
+    pub fn is_subscribed(&self, event_type: WebhookEventType) -> bool {
+        self.is_active && self.event_types.contains(&event_type)
+    }
+}
+
+/// Request to register a webhook endpoint for the calling integrator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RegisterWebhookRequest {
+    pub url: String,
+    pub event_types: Vec<String>,
+}
+
+impl RegisterWebhookRequest {
+    /// Validates the URL and parses the subscribed event names
+    pub fn parsed_event_types(&self) -> Result<Vec<WebhookEventType>> {
+        if !(self.url.starts_with("https://") || self.url.starts_with("http://")) {
+            return Err(TypesError::ParseError(format!("Invalid webhook URL: {}", self.url)));
+        }
+        if self.event_types.is_empty() {
+            return Err(TypesError::ParseError("At least one event type is required".to_string()));
+        }
+
+        self.event_types
+            .iter()
+            .map(|name| name.parse())
+            .collect()
+    }
+}
+
+/// Registration response; the signing secret is only ever returned here
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WebhookRegistration {
+    pub endpoint: WebhookEndpoint,
+    pub secret: String,
+}
+
+/// Body POSTed to integrator endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WebhookPayload {
+    /// Event ID, stable across retries so integrators can deduplicate
+    pub id: Uuid,
+    pub event_type: WebhookEventType,
+    pub created_at: DateTime<Utc>,
+    pub data: OrderStatusChangedEvent,
+}
+
+/// Record of a single delivery attempt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WebhookDeliveryAttempt {
+    pub delivery_id: i64,
+    pub endpoint_id: Uuid,
+    pub event_id: Uuid,
+    pub attempt: u32,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    pub succeeded: bool,
+    pub duration_ms: u64,
+    pub attempted_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_type_names_roundtrip() {
+        for status in [
+            OrderStatus::Pending,
+            OrderStatus::Accepted,
+            OrderStatus::PartiallyFulfilled,
+            OrderStatus::Fulfilled,
+            OrderStatus::Refunded,
+            OrderStatus::Expired,
+        ] {
+            let event = WebhookEventType::from_status(status);
+            assert_eq!(event.as_str().parse::<WebhookEventType>().ok(), Some(event));
+            assert_eq!(serde_json::to_value(event).unwrap(), event.as_str());
+        }
+    }
+
+    #[test]
+    fn test_register_request_validation() {
+        let mut request = RegisterWebhookRequest {
+            url: "https://example.com/hooks".to_string(),
+            event_types: vec!["order.fulfilled".to_string(), "order.refunded".to_string()],
+        };
+        assert_eq!(
+            request.parsed_event_types().unwrap(),
+            vec![WebhookEventType::OrderFulfilled, WebhookEventType::OrderRefunded]
+        );
+
+        request.event_types.push("order.unknown".to_string());
+        assert!(request.parsed_event_types().is_err());
+
+        request.url = "ftp://example.com".to_string();
+        assert!(request.parsed_event_types().is_err());
+    }
+}
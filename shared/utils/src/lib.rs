@@ -1,3 +1,6 @@
+pub mod retry;
+pub mod signing;
+
 pub fn validate_currency(currency: &str) -> bool {
     matches!(currency, "NGN" | "GHS" | "KES" | "USD" | "EUR")
 }
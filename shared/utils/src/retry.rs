@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+/// Exponential backoff schedule shared by retrying workers and clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    /// Delay before the first retry
+    pub initial: Duration,
+    /// Upper bound for any single delay
+    pub max: Duration,
+    /// Growth factor applied per attempt
+    pub multiplier: u32,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(60),
+            multiplier: 2,
+        }
+    }
+}
+
+impl Backoff {
+    pub fn new(initial: Duration, max: Duration, multiplier: u32) -> Self {
+        Self { initial, max, multiplier }
+    }
+
+    /// Delay to wait after the given failed attempt (1-based)
+    ///
+    /// Attempt 1 waits `initial`, attempt 2 waits `initial * multiplier`, and so
+    /// on, capped at `max`.
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1);
+        let factor = self.multiplier.max(1).checked_pow(exponent).unwrap_or(u32::MAX);
+        self.initial
+            .checked_mul(factor)
+            .map_or(self.max, |delay| delay.min(self.max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let backoff = Backoff::new(Duration::from_secs(2), Duration::from_secs(30), 3);
+
+        assert_eq!(backoff.delay_for(1), Duration::from_secs(2));
+        assert_eq!(backoff.delay_for(2), Duration::from_secs(6));
+        assert_eq!(backoff.delay_for(3), Duration::from_secs(18));
+        assert_eq!(backoff.delay_for(4), Duration::from_secs(30));
+        assert_eq!(backoff.delay_for(40), Duration::from_secs(30));
+    }
+}
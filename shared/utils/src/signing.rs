@@ -0,0 +1,178 @@
+use hmac::{Hmac, Mac};
+use sha2::{Sha256, Sha512};
+use thiserror::Error;
+
+type HmacSha256 = Hmac<Sha256>;
+type HmacSha512 = Hmac<Sha512>;
+
+/// Header carrying the timestamped signature of an outbound payload
+pub const SIGNATURE_HEADER: &str = "X-PayNode-Signature";
+
+/// Signature scheme version tag used inside the signature header
+const SCHEME: &str = "v1";
+
+/// Value the example configuration once shipped for secrets; never accepted
+pub const PLACEHOLDER_SECRET: &str = "change-me-in-production";
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SignatureError {
+    #[error("Malformed signature header: {0}")]
+    MalformedHeader(String),
+
+    #[error("Signature timestamp outside tolerance window")]
+    Expired,
+
+    #[error("Signature mismatch")]
+    Mismatch,
+}
+
+/// Computes a hex-encoded HMAC-SHA256 of `payload` keyed by `secret`
+pub fn hmac_sha256_hex(secret: &[u8], payload: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Computes a hex-encoded HMAC-SHA512 of `payload` keyed by `secret`
+pub fn hmac_sha512_hex(secret: &[u8], payload: &[u8]) -> String {
+    let mut mac = HmacSha512::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Builds the value of the signature header for a payload sent at `timestamp`
+///
+/// The signed message is `"{timestamp}.{body}"` so a captured payload cannot be
+/// replayed with a different timestamp.
+///
+/// # Returns
+/// * `String` - Header value in the form `t=<unix seconds>,v1=<hex digest>`
+pub fn sign_payload(secret: &[u8], timestamp: i64, body: &[u8]) -> String {
+    format!("t={},{}={}", timestamp, SCHEME, digest(secret, timestamp, body))
+}
+
+/// Verifies a signature header produced by [`sign_payload`]
+///
+/// # Arguments
+/// * `secret` - Shared secret the payload was signed with
+/// * `header` - Raw signature header value
+/// * `body` - Raw request body exactly as received
+/// * `now` - Current unix time in seconds
+/// * `tolerance_secs` - Maximum accepted age (or clock skew) of the signature
+pub fn verify_payload(
+    secret: &[u8],
+    header: &str,
+    body: &[u8],
+    now: i64,
+    tolerance_secs: i64,
+) -> Result<(), SignatureError> {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => {
+                timestamp = Some(
+                    value
+                        .parse::<i64>()
+                        .map_err(|_| SignatureError::MalformedHeader(header.to_string()))?,
+                );
+            }
+            Some((SCHEME, value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+
+    let timestamp = timestamp.ok_or_else(|| SignatureError::MalformedHeader(header.to_string()))?;
+    if signatures.is_empty() {
+        return Err(SignatureError::MalformedHeader(header.to_string()));
+    }
+    if (now - timestamp).abs() > tolerance_secs {
+        return Err(SignatureError::Expired);
+    }
+
+    let expected = digest(secret, timestamp, body);
+    if signatures.iter().any(|sig| constant_time_eq(sig.as_bytes(), expected.as_bytes())) {
+        Ok(())
+    } else {
+        Err(SignatureError::Mismatch)
+    }
+}
+
+fn digest(secret: &[u8], timestamp: i64, body: &[u8]) -> String {
+    let mut message = format!("{}.", timestamp).into_bytes();
+    message.extend_from_slice(body);
+    hmac_sha256_hex(secret, &message)
+}
+
+/// Reads the secret in environment variable `key`
+///
+/// Services sign with these secrets, so one that is unset, empty or still the
+/// placeholder is an error rather than a default.
+pub fn required_secret(key: &str) -> anyhow::Result<String> {
+    let secret = std::env::var(key).unwrap_or_default();
+    if secret.trim().is_empty() {
+        anyhow::bail!("{} must be set in .env file or environment", key);
+    }
+    if secret.trim() == PLACEHOLDER_SECRET {
+        anyhow::bail!("{} is still the placeholder value; set a real secret", key);
+    }
+    Ok(secret)
+}
+
+/// Compares two byte slices without short-circuiting on the first difference
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify_roundtrip() {
+        let header = sign_payload(b"whsec_test", 1_700_000_000, b"{\"ok\":true}");
+        assert!(header.starts_with("t=1700000000,v1="));
+
+        assert_eq!(verify_payload(b"whsec_test", &header, b"{\"ok\":true}", 1_700_000_010, 300), Ok(()));
+        assert_eq!(
+            verify_payload(b"other", &header, b"{\"ok\":true}", 1_700_000_010, 300),
+            Err(SignatureError::Mismatch)
+        );
+        assert_eq!(
+            verify_payload(b"whsec_test", &header, b"{\"ok\":false}", 1_700_000_010, 300),
+            Err(SignatureError::Mismatch)
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_stale_and_malformed_headers() {
+        let header = sign_payload(b"whsec_test", 1_700_000_000, b"body");
+        assert_eq!(
+            verify_payload(b"whsec_test", &header, b"body", 1_700_001_000, 300),
+            Err(SignatureError::Expired)
+        );
+        assert!(matches!(
+            verify_payload(b"whsec_test", "v1=abc", b"body", 0, 300),
+            Err(SignatureError::MalformedHeader(_))
+        ));
+    }
+
+    #[test]
+    fn test_required_secret_refuses_missing_and_placeholder() {
+        std::env::remove_var("SIGNING_TEST_SECRET_UNSET");
+        assert!(required_secret("SIGNING_TEST_SECRET_UNSET").is_err());
+
+        std::env::set_var("SIGNING_TEST_SECRET_EMPTY", " ");
+        assert!(required_secret("SIGNING_TEST_SECRET_EMPTY").is_err());
+
+        std::env::set_var("SIGNING_TEST_SECRET_PLACEHOLDER", PLACEHOLDER_SECRET);
+        assert!(required_secret("SIGNING_TEST_SECRET_PLACEHOLDER").is_err());
+
+        std::env::set_var("SIGNING_TEST_SECRET_SET", "s3cr3t");
+        assert_eq!(required_secret("SIGNING_TEST_SECRET_SET").unwrap(), "s3cr3t");
+    }
+}